@@ -1,63 +1,284 @@
-use crate::clipboard::copy_to_clipboard;
+use crate::audit;
+use crate::clipboard::{copy_to_clipboard, paste_from_clipboard};
+use crate::column_layout::{ColumnLayout, ColumnLayouts};
 use crate::components::{
+    confirm::ConfirmAction,
+    context_menu::TreeAction,
+    error::ErrorAction,
+    prompt::PromptAction,
+    record_table::CellFilter,
     tab::Tab,
+    table_filter::table_key,
     {
-        command, ConnectionsComponent, DatabasesComponent, ErrorComponent, HelpComponent,
-        PropertiesComponent, RecordTableComponent, SqlEditorComponent, TabComponent,
+        command, ConfigErrorComponent, ConfirmComponent, ConnectionSwitcherComponent,
+        ConnectionsComponent, ContextMenuComponent, DatabasesComponent, ErrorComponent,
+        ExternalCommandsComponent, HelpComponent, JobsComponent, PromptComponent,
+        PropertiesComponent, QueryComponent, RecordTableComponent, ResultSchemaComponent,
+        ServerComponent, SessionVariablesComponent, SqlEditorComponent, TabComponent,
+        TableValueComponent, ToastComponent, ValueFrequencyComponent,
     },
 };
 use crate::components::{
     CommandInfo, Component as _, DrawableComponent as _, EventState, StatefulDrawableComponent,
 };
-use crate::config::Config;
-use crate::database::{MySqlPool, Pool, PostgresPool, SqlitePool};
+use crate::config::{default_limit_size, get_app_config_path, Config, Connection};
+use crate::copy_rows::{copy_rows, parse_target, CopySource, CopyTarget};
 use crate::event::Key;
-use anyhow::Context;
+use crate::export::{
+    dump, export, export_rows, write_xlsx, ExportFormat, ExportOptions, ExportScope, Sheet,
+};
+use crate::external_command::Placeholders;
+use crate::filter_history::FilterHistory;
+use crate::jobs::{Job, JobProgress};
+use crate::key_macro::KeyMacro;
+use crate::mock_data::MockSpec;
+use crate::paste;
+use crate::session::Session;
+use crate::template;
+use crate::undo::{self, UndoEntry, UndoLog};
+use anyhow::anyhow;
 use ratatui::layout::Flex;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zhobo::charset::unrepresentable;
+use zhobo::connection::open_pool;
+use zhobo::database::sqlite::copy_result_statements;
+use zhobo::database::{
+    generated_columns, in_list, insert_statements, is_binary_type, is_geometry_type, is_json_type,
+    is_numeric_type, is_read_only, take_decode_errors, ExecuteResult, JsonPath, Pool, QueryBuilder,
+    SqlitePool, MAX_CONNECTIONS,
+};
+use zhobo::log::log_file;
+use zhobo::tree::{Database, Table};
+
+/// Programs which take over the terminal while zhobo is suspended.
+pub enum External {
+    /// Shows the text in the pager.
+    Pager(String),
+    /// Runs the shell with these environment variables.
+    Shell(Vec<(String, String)>),
+    /// Runs the command line through `sh -c` with these environment variables.
+    Command {
+        command: String,
+        env: Vec<(String, String)>,
+    },
+}
 
 pub enum Focus {
     DatabaseList,
     Table,
     ConnectionList,
 }
+
+/// The query run last, which the rerun key runs again.
+#[derive(Clone)]
+enum LastQuery {
+    /// A statement run from the SQL editor.
+    Editor(String),
+    /// The query of the records tab.
+    Records,
+}
+
 pub struct App {
     record_table: RecordTableComponent,
     properties: PropertiesComponent,
     sql_editor: SqlEditorComponent,
+    server: ServerComponent,
     focus: Focus,
     tab: TabComponent,
     help: HelpComponent,
+    confirm: ConfirmComponent,
+    prompt: PromptComponent,
+    query: QueryComponent,
+    connection_switcher: ConnectionSwitcherComponent,
+    config_error: ConfigErrorComponent,
+    toast: ToastComponent,
+    jobs: JobsComponent,
+    session_variables: SessionVariablesComponent,
+    result_schema: ResultSchemaComponent,
+    external_commands: ExternalCommandsComponent,
+    context_menu: ContextMenuComponent,
+    value_frequency: ValueFrequencyComponent,
+    cell_value: TableValueComponent,
+    column_layouts: ColumnLayouts,
     databases: DatabasesComponent,
     connections: ConnectionsComponent,
     pool: Option<Box<dyn Pool>>,
-    left_main_chunk_percentage: u16,
+    /// The connection of the SQL editor when it is separate from `pool`, or the scratch database
+    /// while the editor runs on it.
+    editor_pool: Option<Box<dyn Pool>>,
+    /// The one of the scratch database and the dedicated editor connection which the SQL editor
+    /// does not run on, swapped with `editor_pool` when switching.
+    standby_pool: Option<Box<dyn Pool>>,
+    /// Whether the SQL editor runs on the scratch database, an in-memory SQLite database kept
+    /// for the session.
+    scratch_active: bool,
+    /// How many results have been saved to the scratch database, to number their tables.
+    scratch_tables: usize,
+    /// Whether the current connection is `protected`.
+    protected: bool,
+    /// Whether the values of the records and the results are masked.
+    privacy: bool,
+    /// The connection in use while it has an `idle_timeout_minute`, to close and reopen it.
+    idle_connection: Option<Connection>,
+    /// Whether the connection has been closed for being idle, greying the screen out.
+    idle_disconnected: bool,
+    /// The variables of the current connection, with those set since connecting.
+    variables: BTreeMap<String, String>,
+    last_query: Option<LastQuery>,
+    undo: UndoLog,
+    session: Session,
+    key_macro: KeyMacro,
+    /// How many times to play the macro once the key which asked for it has been handled.
+    macro_plays: Option<usize>,
+    /// A program to run in the terminal, which main hands over to it.
+    external: Option<External>,
     pub config: Config,
     pub error: ErrorComponent,
 }
 
 impl App {
+    /// Below this size only a notice is drawn.
+    const MIN_WIDTH: u16 = 40;
+    const MIN_HEIGHT: u16 = 10;
+    /// Below this width the database tree is only drawn, as an overlay, while it is focused.
+    const COLLAPSE_TREE_WIDTH: u16 = 80;
+    /// Below this height the tab bar is hidden.
+    const HIDE_TABS_HEIGHT: u16 = 16;
+    const TEST_CONNECTION_TIMEOUT_SECOND: u64 = 3;
+
     pub fn new(config: Config) -> App {
-        Self {
+        let mut app = Self {
             config: config.clone(),
+            config_error: ConfigErrorComponent::new(
+                config.key_config.clone(),
+                config.errors.clone(),
+                config.conn.len(),
+            ),
             connections: ConnectionsComponent::new(config.key_config.clone(), config.conn),
-            record_table: RecordTableComponent::new(config.key_config.clone()),
-            properties: PropertiesComponent::new(config.key_config.clone()),
-            sql_editor: SqlEditorComponent::new(config.key_config.clone()),
+            record_table: RecordTableComponent::new(config.key_config.clone(), config.row_numbers),
+            properties: PropertiesComponent::new(
+                config.key_config.clone(),
+                config.column_comment_lines,
+            ),
+            sql_editor: SqlEditorComponent::new(
+                config.key_config.clone(),
+                config.vim_mode,
+                config.auto_close_brackets,
+            ),
+            server: ServerComponent::new(config.key_config.clone()),
             tab: TabComponent::new(config.key_config.clone()),
             help: HelpComponent::new(config.key_config.clone()),
+            confirm: ConfirmComponent::new(config.key_config.clone()),
+            prompt: PromptComponent::new(config.key_config.clone()),
+            query: QueryComponent::new(config.key_config.clone()),
+            connection_switcher: ConnectionSwitcherComponent::new(config.key_config.clone()),
+            toast: ToastComponent::default(),
+            jobs: JobsComponent::new(config.key_config.clone()),
+            session_variables: SessionVariablesComponent::new(config.key_config.clone()),
+            result_schema: ResultSchemaComponent::new(config.key_config.clone()),
+            external_commands: ExternalCommandsComponent::new(config.key_config.clone()),
+            context_menu: ContextMenuComponent::new(config.key_config.clone()),
+            value_frequency: ValueFrequencyComponent::new(config.key_config.clone()),
+            cell_value: TableValueComponent::new(config.key_config.clone()),
+            column_layouts: ColumnLayouts::default(),
             databases: DatabasesComponent::new(config.key_config.clone()),
             error: ErrorComponent::new(config.key_config),
             focus: Focus::ConnectionList,
             pool: None,
-            left_main_chunk_percentage: 15,
+            editor_pool: None,
+            standby_pool: None,
+            scratch_active: false,
+            scratch_tables: 0,
+            protected: false,
+            privacy: false,
+            idle_connection: None,
+            idle_disconnected: false,
+            variables: BTreeMap::new(),
+            last_query: None,
+            undo: UndoLog::default(),
+            session: Session::default(),
+            key_macro: KeyMacro::default(),
+            macro_plays: None,
+            external: None,
+        };
+        app.sql_editor.set_snippets(app.config.snippet.clone());
+        app.sql_editor.set_row_limit(app.config.editor_row_limit);
+        app.sql_editor
+            .set_max_cell_length(app.config.max_cell_display_length);
+        app.record_table
+            .table
+            .set_max_cell_length(app.config.max_cell_display_length);
+        app
+    }
+
+    /// Shows the errors of the values which failed to decode and are drawn as a placeholder.
+    pub fn show_decode_errors(&mut self) -> anyhow::Result<()> {
+        let errors = take_decode_errors();
+        if errors.is_empty() {
+            return Ok(());
         }
+        self.error.set(format!(
+            "Some values could not be decoded and are shown as <decode error>. They can't be reverted or written back.\n\n{}",
+            errors.join("\n")
+        ))
+    }
+
+    pub fn take_external(&mut self) -> Option<External> {
+        self.external.take()
+    }
+
+    /// Whether the screen changes without any input, so it has to be redrawn on every tick.
+    pub fn is_animating(&self) -> bool {
+        self.jobs.is_running() || self.toast.is_shown() || self.databases.is_filtering()
     }
 
     pub fn draw(&mut self, f: &mut Frame) -> anyhow::Result<()> {
+        self.toast.expire();
+        let size = f.size();
+        if size.width < Self::MIN_WIDTH || size.height < Self::MIN_HEIGHT {
+            let notice = Paragraph::new(format!(
+                "Terminal too small ({}x{})\nResize to at least {}x{}",
+                size.width,
+                size.height,
+                Self::MIN_WIDTH,
+                Self::MIN_HEIGHT
+            ))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true });
+            let area = Rect::new(
+                0,
+                size.height.saturating_sub(2) / 2,
+                size.width,
+                size.height.min(2),
+            );
+            f.render_widget(notice, area);
+            return Ok(());
+        }
+
+        for (message, elapsed) in self.jobs.poll() {
+            self.notify_if_long(elapsed, &message);
+            self.toast.show_message(message);
+        }
+        if let Some(elapsed) = self.sql_editor.take_finished_in() {
+            self.notify_if_long(elapsed, "The query finished");
+        }
+        self.databases.poll();
+
+        self.config_error.draw(f, Rect::default(), false)?;
+        if self.config_error.is_visible() {
+            return Ok(());
+        }
+
         if let Focus::ConnectionList = self.focus {
             match self.connections.draw(
                 f,
@@ -72,29 +293,54 @@ impl App {
                 }
             }
 
+            self.prompt.draw(f, Rect::default(), false)?;
+            self.toast.draw(f, Rect::default(), false)?;
+            if self.idle_disconnected {
+                self.draw_idle_disconnected(f);
+            }
             self.error.draw(f, Rect::default(), false)?;
             self.help.draw(f, Rect::default(), false)?;
             return Ok(());
         }
 
+        let collapse_tree = size.width < Self::COLLAPSE_TREE_WIDTH;
+        let left_main_chunk_percentage = if collapse_tree {
+            0
+        } else {
+            self.session.left_main_chunk_percentage
+        };
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(self.left_main_chunk_percentage),
-                Constraint::Percentage((100_u16).saturating_sub(self.left_main_chunk_percentage)),
+                Constraint::Percentage(left_main_chunk_percentage),
+                Constraint::Percentage((100_u16).saturating_sub(left_main_chunk_percentage)),
             ])
-            .split(f.size());
+            .split(size);
 
-        self.databases
-            .draw(f, main_chunks[0], matches!(self.focus, Focus::DatabaseList))?;
+        if !collapse_tree {
+            self.databases
+                .draw(f, main_chunks[0], matches!(self.focus, Focus::DatabaseList))?;
+        }
 
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
             .flex(Flex::Legacy)
-            .constraints([Constraint::Length(3), Constraint::Length(5)].as_ref())
+            .constraints(
+                [
+                    Constraint::Length(if size.height < Self::HIDE_TABS_HEIGHT {
+                        0
+                    } else {
+                        3
+                    }),
+                    Constraint::Length(5),
+                ]
+                .as_ref(),
+            )
             .split(main_chunks[1]);
 
-        self.tab.draw(f, right_chunks[0], false)?;
+        if size.height >= Self::HIDE_TABS_HEIGHT {
+            self.tab.draw(f, right_chunks[0], false)?;
+        }
 
         match self.tab.selected_tab {
             Tab::Records => {
@@ -109,12 +355,136 @@ impl App {
                 self.properties
                     .draw(f, right_chunks[1], matches!(self.focus, Focus::Table))?;
             }
+            Tab::Server => {
+                self.server
+                    .draw(f, right_chunks[1], matches!(self.focus, Focus::Table))?;
+            }
+        }
+        if collapse_tree && matches!(self.focus, Focus::DatabaseList) {
+            let area = Rect::new(0, 0, (size.width * 3 / 5).max(Self::MIN_WIDTH), size.height);
+            f.render_widget(Clear, area);
+            self.databases.draw(f, area, true)?;
+        }
+        self.prompt.draw(f, Rect::default(), false)?;
+        self.confirm.draw(f, Rect::default(), false)?;
+        self.query.draw(f, Rect::default(), false)?;
+        self.connection_switcher.draw(f, Rect::default(), false)?;
+        self.jobs.draw(f, Rect::default(), false)?;
+        self.session_variables.draw(f, Rect::default(), false)?;
+        self.result_schema.draw(f, Rect::default(), false)?;
+        self.external_commands.draw(f, Rect::default(), false)?;
+        self.context_menu.draw(f, Rect::default(), false)?;
+        self.value_frequency.draw(f, Rect::default(), false)?;
+        self.cell_value.draw(f, Rect::default(), false)?;
+        self.toast.draw(f, Rect::default(), false)?;
+        if self.idle_disconnected {
+            self.draw_idle_disconnected(f);
         }
         self.error.draw(f, Rect::default(), false)?;
         self.help.draw(f, Rect::default(), false)?;
         Ok(())
     }
 
+    /// Greys the screen out and tells how to reconnect.
+    fn draw_idle_disconnected(&self, f: &mut Frame) {
+        let size = f.size();
+        f.buffer_mut()
+            .set_style(size, Style::default().fg(Color::DarkGray).bg(Color::Reset));
+        let minutes = self
+            .idle_connection
+            .as_ref()
+            .and_then(|conn| conn.idle_timeout_minute)
+            .unwrap_or_default();
+        let width = 60.min(size.width);
+        let area = Rect::new(
+            (size.width - width) / 2,
+            size.height.saturating_sub(4) / 2,
+            width,
+            4.min(size.height),
+        );
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(format!(
+                "Disconnected after {} minutes without activity.\nPress [{}] to reconnect.",
+                minutes, self.config.key_config.enter
+            ))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    pub fn load_session(&mut self) -> anyhow::Result<()> {
+        self.session = Session::load(get_app_config_path()?.join("session.ron"));
+        self.sql_editor
+            .set_editor_percentage(self.session.sql_editor_percentage);
+        Ok(())
+    }
+
+    /// Applies a config which was changed while running. A config with errors is not applied.
+    pub fn reload_config(&mut self, config: Config) -> anyhow::Result<()> {
+        if !config.errors.is_empty() {
+            return self.error.set(format!(
+                "config was not reloaded:\n{}",
+                config
+                    .errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ));
+        }
+        let key_config = config.key_config.clone();
+        self.connections = ConnectionsComponent::new(key_config.clone(), config.conn.clone());
+        self.record_table.set_key_config(key_config.clone());
+        self.record_table.table.set_row_numbers(config.row_numbers);
+        self.record_table
+            .table
+            .set_max_cell_length(config.max_cell_display_length);
+        self.properties.set_key_config(key_config.clone());
+        self.properties
+            .set_column_comment_lines(config.column_comment_lines);
+        self.sql_editor.set_key_config(key_config.clone());
+        self.sql_editor
+            .set_options(config.vim_mode, config.auto_close_brackets);
+        self.sql_editor.set_snippets(config.snippet.clone());
+        self.sql_editor.set_row_limit(config.editor_row_limit);
+        self.sql_editor
+            .set_max_cell_length(config.max_cell_display_length);
+        self.server.set_key_config(key_config.clone());
+        self.tab.set_key_config(key_config.clone());
+        self.help.set_key_config(key_config.clone());
+        self.confirm.set_key_config(key_config.clone());
+        self.prompt.set_key_config(key_config.clone());
+        self.query.set_key_config(key_config.clone());
+        self.connection_switcher.set_key_config(key_config.clone());
+        self.jobs.set_key_config(key_config.clone());
+        self.session_variables.set_key_config(key_config.clone());
+        self.result_schema.set_key_config(key_config.clone());
+        self.external_commands.set_key_config(key_config.clone());
+        self.context_menu.set_key_config(key_config.clone());
+        self.value_frequency.set_key_config(key_config.clone());
+        self.cell_value.set_key_config(key_config.clone());
+        self.databases.set_key_config(key_config.clone());
+        self.error.set_key_config(key_config);
+        self.config = config;
+        self.update_commands();
+        Ok(())
+    }
+
+    pub fn load_filter_history(&mut self) -> anyhow::Result<()> {
+        self.record_table.filter.history =
+            FilterHistory::load(get_app_config_path()?.join("filter_history.ron"));
+        Ok(())
+    }
+
+    pub fn load_column_layouts(&mut self) -> anyhow::Result<()> {
+        self.column_layouts =
+            ColumnLayouts::load(get_app_config_path()?.join("column_layouts.ron"));
+        Ok(())
+    }
+
     fn update_commands(&mut self) {
         self.help.set_cmds(self.commands());
     }
@@ -134,102 +504,457 @@ impl App {
             CommandInfo::new(command::extend_or_shorten_widget_width(
                 &self.config.key_config,
             )),
+            CommandInfo::new(command::extend_or_shorten_widget_height(
+                &self.config.key_config,
+            )),
+            CommandInfo::new(command::switch_connection(&self.config.key_config)),
+            CommandInfo::new(command::show_jobs(&self.config.key_config)),
+            CommandInfo::new(command::record_and_play_macro(&self.config.key_config)),
+            CommandInfo::new(command::open_in_pager(&self.config.key_config)),
+            CommandInfo::new(command::open_shell(&self.config.key_config)),
+            CommandInfo::new(command::show_external_commands(&self.config.key_config)),
+            CommandInfo::new(command::toggle_privacy(&self.config.key_config)),
+            CommandInfo::new(command::undo_change(&self.config.key_config)),
+            CommandInfo::new(command::rerun_last_query(&self.config.key_config)),
+            CommandInfo::new(command::set_template_variable(&self.config.key_config)),
         ];
 
         self.databases.commands(&mut res);
         self.record_table.commands(&mut res);
+        self.sql_editor.commands(&mut res);
         self.properties.commands(&mut res);
+        self.server.commands(&mut res);
 
         res
     }
 
+    /// Connects to the selected connection, asking to type its name first if it is protected.
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        match self.connections.selected_connection() {
+            Some(conn) if conn.protected => self.prompt.open(
+                PromptAction::Connect,
+                format!(
+                    "This connection is protected. Type `{}` to connect.",
+                    conn.display_name()
+                ),
+            ),
+            _ => self.update_databases().await,
+        }
+    }
+
     async fn update_databases(&mut self) -> anyhow::Result<()> {
+        if self.connections.selected_connection().is_some() {
+            self.set_scratch_active(false).await?;
+        }
         if let Some(conn) = self.connections.selected_connection() {
             if let Some(pool) = self.pool.as_ref() {
                 pool.close().await;
             }
+            if let Some(pool) = self.editor_pool.take() {
+                pool.close().await;
+            }
 
-            match conn.database_url() {
-                Ok(url) => {
-                    self.pool = if conn.is_mysql() {
-                        Some(Box::new(
-                            MySqlPool::new(url.as_str(), conn.limit_size, conn.timeout_second)
-                                .await?,
-                        ))
-                    } else if conn.is_postgres() {
-                        Some(Box::new(
-                            PostgresPool::new(url.as_str(), conn.limit_size, conn.timeout_second)
-                                .await?,
-                        ))
-                    } else {
-                        Some(Box::new(
-                            SqlitePool::new(url.as_str(), conn.limit_size, conn.timeout_second)
-                                .await?,
-                        ))
-                    };
-                    self.databases
-                        .update(conn, self.pool.as_ref().unwrap())
-                        .await?;
-                    self.focus = Focus::DatabaseList;
-                    self.record_table.reset();
-                    self.tab.reset();
-                }
-                Err(e) => {
-                    return Err(anyhow::anyhow!(e)).context("from Connection::database_url");
+            self.pool = Some(open_pool(conn, conn.timeout_second, MAX_CONNECTIONS).await?);
+            if conn.dedicated_editor_connection {
+                self.editor_pool = Some(open_pool(conn, conn.timeout_second, 1).await?);
+            }
+            self.sql_editor
+                .set_connection(conn.dedicated_editor_connection);
+            self.databases
+                .update(conn, self.pool.as_ref().unwrap())
+                .await?;
+            self.focus = Focus::DatabaseList;
+            self.protected = conn.protected;
+            self.record_table.filter.connection = Some(conn.display_name());
+            self.tab
+                .set_accent(conn.accent.map(|color| (conn.display_name(), color)));
+            self.idle_connection = conn.idle_timeout_minute.map(|_| conn.clone());
+            self.idle_disconnected = false;
+            self.variables = conn.variables.clone();
+            self.sql_editor.set_variables(self.variables.clone());
+            self.record_table.reset();
+            self.server.reset();
+            self.undo.clear();
+            self.last_query = None;
+            self.tab.reset();
+        }
+        Ok(())
+    }
+
+    /// Closes the connection once no key has been pressed for `idle` longer than its
+    /// `idle_timeout_minute`, unless a job is still running. Returns whether it has been closed.
+    pub async fn disconnect_if_idle(&mut self, idle: Duration) -> anyhow::Result<bool> {
+        let Some(minutes) = self
+            .idle_connection
+            .as_ref()
+            .and_then(|conn| conn.idle_timeout_minute)
+        else {
+            return Ok(false);
+        };
+        if self.idle_disconnected
+            || self.jobs.is_running()
+            || idle < Duration::from_secs(minutes * 60)
+        {
+            return Ok(false);
+        }
+        if let Some(pool) = self.pool.as_ref() {
+            pool.close().await;
+        }
+        // While the SQL editor runs on the scratch database, the connection waits as standby.
+        let editor_pool = if self.scratch_active {
+            self.standby_pool.take()
+        } else {
+            self.editor_pool.take()
+        };
+        if let Some(pool) = editor_pool {
+            pool.close().await;
+        }
+        self.idle_disconnected = true;
+        Ok(true)
+    }
+
+    /// Reopens the connection closed for being idle, keeping the screen as it was.
+    async fn reconnect_idle(&mut self) -> anyhow::Result<()> {
+        let Some(conn) = self.idle_connection.as_ref() else {
+            return Ok(());
+        };
+        self.pool = Some(open_pool(conn, conn.timeout_second, MAX_CONNECTIONS).await?);
+        if conn.dedicated_editor_connection {
+            let pool = Some(open_pool(conn, conn.timeout_second, 1).await?);
+            if self.scratch_active {
+                self.standby_pool = pool;
+            } else {
+                self.editor_pool = pool;
+            }
+        }
+        self.idle_disconnected = false;
+        self.toast
+            .show_message(format!("Reconnected to {}", conn.display_name()));
+        Ok(())
+    }
+
+    /// Makes the SQL editor run on the scratch database, which is created the first time, or back
+    /// on the connection.
+    async fn set_scratch_active(&mut self, active: bool) -> anyhow::Result<()> {
+        if active == self.scratch_active {
+            return Ok(());
+        }
+        if active {
+            self.create_scratch().await?;
+        }
+        std::mem::swap(&mut self.editor_pool, &mut self.standby_pool);
+        self.scratch_active = active;
+        self.sql_editor.set_scratch(active);
+        Ok(())
+    }
+
+    /// Creates the scratch database if it does not exist yet. While the SQL editor runs on the
+    /// connection, the scratch database is `standby_pool`.
+    async fn create_scratch(&mut self) -> anyhow::Result<()> {
+        if !self.scratch_active && self.standby_pool.is_none() {
+            self.standby_pool = Some(Box::new(SqlitePool::in_memory(default_limit_size()).await?));
+        }
+        Ok(())
+    }
+
+    /// Copies the SQL editor result shown into a new table of the scratch database.
+    async fn save_result_to_scratch(&mut self) -> anyhow::Result<()> {
+        let Some(table) = self.sql_editor.focused_result() else {
+            return Ok(());
+        };
+        let numeric = (0..table.headers.len())
+            .map(|i| table.is_numeric_column(i))
+            .collect::<Vec<bool>>();
+        let name = format!("result_{}", self.scratch_tables + 1);
+        let statements = copy_result_statements(&name, &table.headers, &table.rows, &numeric);
+        let rows = table.rows.len();
+        self.create_scratch().await?;
+        let scratch = if self.scratch_active {
+            self.editor_pool.as_ref()
+        } else {
+            self.standby_pool.as_ref()
+        }
+        .unwrap();
+        for statement in &statements {
+            scratch.execute(statement).await?;
+        }
+        self.scratch_tables += 1;
+        self.toast.show_message(format!(
+            "Saved {} rows to {} in the scratch database",
+            rows, name
+        ));
+        Ok(())
+    }
+
+    /// Connects to the selected connection without using it, and reports the latency and
+    /// the server version.
+    async fn test_connection(&mut self) -> anyhow::Result<()> {
+        if let Some(conn) = self.connections.selected_connection() {
+            let started_at = Instant::now();
+            let result = match open_pool(
+                conn,
+                conn.timeout_second
+                    .min(Self::TEST_CONNECTION_TIMEOUT_SECOND),
+                1,
+            )
+            .await
+            {
+                Ok(pool) => {
+                    let version = pool.get_version().await;
+                    pool.close().await;
+                    version
                 }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(version) => self.toast.show_message(format!(
+                    "Connected in {} ms: {}",
+                    started_at.elapsed().as_millis(),
+                    version
+                )),
+                Err(e) => self.error.set(format!(
+                    "Failed to connect to {}: {}",
+                    conn.display_name(),
+                    e
+                ))?,
             }
         }
         Ok(())
     }
 
-    async fn update_record_table(&mut self, hold_cursor_position: bool) -> anyhow::Result<()> {
-        if let Some((database, table)) = self.databases.tree().selected_table() {
-            let order_query = self.record_table.table.generate_order_query();
-            let (headers, records) = self
-                .pool
-                .as_ref()
-                .unwrap()
-                .get_records(
-                    &database,
-                    &table,
-                    0,
-                    if self.record_table.filter.input_str().is_empty() {
-                        None
-                    } else {
-                        Some(self.record_table.filter.input_str())
-                    },
-                    order_query,
-                )
-                .await?;
-            let total_row_count = self
-                .pool
+    /// The query executed for the current records view.
+    fn records_query(&self) -> Option<String> {
+        let (database, table) = self.databases.tree().selected_table()?;
+        Some(
+            self.pool
                 .as_ref()
                 .unwrap()
-                .get_total_row_count(
-                    &database,
-                    &table,
-                    if self.record_table.filter.input_str().is_empty() {
-                        None
-                    } else {
-                        Some(self.record_table.filter.input_str())
-                    },
-                )
+                .records_query(&self.record_table.query.builder(database, table)),
+        )
+    }
+
+    async fn update_record_table(&mut self, hold_cursor_position: bool) -> anyhow::Result<()> {
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            self.load_records(database, table, hold_cursor_position)
                 .await?;
+        }
+        Ok(())
+    }
 
-            let header_icons = self.record_table.table.generate_header_icons(headers.len());
-            self.record_table.update(
-                records,
-                Some(total_row_count),
-                self.concat_headers(headers, Some(header_icons)),
-                database.clone(),
-                table.clone(),
-                hold_cursor_position,
-            );
+    /// Reads the records of `table` into the records tab.
+    async fn load_records(
+        &mut self,
+        database: Database,
+        table: Table,
+        hold_cursor_position: bool,
+    ) -> anyhow::Result<()> {
+        let query = self
+            .record_table
+            .query
+            .builder(database.clone(), table.clone());
+        let (headers, records) = self.pool.as_ref().unwrap().get_records(&query).await?;
+        let total_row_count = self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_total_row_count(&query)
+            .await?;
+        let column_types = self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_columns(&database, &table)
+            .await?
+            .iter()
+            .filter_map(|column| {
+                let columns = column.columns();
+                Some((columns.first()?.clone(), columns.get(1)?.clone()))
+            })
+            .collect::<Vec<(String, String)>>();
+        let numeric_columns = headers
+            .iter()
+            .map(|header| {
+                column_types
+                    .iter()
+                    .any(|(name, r#type)| name == header && is_numeric_type(r#type))
+            })
+            .collect::<Vec<bool>>();
+        let binary_columns = headers
+            .iter()
+            .map(|header| {
+                column_types
+                    .iter()
+                    .any(|(name, r#type)| name == header && is_binary_type(r#type))
+            })
+            .collect::<Vec<bool>>();
+        let geometry_columns = headers
+            .iter()
+            .map(|header| {
+                column_types
+                    .iter()
+                    .any(|(name, r#type)| name == header && is_geometry_type(r#type))
+            })
+            .collect::<Vec<bool>>();
+
+        let header_icons = self.record_table.query.header_icons(headers.len());
+        self.record_table.update(
+            records,
+            Some(total_row_count),
+            self.concat_headers(headers, Some(header_icons)),
+            database.clone(),
+            table.clone(),
+            hold_cursor_position,
+        );
+        if !column_types.is_empty() {
+            self.record_table.table.set_numeric_columns(numeric_columns);
+            self.record_table.table.set_binary_columns(binary_columns);
+            self.record_table
+                .table
+                .set_geometry_columns(geometry_columns);
+        }
+        self.last_query = Some(LastQuery::Records);
+        Ok(())
+    }
+
+    /// Runs the last SQL editor statement or records query again and shows its result.
+    async fn rerun_last_query(&mut self) -> anyhow::Result<()> {
+        match self.last_query.clone() {
+            Some(LastQuery::Editor(query)) => self.run_in_editor(query).await?,
+            Some(LastQuery::Records) => {
+                if let Some((database, table)) = self.record_table.table.source().cloned() {
+                    self.tab.selected_tab = Tab::Records;
+                    self.focus = Focus::Table;
+                    self.load_records(database, table, true).await?;
+                }
+            }
+            None => self
+                .toast
+                .show_message("No query has been run yet".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Runs `query` in the SQL editor and shows its result, asking first on a protected
+    /// connection.
+    async fn run_in_editor(&mut self, query: String) -> anyhow::Result<()> {
+        self.tab.selected_tab = Tab::Sql;
+        self.focus = Focus::Table;
+        if self.protected && !self.scratch_active && !is_read_only(&query) {
+            self.confirm.open(
+                ConfirmAction::ExecuteQuery {
+                    query: query.clone(),
+                },
+                format!(
+                    "This connection is protected. Execute the following statement?\n\n{}",
+                    query
+                ),
+            )?;
+            return Ok(());
+        }
+        self.sql_editor
+            .execute(
+                query,
+                self.editor_pool
+                    .as_ref()
+                    .or(self.pool.as_ref())
+                    .unwrap()
+                    .as_ref(),
+            )
+            .await?;
+        self.take_editor_execution();
+        Ok(())
+    }
+
+    /// Runs the action chosen in the error popup.
+    async fn run_error_action(&mut self, action: ErrorAction) -> anyhow::Result<()> {
+        match action {
+            ErrorAction::Retry(query) => match query {
+                Some(query) => self.run_in_editor(query).await?,
+                None => self.update_databases().await?,
+            },
+            ErrorAction::EditQuery(query) => {
+                self.sql_editor.set_query(&query);
+                self.tab.selected_tab = Tab::Sql;
+                self.focus = Focus::Table;
+            }
+            ErrorAction::OpenLog => match std::fs::read_to_string(log_file()) {
+                Ok(log) => self.external = Some(External::Pager(log)),
+                Err(_) => self.error.set(format!(
+                    "There is no log. Debug builds write {}.",
+                    log_file().display()
+                ))?,
+            },
         }
         Ok(())
     }
 
+    /// Rings the bell or shows a desktop notification of `message` when a query or job took
+    /// `notify_after_second` or longer, so the user can wait in another window.
+    fn notify_if_long(&self, elapsed: Duration, message: &str) {
+        // The notification is a convenience, so failing to write it is not an error.
+        let _ = self.config.notification.notify_if_long(
+            self.config.notify_after_second,
+            elapsed,
+            &format!("zhobo: {}", message),
+        );
+    }
+
+    /// Records the statement the SQL editor ran and the change to undo it.
+    fn take_editor_execution(&mut self) {
+        if let Some(query) = self.sql_editor.take_executed() {
+            self.last_query = Some(LastQuery::Editor(query));
+        }
+        if let Some(change) = self.sql_editor.take_change() {
+            self.undo.push(change);
+        }
+    }
+
     pub async fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if self.config_error.is_visible() {
+            return self.config_error.event(key);
+        }
+
+        if key == self.config.key_config.record_macro {
+            if self.key_macro.is_recording() {
+                let len = self.key_macro.stop_recording();
+                self.toast
+                    .show_message(format!("Recorded a macro of {} keys", len));
+            } else {
+                self.key_macro.start_recording();
+                self.toast.show_message("Recording a macro".to_string());
+            }
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.play_macro {
+            if self.key_macro.is_recording() {
+                self.error
+                    .set("Stop recording the macro before playing it".to_string())?;
+            } else if self.key_macro.len() == 0 {
+                self.error.set("No macro has been recorded".to_string())?;
+            } else {
+                self.prompt.open(
+                    PromptAction::PlayMacro,
+                    format!(
+                        "Enter how many times to play the macro of {} keys.",
+                        self.key_macro.len()
+                    ),
+                )?;
+                self.prompt.set_input("1");
+            }
+            return Ok(EventState::Consumed);
+        }
+        self.key_macro.record(key);
+
+        let state = self.key_event(key).await?;
+        if let Some(count) = self.macro_plays.take() {
+            for key in self.key_macro.playback(count) {
+                self.key_event(key).await?;
+            }
+        }
+        Ok(state)
+    }
+
+    async fn key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         self.update_commands();
 
         if self.components_event(key).await?.is_consumed() {
@@ -244,6 +969,207 @@ impl App {
 
     async fn components_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         if self.error.event(key)?.is_consumed() {
+            if let Some(action) = self.error.take_action() {
+                self.run_error_action(action).await?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.idle_disconnected {
+            if key == self.config.key_config.enter {
+                self.reconnect_idle().await?;
+            }
+            if key == self.config.key_config.quit || key == self.config.key_config.exit {
+                return Ok(EventState::NotConsumed);
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.confirm.event(key)?.is_consumed() {
+            if let Some(action) = self.confirm.take_confirmed() {
+                self.run_confirmed_action(action).await?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.prompt.event(key)?.is_consumed() {
+            if let Some((action, value)) = self.prompt.take_submitted() {
+                self.run_prompt_action(action, value).await?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.query.event(key)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+
+        if self.connection_switcher.event(key)?.is_consumed() {
+            if let Some(index) = self.connection_switcher.take_selected() {
+                self.connections.select(index);
+                self.connect().await?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.jobs.event(key)?.is_consumed() {
+            if let Some(error) = self.jobs.take_error() {
+                self.error.set(error)?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.session_variables.event(key)?.is_consumed() {
+            if let Some((name, value)) = self.session_variables.take_edit() {
+                self.prompt.open(
+                    PromptAction::SetSessionVariable { name: name.clone() },
+                    format!(
+                        "Enter the value of {} for the SQL editor session (DEFAULT to reset).",
+                        name
+                    ),
+                )?;
+                self.prompt.set_input(&value);
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.result_schema.event(key)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.rerun_last_query
+            && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.rerun_last_query().await?;
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.set_template_variable
+            && !matches!(self.focus, Focus::ConnectionList)
+        {
+            let defined = self
+                .variables
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<String>>();
+            self.prompt.open(
+                PromptAction::SetTemplateVariable,
+                format!(
+                    "Enter `name = value` to replace {{{{name}}}} in statements and filters on this connection, or `name =` to remove it.\n\nDefined: {}",
+                    if defined.is_empty() {
+                        "none".to_string()
+                    } else {
+                        defined.join(", ")
+                    }
+                ),
+            )?;
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.show_jobs && !matches!(self.focus, Focus::ConnectionList) {
+            self.jobs.show()?;
+            return Ok(EventState::Consumed);
+        }
+
+        if self.external_commands.event(key)?.is_consumed() {
+            if let Some(command) = self.external_commands.take_selected() {
+                if let Some(conn) = self.connections.selected_connection() {
+                    let selected = self.databases.tree().selected_table();
+                    let placeholders = Placeholders {
+                        database: selected.as_ref().map(|(database, _)| database.name.clone()),
+                        schema: selected
+                            .as_ref()
+                            .and_then(|(_, table)| table.schema.clone()),
+                        table: selected.as_ref().map(|(_, table)| table.name.clone()),
+                        connection_url: conn.database_url_without_password().ok(),
+                        connection: Some(conn.display_name()),
+                    };
+                    self.external = Some(External::Command {
+                        command: command.expand(&placeholders)?,
+                        env: conn.shell_env(),
+                    });
+                }
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.context_menu.event(key)?.is_consumed() {
+            if let Some(action) = self.context_menu.take_selected() {
+                self.run_tree_action(action).await?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.cell_value.event(key)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+
+        if self.value_frequency.event(key)?.is_consumed() {
+            if let Some((column, value)) = self.value_frequency.take_selected() {
+                let pool = self.pool.as_ref().unwrap();
+                let binary = self
+                    .record_table
+                    .table
+                    .selected_cell()
+                    .is_some_and(|(_, _, binary)| binary);
+                let condition = CellFilter::Equal.condition(
+                    &pool.quote_identifier(&column),
+                    &pool.cell_literal(&value, binary),
+                );
+                self.record_table
+                    .and_filter(pool.as_ref(), &self.variables, &condition)?;
+                self.update_record_table(false).await?;
+                self.record_table.filter.push_history()?;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.toggle_privacy {
+            self.privacy = !self.privacy;
+            self.record_table.table.set_masked(self.privacy);
+            self.sql_editor.set_masked(self.privacy);
+            self.value_frequency.set_masked(self.privacy);
+            let connection = self
+                .connections
+                .selected_connection()
+                .map(|conn| conn.display_name())
+                .unwrap_or_default();
+            audit::record(
+                &get_app_config_path()?.join(audit::FILE),
+                &if self.privacy {
+                    format!("privacy mode turned on for {}", connection)
+                } else {
+                    format!("values unmasked for {}", connection)
+                },
+            )?;
+            self.toast.show_message(
+                if self.privacy {
+                    "Privacy mode is on: values are masked"
+                } else {
+                    "Privacy mode is off"
+                }
+                .to_string(),
+            );
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.show_external_commands {
+            self.external_commands
+                .open(self.config.external_command.clone())?;
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.open_shell {
+            match self.connections.selected_connection() {
+                Some(conn) => self.external = Some(External::Shell(conn.shell_env())),
+                None => self.error.set("No connection is selected".to_string())?,
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.switch_connection
+            && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.connection_switcher.open(self.connections.names())?;
             return Ok(EventState::Consumed);
         }
 
@@ -258,7 +1184,12 @@ impl App {
                 }
 
                 if key == self.config.key_config.enter {
-                    self.update_databases().await?;
+                    self.connect().await?;
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.test_connection {
+                    self.test_connection().await?;
                     return Ok(EventState::Consumed);
                 }
             }
@@ -268,35 +1199,49 @@ impl App {
                 }
 
                 if key == self.config.key_config.enter && self.databases.tree_focused() {
-                    if let Some((database, table)) = self.databases.tree().selected_table() {
-                        self.record_table.reset();
-                        let (headers, records) = self
-                            .pool
-                            .as_ref()
-                            .unwrap()
-                            .get_records(&database, &table, 0, None, None)
-                            .await?;
-                        let total_row_count = self
-                            .pool
-                            .as_ref()
-                            .unwrap()
-                            .get_total_row_count(&database, &table, None)
-                            .await?;
-                        self.record_table.update(
-                            records,
-                            Some(total_row_count),
-                            headers,
-                            database.clone(),
-                            table.clone(),
-                            false,
-                        );
-                        self.properties
-                            .update(database.clone(), table.clone(), self.pool.as_ref().unwrap())
-                            .await?;
+                    if let (Some(name), Some(_), Some(conn)) = (
+                        self.databases.tree().selected_name(),
+                        self.databases.tree().selected_database_error(),
+                        self.connections.selected_connection(),
+                    ) {
+                        if let Some(error) = self
+                            .databases
+                            .retry_selected(conn, self.pool.as_deref().unwrap())
+                            .await?
+                        {
+                            self.error
+                                .set(format!("Could not list the tables of {}: {}", name, error))?;
+                        }
+                        return Ok(EventState::Consumed);
+                    }
+                    if self.open_selected_table().await? {
                         self.focus = Focus::Table;
                     }
                     return Ok(EventState::Consumed);
                 }
+
+                if key == self.config.key_config.copy && self.databases.tree_focused() {
+                    if let Some((database, table)) = self.databases.tree().selected_table() {
+                        copy_to_clipboard(
+                            &self
+                                .pool
+                                .as_ref()
+                                .unwrap()
+                                .table_identifier(&database, &table),
+                        )?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.context_menu && self.databases.tree_focused() {
+                    if let Some(name) = self.databases.tree().selected_name() {
+                        self.context_menu.open(
+                            name,
+                            TreeAction::for_node(self.databases.tree().selected_table().is_some()),
+                        )?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
             }
             Focus::Table => {
                 match self.tab.selected_tab {
@@ -305,24 +1250,416 @@ impl App {
                             return Ok(EventState::Consumed);
                         };
 
-                        if key == self.config.key_config.sort_by_column
+                        if (key == self.config.key_config.sort_by_column
+                            || (key == self.config.key_config.enter
+                                && self.record_table.table.header_focused()))
                             && !self.record_table.table.headers.is_empty()
                         {
-                            self.record_table.table.add_order();
+                            self.record_table.toggle_order();
                             self.update_record_table(true).await?;
                             return Ok(EventState::Consumed);
                         };
 
+                        if key == self.config.key_config.show_query
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            if let Some(query) = self.records_query() {
+                                self.query.open(query)?;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.open_query_in_editor
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            if let Some(query) = self.records_query() {
+                                self.sql_editor.set_query(&query);
+                                self.tab.selected_tab = Tab::Sql;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.focus_header
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            let focused = self.record_table.table.header_focused();
+                            self.record_table.table.set_header_focus(!focused);
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.exit_popup
+                            && self.record_table.table.header_focused()
+                        {
+                            self.record_table.table.set_header_focus(false);
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.bulk_update
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            if let Some(column) = self.selected_column_name().await? {
+                                if let Some((database, table)) =
+                                    self.databases.tree().selected_table()
+                                {
+                                    let columns = self
+                                        .pool
+                                        .as_ref()
+                                        .unwrap()
+                                        .get_columns(&database, &table)
+                                        .await?;
+                                    if generated_columns(&columns).contains(&column) {
+                                        return Err(anyhow::anyhow!(
+                                            "{} is a generated column, which can't be updated",
+                                            column
+                                        ));
+                                    }
+                                }
+                                self.prompt.open(
+                                    PromptAction::BulkUpdate {
+                                        column: column.clone(),
+                                    },
+                                    format!(
+                                        "Set the value of {} for all filtered rows. Type NULL to set NULL.",
+                                        column
+                                    ),
+                                )?;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if (key == self.config.key_config.copy_distinct_values
+                            || key == self.config.key_config.copy_distinct_values_as_in_list)
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            self.ensure_unmasked()?;
+                            if let (Some(column), Some((database, table))) = (
+                                self.selected_column_name().await?,
+                                self.databases.tree().selected_table(),
+                            ) {
+                                let pool = self.pool.as_ref().unwrap();
+                                let query = QueryBuilder::new(database, table)
+                                    .columns(format!("DISTINCT {}", pool.quote_identifier(&column)))
+                                    .filter(self.record_table.query.filter())
+                                    .select(pool.as_ref());
+                                if let ExecuteResult::Read { rows, types, .. } =
+                                    pool.execute(&query).await?
+                                {
+                                    let values = rows
+                                        .into_iter()
+                                        .filter_map(|row| row.into_iter().next())
+                                        .collect::<Vec<String>>();
+                                    let binary = types.first().is_some_and(|t| is_binary_type(t));
+                                    copy_to_clipboard(
+                                        if key == self.config.key_config.copy_distinct_values {
+                                            values.join("\n")
+                                        } else {
+                                            in_list(
+                                                &pool.quote_identifier(&column),
+                                                &values
+                                                    .iter()
+                                                    .map(|value| pool.cell_literal(value, binary))
+                                                    .collect::<Vec<String>>(),
+                                            )
+                                        }
+                                        .as_str(),
+                                    )?;
+                                }
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.show_cell_value {
+                            self.ensure_unmasked()?;
+                            if let (Some(value), Some((database, table))) = (
+                                self.record_table.table.content(),
+                                self.record_table.table.source().cloned(),
+                            ) {
+                                self.cell_value.open(value, database, table)?;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.value_frequency
+                            && !self.record_table.table.headers.is_empty()
+                        {
+                            self.ensure_unmasked()?;
+                            if let (Some(column), Some((database, table))) = (
+                                self.selected_column_name().await?,
+                                self.databases.tree().selected_table(),
+                            ) {
+                                let pool = self.pool.as_ref().unwrap();
+                                let query = QueryBuilder::new(database, table)
+                                    .filter(self.record_table.query.filter())
+                                    .frequency(
+                                        pool.as_ref(),
+                                        &column,
+                                        ValueFrequencyComponent::LIMIT,
+                                    );
+                                if let ExecuteResult::Read { rows, .. } =
+                                    pool.execute(&query).await?
+                                {
+                                    self.value_frequency.open(column, rows)?;
+                                }
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.generate_data {
+                            if let Some((database, table)) = self.databases.tree().selected_table()
+                            {
+                                let columns = self
+                                    .pool
+                                    .as_ref()
+                                    .unwrap()
+                                    .get_columns(&database, &table)
+                                    .await?;
+                                let generated = generated_columns(&columns);
+                                let columns = columns
+                                    .iter()
+                                    .map(|column| column.columns())
+                                    .filter(|column| {
+                                        column.first().is_some_and(|name| !generated.contains(name))
+                                    })
+                                    .collect::<Vec<Vec<String>>>();
+                                self.prompt.open(
+                                    PromptAction::GenerateData,
+                                    "Enter the number of rows and `column=generator` pairs. Generators: seq(start), int(min,max), string(len), choice(a|b), name, first_name, last_name, email, null.".to_string(),
+                                )?;
+                                self.prompt
+                                    .set_input(&MockSpec::infer(10, &columns).to_string());
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.export_records {
+                            self.ensure_unmasked()?;
+                            if let Some((_, table)) = self.databases.tree().selected_table() {
+                                self.prompt.open_with_choices(
+                                    PromptAction::ExportRecords,
+                                    "Enter the path to export the filtered records to. The extension picks the format: .csv, .jsonl, .parquet or .xlsx. Add .gz or .zst to compress CSV and JSONL, or name Parquet .zstd.parquet, .gzip.parquet or .uncompressed.parquet instead of snappy.".to_string(),
+                                    export_scopes(),
+                                )?;
+                                self.prompt.set_input(&format!("{}.csv", table.name));
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.paste_rows {
+                            if let Some((database, table)) = self.databases.tree().selected_table()
+                            {
+                                let pool = self.pool.as_ref().unwrap();
+                                let columns = pool.get_columns(&database, &table).await?;
+                                // Generated columns are ignored like unknown ones.
+                                let generated = generated_columns(&columns);
+                                let binary = columns
+                                    .iter()
+                                    .filter_map(|column| {
+                                        let columns = column.columns();
+                                        is_binary_type(columns.get(1)?)
+                                            .then(|| columns.first().cloned())?
+                                    })
+                                    .collect::<Vec<String>>();
+                                let columns = columns
+                                    .iter()
+                                    .filter_map(|column| column.columns().first().cloned())
+                                    .filter(|name| !generated.contains(name))
+                                    .collect::<Vec<String>>();
+                                let pasted = paste::parse(&paste_from_clipboard()?, &columns)?;
+                                // Hex copied from a binary column goes back in as a binary literal.
+                                let binary = pasted
+                                    .columns
+                                    .iter()
+                                    .map(|column| binary.contains(column))
+                                    .collect::<Vec<bool>>();
+                                let table_identifier = pool.table_identifier(&database, &table);
+                                let statements = insert_statements(
+                                    &table_identifier,
+                                    &pasted
+                                        .columns
+                                        .iter()
+                                        .map(|column| pool.quote_identifier(column))
+                                        .collect::<Vec<String>>(),
+                                    &pasted
+                                        .rows
+                                        .iter()
+                                        .map(|row| {
+                                            row.iter()
+                                                .zip(&binary)
+                                                .map(|(v, binary)| pool.cell_literal(v, *binary))
+                                                .collect()
+                                        })
+                                        .collect::<Vec<Vec<String>>>(),
+                                );
+                                let mut message = format!(
+                                    "Insert {} row(s) into {}.\nColumns: {}",
+                                    pasted.rows.len(),
+                                    table_identifier,
+                                    pasted.columns.join(", ")
+                                );
+                                if !pasted.ignored.is_empty() {
+                                    message = format!(
+                                        "{}\nIgnored: {}",
+                                        message,
+                                        pasted.ignored.join(", ")
+                                    );
+                                }
+                                if let Some(row) = pasted.rows.first() {
+                                    message =
+                                        format!("{}\n\nFirst row: {}", message, row.join(", "));
+                                }
+                                self.confirm.open(
+                                    ConfirmAction::InsertRows {
+                                        statements,
+                                        summary: format!(
+                                            "Paste {} rows into {}",
+                                            pasted.rows.len(),
+                                            table_identifier
+                                        ),
+                                    },
+                                    message,
+                                )?;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.copy_rows_to_connection {
+                            self.ensure_unmasked()?;
+                            if let Some((_, table)) = self.record_table.table.source() {
+                                let connections = (0..self.connections.connections().len())
+                                    .collect::<Vec<usize>>();
+                                let choices = connections
+                                    .iter()
+                                    .flat_map(|i| {
+                                        let name =
+                                            self.connections.connections()[*i].display_name();
+                                        [
+                                            format!("{}: filtered records", name),
+                                            format!("{}: selected records", name),
+                                        ]
+                                    })
+                                    .collect();
+                                let name = table.name.clone();
+                                self.prompt.open_with_choices(
+                                    PromptAction::CopyRows { connections },
+                                    "Enter the table to copy the records to, as `table` or `table (column, column AS target, ...)` to pick and rename the columns. Press Tab to pick the connection and the records.".to_string(),
+                                    choices,
+                                )?;
+                                self.prompt.set_input(&name);
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
                         if key == self.config.key_config.copy {
+                            self.ensure_unmasked()?;
                             if let Some(text) = self.record_table.table.content() {
                                 copy_to_clipboard(text.as_str())?
                             }
                         }
 
+                        if key == self.config.key_config.extract_json_path {
+                            self.open_json_path_prompt().await?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        let cell_filter = if key == self.config.key_config.exclude_cell_value {
+                            Some(CellFilter::Exclude)
+                        } else if key == self.config.key_config.filter_null {
+                            Some(CellFilter::Null)
+                        } else if key == self.config.key_config.filter_not_null {
+                            Some(CellFilter::NotNull)
+                        } else {
+                            None
+                        };
+                        if let Some(cell_filter) = cell_filter {
+                            // The value is written into the filter, which is not masked.
+                            if cell_filter == CellFilter::Exclude {
+                                self.ensure_unmasked()?;
+                            }
+                            let pool = self.pool.as_ref().unwrap();
+                            if self.record_table.filter_cell(
+                                pool.as_ref(),
+                                &self.variables,
+                                cell_filter,
+                            )? {
+                                self.update_record_table(false).await?;
+                                self.record_table.filter.push_history()?;
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.hide_column {
+                            if let (Some(column), Some((database, table))) = (
+                                self.selected_column_name().await?,
+                                self.databases.tree().selected_table(),
+                            ) {
+                                let mut layout = self
+                                    .column_layouts
+                                    .get(&self.layout_key(&database, &table))
+                                    .cloned()
+                                    .unwrap_or_default();
+                                layout.hidden.push(column);
+                                self.set_column_layout(layout).await?;
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.edit_column_layout {
+                            if let Some((database, table)) = self.databases.tree().selected_table()
+                            {
+                                let columns = self.column_names(&database, &table).await?;
+                                let input = self
+                                    .column_layouts
+                                    .get(&self.layout_key(&database, &table))
+                                    .cloned()
+                                    .unwrap_or_default()
+                                    .to_input(&columns);
+                                self.prompt.open(
+                                    PromptAction::EditColumnLayout,
+                                    format!(
+                                        "Enter the columns of {} in order, separated by commas. Prefix a column with ^ to pin it first or with - to hide it. Columns left out are shown last, and an empty layout shows every column.",
+                                        table.name
+                                    ),
+                                )?;
+                                self.prompt.set_input(&input);
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.add_computed_column {
+                            self.open_computed_column_prompt()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.copy_as_literal {
+                            self.ensure_unmasked()?;
+                            let pool = self.pool.as_ref().unwrap();
+                            if let Some(text) =
+                                self.record_table
+                                    .table
+                                    .content_as_literals(|value, binary| {
+                                        pool.cell_literal(value, binary)
+                                    })
+                            {
+                                copy_to_clipboard(text.as_str())?
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.open_in_pager {
+                            self.ensure_unmasked()?;
+                            self.external = self.record_table.table.content().map(External::Pager);
+                            return Ok(EventState::Consumed);
+                        }
+
                         if key == self.config.key_config.enter && self.record_table.filter_focused()
                         {
                             self.record_table.focus = crate::components::record_table::Focus::Table;
+                            self.record_table.apply_filter(
+                                self.pool.as_ref().unwrap().as_ref(),
+                                &self.variables,
+                            )?;
                             self.update_record_table(false).await?;
+                            self.record_table.filter.push_history()?;
                         }
 
                         if self.record_table.table.eod {
@@ -342,22 +1679,13 @@ impl App {
                                 if let Some((database, table)) =
                                     self.databases.tree().selected_table()
                                 {
-                                    let (_, records) = self
-                                        .pool
-                                        .as_ref()
-                                        .unwrap()
-                                        .get_records(
-                                            &database,
-                                            &table,
-                                            index.saturating_add(1) as u16,
-                                            if self.record_table.filter.input_str().is_empty() {
-                                                None
-                                            } else {
-                                                Some(self.record_table.filter.input_str())
-                                            },
-                                            None,
-                                        )
-                                        .await?;
+                                    let query = self
+                                        .record_table
+                                        .query
+                                        .builder(database, table)
+                                        .offset(index.saturating_add(1));
+                                    let (_, records) =
+                                        self.pool.as_ref().unwrap().get_records(&query).await?;
                                     if !records.is_empty() {
                                         self.record_table.table.rows.extend(records);
                                     } else {
@@ -368,32 +1696,947 @@ impl App {
                         };
                     }
                     Tab::Sql => {
+                        if key == self.config.key_config.show_session_variables {
+                            self.session_variables.open(
+                                self.sql_editor.session_variables(),
+                                self.editor_pool
+                                    .as_ref()
+                                    .or(self.pool.as_ref())
+                                    .unwrap()
+                                    .session_variable_names(),
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.toggle_scratch_database {
+                            self.set_scratch_active(!self.scratch_active).await?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.save_result_to_scratch {
+                            self.save_result_to_scratch().await?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.show_result_schema {
+                            self.result_schema
+                                .open(self.sql_editor.result_columns().to_vec())?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.open_in_pager
+                            && !self.sql_editor.editor_focused()
+                        {
+                            self.ensure_unmasked()?;
+                            self.external = self
+                                .sql_editor
+                                .focused_result()
+                                .and_then(|table| table.content())
+                                .map(External::Pager);
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.export_records
+                            && !self.sql_editor.editor_focused()
+                        {
+                            self.ensure_unmasked()?;
+                            if !self.sql_editor.results().is_empty() {
+                                self.prompt.open_with_choices(
+                                    PromptAction::ExportResults,
+                                    "Enter the path to export the result to. The extension picks the format, as for records. The entire result to .xlsx exports every pinned result, a sheet each.".to_string(),
+                                    export_scopes(),
+                                )?;
+                                self.prompt.set_input("results.xlsx");
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.copy_as_literal
+                            && !self.sql_editor.editor_focused()
+                        {
+                            self.ensure_unmasked()?;
+                            let pool = self.editor_pool.as_ref().or(self.pool.as_ref()).unwrap();
+                            if let Some(text) = self.sql_editor.focused_result().and_then(|table| {
+                                table.content_as_literals(|value, binary| {
+                                    pool.cell_literal(value, binary)
+                                })
+                            }) {
+                                copy_to_clipboard(text.as_str())?
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.copy_result_as_csv
+                            && !self.sql_editor.editor_focused()
+                        {
+                            if let Some(table) = self.sql_editor.focused_result() {
+                                self.ensure_unmasked()?;
+                                let csv = table.csv();
+                                if table.rows.len() > self.config.copy_warning_rows {
+                                    let message = format!(
+                                        "Copy {} rows ({} bytes) to the clipboard?",
+                                        table.rows.len(),
+                                        csv.len()
+                                    );
+                                    self.confirm.open(
+                                        ConfirmAction::CopyToClipboard { text: csv },
+                                        message,
+                                    )?;
+                                } else {
+                                    copy_to_clipboard(&csv)?;
+                                }
+                                return Ok(EventState::Consumed);
+                            }
+                        }
+
+                        if self.protected
+                            && !self.scratch_active
+                            && key == self.config.key_config.enter
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.find_replace_visible()
+                            && !self.sql_editor.completing()
+                            && !is_read_only(&self.sql_editor.query())
+                        {
+                            let query = self.sql_editor.query();
+                            self.confirm.open(
+                                ConfirmAction::ExecuteQuery {
+                                    query: query.clone(),
+                                },
+                                format!(
+                                    "This connection is protected. Execute the following statement?\n\n{}",
+                                    query
+                                ),
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
                         if self.sql_editor.event(key)?.is_consumed()
                             || self
                                 .sql_editor
-                                .async_event(key, self.pool.as_ref().unwrap())
+                                .async_event(
+                                    key,
+                                    self.editor_pool.as_ref().or(self.pool.as_ref()).unwrap(),
+                                )
                                 .await?
                                 .is_consumed()
                         {
+                            self.take_editor_execution();
+                            if let Some(notice) = self.sql_editor.take_notice() {
+                                self.toast.show_message(notice);
+                            }
                             return Ok(EventState::Consumed);
                         };
                     }
                     Tab::Properties => {
+                        if key == self.config.key_config.open_in_pager {
+                            self.external = self.properties.content().map(External::Pager);
+                            return Ok(EventState::Consumed);
+                        }
+                        if key == self.config.key_config.edit_comment {
+                            if let Some((column, comment)) = self.properties.comment_target() {
+                                let message = match &column {
+                                    Some(column) => format!("Comment on the column {}", column),
+                                    None => "Comment on the table".to_string(),
+                                };
+                                self.prompt
+                                    .open(PromptAction::SetComment { column }, message)?;
+                                self.prompt.set_input(&comment);
+                            }
+                            return Ok(EventState::Consumed);
+                        }
                         if self.properties.event(key)?.is_consumed() {
                             return Ok(EventState::Consumed);
                         };
                     }
+                    Tab::Server => {
+                        if self.server.event(key)?.is_consumed()
+                            || self
+                                .server
+                                .async_event(key, self.pool.as_ref().unwrap())
+                                .await?
+                                .is_consumed()
+                        {
+                            return Ok(EventState::Consumed);
+                        };
+                    }
                 };
             }
         }
 
+        if key == self.config.key_config.undo_change && !matches!(self.focus, Focus::ConnectionList)
+        {
+            match self.undo.last() {
+                Some(change) => self.confirm.open(
+                    ConfirmAction::RevertChange,
+                    format!(
+                        "Revert the following change?\n\n{}\n\nThis runs in a transaction:\n\n{}",
+                        change.statement,
+                        undo::preview(&change.inverse)
+                    ),
+                )?,
+                None => self.error.set("There is no change to revert".to_string())?,
+            }
+            return Ok(EventState::Consumed);
+        }
+
         if self.extend_or_shorten_widget_width(key)?.is_consumed() {
             return Ok(EventState::Consumed);
         };
 
+        if matches!(self.tab.selected_tab, Tab::Sql)
+            && self.extend_or_shorten_widget_height(key)?.is_consumed()
+        {
+            return Ok(EventState::Consumed);
+        };
+
         Ok(EventState::NotConsumed)
     }
 
+    async fn run_confirmed_action(&mut self, action: ConfirmAction) -> anyhow::Result<()> {
+        match action {
+            ConfirmAction::RevertChange => {
+                if let Some(change) = self.undo.last() {
+                    self.pool
+                        .as_ref()
+                        .unwrap()
+                        .execute_in_transaction(&change.inverse)
+                        .await?;
+                    self.undo.pop();
+                    if !self.record_table.table.headers.is_empty() {
+                        self.update_record_table(true).await?;
+                    }
+                }
+            }
+            ConfirmAction::BulkUpdate { query } => {
+                let pool = self.pool.as_ref().unwrap();
+                let inverse = undo::inverse_statements(pool.as_ref(), &query)
+                    .await
+                    .unwrap_or(None);
+                pool.execute(&query).await?;
+                if let Some(inverse) = inverse {
+                    self.undo.push(UndoEntry {
+                        statement: query,
+                        inverse,
+                    });
+                }
+                self.update_record_table(true).await?;
+            }
+            ConfirmAction::InsertRows {
+                statements,
+                summary,
+            } => self.insert_rows(statements, summary).await?,
+            ConfirmAction::CopyToClipboard { text } => copy_to_clipboard(&text)?,
+            ConfirmAction::CopyRows {
+                connection,
+                target,
+                selected,
+            } => self.copy_rows(connection, target, selected).await?,
+            ConfirmAction::SetComment { query } => {
+                let pool = self.pool.as_ref().unwrap();
+                pool.execute(&query).await?;
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    self.properties.update(database, table, pool).await?;
+                }
+            }
+            ConfirmAction::ExecuteQuery { query } => {
+                self.sql_editor
+                    .execute(
+                        query,
+                        self.editor_pool
+                            .as_ref()
+                            .or(self.pool.as_ref())
+                            .unwrap()
+                            .as_ref(),
+                    )
+                    .await?;
+                self.take_editor_execution();
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_prompt_action(
+        &mut self,
+        action: PromptAction,
+        value: String,
+    ) -> anyhow::Result<()> {
+        match action {
+            PromptAction::BulkUpdate { column } => {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let pool = self.pool.as_ref().unwrap();
+                    let filter = self.record_table.query.filter();
+                    let count = pool
+                        .get_total_row_count(
+                            &self
+                                .record_table
+                                .query
+                                .builder(database.clone(), table.clone()),
+                        )
+                        .await?;
+                    let mut query = format!(
+                        "UPDATE {} SET {} = {}",
+                        pool.table_identifier(&database, &table),
+                        pool.quote_identifier(&column),
+                        pool.cell_literal(&value, false)
+                    );
+                    if let Some(filter) = filter {
+                        query = format!("{} WHERE {}", query, filter);
+                    }
+                    let mut message = format!("{} row(s) will be updated.\n\n{}", count, query);
+                    let charsets = pool.get_charsets(&database, &table).await?;
+                    if let Some(charset) = charsets.column(&column) {
+                        if let Some(c) = unrepresentable(&value, charset) {
+                            message = format!(
+                                "Warning: {:?} can't be stored in the {} charset of {} and will be mangled.\n\n{}",
+                                c, charset, column, message
+                            );
+                        }
+                    }
+                    self.confirm.open(
+                        ConfirmAction::BulkUpdate {
+                            query: query.clone(),
+                        },
+                        message,
+                    )?;
+                }
+            }
+            PromptAction::GenerateData => {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let spec = value.parse::<MockSpec>()?;
+                    let pool = self.pool.as_ref().unwrap();
+                    let table_identifier = pool.table_identifier(&database, &table);
+                    let statements = insert_statements(
+                        &table_identifier,
+                        &spec
+                            .columns
+                            .iter()
+                            .map(|(column, _)| pool.quote_identifier(column))
+                            .collect::<Vec<String>>(),
+                        &spec.generate_rows(pool.as_ref()),
+                    );
+                    let summary = format!("Generate {} rows into {}", spec.rows, table_identifier);
+                    if self.protected {
+                        self.confirm.open(
+                            ConfirmAction::InsertRows {
+                                statements,
+                                summary: summary.clone(),
+                            },
+                            format!("This connection is protected. {}?", summary),
+                        )?;
+                    } else {
+                        self.insert_rows(statements, summary).await?;
+                    }
+                }
+            }
+            PromptAction::Connect => {
+                if self
+                    .connections
+                    .selected_connection()
+                    .is_some_and(|conn| conn.display_name() == value)
+                {
+                    self.update_databases().await?;
+                } else {
+                    self.error
+                        .set("The typed name does not match the connection".to_string())?;
+                }
+            }
+            PromptAction::PlayMacro => {
+                self.macro_plays = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| anyhow::anyhow!("{} is not a number of times", value))?,
+                );
+            }
+            PromptAction::SetSessionVariable { name } => {
+                if value.contains(';') {
+                    return Err(anyhow::anyhow!("the value of {} can't contain `;`", name));
+                }
+                let confirm = self.protected && !self.scratch_active;
+                self.run_in_editor(format!("SET {} = {}", name, value.trim()))
+                    .await?;
+                // On a protected connection the statement waits for the confirmation.
+                if !confirm {
+                    let pool = self.editor_pool.as_ref().or(self.pool.as_ref()).unwrap();
+                    self.session_variables.open(
+                        self.sql_editor.session_variables(),
+                        pool.session_variable_names(),
+                    )?;
+                }
+            }
+            PromptAction::ExportRecords => {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let path = PathBuf::from(value.trim());
+                    let options = ExportOptions::from_path(&path)?;
+                    let scope = ExportScope::ALL[self.prompt.choice()];
+                    if scope == ExportScope::Selection {
+                        let table = &self.record_table.table;
+                        if let Some((columns, rows)) = table.selected_area() {
+                            let (headers, rows) = table.slice(columns, rows);
+                            export_rows(&path, options, &self.config.export, &headers, &rows)?;
+                            self.toast.show_message(format!(
+                                "Exported {} rows to {}",
+                                rows.len(),
+                                value.trim()
+                            ));
+                        }
+                        return Ok(());
+                    }
+                    let pool = self.pool.as_ref().unwrap();
+                    let mut query = self.record_table.query.builder(database, table.clone());
+                    let progress = Arc::new(JobProgress::default());
+                    progress.set_total_rows(pool.get_total_row_count(&query).await?);
+                    if scope == ExportScope::VisibleColumns {
+                        let headers = &self.record_table.table.headers;
+                        query = query.columns(
+                            headers[self.record_table.table.visible_columns()]
+                                .iter()
+                                .map(|header| pool.quote_identifier(header))
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                        );
+                    }
+                    self.jobs.push(Job::spawn(
+                        format!("Export {} to {}", table.name, value),
+                        progress.clone(),
+                        export(
+                            pool.clone_box(),
+                            query,
+                            path,
+                            options,
+                            self.config.export.clone(),
+                            progress,
+                        ),
+                    ))?;
+                }
+            }
+            PromptAction::SetComment { column } => {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let query = self
+                        .pool
+                        .as_ref()
+                        .unwrap()
+                        .comment_statement(&database, &table, column.as_deref(), value.trim())
+                        .await?;
+                    self.confirm.open(
+                        ConfirmAction::SetComment {
+                            query: query.clone(),
+                        },
+                        query,
+                    )?;
+                }
+            }
+            PromptAction::SetTemplateVariable => {
+                let (name, value) = template::parse_assignment(&value)?;
+                let message = match value {
+                    Some(value) => {
+                        let message = format!("Set {{{{{}}}}} to {}", name, value);
+                        self.variables.insert(name, value);
+                        message
+                    }
+                    None => {
+                        self.variables.remove(&name);
+                        format!("Removed {{{{{}}}}}", name)
+                    }
+                };
+                self.sql_editor.set_variables(self.variables.clone());
+                self.toast.show_message(message);
+            }
+            PromptAction::ExtractJsonPath { column } => {
+                if value.trim().is_empty() {
+                    self.record_table.query.clear_json_columns();
+                } else {
+                    let path = JsonPath::parse(&value)?;
+                    self.record_table.query.add_json_column(column, path);
+                }
+                self.update_record_table(true).await?;
+            }
+            PromptAction::EditColumnLayout => {
+                self.set_column_layout(ColumnLayout::parse(&value)).await?;
+            }
+            PromptAction::AddComputedColumn { table_key } => {
+                let previous = self.record_table.query.computed_columns().to_vec();
+                let mut columns = previous.clone();
+                match value.trim() {
+                    "" => columns.clear(),
+                    expression => columns.push(expression.to_string()),
+                }
+                self.record_table
+                    .query
+                    .set_computed_columns(columns.clone());
+                if let Err(err) = self.update_record_table(true).await {
+                    self.record_table.query.set_computed_columns(previous);
+                    return Err(err);
+                }
+                if columns.is_empty() {
+                    self.session.computed_columns.remove(&table_key);
+                } else {
+                    self.session.computed_columns.insert(table_key, columns);
+                }
+                self.session.save()?;
+            }
+            PromptAction::CopyRows { connections } => {
+                self.ensure_unmasked()?;
+                let choice = self.prompt.choice();
+                let Some(&connection) = connections.get(choice / 2) else {
+                    return Ok(());
+                };
+                let Some((database, table)) = self.record_table.table.source().cloned() else {
+                    return Ok(());
+                };
+                // The headers carry sort arrows and the JSON and computed columns, so the
+                // mapping is made from the columns of the table.
+                let source_columns = match self.record_table.query.columns() {
+                    Some(columns) => columns.to_vec(),
+                    None => self
+                        .pool
+                        .as_ref()
+                        .unwrap()
+                        .get_columns(&database, &table)
+                        .await?
+                        .iter()
+                        .filter_map(|column| column.columns().first().cloned())
+                        .collect(),
+                };
+                let target = parse_target(&value, &source_columns)?;
+                let selected = choice % 2 == 1;
+                let conn = &self.connections.connections()[connection];
+                if conn.protected {
+                    let message = format!(
+                        "{} is protected. Copy the {} records into {}?",
+                        conn.display_name(),
+                        if selected { "selected" } else { "filtered" },
+                        target.table.join(".")
+                    );
+                    self.confirm.open(
+                        ConfirmAction::CopyRows {
+                            connection,
+                            target,
+                            selected,
+                        },
+                        message,
+                    )?;
+                } else {
+                    self.copy_rows(connection, target, selected).await?;
+                }
+            }
+            PromptAction::DumpTable => {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let pool = self.pool.as_ref().unwrap();
+                    let progress = Arc::new(JobProgress::default());
+                    progress.set_total_rows(
+                        pool.get_total_row_count(&QueryBuilder::new(
+                            database.clone(),
+                            table.clone(),
+                        ))
+                        .await?,
+                    );
+                    self.jobs.push(Job::spawn(
+                        format!("Dump {} to {}", table.name, value.trim()),
+                        progress.clone(),
+                        dump(
+                            pool.clone_box(),
+                            database,
+                            table,
+                            PathBuf::from(value.trim()),
+                            progress,
+                        ),
+                    ))?;
+                }
+            }
+            PromptAction::ExportResults => {
+                let path = PathBuf::from(value.trim());
+                let options = ExportOptions::from_path(&path)?;
+                let scope = ExportScope::ALL[self.prompt.choice()];
+                if scope != ExportScope::Entire || options.format != ExportFormat::Xlsx {
+                    let table = self
+                        .sql_editor
+                        .focused_result()
+                        .ok_or_else(|| anyhow::anyhow!("no result is shown"))?;
+                    let (headers, rows) = match scope {
+                        ExportScope::Entire => (table.headers.clone(), table.rows.clone()),
+                        ExportScope::VisibleColumns => {
+                            table.slice(table.visible_columns(), 0..table.rows.len())
+                        }
+                        ExportScope::Selection => match table.selected_area() {
+                            Some((columns, rows)) => table.slice(columns, rows),
+                            None => return Ok(()),
+                        },
+                    };
+                    export_rows(&path, options, &self.config.export, &headers, &rows)?;
+                    self.toast.show_message(format!(
+                        "Exported {} rows to {}",
+                        rows.len(),
+                        value.trim()
+                    ));
+                    return Ok(());
+                }
+                let results = self.sql_editor.results();
+                write_xlsx(
+                    Path::new(value.trim()),
+                    &results
+                        .iter()
+                        .map(|(name, table)| Sheet {
+                            name: name.clone(),
+                            headers: &table.headers,
+                            rows: &table.rows,
+                        })
+                        .collect::<Vec<Sheet>>(),
+                )?;
+                self.toast.show_message(format!(
+                    "Exported {} results to {}",
+                    results.len(),
+                    value.trim()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_tree_action(&mut self, action: TreeAction) -> anyhow::Result<()> {
+        let tab = match action {
+            TreeAction::OpenRecords => Some(Tab::Records),
+            TreeAction::OpenProperties => Some(Tab::Properties),
+            _ => None,
+        };
+        if let Some(tab) = tab {
+            if self.open_selected_table().await? {
+                self.tab.selected_tab = tab;
+                self.focus = Focus::Table;
+            }
+            return Ok(());
+        }
+        let selected = self.databases.tree().selected_table();
+        match (action, selected) {
+            (TreeAction::CopyName, _) => {
+                if let Some(name) = self.databases.tree().selected_name() {
+                    copy_to_clipboard(&name)?;
+                }
+            }
+            (TreeAction::CopyQualifiedName, Some((database, table))) => {
+                let pool = self.pool.as_ref().unwrap();
+                copy_to_clipboard(&pool.table_identifier(&database, &table))?;
+            }
+            (TreeAction::GenerateSelect, Some((database, table))) => {
+                let pool = self.pool.as_ref().unwrap();
+                self.sql_editor
+                    .set_query(&pool.records_query(&QueryBuilder::new(database, table)));
+                self.tab.selected_tab = Tab::Sql;
+                self.focus = Focus::Table;
+            }
+            (TreeAction::DumpTable, Some((_, table))) => {
+                self.prompt.open(
+                    PromptAction::DumpTable,
+                    "Enter the path to dump the definition and the rows of the table to as SQL."
+                        .to_string(),
+                )?;
+                self.prompt.set_input(&format!("{}.sql", table.name));
+            }
+            (TreeAction::Refresh, _) => {
+                if let Some(conn) = self.connections.selected_connection() {
+                    self.databases
+                        .update(conn, self.pool.as_ref().unwrap())
+                        .await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Loads the records and the properties of the table selected in the tree. Returns whether
+    /// a table is selected.
+    async fn open_selected_table(&mut self) -> anyhow::Result<bool> {
+        let Some((database, table)) = self.databases.tree().selected_table() else {
+            return Ok(false);
+        };
+        self.record_table.reset();
+        let columns = match self.column_names(&database, &table).await {
+            Ok(columns) => {
+                self.databases.set_table_error(&database, &table, None);
+                columns
+            }
+            Err(e) => {
+                self.databases
+                    .set_table_error(&database, &table, Some(format!("{:#}", e)));
+                return Err(e.context(format!("Could not read the columns of {}", table.name)));
+            }
+        };
+        let key = table_key(&database, &table);
+        self.record_table.query.set_columns(
+            self.column_layouts
+                .get(&self.layout_key(&database, &table))
+                .map(|layout| layout.apply(&columns))
+                .filter(|shown| !shown.is_empty()),
+        );
+        self.record_table.query.set_computed_columns(
+            self.session
+                .computed_columns
+                .get(&key)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let mut query = self
+            .record_table
+            .query
+            .builder(database.clone(), table.clone());
+        let (headers, records) = match self.pool.as_ref().unwrap().get_records(&query).await {
+            Ok(records) => records,
+            // The table opens without the computed columns when one of them fails, such as after
+            // a column it uses was dropped.
+            Err(err) if !self.record_table.query.computed_columns().is_empty() => {
+                self.toast.show_message(format!(
+                    "The computed columns are not shown since they failed: {}",
+                    err
+                ));
+                self.record_table.query.set_computed_columns(vec![]);
+                query = self
+                    .record_table
+                    .query
+                    .builder(database.clone(), table.clone());
+                self.pool.as_ref().unwrap().get_records(&query).await?
+            }
+            Err(err) => return Err(err),
+        };
+        let total_row_count = self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_total_row_count(&query)
+            .await?;
+        self.record_table.update(
+            records,
+            Some(total_row_count),
+            headers,
+            database.clone(),
+            table.clone(),
+            false,
+        );
+        self.record_table.restore_position();
+        self.sql_editor.set_completion_names(
+            self.databases.tree().table_names(&database.name),
+            columns.clone(),
+        );
+        self.record_table.filter.set_columns(columns);
+        self.properties
+            .update(database.clone(), table.clone(), self.pool.as_ref().unwrap())
+            .await?;
+        Ok(true)
+    }
+
+    /// The key the column layout of `table` is kept by, for the connection it is on, since
+    /// tables of the same name on other connections may differ.
+    fn layout_key(&self, database: &Database, table: &Table) -> String {
+        format!(
+            "{}/{}",
+            self.record_table
+                .filter
+                .connection
+                .as_deref()
+                .unwrap_or_default(),
+            table_key(database, table)
+        )
+    }
+
+    /// The names of the columns of `table`.
+    async fn column_names(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_columns(database, table)
+            .await?
+            .iter()
+            .filter_map(|column| column.columns().first().cloned())
+            .collect())
+    }
+
+    /// Refuses copying, exporting or paging values while privacy mode masks them, so they are
+    /// only written out once unmasked, which the audit log records.
+    fn ensure_unmasked(&self) -> anyhow::Result<()> {
+        if self.privacy {
+            return Err(anyhow::anyhow!(
+                "Values are masked in privacy mode. Press [{}] to unmask them before copying, exporting or filtering by them.",
+                self.config.key_config.toggle_privacy
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets the column layout of the selected table and reads the records with it.
+    async fn set_column_layout(&mut self, layout: ColumnLayout) -> anyhow::Result<()> {
+        let Some((database, table)) = self.databases.tree().selected_table() else {
+            return Ok(());
+        };
+        let shown = layout.apply(&self.column_names(&database, &table).await?);
+        if shown.is_empty() {
+            return Err(anyhow!(
+                "At least one column of {} must be shown",
+                table.name
+            ));
+        }
+        self.record_table
+            .query
+            .set_columns((!layout.is_empty()).then_some(shown));
+        self.column_layouts
+            .set(&self.layout_key(&database, &table), layout)?;
+        self.update_record_table(false).await
+    }
+
+    fn open_computed_column_prompt(&mut self) -> anyhow::Result<()> {
+        let Some((database, table)) = self.databases.tree().selected_table() else {
+            return Ok(());
+        };
+        let columns = self.record_table.query.computed_columns();
+        let shown = if columns.is_empty() {
+            String::new()
+        } else {
+            format!(" Shown now: {}.", columns.join(", "))
+        };
+        self.prompt.open(
+            PromptAction::AddComputedColumn {
+                table_key: table_key(&database, &table),
+            },
+            format!(
+                "Enter an SQL expression to show as a column of {}, such as price * quantity AS total. An empty expression removes the computed columns.{}",
+                table.name, shown
+            ),
+        )
+    }
+
+    /// Asks for a path in the selected column to show as a column, if the column is JSON or its
+    /// selected value is a JSON object or array.
+    async fn open_json_path_prompt(&mut self) -> anyhow::Result<()> {
+        let Some((database, table)) = self.databases.tree().selected_table() else {
+            return Ok(());
+        };
+        let Some(column) = self
+            .record_table
+            .table
+            .headers
+            .get(self.record_table.table.selected_column_index())
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let is_json_column = self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_columns(&database, &table)
+            .await?
+            .iter()
+            .any(|c| {
+                let columns = c.columns();
+                columns.first() == Some(&column) && columns.get(1).is_some_and(|t| is_json_type(t))
+            });
+        let is_json_value = self
+            .record_table
+            .table
+            .content()
+            .and_then(|value| serde_json::from_str::<serde_json::Value>(&value).ok())
+            .is_some_and(|value| value.is_object() || value.is_array());
+        if !is_json_column && !is_json_value {
+            return Err(anyhow!("{} is not a JSON column", column));
+        }
+        self.prompt.open(
+            PromptAction::ExtractJsonPath {
+                column: column.clone(),
+            },
+            format!(
+                "Enter a path in {} to show as a column, such as $.user.id, user.tags[0] or ->'user'->>'id'. An empty path removes the JSON columns.",
+                column
+            ),
+        )?;
+        Ok(())
+    }
+
+    async fn selected_column_name(&self) -> anyhow::Result<Option<String>> {
+        let index = self.record_table.table.selected_column_index();
+        if let Some(columns) = self.record_table.query.columns() {
+            return Ok(columns.get(index).cloned());
+        }
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            let columns = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_columns(&database, &table)
+                .await?;
+            return Ok(columns
+                .get(index)
+                .and_then(|column| column.columns().first().cloned()));
+        }
+        Ok(None)
+    }
+
+    async fn insert_rows(
+        &mut self,
+        statements: Vec<String>,
+        summary: String,
+    ) -> anyhow::Result<()> {
+        let pool = self.pool.as_ref().unwrap();
+        match undo::insert_with_inverse(pool.as_ref(), &statements).await? {
+            Some(inverse) => self.undo.push(UndoEntry {
+                statement: summary,
+                inverse,
+            }),
+            None => self.toast.show_message(
+                "The rows were inserted, but they can't be reverted since they can't be told apart by a key."
+                    .to_string(),
+            ),
+        }
+        self.update_record_table(false).await
+    }
+
+    /// Copies the filtered records, or the selected ones, into `target` on the connection at
+    /// `connection` in the background.
+    async fn copy_rows(
+        &mut self,
+        connection: usize,
+        target: CopyTarget,
+        selected: bool,
+    ) -> anyhow::Result<()> {
+        let Some((database, table)) = self.record_table.table.source().cloned() else {
+            return Ok(());
+        };
+        let progress = Arc::new(JobProgress::default());
+        let pool = self.pool.as_ref().unwrap();
+        let query = self.record_table.query.builder(database, table.clone());
+        let rows = if selected {
+            let Some((_, rows)) = self.record_table.table.selected_area() else {
+                return Ok(());
+            };
+            progress.set_total_rows(rows.len());
+            Some(rows)
+        } else {
+            progress.set_total_rows(pool.get_total_row_count(&query).await?);
+            None
+        };
+        let source = CopySource {
+            pool: pool.clone_box(),
+            query: Box::new(query),
+            rows,
+        };
+        let conn = &self.connections.connections()[connection];
+        let pool = open_pool(conn, conn.timeout_second, 1).await?;
+        self.jobs.push(Job::spawn(
+            format!(
+                "Copy {} to {} on {}",
+                table.name,
+                target.table.join("."),
+                conn.display_name()
+            ),
+            progress.clone(),
+            copy_rows(source, pool, target, progress),
+        ))?;
+        Ok(())
+    }
+
     fn concat_headers(
         &self,
         headers: Vec<String>,
@@ -430,8 +2673,12 @@ impl App {
                 .key_config
                 .extend_or_shorten_widget_width_to_left
         {
-            self.left_main_chunk_percentage =
-                self.left_main_chunk_percentage.saturating_sub(5).max(15);
+            self.session.left_main_chunk_percentage = self
+                .session
+                .left_main_chunk_percentage
+                .saturating_sub(5)
+                .max(15);
+            self.session.save()?;
             return Ok(EventState::Consumed);
         } else if key
             == self
@@ -439,12 +2686,39 @@ impl App {
                 .key_config
                 .extend_or_shorten_widget_width_to_right
         {
-            self.left_main_chunk_percentage = (self.left_main_chunk_percentage + 5).min(70);
+            self.session.left_main_chunk_percentage =
+                (self.session.left_main_chunk_percentage + 5).min(70);
+            self.session.save()?;
             return Ok(EventState::Consumed);
         }
         Ok(EventState::NotConsumed)
     }
 
+    fn extend_or_shorten_widget_height(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let percentage = self.session.sql_editor_percentage;
+        self.session.sql_editor_percentage = if key
+            == self
+                .config
+                .key_config
+                .extend_or_shorten_widget_height_to_top
+        {
+            percentage.saturating_sub(5).max(20)
+        } else if key
+            == self
+                .config
+                .key_config
+                .extend_or_shorten_widget_height_to_bottom
+        {
+            (percentage + 5).min(80)
+        } else {
+            return Ok(EventState::NotConsumed);
+        };
+        self.sql_editor
+            .set_editor_percentage(self.session.sql_editor_percentage);
+        self.session.save()?;
+        Ok(EventState::Consumed)
+    }
+
     fn move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
         if key == self.config.key_config.focus_connections {
             self.focus = Focus::ConnectionList;
@@ -477,9 +2751,17 @@ impl App {
     }
 }
 
+fn export_scopes() -> Vec<String> {
+    ExportScope::ALL
+        .iter()
+        .map(|scope| scope.label().to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
-    use super::{App, Config, EventState, Key};
+    use super::{App, Config, Connection, Duration, EventState, Focus, Key, Tab};
+    use crate::config::ConfigError;
 
     #[test]
     fn test_extend_or_shorten_widget_width() {
@@ -488,27 +2770,48 @@ mod test {
             app.extend_or_shorten_widget_width(Key::Char('>')).unwrap(),
             EventState::Consumed
         );
-        assert_eq!(app.left_main_chunk_percentage, 20);
+        assert_eq!(app.session.left_main_chunk_percentage, 20);
 
-        app.left_main_chunk_percentage = 70;
+        app.session.left_main_chunk_percentage = 70;
         assert_eq!(
             app.extend_or_shorten_widget_width(Key::Char('>')).unwrap(),
             EventState::Consumed
         );
-        assert_eq!(app.left_main_chunk_percentage, 70);
+        assert_eq!(app.session.left_main_chunk_percentage, 70);
 
         assert_eq!(
             app.extend_or_shorten_widget_width(Key::Char('<')).unwrap(),
             EventState::Consumed
         );
-        assert_eq!(app.left_main_chunk_percentage, 65);
+        assert_eq!(app.session.left_main_chunk_percentage, 65);
 
-        app.left_main_chunk_percentage = 15;
+        app.session.left_main_chunk_percentage = 15;
         assert_eq!(
             app.extend_or_shorten_widget_width(Key::Char('<')).unwrap(),
             EventState::Consumed
         );
-        assert_eq!(app.left_main_chunk_percentage, 15);
+        assert_eq!(app.session.left_main_chunk_percentage, 15);
+    }
+
+    #[test]
+    fn test_extend_or_shorten_widget_height() {
+        let mut app = App::new(Config::default());
+        assert_eq!(
+            app.extend_or_shorten_widget_height(Key::Alt('j')).unwrap(),
+            EventState::Consumed
+        );
+        assert_eq!(app.session.sql_editor_percentage, 55);
+
+        app.session.sql_editor_percentage = 20;
+        assert_eq!(
+            app.extend_or_shorten_widget_height(Key::Alt('k')).unwrap(),
+            EventState::Consumed
+        );
+        assert_eq!(app.session.sql_editor_percentage, 20);
+        assert_eq!(
+            app.extend_or_shorten_widget_height(Key::Char('k')).unwrap(),
+            EventState::NotConsumed
+        );
     }
 
     #[test]
@@ -531,4 +2834,94 @@ mod test {
             ]
         )
     }
+
+    #[test]
+    fn test_reload_config() {
+        let mut app = App::new(Config::default());
+        let mut config = Config::default();
+        config.key_config.extend_or_shorten_widget_width_to_right = Key::Char('}');
+        app.reload_config(config).unwrap();
+        assert_eq!(
+            app.extend_or_shorten_widget_width(Key::Char('}')).unwrap(),
+            EventState::Consumed
+        );
+        assert_eq!(app.session.left_main_chunk_percentage, 20);
+
+        let mut config = Config::default();
+        config.errors.push(ConfigError {
+            line: Some(1),
+            message: "missing field `type`".to_string(),
+        });
+        app.reload_config(config).unwrap();
+        assert_eq!(
+            app.config
+                .key_config
+                .extend_or_shorten_widget_width_to_right,
+            Key::Char('}')
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_if_idle() {
+        let mut conn = Connection::demo();
+        conn.idle_timeout_minute = Some(10);
+        let mut app = App::new(Config {
+            conn: vec![conn],
+            ..Config::default()
+        });
+        app.event(Key::Enter).await.unwrap();
+        assert!(app.pool.is_some());
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert!(!app.disconnect_if_idle(minutes(9)).await.unwrap());
+        assert!(app.disconnect_if_idle(minutes(10)).await.unwrap());
+        assert!(!app.disconnect_if_idle(minutes(20)).await.unwrap());
+
+        app.event(Key::Right).await.unwrap();
+        assert!(app.idle_disconnected);
+        assert!(matches!(app.focus, Focus::DatabaseList));
+
+        app.event(Key::Enter).await.unwrap();
+        assert!(!app.idle_disconnected);
+        assert!(app.pool.as_ref().unwrap().get_databases().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_complete_on_protected_connection() {
+        let mut app = App::new(Config {
+            conn: vec![Connection::demo()],
+            ..Config::default()
+        });
+        app.event(Key::Enter).await.unwrap();
+        app.protected = true;
+        app.tab.selected_tab = Tab::Sql;
+        app.focus = Focus::Table;
+        for c in "updat".chars() {
+            app.event(Key::Char(c)).await.unwrap();
+        }
+        assert!(app.sql_editor.completing());
+        // Enter accepts the completion instead of asking to run the statement.
+        app.event(Key::Enter).await.unwrap();
+        assert_eq!(app.sql_editor.query().trim_end(), "UPDATE");
+    }
+
+    #[tokio::test]
+    async fn test_type_in_editor_in_privacy_mode() {
+        let mut app = App::new(Config {
+            conn: vec![Connection::demo()],
+            ..Config::default()
+        });
+        app.event(Key::Enter).await.unwrap();
+        app.privacy = true;
+        app.tab.selected_tab = Tab::Sql;
+        app.focus = Focus::Table;
+        assert!(app.sql_editor.editor_focused());
+        // The key copying a result as CSV is typed while editing, instead of asking to unmask.
+        app.event(app.config.key_config.copy_result_as_csv)
+            .await
+            .unwrap();
+        assert_eq!(
+            app.sql_editor.query(),
+            app.config.key_config.copy_result_as_csv.to_string()
+        );
+    }
 }
@@ -0,0 +1,40 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// The file in the config directory recording when masked values were unmasked.
+pub const FILE: &str = "audit.log";
+
+/// Appends `action` to the audit log at `path`, a line per action starting with the local time.
+pub fn record(path: &Path, action: &str) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{} {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        action
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::record;
+
+    #[test]
+    fn test_record() {
+        let path = std::env::temp_dir().join(format!("zhobo_audit_{}.log", std::process::id()));
+        record(&path, "privacy mode turned on").unwrap();
+        record(&path, "values unmasked on demo").unwrap();
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines = log.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" privacy mode turned on"));
+        assert!(lines[1].ends_with(" values unmasked on demo"));
+        assert_eq!(
+            lines[1].len(),
+            "2024-01-01 00:00:00 values unmasked on demo".len()
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+/// The character set and collation of a database, table, or column.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Charset {
+    pub charset: Option<String>,
+    pub collation: Option<String>,
+}
+
+impl Charset {
+    pub fn new(charset: Option<String>, collation: Option<String>) -> Self {
+        Self { charset, collation }
+    }
+
+    /// Derives the character set from a MySQL collation such as `utf8mb4_general_ci`. The
+    /// legacy `utf8` is reported as `utf8mb3`, which is what it is an alias of.
+    pub fn from_collation(collation: Option<String>) -> Self {
+        Self {
+            charset: collation
+                .as_ref()
+                .and_then(|collation| collation.split('_').next())
+                .map(|charset| match charset {
+                    "utf8" => "utf8mb3".to_string(),
+                    charset => charset.to_string(),
+                }),
+            collation,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.charset.is_none() && self.collation.is_none()
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.charset, &self.collation) {
+            (Some(charset), Some(collation)) => write!(f, "{} ({})", charset, collation),
+            (Some(charset), None) => write!(f, "{}", charset),
+            (None, Some(collation)) => write!(f, "({})", collation),
+            (None, None) => write!(f, "-"),
+        }
+    }
+}
+
+/// The character sets of a table, its database, and its text columns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Charsets {
+    pub database: Charset,
+    pub table: Charset,
+    pub columns: Vec<(String, Charset)>,
+}
+
+impl Charsets {
+    /// The character set text in `column` is stored in, falling back to the table and database
+    /// defaults.
+    pub fn column(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .and_then(|(_, charset)| charset.charset.as_deref())
+            .or(self.table.charset.as_deref())
+            .or(self.database.charset.as_deref())
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!("database: {}", self.database);
+        if !self.table.is_empty() {
+            summary = format!("{}, table: {}", summary, self.table);
+        }
+        summary
+    }
+}
+
+/// Returns the first character of `text` that can't be stored in `charset`. Unknown and
+/// Unicode character sets accept everything.
+pub fn unrepresentable(text: &str, charset: &str) -> Option<char> {
+    let max = match charset.to_lowercase().replace('-', "").as_str() {
+        "ascii" | "usascii" | "us7ascii" => 0x7F,
+        "latin1" | "iso88591" => 0xFF,
+        "utf8mb3" | "ucs2" => 0xFFFF,
+        _ => return None,
+    };
+    text.chars().find(|c| *c as u32 > max)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{unrepresentable, Charset, Charsets};
+
+    #[test]
+    fn test_unrepresentable() {
+        assert_eq!(unrepresentable("abc", "ascii"), None);
+        assert_eq!(unrepresentable("café", "ascii"), Some('é'));
+        assert_eq!(unrepresentable("café", "latin1"), None);
+        assert_eq!(unrepresentable("ok 🍣", "latin1"), Some('🍣'));
+        assert_eq!(unrepresentable("日本", "utf8mb3"), None);
+        assert_eq!(unrepresentable("🍣", "utf8mb3"), Some('🍣'));
+        assert_eq!(unrepresentable("🍣", "utf8mb4"), None);
+        assert_eq!(unrepresentable("🍣", "UTF8"), None);
+        assert_eq!(unrepresentable("🍣", "ISO-8859-1"), Some('🍣'));
+    }
+
+    #[test]
+    fn test_column() {
+        let charsets = Charsets {
+            database: Charset::from_collation(Some("utf8mb4_general_ci".to_string())),
+            table: Charset::from_collation(Some("latin1_swedish_ci".to_string())),
+            columns: vec![(
+                "name".to_string(),
+                Charset::from_collation(Some("ascii_bin".to_string())),
+            )],
+        };
+        assert_eq!(charsets.column("name"), Some("ascii"));
+        assert_eq!(charsets.column("other"), Some("latin1"));
+        assert_eq!(
+            Charset::from_collation(Some("utf8_general_ci".to_string())).charset,
+            Some("utf8mb3".to_string())
+        );
+        assert_eq!(
+            charsets.summary(),
+            "database: utf8mb4 (utf8mb4_general_ci), table: latin1 (latin1_swedish_ci)"
+        );
+    }
+}
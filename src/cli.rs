@@ -1,12 +1,61 @@
-use crate::config::CliConfig;
+use crate::config::{CliConfig, Connection};
+use crate::script;
+use anyhow::anyhow;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
+const SQLITE_EXTENSIONS: &[&str] = &["db", "sqlite", "sqlite3"];
+
 /// A cross-platform TUI database management tool written in Rust
 #[derive(StructOpt, Debug)]
 #[structopt(name = "zhobo")]
 pub struct Cli {
     #[structopt(flatten)]
     pub config: CliConfig,
+
+    /// Adds an in-memory SQLite database with sample data to try zhobo on
+    #[structopt(long)]
+    pub demo: bool,
+
+    /// Runs the keys of a script without showing the screen, printing it at each snapshot
+    #[structopt(long, parse(from_os_str))]
+    pub script: Option<PathBuf>,
+
+    /// The size of the screen a script is run on
+    #[structopt(long, default_value = "120x40", parse(try_from_str = script::parse_size))]
+    pub script_size: (u16, u16),
+
+    /// SQLite files to add to the connection list
+    #[structopt(parse(from_os_str))]
+    pub files: Vec<PathBuf>,
+}
+
+impl Cli {
+    /// Connections given on the command line, which are only kept for this run: the demo
+    /// database, then the SQLite files given as arguments.
+    pub fn connections(&self) -> anyhow::Result<Vec<Connection>> {
+        let demo = self.demo.then(Connection::demo).map(Ok);
+        demo.into_iter()
+            .chain(self.files.iter().map(|file| {
+                let is_sqlite = file
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| {
+                        SQLITE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+                    });
+                if !is_sqlite {
+                    return Err(anyhow!(
+                        "{} is not a SQLite file (expected .{})",
+                        file.display(),
+                        SQLITE_EXTENSIONS.join(", .")
+                    ));
+                }
+                Ok(Connection::sqlite(
+                    std::fs::canonicalize(file).unwrap_or_else(|_| file.clone()),
+                ))
+            }))
+            .collect()
+    }
 }
 
 pub fn parse() -> Cli {
@@ -27,6 +27,22 @@ fn execute_copy_command(command: Command, text: &str) -> Result<()> {
     Ok(())
 }
 
+fn execute_paste_command(command: Command) -> Result<String> {
+    let mut command = command;
+
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("`{:?}`: {}", command, output.status));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| anyhow!("`{:?}`: {}", command, e))
+}
+
 #[cfg(all(target_family = "unix", not(target_os = "macos")))]
 fn gen_command(path: impl AsRef<OsStr>, xclip_syntax: bool) -> Command {
     let mut c = Command::new(path);
@@ -57,6 +73,25 @@ pub fn copy_to_clipboard(string: &str) -> Result<()> {
     execute_copy_command(cmd, string)
 }
 
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+pub fn paste_from_clipboard() -> Result<String> {
+    use std::path::PathBuf;
+    use which::which;
+    let (path, xclip_syntax) = which("xclip").ok().map_or_else(
+        || {
+            (
+                which("xsel").ok().unwrap_or_else(|| PathBuf::from("xsel")),
+                false,
+            )
+        },
+        |path| (path, true),
+    );
+
+    let mut cmd = gen_command(path, xclip_syntax);
+    cmd.arg(if xclip_syntax { "-o" } else { "--output" });
+    execute_paste_command(cmd)
+}
+
 #[cfg(target_os = "macos")]
 pub fn copy_to_clipboard(string: &str) -> Result<()> {
     execute_copy_command(Command::new("pbcopy"), string)
@@ -66,3 +101,15 @@ pub fn copy_to_clipboard(string: &str) -> Result<()> {
 pub fn copy_to_clipboard(string: &str) -> Result<()> {
     execute_copy_command(Command::new("clip"), string)
 }
+
+#[cfg(target_os = "macos")]
+pub fn paste_from_clipboard() -> Result<String> {
+    execute_paste_command(Command::new("pbpaste"))
+}
+
+#[cfg(windows)]
+pub fn paste_from_clipboard() -> Result<String> {
+    let mut command = Command::new("powershell");
+    command.args(["-NoProfile", "-Command", "Get-Clipboard -Raw"]);
+    execute_paste_command(command)
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+
+/// How the columns of a table are shown in the records: the pinned columns first, then the
+/// ordered ones, then the rest in the order of the table, without the hidden ones.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnLayout {
+    pub pinned: Vec<String>,
+    pub order: Vec<String>,
+    pub hidden: Vec<String>,
+}
+
+impl ColumnLayout {
+    /// Parses `^id, name, -email`, the columns in order where `^` pins a column and `-` hides
+    /// it.
+    pub fn parse(input: &str) -> Self {
+        let mut layout = Self::default();
+        for column in input.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            if let Some(column) = column.strip_prefix('^') {
+                layout.pinned.push(column.trim().to_string());
+            } else if let Some(column) = column.strip_prefix('-') {
+                layout.hidden.push(column.trim().to_string());
+            } else {
+                layout.order.push(column.to_string());
+            }
+        }
+        layout
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pinned.is_empty() && self.order.is_empty() && self.hidden.is_empty()
+    }
+
+    /// The shown columns out of `columns`, the columns of the table. Columns the table no longer
+    /// has are skipped, and columns added to it since are shown last.
+    pub fn apply(&self, columns: &[String]) -> Vec<String> {
+        let mut shown: Vec<String> = vec![];
+        for column in self.pinned.iter().chain(&self.order).chain(columns) {
+            if columns.contains(column) && !self.hidden.contains(column) && !shown.contains(column)
+            {
+                shown.push(column.clone());
+            }
+        }
+        shown
+    }
+
+    /// The layout of `columns` written as `parse` reads it, listing every column.
+    pub fn to_input(&self, columns: &[String]) -> String {
+        self.apply(columns)
+            .into_iter()
+            .map(|column| {
+                if self.pinned.contains(&column) {
+                    format!("^{}", column)
+                } else {
+                    column
+                }
+            })
+            .chain(
+                columns
+                    .iter()
+                    .filter(|column| self.hidden.contains(column))
+                    .map(|column| format!("-{}", column)),
+            )
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Column layouts per table, kept between runs.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ColumnLayouts {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    tables: HashMap<String, ColumnLayout>,
+}
+
+impl ColumnLayouts {
+    /// Loads the layouts from `path`. A missing or broken file results in no layouts.
+    pub fn load(path: PathBuf) -> Self {
+        let mut contents = String::new();
+        let mut layouts = File::open(&path)
+            .ok()
+            .and_then(|mut file| file.read_to_string(&mut contents).ok())
+            .and_then(|_| ron::from_str::<Self>(&contents).ok())
+            .unwrap_or_default();
+        layouts.path = Some(path);
+        layouts
+    }
+
+    pub fn get(&self, table: &str) -> Option<&ColumnLayout> {
+        self.tables.get(table)
+    }
+
+    /// Sets the layout of `table`, forgetting it when it is empty.
+    pub fn set(&mut self, table: &str, layout: ColumnLayout) -> anyhow::Result<()> {
+        if layout.is_empty() {
+            self.tables.remove(table);
+        } else {
+            self.tables.insert(table.to_string(), layout);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.path {
+            fs::write(path, ron::ser::to_string_pretty(self, Default::default())?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColumnLayout, ColumnLayouts};
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let layout = ColumnLayout::parse(" name, ^email , -note, id, gone");
+        assert_eq!(layout.pinned, ["email"]);
+        assert_eq!(layout.order, ["name", "id", "gone"]);
+        assert_eq!(layout.hidden, ["note"]);
+
+        let table = columns(&["id", "name", "email", "note", "created_at"]);
+        assert_eq!(layout.apply(&table), ["email", "name", "id", "created_at"]);
+        assert_eq!(
+            layout.to_input(&table),
+            "^email, name, id, created_at, -note"
+        );
+        assert_eq!(
+            ColumnLayout::parse(&layout.to_input(&table)).apply(&table),
+            layout.apply(&table)
+        );
+        assert!(ColumnLayout::parse(" , ").is_empty());
+        assert_eq!(ColumnLayout::default().apply(&table), table);
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let path =
+            std::env::temp_dir().join(format!("zhobo_column_layouts_{}.ron", std::process::id()));
+        let mut layouts = ColumnLayouts::load(path.clone());
+        assert!(layouts.get("db.users").is_none());
+        layouts
+            .set("db.users", ColumnLayout::parse("^name, -email"))
+            .unwrap();
+
+        let mut layouts = ColumnLayouts::load(path.clone());
+        assert_eq!(
+            layouts.get("db.users"),
+            Some(&ColumnLayout::parse("^name, -email"))
+        );
+        layouts.set("db.users", ColumnLayout::default()).unwrap();
+        assert!(ColumnLayouts::load(path.clone()).get("db.users").is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,17 +1,22 @@
 use ratatui::{
     layout::{Constraint, Direction, Flex, Layout, Margin, Rect},
     style::{Color, Style},
+    text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::{
-    config::KeyConfig,
-    event::Key,
-    tree::{Database, Table as DTable},
+use crate::{config::KeyConfig, event::Key};
+use zhobo::tree::{Database, Table as DTable};
+
+use super::{
+    utils::scroll_vertical::VerticalScroll, Component, DatabaseFilterComponent, DrawableComponent,
+    EventState, PropertyTrait,
 };
 
-use super::{utils::scroll_vertical::VerticalScroll, EventState, PropertyTrait};
+/// A search match as (line index, start byte, end byte).
+type Match = (usize, usize, usize);
 
 pub struct ClipboardComponent {
     table: Option<(Database, DTable)>,
@@ -19,6 +24,11 @@ pub struct ClipboardComponent {
     key_config: KeyConfig,
     position: u16,
     scroll: VerticalScroll,
+    search: DatabaseFilterComponent,
+    search_focused: bool,
+    matches: Vec<Match>,
+    match_index: usize,
+    width: u16,
 }
 
 impl ClipboardComponent {
@@ -30,13 +40,114 @@ impl ClipboardComponent {
             key_config,
             position: 0,
             scroll: VerticalScroll::new(false, false),
+            search: DatabaseFilterComponent::with_placeholder("Search"),
+            search_focused: false,
+            matches: vec![],
+            match_index: 0,
+            width: 0,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
     pub fn reset(&mut self) {
         self.table = None;
         self.content = None;
         self.position = 0;
+        self.search.reset();
+        self.search_focused = false;
+        self.matches = vec![];
+        self.match_index = 0;
+    }
+
+    pub fn searching(&self) -> bool {
+        self.search_focused
+    }
+
+    fn update_matches(&mut self) {
+        let query = self.search.input_str().to_ascii_lowercase();
+        self.matches = vec![];
+        self.match_index = 0;
+        if query.is_empty() {
+            return;
+        }
+        for (line_index, line) in self.unwrap_content().lines().enumerate() {
+            let line = line.to_ascii_lowercase();
+            let mut start = 0;
+            while let Some(offset) = line[start..].find(query.as_str()) {
+                self.matches
+                    .push((line_index, start + offset, start + offset + query.len()));
+                start += offset + query.len();
+            }
+        }
+        self.scroll_to_match();
+    }
+
+    fn next_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.match_index = (self.match_index + 1) % self.matches.len();
+            self.scroll_to_match();
+        }
+    }
+
+    fn previous_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.match_index = self
+                .match_index
+                .checked_sub(1)
+                .unwrap_or(self.matches.len() - 1);
+            self.scroll_to_match();
+        }
+    }
+
+    /// Scrolls so that the current match is at the top, counting wrapped lines approximately.
+    fn scroll_to_match(&mut self) {
+        if let Some((line_index, _, _)) = self.matches.get(self.match_index) {
+            let width = self.width.max(1) as usize;
+            self.position = self
+                .unwrap_content()
+                .lines()
+                .take(*line_index)
+                .map(|line| line.width().max(1).div_ceil(width))
+                .sum::<usize>() as u16;
+        }
+    }
+
+    fn highlighted_text(&self) -> Text<'static> {
+        if self.matches.is_empty() {
+            return Text::from(self.unwrap_content());
+        }
+        Text::from(
+            self.unwrap_content()
+                .lines()
+                .enumerate()
+                .map(|(line_index, line)| {
+                    let mut spans = vec![];
+                    let mut last = 0;
+                    for (index, (_, start, end)) in self
+                        .matches
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (l, _, _))| *l == line_index)
+                    {
+                        spans.push(Span::raw(line[last..*start].to_string()));
+                        spans.push(Span::styled(
+                            line[*start..*end].to_string(),
+                            if index == self.match_index {
+                                Style::default().fg(Color::Black).bg(Color::LightRed)
+                            } else {
+                                Style::default().fg(Color::Black).bg(Color::Yellow)
+                            },
+                        ));
+                        last = *end;
+                    }
+                    spans.push(Span::raw(line[last..].to_string()));
+                    Line::from(spans)
+                })
+                .collect::<Vec<Line>>(),
+        )
     }
 
     pub fn title(&mut self) -> String {
@@ -81,8 +192,21 @@ impl PropertyTrait for ClipboardComponent {
                 horizontal: 1,
             }));
 
+        let chunks = if self.search_focused || !self.search.input_str().is_empty() {
+            let search_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(2), Constraint::Min(1)])
+                .split(chunks[0]);
+            self.search
+                .draw(f, search_chunks[0], focused && self.search_focused)?;
+            vec![search_chunks[1]]
+        } else {
+            vec![chunks[0]]
+        };
+        self.width = chunks[0].width;
+
         // can scroll = content.height - widget.height
-        let paragraph = Paragraph::new(self.unwrap_content())
+        let paragraph = Paragraph::new(self.highlighted_text())
             .scroll((self.position, 0))
             .wrap(Wrap { trim: false });
 
@@ -104,7 +228,25 @@ impl PropertyTrait for ClipboardComponent {
     }
 
     fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
-        if key == self.key_config.scroll_down {
+        if self.search_focused {
+            if key == self.key_config.enter || key == self.key_config.exit_popup {
+                self.search_focused = false;
+            } else if self.search.event(key)?.is_consumed() {
+                self.update_matches();
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.key_config.filter {
+            self.search_focused = true;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.next_match {
+            self.next_match();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.previous_match {
+            self.previous_match();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.scroll_down {
             self.position += 1;
             return Ok(EventState::NotConsumed);
         } else if key == self.key_config.scroll_up {
@@ -118,3 +260,67 @@ impl PropertyTrait for ClipboardComponent {
         self.content.clone()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ClipboardComponent, Key, KeyConfig, PropertyTrait};
+    use zhobo::tree::{Database, Table};
+
+    fn component() -> ClipboardComponent {
+        let mut component = ClipboardComponent::new(KeyConfig::default());
+        component.update(
+            "CREATE TABLE `users` (\n  `id` int,\n  `user_id` int\n)".to_string(),
+            Database::new("db".to_string(), vec![]),
+            Table {
+                name: "users".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        );
+        component.width = 80;
+        component
+    }
+
+    #[test]
+    fn test_search() {
+        let mut component = component();
+        assert!(component.event(Key::Char('/')).unwrap().is_consumed());
+        assert!(component.searching());
+        for c in "ID".chars() {
+            assert!(component.event(Key::Char(c)).unwrap().is_consumed());
+        }
+        assert!(component.event(Key::Enter).unwrap().is_consumed());
+        assert!(!component.searching());
+        assert_eq!(component.matches, vec![(1, 3, 5), (2, 8, 10)]);
+        assert_eq!(component.position, 1);
+
+        assert!(component.event(Key::Char('n')).unwrap().is_consumed());
+        assert_eq!(component.match_index, 1);
+        assert_eq!(component.position, 2);
+        assert!(component.event(Key::Char('n')).unwrap().is_consumed());
+        assert_eq!(component.match_index, 0);
+        assert!(component.event(Key::Char('N')).unwrap().is_consumed());
+        assert_eq!(component.match_index, 1);
+    }
+
+    #[test]
+    fn test_highlighted_text() {
+        let mut component = component();
+        assert_eq!(component.highlighted_text().lines.len(), 4);
+        component.event(Key::Char('/')).unwrap();
+        component.event(Key::Char('i')).unwrap();
+        component.event(Key::Char('d')).unwrap();
+        let text = component.highlighted_text();
+        assert_eq!(
+            text.lines[2]
+                .spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<Vec<String>>(),
+            vec!["  `user_", "id", "` int"]
+        );
+    }
+}
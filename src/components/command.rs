@@ -4,6 +4,8 @@ static CMD_GROUP_GENERAL: &str = "-- General --";
 static CMD_GROUP_TABLE: &str = "-- Table --";
 static CMD_GROUP_DATABASES: &str = "-- Databases --";
 static CMD_GROUP_PROPERTIES: &str = "-- Properties --";
+static CMD_GROUP_SERVER: &str = "-- Server --";
+static CMD_GROUP_SQL_EDITOR: &str = "-- SQL Editor --";
 
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub struct CommandText {
@@ -83,6 +85,23 @@ pub fn filter(key: &KeyConfig) -> CommandText {
     CommandText::new(format!("Filter [{}]", key.filter), CMD_GROUP_GENERAL)
 }
 
+pub fn toggle_regex_filter(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Toggle regex filter [{}]", key.toggle_regex_filter),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn toggle_ignore_case_filter(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Toggle ignoring case in filter [{}]",
+            key.toggle_ignore_case_filter
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
 pub fn move_focus(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
@@ -100,6 +119,141 @@ pub fn sort_by_column(key: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn bulk_update(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Set column value for filtered rows [{}]", key.bulk_update),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn generate_data(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Generate mock data [{}]", key.generate_data),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn export_records(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Export records [{}]", key.export_records),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn export_results(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Export results as Excel [{}]", key.export_records),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn paste_rows(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Paste rows from clipboard [{}]", key.paste_rows),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn copy_rows_to_connection(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Copy rows to a connection [{}]",
+            key.copy_rows_to_connection
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn value_frequency(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show the most frequent values of the selected column [{}]",
+            key.value_frequency
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn show_cell_value(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show the value of the selected cell to search it [{}]",
+            key.show_cell_value
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn copy_distinct_values(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Copy distinct values [{},{}]",
+            key.copy_distinct_values, key.copy_distinct_values_as_in_list
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn extract_json_path(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show a JSON path of the selected column as a column [{}]",
+            key.extract_json_path
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn exclude_cell_value(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Filter out the records holding the selected value [{}]",
+            key.exclude_cell_value
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn filter_null(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Filter the records to NULL / non-NULL of the selected column [{}/{}]",
+            key.filter_null, key.filter_not_null
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn column_layout(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Hide the selected column / Edit the column layout [{}/{}]",
+            key.hide_column, key.edit_column_layout
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn add_computed_column(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show an SQL expression as a column of the table [{}]",
+            key.add_computed_column
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn copy_as_literal(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Copy selected cells as SQL literals [{}]",
+            key.copy_as_literal
+        ),
+        CMD_GROUP_TABLE,
+    )
+}
+
 pub fn extend_selection_by_one_cell(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
@@ -123,6 +277,35 @@ pub fn extend_selection_by_line(key: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn show_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(format!("Show query [{}]", key.show_query), CMD_GROUP_TABLE)
+}
+
+pub fn open_query_in_editor(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Open query in SQL editor [{}]", key.open_query_in_editor),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn focus_header(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Sort from header [{}]", key.focus_header),
+        CMD_GROUP_TABLE,
+    )
+}
+
+pub fn extend_or_shorten_widget_height(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Extend/shorten SQL editor height to top/bottom [{},{}]",
+            key.extend_or_shorten_widget_height_to_top,
+            key.extend_or_shorten_widget_height_to_bottom
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
 pub fn extend_or_shorten_widget_width(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
@@ -177,13 +360,33 @@ pub fn tab_properties(key: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn tab_server(key: &KeyConfig) -> CommandText {
+    CommandText::new(format!("Server [{}]", key.tab_server), CMD_GROUP_TABLE)
+}
+
+pub fn tab_locks(key: &KeyConfig) -> CommandText {
+    CommandText::new(format!("Locks [{}]", key.tab_locks), CMD_GROUP_SERVER)
+}
+
+pub fn tab_variables(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Variables [{}]", key.tab_variables),
+        CMD_GROUP_SERVER,
+    )
+}
+
+pub fn refresh(key: &KeyConfig) -> CommandText {
+    CommandText::new(format!("Refresh [{}]", key.refresh), CMD_GROUP_SERVER)
+}
+
 pub fn toggle_tabs(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Tab [{},{},{},{},{},{},{},{}]",
+            "Tab [{},{},{},{},{},{},{},{},{}]",
             key_config.tab_records,
             key_config.tab_properties,
             key_config.tab_sql_editor,
+            key_config.tab_server,
             key_config.tab_columns,
             key_config.tab_constraints,
             key_config.tab_foreign_keys,
@@ -194,6 +397,16 @@ pub fn toggle_tabs(key_config: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn search_definition(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Search definition [{}] next/previous match [{},{}]",
+            key_config.filter, key_config.next_match, key_config.previous_match
+        ),
+        CMD_GROUP_PROPERTIES,
+    )
+}
+
 pub fn toggle_property_tabs(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
@@ -208,6 +421,16 @@ pub fn toggle_property_tabs(key_config: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn toggle_server_tabs(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Tab [{},{}]",
+            key_config.tab_locks, key_config.tab_variables
+        ),
+        CMD_GROUP_SERVER,
+    )
+}
+
 pub fn help(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
         format!("Help [{}]", key_config.open_help),
@@ -215,9 +438,235 @@ pub fn help(key_config: &KeyConfig) -> CommandText {
     )
 }
 
+pub fn switch_connection(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Switch connection [{}]", key_config.switch_connection),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn show_jobs(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show background jobs [{}] / cancel [{}]",
+            key_config.show_jobs, key_config.cancel_job
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn open_in_pager(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Open in pager [{}]", key_config.open_in_pager),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn open_shell(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Open a shell with the connection environment [{}]",
+            key_config.open_shell
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn show_external_commands(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Run an external command on the selected table [{}]",
+            key_config.show_external_commands
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn toggle_privacy(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Toggle privacy mode, masking the values of the records and results [{}]",
+            key_config.toggle_privacy
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn context_menu(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show the actions on the selected node [{}]",
+            key_config.context_menu
+        ),
+        CMD_GROUP_DATABASES,
+    )
+}
+
+pub fn toggle_system_objects(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show or hide system schemas and tables [{}]",
+            key_config.toggle_system_objects
+        ),
+        CMD_GROUP_DATABASES,
+    )
+}
+
+pub fn copy_qualified_name(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Copy the qualified name of the selected table [{}]",
+            key_config.copy
+        ),
+        CMD_GROUP_DATABASES,
+    )
+}
+
+pub fn copy_column_list(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Copy the column names [{}]", key_config.copy_column_list),
+        CMD_GROUP_PROPERTIES,
+    )
+}
+
+pub fn edit_comment(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Edit the comment [{}]", key_config.edit_comment),
+        CMD_GROUP_PROPERTIES,
+    )
+}
+
+pub fn record_and_play_macro(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Start/stop recording a macro [{}] / play it [{}]",
+            key_config.record_macro, key_config.play_macro
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn undo_change(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Revert last change [{}]", key_config.undo_change),
+        CMD_GROUP_GENERAL,
+    )
+}
+
 pub fn exit_pop_up(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
         format!("Exit pop up [{}]", key_config.exit_popup),
         CMD_GROUP_GENERAL,
     )
 }
+
+pub fn find_replace(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Find and replace [{}]", key_config.find_replace),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn suggest_index(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Suggest indexes from EXPLAIN [{}]",
+            key_config.suggest_index
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn show_session_variables(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show session variables [{}]",
+            key_config.show_session_variables
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn show_result_schema(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Show the columns of the query result [{}]",
+            key_config.show_result_schema
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn rerun_last_query(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Run the last statement or records query again [{}]",
+            key_config.rerun_last_query
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn set_template_variable(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Set a variable of the connection [{}]",
+            key_config.set_template_variable
+        ),
+        CMD_GROUP_GENERAL,
+    )
+}
+
+pub fn toggle_scratch_database(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Run statements on the scratch database or the connection [{}]",
+            key_config.toggle_scratch_database
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn save_result_to_scratch(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Save the result to the scratch database [{}]",
+            key_config.save_result_to_scratch
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn run_without_limit(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Run the truncated query without the row limit [{}]",
+            key_config.run_without_limit
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn pin_result(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Pin result [{}]", key_config.pin_result),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn switch_result(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "Switch result [{},{}]",
+            key_config.previous_result, key_config.next_result
+        ),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
+
+pub fn copy_result_as_csv(key_config: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("Copy result as CSV [{}]", key_config.copy_result_as_csv),
+        CMD_GROUP_SQL_EDITOR,
+    )
+}
@@ -10,16 +10,83 @@ use ratatui::{
     Frame,
 };
 
-const RESERVED_WORDS_IN_WHERE_CLAUSE: &[&str] = &["IN", "AND", "OR", "NOT", "NULL", "IS"];
+const RESERVED_WORDS_IN_WHERE_CLAUSE: &[&str] =
+    &["IN", "AND", "OR", "NOT", "NULL", "IS", "LIKE", "BETWEEN"];
 const ALL_RESERVED_WORDS: &[&str] = &[
-    "IN", "AND", "OR", "NOT", "NULL", "IS", "SELECT", "UPDATE", "DELETE", "FROM", "LIMIT", "WHERE",
+    "IN", "AND", "OR", "NOT", "NULL", "IS", "LIKE", "BETWEEN", "SELECT", "UPDATE", "DELETE",
+    "FROM", "LIMIT", "WHERE", "INSERT", "INTO", "VALUES", "SET", "JOIN", "ON", "ORDER", "GROUP",
+    "BY", "HAVING", "AS", "DISTINCT",
 ];
+/// Keywords followed by a table name.
+const TABLE_KEYWORDS: &[&str] = &["FROM", "JOIN", "INTO", "UPDATE", "TABLE"];
+/// Keywords followed by a column name.
+const COLUMN_KEYWORDS: &[&str] = &[
+    "SELECT", "WHERE", "AND", "OR", "ON", "BY", "SET", "HAVING", "DISTINCT", "NOT",
+];
+const MAX_RECENT: usize = 20;
+
+/// The kind of word expected at the cursor, whose candidates are suggested first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionContext {
+    Column,
+    Table,
+    Keyword,
+}
+
+impl CompletionContext {
+    /// Guesses the context from the text before the word being typed.
+    pub fn from_preceding(text: &str) -> Self {
+        let tokens = text
+            .split(|c: char| c.is_whitespace() || c == '(')
+            .filter(|token| !token.is_empty())
+            .collect::<Vec<&str>>();
+        let Some(last) = tokens.last() else {
+            return Self::Keyword;
+        };
+        if last.ends_with(',') {
+            return tokens
+                .iter()
+                .rev()
+                .find_map(|token| Self::after_keyword(token))
+                .unwrap_or(Self::Keyword);
+        }
+        if last.ends_with(['=', '<', '>']) {
+            return Self::Column;
+        }
+        Self::after_keyword(last).unwrap_or(Self::Keyword)
+    }
+
+    fn after_keyword(token: &str) -> Option<Self> {
+        let token = token.to_uppercase();
+        if TABLE_KEYWORDS.contains(&token.as_str()) {
+            Some(Self::Table)
+        } else if COLUMN_KEYWORDS.contains(&token.as_str()) {
+            Some(Self::Column)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns true if `candidate` starts with the first character of `word` and the other
+/// characters appear in it in order. A single character only matches as a prefix.
+fn fuzzy_match(word: &str, candidate: &str) -> bool {
+    let mut chars = candidate.chars();
+    word.chars().count() > 1
+        && word.chars().next() == chars.next()
+        && word.chars().skip(1).all(|w| chars.any(|c| c == w))
+}
 
 pub struct CompletionComponent {
     key_config: KeyConfig,
     state: ListState,
     word: String,
     candidates: Vec<String>,
+    columns: Vec<String>,
+    tables: Vec<String>,
+    /// Identifiers picked from the list, from oldest to newest.
+    recent: Vec<String>,
+    context: CompletionContext,
 }
 
 impl CompletionComponent {
@@ -36,6 +103,44 @@ impl CompletionComponent {
                     .map(|w| w.to_string())
                     .collect()
             },
+            columns: vec![],
+            tables: vec![],
+            recent: vec![],
+            context: CompletionContext::Column,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    /// Sets column names which are suggested before reserved words.
+    pub fn set_columns(&mut self, columns: Vec<String>) {
+        self.columns = columns;
+    }
+
+    pub fn set_tables(&mut self, tables: Vec<String>) {
+        self.tables = tables;
+    }
+
+    pub fn set_context(&mut self, context: CompletionContext) {
+        self.context = context;
+    }
+
+    /// Remembers `candidate` so it is suggested before other identifiers next time.
+    pub fn push_recent(&mut self, candidate: &str) {
+        if !self
+            .columns
+            .iter()
+            .chain(self.tables.iter())
+            .any(|c| c == candidate)
+        {
+            return;
+        }
+        self.recent.retain(|r| r != candidate);
+        self.recent.push(candidate.to_string());
+        if self.recent.len() > MAX_RECENT {
+            self.recent.remove(0);
         }
     }
 
@@ -73,12 +178,43 @@ impl CompletionComponent {
         self.state.select(Some(i));
     }
 
+    /// Candidates matching the word, prefix matches before fuzzy ones. Within each, candidates
+    /// of the current context come first, and recently picked identifiers first among them.
     fn filtered_candidates(&self) -> impl Iterator<Item = &String> {
-        self.candidates.iter().filter(move |c| {
-            (c.starts_with(self.word.to_lowercase().as_str())
-                || c.starts_with(self.word.to_uppercase().as_str()))
-                && !self.word.is_empty()
-        })
+        let word = self.word.to_lowercase();
+        if word.is_empty() {
+            return Vec::new().into_iter();
+        }
+        let groups = match self.context {
+            CompletionContext::Column => [&self.columns, &self.tables, &self.candidates],
+            CompletionContext::Table => [&self.tables, &self.columns, &self.candidates],
+            CompletionContext::Keyword => [&self.candidates, &self.columns, &self.tables],
+        };
+        let mut matches = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, candidates)| candidates.iter().map(move |c| (group, c)))
+            .filter_map(|(group, c)| {
+                let lower = c.to_lowercase();
+                let fuzzy = if lower.starts_with(word.as_str()) {
+                    false
+                } else if fuzzy_match(&word, &lower) {
+                    true
+                } else {
+                    return None;
+                };
+                let recent = self.recent.iter().rev().position(|r| r == c);
+                Some(((fuzzy, group, recent.is_none(), recent), c))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(key, _)| *key);
+        let mut seen = std::collections::HashSet::new();
+        matches
+            .into_iter()
+            .map(|(_, c)| c)
+            .filter(|c| seen.insert(c.as_str()))
+            .collect::<Vec<&String>>()
+            .into_iter()
     }
 
     pub fn selected_candidate(&self) -> Option<String> {
@@ -142,7 +278,85 @@ impl Component for CompletionComponent {
 
 #[cfg(test)]
 mod test {
-    use super::{CompletionComponent, KeyConfig};
+    use super::{CompletionComponent, CompletionContext, KeyConfig};
+
+    #[test]
+    fn test_context() {
+        assert_eq!(
+            CompletionContext::from_preceding(""),
+            CompletionContext::Keyword
+        );
+        assert_eq!(
+            CompletionContext::from_preceding("select "),
+            CompletionContext::Column
+        );
+        assert_eq!(
+            CompletionContext::from_preceding("SELECT id, "),
+            CompletionContext::Column
+        );
+        assert_eq!(
+            CompletionContext::from_preceding("SELECT id FROM users JOIN "),
+            CompletionContext::Table
+        );
+        assert_eq!(
+            CompletionContext::from_preceding("SELECT id FROM users "),
+            CompletionContext::Keyword
+        );
+        assert_eq!(
+            CompletionContext::from_preceding("SELECT id FROM users WHERE age >"),
+            CompletionContext::Column
+        );
+    }
+
+    #[test]
+    fn test_ranking() {
+        let mut completion = CompletionComponent::new(KeyConfig::default(), "", true);
+        completion.set_columns(vec!["user_id".to_string(), "order_id".to_string()]);
+        completion.set_tables(vec!["users".to_string(), "orders".to_string()]);
+
+        completion.set_context(CompletionContext::Table);
+        completion.update("o");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["orders", "order_id", "OR", "ON", "ORDER"]
+        );
+
+        completion.set_context(CompletionContext::Column);
+        completion.update("uid");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["user_id"]
+        );
+        completion.update("us");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["user_id", "users"]
+        );
+        completion.push_recent("users");
+        completion.push_recent("SELECT");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["user_id", "users"]
+        );
+        completion.set_columns(vec!["user_id".to_string(), "username".to_string()]);
+        completion.push_recent("username");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["username", "user_id", "users"]
+        );
+
+        completion.set_context(CompletionContext::Keyword);
+        completion.update("se");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["SELECT", "SET"]
+        );
+        completion.update("sl");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec!["SELECT"]
+        );
+    }
 
     #[test]
     fn test_filtered_candidates_lowercase() {
@@ -180,4 +394,20 @@ mod test {
             vec![&"NOT".to_string(), &"NULL".to_string()]
         );
     }
+
+    #[test]
+    fn test_filtered_candidates_columns() {
+        let mut completion = CompletionComponent::new(KeyConfig::default(), "", false);
+        completion.set_columns(vec!["userId".to_string(), "name".to_string()]);
+        completion.update("USERI");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec![&"userId".to_string()]
+        );
+        completion.update("n");
+        assert_eq!(
+            completion.filtered_candidates().collect::<Vec<&String>>(),
+            vec![&"name".to_string(), &"NOT".to_string(), &"NULL".to_string()]
+        );
+    }
 }
@@ -0,0 +1,136 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::{ConfigError, KeyConfig};
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Lists the problems found in the config file at startup, before anything else is shown.
+pub struct ConfigErrorComponent {
+    errors: Vec<ConfigError>,
+    connections: usize,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ConfigErrorComponent {
+    pub fn new(key_config: KeyConfig, errors: Vec<ConfigError>, connections: usize) -> Self {
+        Self {
+            visible: !errors.is_empty(),
+            errors,
+            connections,
+            key_config,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl DrawableComponent for ConfigErrorComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = f.size();
+            let block = Block::default()
+                .title("Config Error")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Red));
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let lines = self
+                .errors
+                .iter()
+                .map(|error| Line::from(error.to_string()))
+                .collect::<Vec<Line>>();
+            f.render_widget(Clear, area);
+            f.render_widget(block, area);
+            f.render_widget(
+                Paragraph::new(lines)
+                    .style(Style::default().fg(Color::Reset))
+                    .wrap(Wrap { trim: false }),
+                chunks[0],
+            );
+            f.render_widget(
+                Paragraph::new(Line::from(vec![Span::styled(
+                    format!(
+                        "Press [{}] to continue with {} valid connection(s), [{}] to quit.",
+                        self.key_config.enter, self.connections, self.key_config.quit
+                    ),
+                    Style::default(),
+                )])),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ConfigErrorComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                self.hide();
+            } else if key == self.key_config.quit || key == self.key_config.exit {
+                return Ok(EventState::NotConsumed);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component, ConfigErrorComponent, EventState};
+    use crate::config::{ConfigError, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_event() {
+        let errors = vec![ConfigError {
+            line: Some(1),
+            message: "missing field `type`".to_string(),
+        }];
+        let mut component = ConfigErrorComponent::new(KeyConfig::default(), errors, 1);
+        assert_eq!(
+            component.event(Key::Char('j')).unwrap(),
+            EventState::Consumed
+        );
+        assert_eq!(
+            component.event(Key::Char('q')).unwrap(),
+            EventState::NotConsumed
+        );
+        assert_eq!(component.event(Key::Enter).unwrap(), EventState::Consumed);
+        assert_eq!(
+            component.event(Key::Char('j')).unwrap(),
+            EventState::NotConsumed
+        );
+
+        let component = ConfigErrorComponent::new(KeyConfig::default(), vec![], 1);
+        assert!(!component.is_visible());
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::copy_rows::CopyTarget;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    RevertChange,
+    BulkUpdate {
+        query: String,
+    },
+    InsertRows {
+        statements: Vec<String>,
+        summary: String,
+    },
+    CopyToClipboard {
+        text: String,
+    },
+    ExecuteQuery {
+        query: String,
+    },
+    SetComment {
+        query: String,
+    },
+    CopyRows {
+        connection: usize,
+        target: CopyTarget,
+        selected: bool,
+    },
+}
+
+pub struct ConfirmComponent {
+    message: String,
+    action: Option<ConfirmAction>,
+    confirmed: Option<ConfirmAction>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ConfirmComponent {
+    const WIDTH: u16 = 65;
+    const HEIGHT: u16 = 10;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            message: String::new(),
+            action: None,
+            confirmed: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, action: ConfirmAction, message: String) -> Result<()> {
+        self.message = message;
+        self.action = Some(action);
+        self.show()
+    }
+
+    pub fn take_confirmed(&mut self) -> Option<ConfirmAction> {
+        self.confirmed.take()
+    }
+}
+
+impl DrawableComponent for ConfirmComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let confirm = Block::default()
+                .title("Confirm")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Yellow));
+
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            f.render_widget(Clear, area);
+            f.render_widget(confirm, area);
+            f.render_widget(
+                Paragraph::new(self.message.to_string()).wrap(Wrap { trim: true }),
+                chunks[0],
+            );
+            f.render_widget(
+                Paragraph::new(Line::from(vec![Span::styled(
+                    format!(
+                        "Press [{}] to confirm, [{}] to cancel.",
+                        self.key_config.enter, self.key_config.exit_popup
+                    ),
+                    Style::default(),
+                )]))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ConfirmComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                self.confirmed = self.action.take();
+                self.hide();
+            } else if key == self.key_config.exit_popup {
+                self.action = None;
+                self.hide();
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component, ConfirmAction, ConfirmComponent, Key, KeyConfig};
+
+    #[test]
+    fn test_confirm() {
+        let mut component = ConfirmComponent::new(KeyConfig::default());
+        assert!(!component.event(Key::Enter).unwrap().is_consumed());
+
+        component
+            .open(ConfirmAction::RevertChange, "message".to_string())
+            .unwrap();
+        assert!(component.event(Key::Char('j')).unwrap().is_consumed());
+        assert_eq!(component.take_confirmed(), None);
+        assert!(component.event(Key::Enter).unwrap().is_consumed());
+        assert_eq!(
+            component.take_confirmed(),
+            Some(ConfirmAction::RevertChange)
+        );
+
+        component
+            .open(ConfirmAction::RevertChange, "message".to_string())
+            .unwrap();
+        assert!(component.event(Key::Esc).unwrap().is_consumed());
+        assert_eq!(component.take_confirmed(), None);
+    }
+}
@@ -0,0 +1,205 @@
+use super::{Component, DatabaseFilterComponent, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+/// A popup to switch to another connection by typing part of its name.
+pub struct ConnectionSwitcherComponent {
+    names: Vec<String>,
+    input: DatabaseFilterComponent,
+    matches: Vec<usize>,
+    state: ListState,
+    selected: Option<usize>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ConnectionSwitcherComponent {
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 15;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            names: vec![],
+            input: DatabaseFilterComponent::with_placeholder("Filter connections"),
+            matches: vec![],
+            state: ListState::default(),
+            selected: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, names: Vec<String>) -> Result<()> {
+        self.names = names;
+        self.input.reset();
+        self.update_matches();
+        self.show()
+    }
+
+    /// Index of the connection chosen by the user.
+    pub fn take_selected(&mut self) -> Option<usize> {
+        self.selected.take()
+    }
+
+    fn update_matches(&mut self) {
+        let pattern = self.input.input_str();
+        self.matches = self
+            .names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| fuzzy_match(&pattern, name))
+            .map(|(index, _)| index)
+            .collect();
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.matches.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+/// Returns true if all characters of `pattern` appear in `text` in order, ignoring case.
+fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|p| chars.any(|c| c == p))
+}
+
+impl DrawableComponent for ConnectionSwitcherComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(2), Constraint::Min(1)].as_ref())
+                .split(area);
+
+            let items = self
+                .matches
+                .iter()
+                .map(|index| ListItem::new(Line::from(Span::raw(self.names[*index].as_str()))))
+                .collect::<Vec<ListItem>>();
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title("Switch Connection")
+                    .borders(Borders::ALL),
+                area,
+            );
+            self.input.draw(f, chunks[0], true)?;
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[1],
+                &mut state,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ConnectionSwitcherComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                self.selected = self.state.selected().map(|i| self.matches[i]);
+                self.hide();
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down {
+                self.move_selection(true);
+            } else if key == Key::Up {
+                self.move_selection(false);
+            } else if self.input.event(key)?.is_consumed() {
+                self.update_matches();
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fuzzy_match, Component, ConnectionSwitcherComponent, Key, KeyConfig};
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("", "anything"));
+        assert!(fuzzy_match(
+            "pgbar",
+            "[postgres Bar DB] postgres://root@localhost"
+        ));
+        assert!(fuzzy_match(
+            "My Foo",
+            "[mysql Foo DB] mysql://root@localhost"
+        ));
+        assert!(!fuzzy_match("oof", "foo"));
+    }
+
+    #[test]
+    fn test_select() {
+        let mut component = ConnectionSwitcherComponent::new(KeyConfig::default());
+        component
+            .open(vec![
+                "[local] mysql://root@localhost:3306".to_string(),
+                "[staging] postgres://root@staging:5432".to_string(),
+                "[production] postgres://root@production:5432".to_string(),
+            ])
+            .unwrap();
+        for c in "pos".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        assert_eq!(component.matches, vec![1, 2]);
+        component.event(Key::Down).unwrap();
+        component.event(Key::Down).unwrap();
+        component.event(Key::Enter).unwrap();
+        assert_eq!(component.take_selected(), Some(2));
+        assert!(!component.event(Key::Enter).unwrap().is_consumed());
+
+        component.open(vec!["local".to_string()]).unwrap();
+        component.event(Key::Char('x')).unwrap();
+        component.event(Key::Enter).unwrap();
+        assert_eq!(component.take_selected(), None);
+    }
+}
@@ -1,13 +1,13 @@
 use super::{Component, EventState, StatefulDrawableComponent};
 use crate::components::command::CommandInfo;
-use crate::config::{Connection, KeyConfig};
+use crate::config::{get_app_config_path, Connection, KeyConfig};
 use crate::event::Key;
 use anyhow::Result;
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
@@ -30,6 +30,51 @@ impl ConnectionsComponent {
         }
     }
 
+    /// How to add a connection, shown instead of the empty list.
+    fn empty_hint(&self) -> Vec<Line<'static>> {
+        let config_path = get_app_config_path()
+            .map(|path| path.join("config.toml").display().to_string())
+            .unwrap_or_else(|_| "config.toml".to_string());
+        vec![
+            Line::from(Span::styled(
+                "No connection is configured.",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(format!("Add a connection to {}:", config_path)),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  [[conn]]",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(Span::styled(
+                "  type = \"postgres\"  # mysql, postgres or sqlite",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(Span::styled(
+                "  host = \"localhost\"",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(Span::styled(
+                "  user = \"root\"",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(""),
+            Line::from("It is read again when saved. A SQLite file can also be opened with"),
+            Line::from(Span::styled(
+                "  zhobo path/to/file.db",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from("or a sample database tried with"),
+            Line::from(Span::styled(
+                "  zhobo --demo",
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(""),
+            Line::from(format!("Press [{}] to quit.", self.key_config.exit)),
+        ]
+    }
+
     fn next_connection(&mut self, lines: usize) {
         let i = match self.state.selected() {
             Some(i) => {
@@ -72,6 +117,23 @@ impl ConnectionsComponent {
         self.state.select(Some(self.connections.len() - 1));
     }
 
+    pub fn select(&mut self, index: usize) {
+        if index < self.connections.len() {
+            self.state.select(Some(index));
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.connections
+            .iter()
+            .map(|c| c.database_url_with_name().unwrap_or_default())
+            .collect()
+    }
+
+    pub fn connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
     pub fn selected_connection(&self) -> Option<&Connection> {
         match self.state.selected() {
             Some(i) => self.connections.get(i),
@@ -88,8 +150,12 @@ impl StatefulDrawableComponent for ConnectionsComponent {
         let mut connections: Vec<ListItem> = Vec::new();
         for c in conns {
             match c.database_url_with_name() {
-                Ok(url) => connections
-                    .push(ListItem::new(vec![Line::from(Span::raw(url))]).style(Style::default())),
+                Ok(url) => connections.push(
+                    ListItem::new(vec![Line::from(Span::raw(url))]).style(
+                        c.accent
+                            .map_or_else(Style::default, |color| Style::default().fg(color)),
+                    ),
+                ),
                 Err(e) => {
                     return Err(anyhow::anyhow!(e).context("Failed to database_url_with_name"));
                 }
@@ -108,6 +174,15 @@ impl StatefulDrawableComponent for ConnectionsComponent {
         );
 
         f.render_widget(Clear, area);
+        if self.connections.is_empty() {
+            f.render_widget(
+                Paragraph::new(self.empty_hint())
+                    .block(Block::default().borders(Borders::ALL).title("Connections"))
+                    .wrap(Wrap { trim: false }),
+                area,
+            );
+            return Ok(());
+        }
         f.render_stateful_widget(connections, area, &mut self.state);
         Ok(())
     }
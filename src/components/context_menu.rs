@@ -0,0 +1,213 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// An action on the selected node of the database tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TreeAction {
+    OpenRecords,
+    OpenProperties,
+    CopyName,
+    CopyQualifiedName,
+    GenerateSelect,
+    DumpTable,
+    Refresh,
+}
+
+impl TreeAction {
+    /// The actions on a table, or on a database or a schema.
+    pub fn for_node(is_table: bool) -> Vec<TreeAction> {
+        if is_table {
+            vec![
+                TreeAction::OpenRecords,
+                TreeAction::OpenProperties,
+                TreeAction::CopyName,
+                TreeAction::CopyQualifiedName,
+                TreeAction::GenerateSelect,
+                TreeAction::DumpTable,
+                TreeAction::Refresh,
+            ]
+        } else {
+            vec![TreeAction::CopyName, TreeAction::Refresh]
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            TreeAction::OpenRecords => "Open records",
+            TreeAction::OpenProperties => "Open properties",
+            TreeAction::CopyName => "Copy name",
+            TreeAction::CopyQualifiedName => "Copy qualified name",
+            TreeAction::GenerateSelect => "Generate SELECT in the SQL editor",
+            TreeAction::DumpTable => "Dump table as SQL",
+            TreeAction::Refresh => "Refresh the tree",
+        }
+    }
+}
+
+/// The actions on the selected node of the database tree.
+pub struct ContextMenuComponent {
+    name: String,
+    actions: Vec<TreeAction>,
+    state: ListState,
+    selected: Option<TreeAction>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ContextMenuComponent {
+    const WIDTH: u16 = 50;
+    const HEIGHT: u16 = 11;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            name: String::new(),
+            actions: vec![],
+            state: ListState::default(),
+            selected: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    /// Shows `actions` on the node named `name`.
+    pub fn open(&mut self, name: String, actions: Vec<TreeAction>) -> Result<()> {
+        self.name = name;
+        self.actions = actions;
+        self.state.select((!self.actions.is_empty()).then_some(0));
+        self.show()
+    }
+
+    /// The action chosen to be run.
+    pub fn take_selected(&mut self) -> Option<TreeAction> {
+        self.selected.take()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.actions.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+impl DrawableComponent for ContextMenuComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let items = self
+                .actions
+                .iter()
+                .map(|action| ListItem::new(action.label()))
+                .collect::<Vec<ListItem>>();
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title(self.name.as_str())
+                    .borders(Borders::ALL),
+                area,
+            );
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[0],
+                &mut state,
+            );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Press [{}] to run, [{}] to close.",
+                    self.key_config.enter, self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ContextMenuComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                if let Some(action) = self.state.selected().and_then(|i| self.actions.get(i)) {
+                    self.selected = Some(*action);
+                    self.hide();
+                }
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down || key == self.key_config.scroll_down {
+                self.move_selection(true);
+            } else if key == Key::Up || key == self.key_config.scroll_up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ContextMenuComponent, TreeAction};
+    use crate::components::Component;
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+
+    #[test]
+    fn test_choose_action() {
+        let mut menu = ContextMenuComponent::new(KeyConfig::default());
+        assert!(!menu.event(Key::Enter).unwrap().is_consumed());
+
+        menu.open("users".to_string(), TreeAction::for_node(true))
+            .unwrap();
+        menu.event(Key::Down).unwrap();
+        menu.event(Key::Down).unwrap();
+        menu.event(Key::Up).unwrap();
+        menu.event(Key::Enter).unwrap();
+        assert_eq!(menu.take_selected(), Some(TreeAction::OpenProperties));
+        assert!(!menu.event(Key::Enter).unwrap().is_consumed());
+
+        menu.open("app".to_string(), TreeAction::for_node(false))
+            .unwrap();
+        menu.event(Key::Esc).unwrap();
+        assert_eq!(menu.take_selected(), None);
+    }
+}
@@ -1,7 +1,6 @@
 use super::{compute_character_width, Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
 use crate::event::Key;
-use crate::tree::Table;
 use anyhow::Result;
 use ratatui::{
     layout::Rect,
@@ -11,21 +10,28 @@ use ratatui::{
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
+use zhobo::tree::Table;
 
 pub struct DatabaseFilterComponent {
     pub table: Option<Table>,
     input: Vec<char>,
     input_idx: usize,
     input_cursor_position: u16,
+    placeholder: String,
 }
 
 impl DatabaseFilterComponent {
     pub fn new() -> Self {
+        Self::with_placeholder("Filter tables")
+    }
+
+    pub fn with_placeholder(placeholder: impl Into<String>) -> Self {
         Self {
             table: None,
             input: Vec::new(),
             input_idx: 0,
             input_cursor_position: 0,
+            placeholder: placeholder.into(),
         }
     }
 
@@ -33,6 +39,12 @@ impl DatabaseFilterComponent {
         self.input.iter().collect()
     }
 
+    pub fn set_input(&mut self, input: &str) {
+        self.input = input.chars().collect();
+        self.input_idx = self.input.len();
+        self.input_cursor_position = input.width() as u16;
+    }
+
     pub fn reset(&mut self) {
         self.table = None;
         self.input = Vec::new();
@@ -46,7 +58,7 @@ impl DrawableComponent for DatabaseFilterComponent {
         let query = Paragraph::new(Line::from(format!(
             "{:w$}",
             if self.input.is_empty() && !focused {
-                "Filter tables".to_string()
+                self.placeholder.to_string()
             } else {
                 self.input_str()
             },
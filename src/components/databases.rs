@@ -4,27 +4,49 @@ use super::{
 };
 use crate::components::command::{self, CommandInfo};
 use crate::config::{Connection, KeyConfig};
-use crate::database::Pool;
 use crate::event::Key;
-use crate::tree::{Database, DatabaseTree, DatabaseTreeItem};
 use crate::ui::common_nav;
 use crate::ui::scrolllist::draw_list_block;
 use anyhow::Result;
+use futures::{FutureExt, StreamExt};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::From;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use zhobo::database::Pool;
+use zhobo::tree::{Child, Database, DatabaseTree, DatabaseTreeItem, Table};
 
 // ▸
 const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}";
 // ▾
 const FOLDER_ICON_EXPANDED: &str = "\u{25be}";
+// 🔒, in place of the arrow of a database whose tables could not be listed
+const LOCK_ICON: &str = "\u{1f512}";
 const EMPTY_STR: &str = "";
+/// How long typing has to pause before the tree is filtered again.
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(150);
+/// How many databases have their tables listed at once when refreshing the tree.
+const FETCH_CONCURRENCY: usize = 4;
+/// Databases and schemas of the database itself, which are hidden unless system objects are
+/// shown.
+const SYSTEM_SCHEMAS: [&str; 8] = [
+    "information_schema",
+    "mysql",
+    "performance_schema",
+    "sys",
+    "pg_catalog",
+    "pg_toast",
+    "template0",
+    "template1",
+];
 
 #[derive(PartialEq)]
 pub enum Focus {
@@ -36,8 +58,26 @@ pub struct DatabasesComponent {
     tree: DatabaseTree,
     filter: DatabaseFilterComponent,
     filtered_tree: Option<DatabaseTree>,
+    /// Filter text waiting for typing to pause, and when it was typed.
+    pending_filter: Option<(String, Instant)>,
+    /// Filtering running in the background, and the text it filters by.
+    filtering: Option<(String, JoinHandle<DatabaseTree>)>,
+    /// The tree the background filtering reads, shared instead of copied for each filter text.
+    filter_source: Option<Arc<DatabaseTree>>,
     scroll: VerticalScroll,
     focus: Focus,
+    /// Notes about the connection shown in the title, such as the SQLite journal mode.
+    status: Vec<String>,
+    /// All the databases of the connection, including system ones.
+    databases: Vec<Database>,
+    /// How long listing the tables of each database took, shown in debug builds.
+    durations: BTreeMap<String, Duration>,
+    /// Tables whose columns could not be read, with the name of their database and why, kept
+    /// when the tree is built again.
+    table_errors: Vec<(String, Table, String)>,
+    show_system_objects: bool,
+    /// The accent color of the connection, drawn on the border.
+    accent: Option<Color>,
     key_config: KeyConfig,
 }
 
@@ -47,26 +87,179 @@ impl DatabasesComponent {
             tree: DatabaseTree::default(),
             filter: DatabaseFilterComponent::new(),
             filtered_tree: None,
+            pending_filter: None,
+            filtering: None,
+            filter_source: None,
             scroll: VerticalScroll::new(false, false),
             focus: Focus::Tree,
+            status: vec![],
+            databases: vec![],
+            durations: BTreeMap::new(),
+            table_errors: vec![],
+            show_system_objects: false,
+            accent: None,
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
     pub async fn update(&mut self, connection: &Connection, pool: &Box<dyn Pool>) -> Result<()> {
-        let databases = match &connection.database {
-            Some(database) => vec![Database::new(
-                database.clone(),
-                pool.get_tables(database.clone()).await?,
-            )],
-            None => pool.get_databases().await?,
+        let names = match &connection.database {
+            Some(database) => vec![database.clone()],
+            None => pool.get_database_names().await?,
         };
-        self.tree = DatabaseTree::new(databases.as_slice(), &BTreeSet::new())?;
-        self.filtered_tree = None;
+        let timeout = Duration::from_secs(connection.list_timeout_second);
+        let fetched = futures::stream::iter(names)
+            .map(|name| fetch_database(pool.as_ref(), name, timeout))
+            .buffered(FETCH_CONCURRENCY)
+            .collect::<Vec<(Database, Duration)>>()
+            .await;
+        self.durations = fetched
+            .iter()
+            .map(|(database, duration)| (database.name.clone(), *duration))
+            .collect();
+        self.databases = fetched.into_iter().map(|(database, _)| database).collect();
+        self.accent = connection.accent;
+        self.table_errors.clear();
+        self.build_tree()?;
+        let schema = connection.default_schema();
+        if schema.is_some() || connection.database.is_some() {
+            self.tree
+                .select(connection.database.as_deref(), schema.as_deref());
+        }
         self.filter.reset();
+        self.status.clear();
+        if let Some(journal_mode) = pool.get_journal_mode().await? {
+            self.status.push(format!("journal: {}", journal_mode));
+        }
+        if connection.is_read_only() {
+            self.status.push("read only".to_string());
+        }
         Ok(())
     }
 
+    /// Lists the tables of the selected database again when they could not be listed, keeping
+    /// the others as they are. Returns the error when they still can't be listed.
+    pub async fn retry_selected(
+        &mut self,
+        connection: &Connection,
+        pool: &dyn Pool,
+    ) -> Result<Option<String>> {
+        let Some(name) = self
+            .tree()
+            .selected_database_error()
+            .and(self.tree().selected_name())
+        else {
+            return Ok(None);
+        };
+        let (database, duration) = fetch_database(
+            pool,
+            name.clone(),
+            Duration::from_secs(connection.list_timeout_second),
+        )
+        .await;
+        let error = database.error.clone();
+        self.durations.insert(name.clone(), duration);
+        if let Some(old) = self.databases.iter_mut().find(|d| d.name == name) {
+            *old = database;
+        }
+        self.build_tree()?;
+        self.tree.select(Some(&name), None);
+        Ok(error)
+    }
+
+    /// Builds the tree from the databases, without the system ones unless they are shown.
+    fn build_tree(&mut self) -> Result<()> {
+        self.tree = if self.show_system_objects {
+            DatabaseTree::new(&self.databases, &BTreeSet::new())?
+        } else {
+            DatabaseTree::new(&without_system_objects(&self.databases), &BTreeSet::new())?
+        };
+        for (database, table, error) in &self.table_errors {
+            self.tree
+                .set_table_error(database, table, Some(error.clone()));
+        }
+        self.filtered_tree = None;
+        self.pending_filter = None;
+        self.filtering = None;
+        self.filter_source = None;
+        Ok(())
+    }
+
+    /// Marks `table` with a lock when its columns could not be read, such as for missing
+    /// privileges, or clears the mark with `None` once they could.
+    pub fn set_table_error(&mut self, database: &Database, table: &Table, error: Option<String>) {
+        self.table_errors
+            .retain(|(d, t, _)| !(d == &database.name && t == table));
+        if let Some(error) = &error {
+            self.table_errors
+                .push((database.name.clone(), table.clone(), error.clone()));
+        }
+        self.tree
+            .set_table_error(&database.name, table, error.clone());
+        if let Some(tree) = self.filtered_tree.as_mut() {
+            tree.set_table_error(&database.name, table, error);
+        }
+        self.filter_source = None;
+    }
+
+    /// Whether the filtered tree is waiting for typing to pause or for the background filtering.
+    pub fn is_filtering(&self) -> bool {
+        self.pending_filter.is_some() || self.filtering.is_some()
+    }
+
+    /// Shows the result of the background filtering once it is ready, and starts filtering by
+    /// the latest text once typing pauses.
+    pub fn poll(&mut self) {
+        if self
+            .filtering
+            .as_ref()
+            .is_some_and(|(_, handle)| handle.is_finished())
+        {
+            if let Some((text, handle)) = self.filtering.take() {
+                if let Some(Ok(tree)) = handle.now_or_never() {
+                    if text == self.filter.input_str() {
+                        self.filtered_tree = Some(tree);
+                    }
+                }
+            }
+        }
+        if self.filtering.is_none()
+            && self
+                .pending_filter
+                .as_ref()
+                .is_some_and(|(_, typed_at)| typed_at.elapsed() >= FILTER_DEBOUNCE)
+        {
+            self.start_filtering();
+        }
+    }
+
+    fn start_filtering(&mut self) {
+        if let Some((text, _)) = self.pending_filter.take() {
+            let source = self
+                .filter_source
+                .get_or_insert_with(|| Arc::new(self.tree.clone()))
+                .clone();
+            self.filtering = Some((
+                text.clone(),
+                tokio::task::spawn_blocking(move || source.filter(text)),
+            ));
+        }
+    }
+
+    fn filter_changed(&mut self) {
+        let text = self.filter.input_str();
+        if text.is_empty() {
+            self.filtered_tree = None;
+            self.pending_filter = None;
+        } else {
+            self.pending_filter = Some((text, Instant::now()));
+        }
+    }
+
     pub fn tree_focused(&self) -> bool {
         matches!(self.focus, Focus::Tree)
     }
@@ -90,8 +283,21 @@ impl DatabasesComponent {
             format!("{:w$}", " ", w = (indent as usize) * 2)
         };
 
-        let arrow = if item.kind().is_database() || item.kind().is_schema() {
-            if item.kind().is_database_collapsed() || item.kind().is_schema_collapsed() {
+        let arrow = if item
+            .kind()
+            .database_error()
+            .or(item.kind().table_error())
+            .is_some()
+        {
+            LOCK_ICON
+        } else if item.kind().is_database()
+            || item.kind().is_schema()
+            || item.kind().is_parent_table()
+        {
+            if item.kind().is_database_collapsed()
+                || item.kind().is_schema_collapsed()
+                || item.kind().is_table_collapsed()
+            {
                 FOLDER_ICON_COLLAPSED
             } else {
                 FOLDER_ICON_EXPANDED
@@ -143,16 +349,124 @@ impl DatabasesComponent {
         ))
     }
 
+    /// Why the tables of the selected database or the columns of the selected table could not
+    /// be read, when it is locked.
+    fn locked_hint(&self) -> Option<Vec<String>> {
+        if let Some(error) = self.tree().selected_table_error() {
+            return Some(vec![
+                "The columns could not be read:".to_string(),
+                error,
+                format!("Press [{}] to try again.", self.key_config.enter),
+            ]);
+        }
+        self.tree().selected_database_error().map(|error| {
+            vec![
+                "The tables could not be listed:".to_string(),
+                error,
+                format!(
+                    "Press [{}] to try again and show it in full if it still fails.",
+                    self.key_config.enter
+                ),
+            ]
+        })
+    }
+
+    /// What to do next when the tree shows no tables, or `None` when it shows some. The tree is
+    /// empty before connecting, which the connection list covers.
+    fn empty_hint(&self) -> Option<Vec<String>> {
+        if self.databases.is_empty() {
+            return None;
+        }
+        if self.tree().iterate(0, 1).next().is_none() {
+            return Some(if self.filter.input_str().is_empty() {
+                vec![
+                    "No databases to show.".to_string(),
+                    format!(
+                        "Press [{}] to show the system databases.",
+                        self.key_config.toggle_system_objects
+                    ),
+                ]
+            } else {
+                vec!["No table matches the filter.".to_string()]
+            });
+        }
+        let databases = if self.show_system_objects {
+            self.databases.clone()
+        } else {
+            without_system_objects(&self.databases)
+        };
+        let has_tables = databases.iter().any(|database| {
+            database.children.iter().any(|child| match child {
+                Child::Table(_) => true,
+                Child::Schema(schema) => !schema.tables.is_empty(),
+            })
+        });
+        (!has_tables).then(|| {
+            vec![
+                format!(
+                    "No tables in {}.",
+                    databases
+                        .iter()
+                        .map(|database| database.name.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ),
+                format!(
+                    "Press [{}] to create one in the SQL editor, then [{}] on the database to refresh the tree.",
+                    self.key_config.tab_sql_editor, self.key_config.context_menu
+                ),
+            ]
+        })
+    }
+
+    /// Draws how long listing the tables took at the right of each database shown.
+    fn draw_durations(&self, f: &mut Frame, area: Rect, tree: &DatabaseTree) {
+        for (row, (item, _)) in tree
+            .iterate(self.scroll.get_top(), area.height as usize)
+            .enumerate()
+        {
+            let Some(duration) = item
+                .is_database()
+                .then(|| self.durations.get(&item.kind().name()))
+                .flatten()
+            else {
+                continue;
+            };
+            let text = format!("{}ms", duration.as_millis());
+            let width = (text.len() as u16).min(area.width);
+            f.render_widget(
+                Paragraph::new(text).style(Style::default().fg(Color::DarkGray)),
+                Rect::new(area.right() - width, area.y + row as u16, width, 1),
+            );
+        }
+    }
+
     fn draw_tree(&self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
         f.render_widget(
             Block::default()
-                .title("Databases")
+                .title({
+                    let status = self
+                        .status
+                        .iter()
+                        .map(String::as_str)
+                        .chain(self.show_system_objects.then_some("system objects"))
+                        .collect::<Vec<&str>>();
+                    if status.is_empty() {
+                        "Databases".to_string()
+                    } else {
+                        format!("Databases [{}]", status.join(", "))
+                    }
+                })
                 .borders(Borders::ALL)
                 .style(if focused {
                     Style::default()
                 } else {
                     Style::default().fg(Color::DarkGray)
-                }),
+                })
+                .border_style(
+                    self.accent
+                        .map_or_else(Style::default, |color| Style::default().fg(color)),
+                ),
             area,
         );
 
@@ -182,24 +496,41 @@ impl DatabasesComponent {
             },
         );
 
+        let filter = Some(self.filter.input_str()).filter(|filter| !filter.is_empty());
         let items = tree
             .iterate(self.scroll.get_top(), tree_height)
             .map(|(item, selected)| {
-                Self::tree_item_to_span(
-                    item.clone(),
-                    selected,
-                    area.width,
-                    if self.filter.input_str().is_empty() {
-                        None
-                    } else {
-                        Some(self.filter.input_str())
-                    },
-                )
-            });
+                Self::tree_item_to_span(item.clone(), selected, area.width, filter.clone())
+            })
+            .collect::<Vec<Line>>();
 
-        draw_list_block(f, chunks[1], Block::default().borders(Borders::NONE), items);
+        let shown = items.len() as u16;
+        draw_list_block(
+            f,
+            chunks[1],
+            Block::default().borders(Borders::NONE),
+            items.into_iter(),
+        );
+        if cfg!(debug_assertions) {
+            self.draw_durations(f, chunks[1], tree);
+        }
         self.scroll.draw(f, chunks[1]);
 
+        if let Some(hint) = self.locked_hint().or_else(|| self.empty_hint()) {
+            // Below the databases, leaving a blank line.
+            let offset = if shown == 0 { 0 } else { shown + 1 };
+            f.render_widget(
+                Paragraph::new(hint.into_iter().map(Line::from).collect::<Vec<Line>>())
+                    .style(Style::default().fg(Color::DarkGray))
+                    .wrap(Wrap { trim: false }),
+                Rect {
+                    y: chunks[1].y + offset.min(chunks[1].height),
+                    height: chunks[1].height.saturating_sub(offset),
+                    ..chunks[1]
+                },
+            );
+        }
+
         Ok(())
     }
 }
@@ -218,7 +549,14 @@ impl DrawableComponent for DatabasesComponent {
 
 impl Component for DatabasesComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>) {
-        out.push(CommandInfo::new(command::expand_collapse(&self.key_config)))
+        out.push(CommandInfo::new(command::expand_collapse(&self.key_config)));
+        out.push(CommandInfo::new(command::context_menu(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_qualified_name(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::toggle_system_objects(
+            &self.key_config,
+        )));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
@@ -227,24 +565,31 @@ impl Component for DatabasesComponent {
             return Ok(EventState::Consumed);
         }
 
-        if matches!(self.focus, Focus::Filter) {
-            self.filtered_tree = if self.filter.input_str().is_empty() {
-                None
-            } else {
-                Some(self.tree.filter(self.filter.input_str()))
-            };
-        }
-
         match key {
             Key::Enter if matches!(self.focus, Focus::Filter) => {
                 self.focus = Focus::Tree;
+                if self.filtering.is_none() {
+                    self.start_filtering();
+                }
                 return Ok(EventState::Consumed);
             }
             key if matches!(self.focus, Focus::Filter) => {
+                let text = self.filter.input_str();
                 if self.filter.event(key)?.is_consumed() {
+                    if self.filter.input_str() != text {
+                        self.filter_changed();
+                    }
                     return Ok(EventState::Consumed);
                 }
             }
+            key if key == self.key_config.toggle_system_objects => {
+                self.show_system_objects = !self.show_system_objects;
+                self.build_tree()?;
+                if !self.filter.input_str().is_empty() {
+                    self.pending_filter = Some((self.filter.input_str(), Instant::now()));
+                }
+                return Ok(EventState::Consumed);
+            }
             key => {
                 if tree_nav(
                     if let Some(tree) = self.filtered_tree.as_mut() {
@@ -263,6 +608,46 @@ impl Component for DatabasesComponent {
     }
 }
 
+/// Lists the tables of `name`, giving up after `timeout` so one slow database can't hold up the
+/// others, and how long it took.
+async fn fetch_database(pool: &dyn Pool, name: String, timeout: Duration) -> (Database, Duration) {
+    let start = Instant::now();
+    let tables = match tokio::time::timeout(timeout, pool.get_tables(name.clone())).await {
+        Ok(tables) => tables,
+        Err(_) => Err(anyhow::anyhow!(
+            "listing the tables timed out after {}s",
+            timeout.as_secs()
+        )),
+    };
+    (Database::from_tables(name, tables), start.elapsed())
+}
+
+/// `databases` without the system databases, schemas and tables.
+fn without_system_objects(databases: &[Database]) -> Vec<Database> {
+    let is_system_schema = |name: &str| {
+        SYSTEM_SCHEMAS.contains(&name)
+            || name.starts_with("pg_temp_")
+            || name.starts_with("pg_toast_")
+    };
+    databases
+        .iter()
+        .filter(|database| !is_system_schema(&database.name))
+        .map(|database| Database {
+            name: database.name.clone(),
+            children: database
+                .children
+                .iter()
+                .filter(|child| match child {
+                    Child::Schema(schema) => !is_system_schema(&schema.name),
+                    Child::Table(table) => !table.name.starts_with("sqlite_"),
+                })
+                .cloned()
+                .collect(),
+            error: database.error.clone(),
+        })
+        .collect()
+}
+
 fn tree_nav(tree: &mut DatabaseTree, key: Key, key_config: &KeyConfig) -> bool {
     if let Some(common_nav) = common_nav(key, key_config) {
         tree.move_selection(common_nav)
@@ -273,8 +658,204 @@ fn tree_nav(tree: &mut DatabaseTree, key: Key, key_config: &KeyConfig) -> bool {
 
 #[cfg(test)]
 mod test {
-    use super::{Color, Database, DatabaseTreeItem, DatabasesComponent, Line, Span, Style};
-    use crate::tree::Table;
+    use super::{
+        Color, Component, Database, DatabaseTree, DatabaseTreeItem, DatabasesComponent, Line, Span,
+        Style,
+    };
+    use crate::config::{Connection, KeyConfig};
+    use crate::event::Key;
+    use std::collections::BTreeSet;
+    use zhobo::connection::open_pool;
+    use zhobo::tree::{Child, MoveSelection, Schema, Table};
+
+    fn table(name: &str) -> Child {
+        Child::Table(Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            parent: None,
+            schema: None,
+        })
+    }
+
+    #[test]
+    fn test_empty_hint() {
+        let mut component = DatabasesComponent::new(KeyConfig::default());
+        assert_eq!(component.empty_hint(), None);
+
+        component.databases = vec![Database::new("app".to_string(), vec![])];
+        component.build_tree().unwrap();
+        assert_eq!(
+            component.empty_hint().unwrap()[0],
+            "No tables in app.".to_string()
+        );
+
+        component.databases = vec![Database::new("app".to_string(), vec![table("users")])];
+        component.build_tree().unwrap();
+        assert_eq!(component.empty_hint(), None);
+    }
+
+    #[tokio::test]
+    async fn test_debounced_filter() {
+        let mut component = DatabasesComponent::new(KeyConfig::default());
+        component.tree = DatabaseTree::new(
+            &[Database::new(
+                "db".to_string(),
+                vec![table("users"), table("orders"), table("user_roles")],
+            )],
+            &BTreeSet::new(),
+        )
+        .unwrap();
+        component.event(component.key_config.filter).unwrap();
+        for c in "user".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.poll();
+        assert!(component.is_filtering());
+        assert!(component.filtered_tree.is_none());
+
+        while component.is_filtering() {
+            tokio::time::sleep(super::FILTER_DEBOUNCE).await;
+            component.poll();
+        }
+        assert_eq!(
+            component.tree().table_names("db"),
+            vec!["users".to_string(), "user_roles".to_string()]
+        );
+
+        for _ in 0..4 {
+            component.event(Key::Backspace).unwrap();
+        }
+        assert!(!component.is_filtering());
+        assert_eq!(component.tree().table_names("db").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_update() {
+        let connection = Connection::demo();
+        let pool = open_pool(&connection, connection.timeout_second, 1)
+            .await
+            .unwrap();
+        let mut component = DatabasesComponent::new(KeyConfig::default());
+        component.update(&connection, &pool).await.unwrap();
+        assert_eq!(
+            component.tree().table_names("main"),
+            ["customers", "products", "orders", "order_items"]
+        );
+        assert!(component.durations.contains_key("main"));
+        assert_eq!(
+            component
+                .retry_selected(&connection, pool.as_ref())
+                .await
+                .unwrap(),
+            None
+        );
+
+        component.databases = vec![Database::from_tables(
+            "main".to_string(),
+            Err(anyhow::anyhow!("timed out")),
+        )];
+        component.build_tree().unwrap();
+        component.tree.select(Some("main"), None);
+        assert_eq!(
+            component
+                .retry_selected(&connection, pool.as_ref())
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(component.tree().table_names("main").len(), 4);
+        assert_eq!(component.tree().selected_name(), Some("main".to_string()));
+    }
+
+    #[test]
+    fn test_set_table_error() {
+        let mut component = DatabasesComponent::new(KeyConfig::default());
+        component.databases = vec![Database::new(
+            "db".to_string(),
+            vec![table("users"), table("secrets")],
+        )];
+        component.build_tree().unwrap();
+        let Child::Table(secrets) = table("secrets") else {
+            unreachable!()
+        };
+        component.set_table_error(
+            &component.databases[0].clone(),
+            &secrets,
+            Some("permission denied for table secrets".to_string()),
+        );
+
+        // Kept when the tree is built again, such as for showing system objects.
+        component.build_tree().unwrap();
+        component.tree.select(Some("db"), None);
+        component.tree.move_selection(MoveSelection::Down);
+        assert_eq!(component.tree().selected_name(), Some("users".to_string()));
+        assert_eq!(component.locked_hint(), None);
+        component.tree.move_selection(MoveSelection::Down);
+        assert_eq!(
+            component.tree().selected_table_error(),
+            Some("permission denied for table secrets".to_string())
+        );
+        assert_eq!(
+            component.locked_hint().unwrap()[1],
+            "permission denied for table secrets"
+        );
+
+        component.set_table_error(&component.databases[0].clone(), &secrets, None);
+        assert_eq!(component.tree().selected_table_error(), None);
+        component.build_tree().unwrap();
+        assert!(component.table_errors.is_empty());
+    }
+
+    #[test]
+    fn test_locked_table_tree_item_to_span() {
+        const WIDTH: u16 = 10;
+        let database = Database::new("foo".to_string(), vec![table("bar")]);
+        let mut tree = DatabaseTree::new(&[database], &BTreeSet::new()).unwrap();
+        let Child::Table(bar) = table("bar") else {
+            unreachable!()
+        };
+        tree.set_table_error("foo", &bar, Some("permission denied".to_string()));
+        tree.select(Some("foo"), None);
+        let item = tree.iterate(0, 2).nth(1).unwrap().0.clone();
+        assert_eq!(
+            DatabasesComponent::tree_item_to_span(item, false, WIDTH, None),
+            Line::from(vec![Span::raw(format!(
+                "  \u{1f512}{:w$}",
+                "bar",
+                w = WIDTH as usize
+            ))])
+        );
+    }
+
+    #[test]
+    fn test_without_system_objects() {
+        let schema = |name: &str| {
+            Child::Schema(Schema {
+                name: name.to_string(),
+                tables: vec![],
+            })
+        };
+        let databases = [
+            Database::new("information_schema".to_string(), vec![table("TABLES")]),
+            Database::new(
+                "app".to_string(),
+                vec![schema("public"), schema("pg_catalog"), schema("pg_temp_3")],
+            ),
+            Database::new(
+                "main".to_string(),
+                vec![table("sqlite_master"), table("users")],
+            ),
+        ];
+        assert_eq!(
+            super::without_system_objects(&databases),
+            [
+                Database::new("app".to_string(), vec![schema("public")]),
+                Database::new("main".to_string(), vec![table("users")]),
+            ]
+        );
+    }
 
     #[test]
     fn test_tree_tree_item_to_span() {
@@ -282,10 +863,7 @@ mod test {
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_database(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     false,
                 ),
                 false,
@@ -302,10 +880,7 @@ mod test {
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_database(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     false,
                 ),
                 true,
@@ -319,23 +894,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_locked_tree_item_to_span() {
+        const WIDTH: u16 = 10;
+        assert_eq!(
+            DatabasesComponent::tree_item_to_span(
+                DatabaseTreeItem::new_database(
+                    &Database::from_tables(
+                        "foo".to_string(),
+                        Err(anyhow::anyhow!("permission denied")),
+                    ),
+                    false,
+                ),
+                false,
+                WIDTH,
+                None,
+            ),
+            Line::from(vec![Span::raw(format!(
+                "\u{1f512}{:w$}",
+                "foo",
+                w = WIDTH as usize
+            ))])
+        );
+    }
+
     #[test]
     fn test_tree_table_tree_item_to_span() {
         const WIDTH: u16 = 10;
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_table(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     &Table {
                         name: "bar".to_string(),
                         create_time: None,
                         update_time: None,
                         engine: None,
+                        parent: None,
                         schema: None
                     },
+                    0,
+                    false,
                 ),
                 false,
                 WIDTH,
@@ -351,17 +950,17 @@ mod test {
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_table(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     &Table {
                         name: "bar".to_string(),
                         create_time: None,
                         update_time: None,
                         engine: None,
+                        parent: None,
                         schema: None
                     },
+                    0,
+                    false,
                 ),
                 true,
                 WIDTH,
@@ -380,17 +979,17 @@ mod test {
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_table(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     &Table {
                         name: "barbaz".to_string(),
                         create_time: None,
                         update_time: None,
                         engine: None,
+                        parent: None,
                         schema: None
                     },
+                    0,
+                    false,
                 ),
                 false,
                 WIDTH,
@@ -406,17 +1005,17 @@ mod test {
         assert_eq!(
             DatabasesComponent::tree_item_to_span(
                 DatabaseTreeItem::new_table(
-                    &Database {
-                        name: "foo".to_string(),
-                        children: Vec::new(),
-                    },
+                    &Database::new("foo".to_string(), Vec::new()),
                     &Table {
                         name: "barbaz".to_string(),
                         create_time: None,
                         update_time: None,
                         engine: None,
+                        parent: None,
                         schema: None
                     },
+                    0,
+                    false,
                 ),
                 true,
                 WIDTH,
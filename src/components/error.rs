@@ -5,14 +5,27 @@ use crate::event::Key;
 use anyhow::Result;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use zhobo::database::{ErrorDetails, ErrorKind};
+
+/// An action offered by the error popup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorAction {
+    /// Runs the failed statement again, or connects again after a connection error without one.
+    Retry(Option<String>),
+    /// Opens the failed statement in the SQL editor.
+    EditQuery(String),
+    OpenLog,
+}
 
 pub struct ErrorComponent {
     pub error: String,
+    details: Option<ErrorDetails>,
+    action: Option<ErrorAction>,
     visible: bool,
     key_config: KeyConfig,
 }
@@ -20,18 +33,99 @@ pub struct ErrorComponent {
 impl ErrorComponent {
     const WIDTH: u16 = 65;
     const HEIGHT: u16 = 10;
+    const DETAILS_HEIGHT: u16 = 18;
     pub fn new(key_config: KeyConfig) -> Self {
         Self {
             error: String::new(),
+            details: None,
+            action: None,
             visible: false,
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
     pub fn set(&mut self, error: String) -> anyhow::Result<()> {
         self.error = error;
+        self.details = None;
         self.show()
     }
+
+    /// Shows `error` with its kind, code, failed statement and the actions it allows.
+    pub fn set_error(&mut self, error: &anyhow::Error) -> anyhow::Result<()> {
+        let details = ErrorDetails::new(error);
+        self.error = details.message.clone();
+        self.details = Some(details);
+        self.show()
+    }
+
+    /// The action chosen to be run.
+    pub fn take_action(&mut self) -> Option<ErrorAction> {
+        self.action.take()
+    }
+
+    fn can_retry(&self) -> bool {
+        self.details
+            .as_ref()
+            .is_some_and(|details| details.query.is_some() || details.kind == ErrorKind::Connection)
+    }
+
+    fn details_lines(&self, details: &ErrorDetails) -> Vec<Line<'static>> {
+        let mut heading = vec![Span::styled(
+            details.kind.label(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if let Some(code) = &details.code {
+            heading.push(Span::raw(format!("  code {}", code)));
+        }
+        let mut lines = vec![
+            Line::from(heading),
+            Line::from(""),
+            Line::from(details.message.clone()),
+        ];
+        if let Some(query) = &details.query {
+            lines.push(Line::from(""));
+            lines.extend(query.lines().map(|line| {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ))
+            }));
+        }
+        if let Some(hint) = details.kind.hint() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                hint,
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        lines
+    }
+
+    fn actions_line(&self) -> String {
+        let mut actions = vec![];
+        if self.can_retry() {
+            actions.push(format!("[{}] retry", self.key_config.enter));
+        }
+        if self
+            .details
+            .as_ref()
+            .is_some_and(|details| details.query.is_some())
+        {
+            actions.push(format!(
+                "[{}] edit query",
+                self.key_config.open_query_in_editor
+            ));
+        }
+        if self.details.is_some() {
+            actions.push(format!("[{}] open log", self.key_config.open_in_pager));
+        }
+        actions.push(format!("[{}] close", self.key_config.exit_popup));
+        actions.join("  ")
+    }
 }
 
 impl DrawableComponent for ErrorComponent {
@@ -42,11 +136,16 @@ impl DrawableComponent for ErrorComponent {
                 .borders(Borders::ALL)
                 .style(Style::default().fg(Color::Red));
 
+            let height = if self.details.is_some() {
+                Self::DETAILS_HEIGHT
+            } else {
+                Self::HEIGHT
+            };
             let area = Rect::new(
                 (f.size().width.saturating_sub(Self::WIDTH)) / 2,
-                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                (f.size().height.saturating_sub(height)) / 2,
                 Self::WIDTH.min(f.size().width),
-                Self::HEIGHT.min(f.size().height),
+                height.min(f.size().height),
             );
             let chunks = Layout::default()
                 .vertical_margin(1)
@@ -57,16 +156,26 @@ impl DrawableComponent for ErrorComponent {
 
             f.render_widget(Clear, area);
             f.render_widget(error, area);
-            f.render_widget(
-                Paragraph::new(self.error.to_string()).wrap(Wrap { trim: true }),
-                chunks[0],
-            );
+            match &self.details {
+                Some(details) => f.render_widget(
+                    Paragraph::new(self.details_lines(details)).wrap(Wrap { trim: false }),
+                    chunks[0],
+                ),
+                None => f.render_widget(
+                    Paragraph::new(self.error.to_string()).wrap(Wrap { trim: true }),
+                    chunks[0],
+                ),
+            }
             f.render_widget(
                 Paragraph::new(Line::from(vec![Span::styled(
-                    format!(
-                        "Press [{}] to close this modal.",
-                        self.key_config.exit_popup
-                    ),
+                    if self.details.is_some() {
+                        self.actions_line()
+                    } else {
+                        format!(
+                            "Press [{}] to close this modal.",
+                            self.key_config.exit_popup
+                        )
+                    },
                     Style::default(),
                 )]))
                 .alignment(Alignment::Right),
@@ -82,8 +191,26 @@ impl Component for ErrorComponent {
 
     fn event(&mut self, key: Key) -> Result<EventState> {
         if self.visible {
-            if key == self.key_config.exit_popup {
+            let action = if key == self.key_config.enter && self.can_retry() {
+                Some(ErrorAction::Retry(
+                    self.details
+                        .as_ref()
+                        .and_then(|details| details.query.clone()),
+                ))
+            } else if key == self.key_config.open_query_in_editor {
+                self.details
+                    .as_ref()
+                    .and_then(|details| details.query.clone())
+                    .map(ErrorAction::EditQuery)
+            } else if key == self.key_config.open_in_pager && self.details.is_some() {
+                Some(ErrorAction::OpenLog)
+            } else {
+                None
+            };
+            if action.is_some() || key == self.key_config.exit_popup {
+                self.action = action;
                 self.error = String::new();
+                self.details = None;
                 self.hide();
                 return Ok(EventState::Consumed);
             }
@@ -102,3 +229,43 @@ impl Component for ErrorComponent {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ErrorAction, ErrorComponent};
+    use crate::components::Component;
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use zhobo::database::FailedQuery;
+
+    #[test]
+    fn test_actions() {
+        let key_config = KeyConfig::default();
+        let mut component = ErrorComponent::new(key_config.clone());
+        component
+            .set_error(
+                &anyhow::anyhow!("near \"SELEC\": syntax error")
+                    .context(FailedQuery("SELEC 1".to_string())),
+            )
+            .unwrap();
+        assert_eq!(component.error, "near \"SELEC\": syntax error");
+        assert!(component
+            .event(key_config.open_query_in_editor)
+            .unwrap()
+            .is_consumed());
+        assert_eq!(
+            component.take_action(),
+            Some(ErrorAction::EditQuery("SELEC 1".to_string()))
+        );
+
+        component
+            .set("No connection is selected".to_string())
+            .unwrap();
+        assert!(!component.event(Key::Enter).unwrap().is_consumed());
+        assert!(component
+            .event(key_config.exit_popup)
+            .unwrap()
+            .is_consumed());
+        assert_eq!(component.take_action(), None);
+    }
+}
@@ -0,0 +1,142 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::external_command::ExternalCommand;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// The external commands in the config, to run one against the selected table.
+pub struct ExternalCommandsComponent {
+    commands: Vec<ExternalCommand>,
+    state: ListState,
+    selected: Option<ExternalCommand>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ExternalCommandsComponent {
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 12;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            commands: vec![],
+            state: ListState::default(),
+            selected: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, commands: Vec<ExternalCommand>) -> Result<()> {
+        self.commands = commands;
+        self.state.select((!self.commands.is_empty()).then_some(0));
+        self.show()
+    }
+
+    /// The command chosen to be run.
+    pub fn take_selected(&mut self) -> Option<ExternalCommand> {
+        self.selected.take()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.commands.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+impl DrawableComponent for ExternalCommandsComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let items = if self.commands.is_empty() {
+                vec![ListItem::new("No [[external_command]] in config.toml")]
+            } else {
+                self.commands
+                    .iter()
+                    .map(|command| ListItem::new(format!("{}: {}", command.name, command.command)))
+                    .collect::<Vec<ListItem>>()
+            };
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title("External commands")
+                    .borders(Borders::ALL),
+                area,
+            );
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[0],
+                &mut state,
+            );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Press [{}] to run, [{}] to close.",
+                    self.key_config.enter, self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ExternalCommandsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                if let Some(command) = self.state.selected().and_then(|i| self.commands.get(i)) {
+                    self.selected = Some(command.clone());
+                    self.hide();
+                }
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down {
+                self.move_selection(true);
+            } else if key == Key::Up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+use super::{Component, DatabaseFilterComponent, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::{Context, Result};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use regex::{NoExpand, Regex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FindReplaceAction {
+    FindNext,
+    ReplaceOne,
+    ReplaceAll,
+}
+
+/// A dialog to search the SQL editor and replace matches, as plain text or a regex.
+pub struct FindReplaceComponent {
+    find: DatabaseFilterComponent,
+    replace: DatabaseFilterComponent,
+    replace_focused: bool,
+    regex: bool,
+    message: String,
+    action: Option<FindReplaceAction>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl FindReplaceComponent {
+    const WIDTH: u16 = 50;
+    const HEIGHT: u16 = 7;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            find: DatabaseFilterComponent::with_placeholder("Find"),
+            replace: DatabaseFilterComponent::with_placeholder("Replace with"),
+            replace_focused: false,
+            regex: false,
+            message: String::new(),
+            action: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn take_action(&mut self) -> Option<FindReplaceAction> {
+        self.action.take()
+    }
+
+    pub fn set_message(&mut self, message: String) {
+        self.message = message;
+    }
+
+    pub fn replacement(&self) -> String {
+        self.replace.input_str()
+    }
+
+    /// The search compiled as a regex. A plain search is escaped.
+    pub fn pattern(&self) -> Result<Option<Regex>> {
+        let find = self.find.input_str();
+        if find.is_empty() {
+            return Ok(None);
+        }
+        if self.regex {
+            Regex::new(&find)
+                .map(Some)
+                .with_context(|| format!("invalid regex `{}`", find))
+        } else {
+            Ok(Some(Regex::new(&regex::escape(&find))?))
+        }
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.regex
+    }
+}
+
+fn char_index(text: &str, byte: usize) -> usize {
+    text[..byte].chars().count()
+}
+
+fn byte_index(text: &str, char: usize) -> usize {
+    text.char_indices().nth(char).map_or(text.len(), |(i, _)| i)
+}
+
+/// Start of the first match at or after `from`, wrapping around to the top. In characters.
+pub fn find_next(text: &str, pattern: &Regex, from: usize) -> Option<usize> {
+    let starts = pattern
+        .find_iter(text)
+        .filter(|m| !m.is_empty())
+        .map(|m| char_index(text, m.start()))
+        .collect::<Vec<usize>>();
+    starts
+        .iter()
+        .find(|start| **start >= from)
+        .or_else(|| starts.first())
+        .copied()
+}
+
+/// Replaces the match starting at `at` and returns the new text with the end of the
+/// replacement, or `None` if no match starts there. In characters.
+pub fn replace_at(
+    text: &str,
+    pattern: &Regex,
+    replacement: &str,
+    expand: bool,
+    at: usize,
+) -> Option<(String, usize)> {
+    let at = byte_index(text, at);
+    let captures = pattern.captures_iter(text).find(|captures| {
+        captures
+            .get(0)
+            .is_some_and(|m| m.start() == at && !m.is_empty())
+    })?;
+    let range = captures.get(0)?.range();
+    let mut replaced = String::new();
+    if expand {
+        captures.expand(replacement, &mut replaced);
+    } else {
+        replaced.push_str(replacement);
+    }
+    let end = char_index(text, range.start) + replaced.chars().count();
+    Some((
+        format!("{}{}{}", &text[..range.start], replaced, &text[range.end..]),
+        end,
+    ))
+}
+
+/// Replaces every match and returns the new text with the number of replacements.
+pub fn replace_all(
+    text: &str,
+    pattern: &Regex,
+    replacement: &str,
+    expand: bool,
+) -> (String, usize) {
+    let count = pattern.find_iter(text).filter(|m| !m.is_empty()).count();
+    let replaced = if expand {
+        pattern.replace_all(text, replacement)
+    } else {
+        pattern.replace_all(text, NoExpand(replacement))
+    };
+    (replaced.into_owned(), count)
+}
+
+impl DrawableComponent for FindReplaceComponent {
+    fn draw(&self, f: &mut Frame, area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let width = Self::WIDTH.min(area.width);
+            let area = Rect::new(
+                area.right().saturating_sub(width),
+                area.y,
+                width,
+                Self::HEIGHT.min(area.height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Length(2),
+                        Constraint::Length(2),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
+                )
+                .split(area);
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title(if self.regex {
+                        "Find and Replace (regex)"
+                    } else {
+                        "Find and Replace"
+                    })
+                    .borders(Borders::ALL),
+                area,
+            );
+            self.find.draw(f, chunks[0], !self.replace_focused)?;
+            self.replace.draw(f, chunks[1], self.replace_focused)?;
+            f.render_widget(
+                Paragraph::new(if self.message.is_empty() {
+                    format!(
+                        "[{}] regex, [{}] replace all",
+                        self.key_config.toggle_regex_filter, self.key_config.replace_all
+                    )
+                } else {
+                    self.message.clone()
+                })
+                .style(Style::default().fg(Color::DarkGray)),
+                chunks[2],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for FindReplaceComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            self.message.clear();
+            if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Tab {
+                self.replace_focused = !self.replace_focused;
+            } else if key == self.key_config.toggle_regex_filter {
+                self.regex = !self.regex;
+            } else if key == self.key_config.replace_all {
+                self.action = Some(FindReplaceAction::ReplaceAll);
+            } else if key == self.key_config.enter {
+                self.action = Some(if self.replace_focused {
+                    FindReplaceAction::ReplaceOne
+                } else {
+                    FindReplaceAction::FindNext
+                });
+            } else if self.replace_focused {
+                self.replace.event(key)?;
+            } else {
+                self.find.event(key)?;
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.replace_focused = false;
+        self.message.clear();
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_next, replace_all, replace_at};
+    use regex::Regex;
+
+    #[test]
+    fn test_find_next() {
+        let pattern = Regex::new("id").unwrap();
+        let text = "SELECT id FROM users WHERE id = 1";
+        assert_eq!(find_next(text, &pattern, 0), Some(7));
+        assert_eq!(find_next(text, &pattern, 8), Some(27));
+        assert_eq!(find_next(text, &pattern, 28), Some(7));
+        assert_eq!(find_next(text, &Regex::new("name").unwrap(), 0), None);
+    }
+
+    #[test]
+    fn test_replace() {
+        let plain = Regex::new(&regex::escape("a.b")).unwrap();
+        assert_eq!(
+            replace_at("a.b, a.b", &plain, "$1", false, 5),
+            Some(("a.b, $1".to_string(), 7))
+        );
+        assert_eq!(replace_at("a.b, a.b", &plain, "x", false, 1), None);
+        assert_eq!(
+            replace_all("あa.b a.b", &plain, "c", false),
+            ("あc c".to_string(), 2)
+        );
+
+        let regex = Regex::new(r"(\w+)_id").unwrap();
+        assert_eq!(
+            replace_at("user_id, あ group_id", &regex, "${1}Id", true, 11),
+            Some(("user_id, あ groupId".to_string(), 18))
+        );
+        assert_eq!(
+            replace_all("user_id, group_id", &regex, "${1}Id", true),
+            ("userId, groupId".to_string(), 2)
+        );
+    }
+}
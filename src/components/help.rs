@@ -115,6 +115,10 @@ impl HelpComponent {
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
     pub fn set_cmds(&mut self, cmds: Vec<CommandInfo>) {
         self.cmds = cmds
             .into_iter()
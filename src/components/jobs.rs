@@ -0,0 +1,245 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::jobs::{Job, JobStatus};
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+/// Background jobs with their progress. Hiding the popup does not stop the jobs.
+pub struct JobsComponent {
+    jobs: Vec<Job>,
+    state: ListState,
+    error: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl JobsComponent {
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 12;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            jobs: vec![],
+            state: ListState::default(),
+            error: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    /// Adds `job` and shows it selected.
+    pub fn push(&mut self, job: Job) -> Result<()> {
+        self.jobs.push(job);
+        self.state.select(Some(self.jobs.len() - 1));
+        self.show()
+    }
+
+    /// Error of the failed job chosen by the user.
+    pub fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.jobs.iter().any(|job| job.status == JobStatus::Running)
+    }
+
+    /// Updates the status of running jobs and returns a message for each job which has finished,
+    /// with the time it took.
+    pub fn poll(&mut self) -> Vec<(String, Duration)> {
+        let mut messages = vec![];
+        for job in self.jobs.iter_mut() {
+            if job.status != JobStatus::Running {
+                continue;
+            }
+            job.poll();
+            let message = match &job.status {
+                JobStatus::Running => continue,
+                JobStatus::Succeeded => format!("{} succeeded", job.title),
+                JobStatus::Failed(_) => format!("{} failed", job.title),
+                JobStatus::Cancelled => format!("{} cancelled", job.title),
+            };
+            messages.push((message, job.elapsed()));
+        }
+        messages
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.jobs.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MiB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn job_line(job: &Job) -> String {
+    let rows = match job.progress.total_rows() {
+        Some(total_rows) => format!("{}/{} rows", job.progress.rows(), total_rows),
+        None => format!("{} rows", job.progress.rows()),
+    };
+    let mut line = format!(
+        "[{}] {}  {}  {}  {}",
+        job.status.label(),
+        job.title,
+        rows,
+        format_bytes(job.progress.bytes()),
+        format_duration(job.elapsed())
+    );
+    if let Some(eta) = job.eta() {
+        line = format!("{}  ETA {}", line, format_duration(eta));
+    }
+    line
+}
+
+impl DrawableComponent for JobsComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let items = if self.jobs.is_empty() {
+                vec![ListItem::new("No jobs")]
+            } else {
+                self.jobs
+                    .iter()
+                    .map(|job| ListItem::new(Line::from(Span::raw(job_line(job)))))
+                    .collect::<Vec<ListItem>>()
+            };
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(Block::default().title("Jobs").borders(Borders::ALL), area);
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[0],
+                &mut state,
+            );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Press [{}] to cancel, [{}] to view the error, [{}] to close.",
+                    self.key_config.cancel_job, self.key_config.enter, self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for JobsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.cancel_job {
+                if let Some(job) = self.state.selected().and_then(|i| self.jobs.get(i)) {
+                    job.progress.cancel();
+                }
+            } else if key == self.key_config.enter {
+                if let Some(JobStatus::Failed(e)) = self
+                    .state
+                    .selected()
+                    .and_then(|i| self.jobs.get(i))
+                    .map(|job| &job.status)
+                {
+                    self.error = Some(e.clone());
+                }
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down {
+                self.move_selection(true);
+            } else if key == Key::Up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_bytes, format_duration, JobsComponent};
+    use crate::components::Component;
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use crate::jobs::{Job, JobProgress};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_view_error() {
+        let mut jobs = JobsComponent::new(KeyConfig::default());
+        jobs.push(Job::spawn(
+            "export".to_string(),
+            Arc::new(JobProgress::default()),
+            async { Err(anyhow::anyhow!("disk full")) },
+        ))
+        .unwrap();
+        let mut messages = vec![];
+        while messages.is_empty() {
+            tokio::task::yield_now().await;
+            messages = jobs.poll();
+        }
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, "export failed");
+
+        assert!(jobs.event(Key::Enter).unwrap().is_consumed());
+        assert_eq!(jobs.take_error(), Some("disk full".to_string()));
+        assert_eq!(jobs.take_error(), None);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2:05");
+    }
+}
@@ -1,46 +1,75 @@
 pub mod clipboard;
 pub mod command;
 pub mod completion;
+pub mod config_error;
+pub mod confirm;
+pub mod connection_switcher;
 pub mod connections;
+pub mod context_menu;
 pub mod database_filter;
 pub mod databases;
 pub mod error;
+pub mod external_commands;
+pub mod find_replace;
 pub mod help;
+pub mod jobs;
+pub mod prompt;
 pub mod properties;
+pub mod query;
 pub mod record_table;
+pub mod result_schema;
+pub mod server;
+pub mod session_variables;
 pub mod sql_editor;
 pub mod tab;
 pub mod table;
 pub mod table_filter;
 pub mod table_status;
 pub mod table_value;
+pub mod toast;
 pub mod utils;
+pub mod value_frequency;
 
 #[cfg(debug_assertions)]
 pub mod debug;
 pub use clipboard::ClipboardComponent;
 pub use command::CommandInfo;
 pub use completion::CompletionComponent;
+pub use config_error::ConfigErrorComponent;
+pub use confirm::ConfirmComponent;
+pub use connection_switcher::ConnectionSwitcherComponent;
 pub use connections::ConnectionsComponent;
+pub use context_menu::ContextMenuComponent;
 pub use database_filter::DatabaseFilterComponent;
 pub use databases::DatabasesComponent;
 pub use error::ErrorComponent;
+pub use external_commands::ExternalCommandsComponent;
+pub use find_replace::FindReplaceComponent;
 pub use help::HelpComponent;
+pub use jobs::JobsComponent;
+pub use prompt::PromptComponent;
 pub use properties::PropertiesComponent;
+pub use query::QueryComponent;
 pub use record_table::RecordTableComponent;
+pub use result_schema::ResultSchemaComponent;
+pub use server::ServerComponent;
+pub use session_variables::SessionVariablesComponent;
 pub use sql_editor::SqlEditorComponent;
 pub use tab::TabComponent;
 pub use table::TableComponent;
 pub use table_filter::TableFilterComponent;
 pub use table_status::TableStatusComponent;
 pub use table_value::TableValueComponent;
+pub use toast::ToastComponent;
+pub use value_frequency::ValueFrequencyComponent;
 
-use crate::{database::Pool, event::Key};
+use crate::event::Key;
 use anyhow::Result;
 use async_trait::async_trait;
 use ratatui::{layout::Rect, Frame};
 use std::convert::TryInto;
 use unicode_width::UnicodeWidthChar;
+use zhobo::database::Pool;
 
 #[derive(PartialEq, Debug)]
 pub enum EventState {
@@ -0,0 +1,241 @@
+use super::{Component, DatabaseFilterComponent, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptAction {
+    BulkUpdate {
+        column: String,
+    },
+    GenerateData,
+    Connect,
+    ExportRecords,
+    ExportResults,
+    DumpTable,
+    SetSessionVariable {
+        name: String,
+    },
+    SetComment {
+        column: Option<String>,
+    },
+    PlayMacro,
+    SetTemplateVariable,
+    /// Shows a path in the JSON `column` of the records as a column.
+    ExtractJsonPath {
+        column: String,
+    },
+    /// Sets the column layout of the selected table.
+    EditColumnLayout,
+    /// Shows an SQL expression as a column of the records of the table of `table_key`.
+    AddComputedColumn {
+        table_key: String,
+    },
+    /// Copies records to the connection of the index picked from `connections`, twice each for
+    /// the filtered and the selected records.
+    CopyRows {
+        connections: Vec<usize>,
+    },
+}
+
+pub struct PromptComponent {
+    message: String,
+    input: DatabaseFilterComponent,
+    action: Option<PromptAction>,
+    submitted: Option<(PromptAction, String)>,
+    /// Options chosen with Tab besides the input, such as what to export.
+    choices: Vec<String>,
+    choice: usize,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl PromptComponent {
+    const WIDTH: u16 = 65;
+    const HEIGHT: u16 = 10;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            message: String::new(),
+            input: DatabaseFilterComponent::with_placeholder(""),
+            action: None,
+            submitted: None,
+            choices: vec![],
+            choice: 0,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, action: PromptAction, message: String) -> Result<()> {
+        self.message = message;
+        self.action = Some(action);
+        self.input.reset();
+        self.choices = vec![];
+        self.choice = 0;
+        self.show()
+    }
+
+    /// Opens the prompt with `choices` to pick one of with Tab.
+    pub fn open_with_choices(
+        &mut self,
+        action: PromptAction,
+        message: String,
+        choices: Vec<String>,
+    ) -> Result<()> {
+        self.open(action, message)?;
+        self.choices = choices;
+        Ok(())
+    }
+
+    /// The index of the choice picked when the prompt was submitted.
+    pub fn choice(&self) -> usize {
+        self.choice
+    }
+
+    pub fn set_input(&mut self, input: &str) {
+        self.input.set_input(input);
+    }
+
+    pub fn take_submitted(&mut self) -> Option<(PromptAction, String)> {
+        self.submitted.take()
+    }
+}
+
+impl DrawableComponent for PromptComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let prompt = Block::default()
+                .title("Input")
+                .borders(Borders::ALL)
+                .style(Style::default());
+
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(2),
+                        Constraint::Length(u16::from(!self.choices.is_empty())),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
+                )
+                .split(area);
+
+            f.render_widget(Clear, area);
+            f.render_widget(prompt, area);
+            f.render_widget(
+                Paragraph::new(self.message.to_string()).wrap(Wrap { trim: true }),
+                chunks[0],
+            );
+            self.input.draw(f, chunks[1], true)?;
+            let mut choices = vec![Span::raw("[Tab] ")];
+            for (i, choice) in self.choices.iter().enumerate() {
+                let style = if i == self.choice {
+                    Style::default().fg(Color::Reset).bg(Color::Blue)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                choices.push(Span::styled(choice.to_string(), style));
+                choices.push(Span::raw(" "));
+            }
+            f.render_widget(Paragraph::new(Line::from(choices)), chunks[2]);
+            f.render_widget(
+                Paragraph::new(Line::from(vec![Span::styled(
+                    format!(
+                        "Press [{}] to submit, [{}] to cancel.",
+                        self.key_config.enter, self.key_config.exit_popup
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                )]))
+                .alignment(Alignment::Right),
+                chunks[3],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for PromptComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                self.submitted = self
+                    .action
+                    .take()
+                    .map(|action| (action, self.input.input_str()));
+                self.hide();
+            } else if key == self.key_config.exit_popup {
+                self.action = None;
+                self.hide();
+            } else if key == Key::Tab && !self.choices.is_empty() {
+                self.choice = (self.choice + 1) % self.choices.len();
+            } else {
+                self.input.event(key)?;
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component, Key, KeyConfig, PromptAction, PromptComponent};
+
+    #[test]
+    fn test_prompt() {
+        let mut component = PromptComponent::new(KeyConfig::default());
+        let action = PromptAction::BulkUpdate {
+            column: "name".to_string(),
+        };
+        component
+            .open(action.clone(), "message".to_string())
+            .unwrap();
+        for c in "foo".chars() {
+            assert!(component.event(Key::Char(c)).unwrap().is_consumed());
+        }
+        assert!(component.event(Key::Enter).unwrap().is_consumed());
+        assert_eq!(
+            component.take_submitted(),
+            Some((action.clone(), "foo".to_string()))
+        );
+        assert!(!component.event(Key::Enter).unwrap().is_consumed());
+
+        component.open(action, "message".to_string()).unwrap();
+        assert!(component.event(Key::Esc).unwrap().is_consumed());
+        assert_eq!(component.take_submitted(), None);
+    }
+}
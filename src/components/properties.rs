@@ -3,17 +3,18 @@ use crate::clipboard::copy_to_clipboard;
 use crate::components::command::{self, CommandInfo};
 use crate::components::TableComponent;
 use crate::config::KeyConfig;
-use crate::database::Pool;
 use crate::event::Key;
-use crate::tree::{Database, Table};
 use anyhow::Result;
 use async_trait::async_trait;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use zhobo::charset::Charsets;
+use zhobo::database::Pool;
+use zhobo::tree::{Database, Table};
 
 #[derive(Debug, PartialEq)]
 pub enum Focus {
@@ -36,23 +37,52 @@ pub struct PropertiesComponent {
     foreign_key_table: TableComponent,
     index_table: TableComponent,
     definition_viewer: ClipboardComponent,
+    charset_summary: Option<String>,
+    table_comment: Option<String>,
+    /// How the table is related to its parents and children, such as partitions.
+    inheritance: Vec<String>,
+    /// Whether column comments are drawn below the columns instead of in a column.
+    column_comment_lines: bool,
+    /// The names of the columns, quoted for the database.
+    column_names: Vec<String>,
+    /// The name and the comment of each column.
+    column_comments: Vec<(String, String)>,
     focus: Focus,
     key_config: KeyConfig,
 }
 
 impl PropertiesComponent {
-    pub fn new(key_config: KeyConfig) -> Self {
+    pub fn new(key_config: KeyConfig, column_comment_lines: bool) -> Self {
         Self {
             column_table: TableComponent::new(key_config.clone()),
             constraint_table: TableComponent::new(key_config.clone()),
             foreign_key_table: TableComponent::new(key_config.clone()),
             index_table: TableComponent::new(key_config.clone()),
             definition_viewer: ClipboardComponent::new(key_config.clone()),
+            charset_summary: None,
+            table_comment: None,
+            inheritance: vec![],
+            column_comment_lines,
+            column_names: vec![],
+            column_comments: vec![],
             focus: Focus::Column,
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.column_table.set_key_config(key_config.clone());
+        self.constraint_table.set_key_config(key_config.clone());
+        self.foreign_key_table.set_key_config(key_config.clone());
+        self.index_table.set_key_config(key_config.clone());
+        self.definition_viewer.set_key_config(key_config.clone());
+        self.key_config = key_config;
+    }
+
+    pub fn set_column_comment_lines(&mut self, column_comment_lines: bool) {
+        self.column_comment_lines = column_comment_lines;
+    }
+
     fn focused_component(&mut self) -> &mut dyn PropertyTrait {
         match self.focus {
             Focus::Column => &mut self.column_table,
@@ -63,6 +93,26 @@ impl PropertiesComponent {
         }
     }
 
+    /// The selected cells, or the definition, of the focused tab.
+    pub fn content(&mut self) -> Option<String> {
+        self.focused_component().content()
+    }
+
+    /// The column selected in the Columns tab, or `None` for the table in the other tabs, with
+    /// its comment. `None` if no table is shown.
+    pub fn comment_target(&self) -> Option<(Option<String>, String)> {
+        self.charset_summary.as_ref()?;
+        match self.focus {
+            Focus::Column => self
+                .column_table
+                .selected_row
+                .selected()
+                .and_then(|index| self.column_comments.get(index))
+                .map(|(name, comment)| (Some(name.clone()), comment.clone())),
+            _ => Some((None, self.table_comment.clone().unwrap_or_default())),
+        }
+    }
+
     pub async fn update(
         &mut self,
         database: Database,
@@ -70,19 +120,45 @@ impl PropertiesComponent {
         pool: &Box<dyn Pool>,
     ) -> Result<()> {
         self.column_table.reset();
+        let charsets = pool.get_charsets(&database, &table).await?;
+        self.charset_summary = Some(charsets.summary());
+        self.table_comment = pool.get_table_comment(&database, &table).await?;
+        self.inheritance = pool.get_inheritance(&database, &table).await?.lines();
         let columns = pool.get_columns(&database, &table).await?;
-        if !columns.is_empty() {
-            self.column_table.update(
-                columns
+        self.column_names = columns
+            .iter()
+            .filter_map(|column| column.columns().first().cloned())
+            .map(|name| pool.quote_identifier(&name))
+            .collect();
+        self.column_comments = columns
+            .iter()
+            .map(|column| {
+                let comment = column
+                    .fields()
                     .iter()
-                    .map(|c| c.columns())
-                    .collect::<Vec<Vec<String>>>(),
-                None,
-                columns.first().unwrap().fields(),
-                database.clone(),
-                table.clone(),
-                false,
-            );
+                    .position(|field| field == "comment")
+                    .and_then(|index| column.columns().get(index).cloned());
+                (
+                    column.columns().first().cloned().unwrap_or_default(),
+                    comment.unwrap_or_default(),
+                )
+            })
+            .collect();
+        if !columns.is_empty() {
+            let rows = columns
+                .iter()
+                .map(|c| c.columns())
+                .collect::<Vec<Vec<String>>>();
+            let headers = columns.first().unwrap().fields();
+            let (rows, headers, comments) = if self.column_comment_lines {
+                take_comments(rows, headers)
+            } else {
+                (rows, headers, vec![])
+            };
+            let (rows, headers) = with_charsets(rows, headers, &charsets);
+            self.column_table
+                .update(rows, None, headers, database.clone(), table.clone(), false);
+            self.column_table.set_notes(comments);
         }
         self.constraint_table.reset();
         let constraints = pool.get_constraints(&database, &table).await?;
@@ -159,6 +235,62 @@ impl PropertiesComponent {
     }
 }
 
+/// Removes the comment column, returning the comment of each row separately.
+fn take_comments(
+    mut rows: Vec<Vec<String>>,
+    mut headers: Vec<String>,
+) -> (Vec<Vec<String>>, Vec<String>, Vec<String>) {
+    let Some(index) = headers.iter().position(|header| header == "comment") else {
+        return (rows, headers, vec![]);
+    };
+    headers.remove(index);
+    let comments = rows
+        .iter_mut()
+        .map(|row| {
+            if index < row.len() {
+                row.remove(index)
+            } else {
+                String::new()
+            }
+        })
+        .collect();
+    (rows, headers, comments)
+}
+
+/// Appends the charset and collation of each column, which are empty for non-text columns.
+fn with_charsets(
+    rows: Vec<Vec<String>>,
+    mut headers: Vec<String>,
+    charsets: &Charsets,
+) -> (Vec<Vec<String>>, Vec<String>) {
+    headers.push("charset".to_string());
+    headers.push("collation".to_string());
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            let charset = row.first().and_then(|name| {
+                charsets
+                    .columns
+                    .iter()
+                    .find(|(column, _)| column == name)
+                    .map(|(_, charset)| charset)
+            });
+            row.push(
+                charset
+                    .and_then(|charset| charset.charset.clone())
+                    .unwrap_or_default(),
+            );
+            row.push(
+                charset
+                    .and_then(|charset| charset.collation.clone())
+                    .unwrap_or_default(),
+            );
+            row
+        })
+        .collect();
+    (rows, headers)
+}
+
 impl StatefulDrawableComponent for PropertiesComponent {
     fn draw(&mut self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
         let layout = Layout::default()
@@ -188,7 +320,31 @@ impl StatefulDrawableComponent for PropertiesComponent {
 
         f.render_widget(tab_list, layout[0]);
 
-        self.focused_component().draw(f, layout[1], focused)?;
+        let summary = self
+            .charset_summary
+            .iter()
+            .cloned()
+            .chain(
+                self.table_comment
+                    .iter()
+                    .map(|comment| format!("comment: {}", comment.replace('\n', " "))),
+            )
+            .chain(self.inheritance.iter().cloned())
+            .collect::<Vec<String>>();
+        let area = if summary.is_empty() {
+            layout[1]
+        } else {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(summary.len() as u16), Constraint::Min(1)])
+                .split(layout[1]);
+            f.render_widget(
+                Paragraph::new(summary.join("\n")).style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+            chunks[1]
+        };
+        self.focused_component().draw(f, area, focused)?;
         Ok(())
     }
 }
@@ -199,15 +355,31 @@ impl Component for PropertiesComponent {
         out.push(CommandInfo::new(command::toggle_property_tabs(
             &self.key_config,
         )));
+        out.push(CommandInfo::new(command::search_definition(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::copy_column_list(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::edit_comment(&self.key_config)));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
+        if matches!(self.focus, Focus::Definition) && self.definition_viewer.searching() {
+            return self.definition_viewer.event(key);
+        }
+
         self.focused_component().event(key)?;
 
         if key == self.key_config.copy {
             if let Some(text) = self.focused_component().content() {
                 copy_to_clipboard(text.as_str())?
             }
+        } else if key == self.key_config.copy_column_list
+            && matches!(self.focus, Focus::Column)
+            && !self.column_names.is_empty()
+        {
+            copy_to_clipboard(&self.column_names.join(", "))?
         } else if key == self.key_config.tab_columns {
             self.focus = Focus::Column;
         } else if key == self.key_config.tab_constraints {
@@ -222,3 +394,61 @@ impl Component for PropertiesComponent {
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{take_comments, with_charsets};
+    use zhobo::charset::{Charset, Charsets};
+
+    #[test]
+    fn test_with_charsets() {
+        let charsets = Charsets {
+            columns: vec![(
+                "name".to_string(),
+                Charset::from_collation(Some("latin1_swedish_ci".to_string())),
+            )],
+            ..Charsets::default()
+        };
+        let (rows, headers) = with_charsets(
+            vec![
+                vec!["id".to_string(), "int".to_string()],
+                vec!["name".to_string(), "varchar(255)".to_string()],
+            ],
+            vec!["name".to_string(), "type".to_string()],
+            &charsets,
+        );
+        assert_eq!(headers, vec!["name", "type", "charset", "collation"]);
+        assert_eq!(rows[0], vec!["id", "int", "", ""]);
+        assert_eq!(
+            rows[1],
+            vec!["name", "varchar(255)", "latin1", "latin1_swedish_ci"]
+        );
+    }
+
+    #[test]
+    fn test_take_comments() {
+        let (rows, headers, comments) = take_comments(
+            vec![
+                vec!["id".to_string(), "".to_string(), "int".to_string()],
+                vec![
+                    "name".to_string(),
+                    "full name".to_string(),
+                    "text".to_string(),
+                ],
+            ],
+            vec![
+                "name".to_string(),
+                "comment".to_string(),
+                "type".to_string(),
+            ],
+        );
+        assert_eq!(headers, vec!["name", "type"]);
+        assert_eq!(rows, vec![vec!["id", "int"], vec!["name", "text"]]);
+        assert_eq!(comments, vec!["", "full name"]);
+
+        let (_, headers, comments) =
+            take_comments(vec![vec!["id".to_string()]], vec!["name".to_string()]);
+        assert_eq!(headers, vec!["name"]);
+        assert!(comments.is_empty());
+    }
+}
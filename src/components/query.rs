@@ -0,0 +1,113 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::clipboard::copy_to_clipboard;
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Shows the query executed for the current view.
+pub struct QueryComponent {
+    query: String,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl QueryComponent {
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 12;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            query: String::new(),
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, query: String) -> Result<()> {
+        self.query = query;
+        self.show()
+    }
+}
+
+impl DrawableComponent for QueryComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let query = Block::default()
+                .title("Query")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan));
+
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            f.render_widget(Clear, area);
+            f.render_widget(query, area);
+            f.render_widget(
+                Paragraph::new(self.query.to_string())
+                    .style(Style::default().fg(Color::Reset))
+                    .wrap(Wrap { trim: false }),
+                chunks[0],
+            );
+            f.render_widget(
+                Paragraph::new(Line::from(vec![Span::styled(
+                    format!(
+                        "Press [{}] to copy, [{}] to close.",
+                        self.key_config.copy, self.key_config.exit_popup
+                    ),
+                    Style::default(),
+                )]))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for QueryComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.copy {
+                copy_to_clipboard(&self.query)?;
+                self.hide();
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
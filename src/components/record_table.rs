@@ -1,38 +1,164 @@
 use super::{Component, EventState, StatefulDrawableComponent};
-use crate::components::command::CommandInfo;
+use crate::components::command::{self, CommandInfo};
+use crate::components::table::{OrderManager, TablePosition};
 use crate::components::{TableComponent, TableFilterComponent};
 use crate::config::KeyConfig;
 use crate::event::Key;
-use crate::tree::{Database, Table as DTable};
+use crate::template;
 use anyhow::Result;
 use ratatui::layout::Flex;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
+use std::collections::{BTreeMap, HashMap};
+use zhobo::database::{JsonPath, Pool, QueryBuilder};
+use zhobo::tree::{Database, Table as DTable};
 
 pub enum Focus {
     Table,
     Filter,
 }
 
+/// A condition on the column of the selected cell, added to the filter by a key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellFilter {
+    /// Shows the records holding the value of the cell.
+    Equal,
+    /// Hides the records holding the value of the cell, keeping the NULL ones.
+    Exclude,
+    Null,
+    NotNull,
+}
+
+impl CellFilter {
+    /// The condition on `column`, quoted, for the cell holding `literal`.
+    pub fn condition(self, column: &str, literal: &str) -> String {
+        match self {
+            Self::Equal if literal == "NULL" => format!("{} IS NULL", column),
+            Self::Equal => format!("{} = {}", column, literal),
+            Self::Exclude if literal == "NULL" => format!("{} IS NOT NULL", column),
+            Self::Exclude => format!("({} != {} OR {} IS NULL)", column, literal, column),
+            Self::Null => format!("{} IS NULL", column),
+            Self::NotNull => format!("{} IS NOT NULL", column),
+        }
+    }
+}
+
+/// The filter and the sort order the records are read with. Either changes without touching the
+/// other, and both are cleared when another table is opened.
+#[derive(Default)]
+pub struct QueryState {
+    /// The condition of the filter applied last, which stays while the input is being edited.
+    filter: Option<String>,
+    orders: OrderManager,
+    /// The columns of the table shown by its column layout, or all of them when `None`.
+    columns: Option<Vec<String>>,
+    /// Paths in JSON columns shown as columns of their own after the columns of the table.
+    json_columns: Vec<(String, JsonPath)>,
+    /// SQL expressions shown as columns after the JSON columns, kept in the session.
+    computed_columns: Vec<String>,
+}
+
+impl QueryState {
+    pub fn filter(&self) -> Option<String> {
+        self.filter.clone()
+    }
+
+    pub fn set_filter(&mut self, filter: Option<String>) {
+        self.filter = filter;
+    }
+
+    /// Sorts by the column at `column_index` ascending, then descending, then not at all.
+    pub fn toggle_order(&mut self, column_index: usize) {
+        self.orders.add_order(column_index);
+    }
+
+    /// Shows the value at `path` in `column` as a column, unless it is already shown.
+    pub fn add_json_column(&mut self, column: String, path: JsonPath) {
+        if !self
+            .json_columns
+            .iter()
+            .any(|(c, p)| *c == column && *p == path)
+        {
+            self.json_columns.push((column, path));
+        }
+    }
+
+    pub fn clear_json_columns(&mut self) {
+        self.json_columns.clear();
+    }
+
+    pub fn columns(&self) -> Option<&[String]> {
+        self.columns.as_deref()
+    }
+
+    /// Shows `columns` of the table in their order. The sort order is cleared, since it refers to
+    /// the columns by their position.
+    pub fn set_columns(&mut self, columns: Option<Vec<String>>) {
+        self.columns = columns;
+        self.orders = OrderManager::default();
+    }
+
+    pub fn computed_columns(&self) -> &[String] {
+        &self.computed_columns
+    }
+
+    pub fn set_computed_columns(&mut self, computed_columns: Vec<String>) {
+        self.computed_columns = computed_columns;
+    }
+
+    pub fn order_query(&self) -> Option<String> {
+        self.orders.generate_order_query()
+    }
+
+    /// The query on the records of `table` with the shown, JSON and computed columns, the filter
+    /// and the sort order.
+    pub fn builder(&self, database: Database, table: DTable) -> QueryBuilder {
+        QueryBuilder::new(database, table)
+            .table_columns(self.columns.clone())
+            .json_columns(self.json_columns.clone())
+            .computed_columns(self.computed_columns.clone())
+            .filter(self.filter())
+            .orders(self.order_query())
+    }
+
+    /// The sort arrow and priority of each of the `len` columns.
+    pub fn header_icons(&self, len: usize) -> Vec<String> {
+        self.orders.generate_header_icons(len)
+    }
+}
+
 pub struct RecordTableComponent {
     pub filter: TableFilterComponent,
     pub table: TableComponent,
+    pub query: QueryState,
     pub focus: Focus,
+    /// Positions of tables opened before, keyed by the table key of the filter.
+    positions: HashMap<String, TablePosition>,
     key_config: KeyConfig,
 }
 
 impl RecordTableComponent {
-    pub fn new(key_config: KeyConfig) -> Self {
+    pub fn new(key_config: KeyConfig, row_numbers: bool) -> Self {
+        let mut table = TableComponent::new(key_config.clone());
+        table.set_row_numbers(row_numbers);
         Self {
             filter: TableFilterComponent::new(key_config.clone()),
-            table: TableComponent::new(key_config.clone()),
+            table,
+            query: QueryState::default(),
             focus: Focus::Table,
+            positions: HashMap::new(),
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.filter.set_key_config(key_config.clone());
+        self.table.set_key_config(key_config.clone());
+        self.key_config = key_config;
+    }
+
     pub fn update(
         &mut self,
         rows: Vec<Vec<String>>,
@@ -46,16 +172,120 @@ impl RecordTableComponent {
             rows,
             total_row_count,
             headers,
-            database,
+            database.clone(),
             table.clone(),
             hold_cursor_position,
         );
         self.filter.table = Some(table);
+        self.filter.database = Some(database);
+    }
+
+    /// Restores the position the current table had when it was last left.
+    pub fn restore_position(&mut self) {
+        if let Some(position) = self
+            .filter
+            .table_key()
+            .and_then(|key| self.positions.get(&key).copied())
+        {
+            self.table.restore_position(position);
+        }
     }
 
     pub fn reset(&mut self) {
+        if let (Some(key), Some(position)) = (self.filter.table_key(), self.table.position()) {
+            self.positions.insert(key, position);
+        }
         self.table.reset();
         self.filter.reset();
+        self.query = QueryState::default();
+    }
+
+    /// Applies the condition of the filter input with `{{name}}` replaced with `variables`,
+    /// keeping the sort order.
+    pub fn apply_filter(
+        &mut self,
+        pool: &dyn Pool,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        let condition = self
+            .filter
+            .condition(pool)?
+            .map(|condition| template::substitute(&condition, variables))
+            .transpose()?;
+        self.query.set_filter(condition);
+        Ok(())
+    }
+
+    /// Adds the condition of `cell_filter` on the selected cell to the filter and applies it.
+    pub fn filter_cell(
+        &mut self,
+        pool: &dyn Pool,
+        variables: &BTreeMap<String, String>,
+        cell_filter: CellFilter,
+    ) -> Result<bool> {
+        let Some((column, value, binary)) = self.table.selected_cell() else {
+            return Ok(false);
+        };
+        let condition = cell_filter.condition(
+            &pool.quote_identifier(column),
+            &pool.cell_literal(value, binary),
+        );
+        self.and_filter(pool, variables, &condition)?;
+        Ok(true)
+    }
+
+    /// Adds `condition` to the filter with AND and applies it. Only the filter input has
+    /// `{{name}}` replaced with `variables`, since `condition` may contain values of cells.
+    pub fn and_filter(
+        &mut self,
+        pool: &dyn Pool,
+        variables: &BTreeMap<String, String>,
+        condition: &str,
+    ) -> Result<()> {
+        let current = self
+            .filter
+            .condition(pool)?
+            .map(|current| template::substitute(&current, variables))
+            .transpose()?;
+        self.filter.and_condition(current, condition);
+        self.query.set_filter(self.filter.condition(pool)?);
+        Ok(())
+    }
+
+    /// Sorts by the selected column, keeping the filter.
+    pub fn toggle_order(&mut self) {
+        self.query.toggle_order(self.table.selected_column_index());
+    }
+
+    /// What to do next when there are no records to show.
+    fn empty_hint(&self) -> Vec<String> {
+        if self.table.source().is_none() {
+            return vec![
+                "No table is open.".to_string(),
+                format!(
+                    "Select a table in the database tree and press [{}].",
+                    self.key_config.enter
+                ),
+            ];
+        }
+        if self.query.filter().is_some() {
+            return vec![
+                "No records match the filter.".to_string(),
+                format!(
+                    "Press [{}] to change it, or clear it and press [{}].",
+                    self.key_config.filter, self.key_config.enter
+                ),
+            ];
+        }
+        vec![
+            "The table has no records.".to_string(),
+            format!(
+                "Press [{}] to insert rows from the clipboard, [{}] to generate some, or [{}] to write an INSERT in the SQL editor.",
+                self.key_config.paste_rows,
+                self.key_config.generate_data,
+                self.key_config.tab_sql_editor
+            ),
+        ]
     }
 
     pub fn filter_focused(&self) -> bool {
@@ -71,6 +301,7 @@ impl StatefulDrawableComponent for RecordTableComponent {
             .flex(Flex::Legacy)
             .split(area);
 
+        self.table.set_empty_hint(self.empty_hint());
         self.table
             .draw(f, layout[1], focused && matches!(self.focus, Focus::Table))?;
 
@@ -82,7 +313,42 @@ impl StatefulDrawableComponent for RecordTableComponent {
 
 impl Component for RecordTableComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>) {
-        self.table.commands(out)
+        self.table.commands(out);
+        out.push(CommandInfo::new(command::toggle_regex_filter(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::toggle_ignore_case_filter(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::exclude_cell_value(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::filter_null(&self.key_config)));
+        out.push(CommandInfo::new(command::focus_header(&self.key_config)));
+        out.push(CommandInfo::new(command::show_query(&self.key_config)));
+        out.push(CommandInfo::new(command::open_query_in_editor(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::bulk_update(&self.key_config)));
+        out.push(CommandInfo::new(command::generate_data(&self.key_config)));
+        out.push(CommandInfo::new(command::export_records(&self.key_config)));
+        out.push(CommandInfo::new(command::paste_rows(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_rows_to_connection(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::copy_distinct_values(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::value_frequency(&self.key_config)));
+        out.push(CommandInfo::new(command::show_cell_value(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_as_literal(&self.key_config)));
+        out.push(CommandInfo::new(command::extract_json_path(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::column_layout(&self.key_config)));
+        out.push(CommandInfo::new(command::add_computed_column(
+            &self.key_config,
+        )));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
@@ -98,3 +364,116 @@ impl Component for RecordTableComponent {
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{CellFilter, QueryState, RecordTableComponent};
+    use crate::config::KeyConfig;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use std::collections::BTreeMap;
+    use zhobo::database::{SqlitePool, MAX_CONNECTIONS};
+    use zhobo::tree::{Database, Table as DTable};
+
+    #[test]
+    fn test_query_state() {
+        let mut query = QueryState::default();
+        query.toggle_order(1);
+        query.set_filter(Some("id > 1".to_string()));
+        assert_eq!(query.order_query(), Some("ORDER BY 2 ASC".to_string()));
+        assert_eq!(query.header_icons(2), ["", "↑1"]);
+
+        query.set_filter(None);
+        query.toggle_order(1);
+        assert_eq!(query.order_query(), Some("ORDER BY 2 DESC".to_string()));
+        query.set_filter(Some("id > 2".to_string()));
+        assert_eq!(query.filter(), Some("id > 2".to_string()));
+        assert_eq!(query.order_query(), Some("ORDER BY 2 DESC".to_string()));
+    }
+
+    #[test]
+    fn test_cell_filter() {
+        assert_eq!(
+            CellFilter::Equal.condition("\"status\"", "'paid'"),
+            "\"status\" = 'paid'"
+        );
+        assert_eq!(
+            CellFilter::Equal.condition("\"note\"", "NULL"),
+            "\"note\" IS NULL"
+        );
+        assert_eq!(
+            CellFilter::Exclude.condition("\"status\"", "'paid'"),
+            "(\"status\" != 'paid' OR \"status\" IS NULL)"
+        );
+        assert_eq!(
+            CellFilter::Exclude.condition("\"note\"", "NULL"),
+            "\"note\" IS NOT NULL"
+        );
+        assert_eq!(
+            CellFilter::Null.condition("\"note\"", "'gift'"),
+            "\"note\" IS NULL"
+        );
+        assert_eq!(
+            CellFilter::NotNull.condition("\"note\"", "NULL"),
+            "\"note\" IS NOT NULL"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_and_filter() {
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new().in_memory(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        let variables = BTreeMap::from([("id".to_string(), "1".to_string())]);
+        let mut component = RecordTableComponent::new(KeyConfig::default(), true);
+        component.filter.and_condition(None, "id > {{id}}");
+        // The value of a cell is not taken for a variable.
+        component
+            .and_filter(&pool, &variables, "\"note\" = '{{x}}'")
+            .unwrap();
+        assert_eq!(
+            component.query.filter(),
+            Some("(id > 1) AND \"note\" = '{{x}}'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_query_state() {
+        let mut component = RecordTableComponent::new(KeyConfig::default(), true);
+        component.query.set_filter(Some("id > 1".to_string()));
+        component.toggle_order();
+        component.reset();
+        assert_eq!(component.query.filter(), None);
+        assert_eq!(component.query.order_query(), None);
+    }
+
+    #[test]
+    fn test_empty_hint() {
+        let mut component = RecordTableComponent::new(KeyConfig::default(), true);
+        assert_eq!(component.empty_hint()[0], "No table is open.");
+
+        component.table.update(
+            vec![],
+            Some(0),
+            vec!["id".to_string()],
+            Database::new("app".to_string(), vec![]),
+            DTable {
+                name: "users".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+            false,
+        );
+        assert_eq!(component.empty_hint()[0], "The table has no records.");
+
+        component.query.set_filter(Some("id > 1".to_string()));
+        assert_eq!(component.empty_hint()[0], "No records match the filter.");
+    }
+}
@@ -0,0 +1,145 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::result_schema::ResultColumn;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+/// The name, type and origin table of each column of a SQL editor result.
+pub struct ResultSchemaComponent {
+    columns: Vec<ResultColumn>,
+    state: TableState,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ResultSchemaComponent {
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 16;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            columns: vec![],
+            state: TableState::default(),
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn open(&mut self, columns: Vec<ResultColumn>) -> Result<()> {
+        self.columns = columns;
+        self.state.select((!self.columns.is_empty()).then_some(0));
+        self.show()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.columns.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+impl DrawableComponent for ResultSchemaComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title("Result columns")
+                    .borders(Borders::ALL),
+                area,
+            );
+            if self.columns.is_empty() {
+                f.render_widget(Paragraph::new("No result to describe"), chunks[0]);
+            } else {
+                let rows = self.columns.iter().map(|column| {
+                    Row::new([
+                        Cell::from(column.name.as_str()),
+                        Cell::from(column.type_name.as_str()),
+                        Cell::from(column.origin.as_deref().unwrap_or("-")),
+                    ])
+                });
+                let mut state = self.state.clone();
+                f.render_stateful_widget(
+                    Table::new(
+                        rows,
+                        [
+                            Constraint::Percentage(40),
+                            Constraint::Percentage(25),
+                            Constraint::Percentage(35),
+                        ],
+                    )
+                    .header(
+                        Row::new(["name", "type", "origin"])
+                            .style(Style::default().fg(Color::DarkGray)),
+                    )
+                    .highlight_style(Style::default().bg(Color::Blue)),
+                    chunks[0],
+                    &mut state,
+                );
+            }
+            f.render_widget(
+                Paragraph::new(format!(
+                    "The origin is inferred from the query. Press [{}] to close.",
+                    self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ResultSchemaComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down || key == self.key_config.scroll_down {
+                self.move_selection(true);
+            } else if key == Key::Up || key == self.key_config.scroll_up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
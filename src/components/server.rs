@@ -0,0 +1,292 @@
+use super::{
+    Component, DatabaseFilterComponent, DrawableComponent, EventState, PropertyTrait,
+    StatefulDrawableComponent,
+};
+use crate::clipboard::copy_to_clipboard;
+use crate::components::command::{self, CommandInfo};
+use crate::components::TableComponent;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use async_trait::async_trait;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+use zhobo::database::Pool;
+use zhobo::tree::{Database, Table};
+
+#[derive(Debug, PartialEq)]
+pub enum Focus {
+    Lock,
+    Variable,
+}
+
+impl std::fmt::Display for Focus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub struct ServerComponent {
+    lock_table: TableComponent,
+    variable_table: TableComponent,
+    variable_filter: DatabaseFilterComponent,
+    variables: (Vec<String>, Vec<Vec<String>>),
+    filter_focused: bool,
+    focus: Focus,
+    key_config: KeyConfig,
+}
+
+impl ServerComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            lock_table: TableComponent::new(key_config.clone()),
+            variable_table: TableComponent::new(key_config.clone()),
+            variable_filter: DatabaseFilterComponent::with_placeholder("Search variables"),
+            variables: (vec![], vec![]),
+            filter_focused: false,
+            focus: Focus::Lock,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.lock_table.set_key_config(key_config.clone());
+        self.variable_table.set_key_config(key_config.clone());
+        self.key_config = key_config;
+    }
+
+    pub fn reset(&mut self) {
+        self.lock_table.reset();
+        self.variable_table.reset();
+        self.variable_filter.reset();
+        self.variables = (vec![], vec![]);
+        self.filter_focused = false;
+    }
+
+    fn focused_component(&mut self) -> &mut dyn PropertyTrait {
+        match self.focus {
+            Focus::Lock => &mut self.lock_table,
+            Focus::Variable => &mut self.variable_table,
+        }
+    }
+
+    async fn refresh(&mut self, pool: &dyn Pool) -> Result<()> {
+        match self.focus {
+            Focus::Lock => {
+                let locks = pool.get_locks().await?;
+                Self::update_table(
+                    &mut self.lock_table,
+                    locks.first().map_or(vec![], |l| l.fields()),
+                    locks.iter().map(|l| l.columns()).collect(),
+                    "locks",
+                );
+            }
+            Focus::Variable => {
+                let variables = pool.get_variables().await?;
+                self.variables = (
+                    variables.first().map_or(vec![], |v| v.fields()),
+                    variables.iter().map(|v| v.columns()).collect(),
+                );
+                self.apply_variable_filter();
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_variable_filter(&mut self) {
+        let filter = self.variable_filter.input_str().to_lowercase();
+        let rows = self
+            .variables
+            .1
+            .iter()
+            .filter(|row| {
+                filter.is_empty()
+                    || row
+                        .iter()
+                        .any(|cell| cell.to_lowercase().contains(filter.as_str()))
+            })
+            .cloned()
+            .collect::<Vec<Vec<String>>>();
+        Self::update_table(
+            &mut self.variable_table,
+            self.variables.0.clone(),
+            rows,
+            "variables",
+        );
+    }
+
+    fn update_table(
+        table: &mut TableComponent,
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+        name: &str,
+    ) {
+        table.reset();
+        if !rows.is_empty() {
+            table.update(
+                rows.clone(),
+                Some(rows.len()),
+                headers,
+                Database::new("server".to_string(), vec![]),
+                Table {
+                    name: name.to_string(),
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    parent: None,
+                    schema: None,
+                },
+                false,
+            );
+        }
+    }
+
+    fn tab_names(&self) -> Vec<(Focus, String)> {
+        vec![
+            (Focus::Lock, command::tab_locks(&self.key_config).name),
+            (
+                Focus::Variable,
+                command::tab_variables(&self.key_config).name,
+            ),
+        ]
+    }
+}
+
+impl StatefulDrawableComponent for ServerComponent {
+    fn draw(&mut self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Length(20), Constraint::Min(1)])
+            .split(area);
+
+        let tab_names = self
+            .tab_names()
+            .iter()
+            .map(|(f, c)| {
+                ListItem::new(c.to_string()).style(if *f == self.focus {
+                    Style::default().bg(Color::Blue)
+                } else {
+                    Style::default()
+                })
+            })
+            .collect::<Vec<ListItem>>();
+
+        let tab_list = List::new(tab_names)
+            .block(Block::default().borders(Borders::ALL).style(if focused {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }))
+            .style(Style::default());
+
+        f.render_widget(tab_list, layout[0]);
+
+        if matches!(self.focus, Focus::Variable) {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(2), Constraint::Min(1)])
+                .split(layout[1]);
+            self.variable_filter
+                .draw(f, chunks[0], focused && self.filter_focused)?;
+            StatefulDrawableComponent::draw(
+                &mut self.variable_table,
+                f,
+                chunks[1],
+                focused && !self.filter_focused,
+            )?;
+            return Ok(());
+        }
+
+        self.focused_component().draw(f, layout[1], focused)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Component for ServerComponent {
+    fn commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(command::toggle_server_tabs(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::refresh(&self.key_config)));
+    }
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.filter_focused {
+            if key == self.key_config.enter || key == self.key_config.exit_popup {
+                self.filter_focused = false;
+                return Ok(EventState::Consumed);
+            }
+            if self.variable_filter.event(key)?.is_consumed() {
+                self.apply_variable_filter();
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.key_config.filter && matches!(self.focus, Focus::Variable) {
+            self.filter_focused = true;
+            return Ok(EventState::Consumed);
+        }
+
+        self.focused_component().event(key)?;
+
+        if key == self.key_config.copy {
+            if let Some(text) = self.focused_component().content() {
+                copy_to_clipboard(text.as_str())?
+            }
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    async fn async_event(&mut self, key: Key, pool: &Box<dyn Pool>) -> Result<EventState> {
+        if key == self.key_config.refresh {
+            self.refresh(pool.as_ref()).await?;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_locks {
+            self.focus = Focus::Lock;
+            self.refresh(pool.as_ref()).await?;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_variables {
+            self.focus = Focus::Variable;
+            self.refresh(pool.as_ref()).await?;
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component, Focus, Key, KeyConfig, ServerComponent};
+
+    #[test]
+    fn test_apply_variable_filter() {
+        let mut component = ServerComponent::new(KeyConfig::default());
+        component.focus = Focus::Variable;
+        component.variables = (
+            vec!["name".to_string(), "value".to_string()],
+            vec![
+                vec!["max_connections".to_string(), "151".to_string()],
+                vec!["wait_timeout".to_string(), "28800".to_string()],
+            ],
+        );
+        component.apply_variable_filter();
+        assert_eq!(component.variable_table.rows.len(), 2);
+
+        assert!(component.event(Key::Char('/')).unwrap().is_consumed());
+        for c in "TIMEOUT".chars() {
+            assert!(component.event(Key::Char(c)).unwrap().is_consumed());
+        }
+        assert_eq!(
+            component.variable_table.rows,
+            vec![vec!["wait_timeout".to_string(), "28800".to_string()]]
+        );
+
+        assert!(component.event(Key::Enter).unwrap().is_consumed());
+        assert!(!component.filter_focused);
+    }
+}
@@ -0,0 +1,207 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::session_variables::SessionVariables;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+struct Row {
+    name: String,
+    value: Option<String>,
+    local: bool,
+}
+
+/// Session variables the SQL editor connection carries between statements.
+pub struct SessionVariablesComponent {
+    rows: Vec<Row>,
+    state: ListState,
+    edit: Option<(String, String)>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl SessionVariablesComponent {
+    const WIDTH: u16 = 70;
+    const HEIGHT: u16 = 12;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            rows: vec![],
+            state: ListState::default(),
+            edit: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    /// Lists `names` followed by the other variables set in the SQL editor.
+    pub fn open(&mut self, variables: &SessionVariables, names: Vec<&str>) -> Result<()> {
+        self.rows = names
+            .iter()
+            .map(|name| Row {
+                name: name.to_string(),
+                value: variables.get(name).map(|variable| variable.value.clone()),
+                local: variables.get(name).is_some_and(|variable| variable.local),
+            })
+            .chain(
+                variables
+                    .variables()
+                    .iter()
+                    .filter(|variable| !names.contains(&variable.name.as_str()))
+                    .map(|variable| Row {
+                        name: variable.name.clone(),
+                        value: Some(variable.value.clone()),
+                        local: variable.local,
+                    }),
+            )
+            .collect();
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(
+            (!self.rows.is_empty()).then(|| selected.min(self.rows.len().saturating_sub(1))),
+        );
+        self.show()
+    }
+
+    /// The name and current value of the variable chosen to be set.
+    pub fn take_edit(&mut self) -> Option<(String, String)> {
+        self.edit.take()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.rows.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+impl DrawableComponent for SessionVariablesComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let items = if self.rows.is_empty() {
+                vec![ListItem::new("No session variables set with SET")]
+            } else {
+                self.rows
+                    .iter()
+                    .map(|row| {
+                        ListItem::new(format!(
+                            "{} = {}{}",
+                            row.name,
+                            row.value.as_deref().unwrap_or("(server default)"),
+                            if row.local { "  [local]" } else { "" }
+                        ))
+                    })
+                    .collect::<Vec<ListItem>>()
+            };
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title("Session variables")
+                    .borders(Borders::ALL),
+                area,
+            );
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[0],
+                &mut state,
+            );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Press [{}] to set, [{}] to close.",
+                    self.key_config.enter, self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for SessionVariablesComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                if let Some(row) = self.state.selected().and_then(|i| self.rows.get(i)) {
+                    self.edit = Some((row.name.clone(), row.value.clone().unwrap_or_default()));
+                    self.hide();
+                }
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down {
+                self.move_selection(true);
+            } else if key == Key::Up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SessionVariablesComponent;
+    use crate::components::Component;
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use crate::session_variables::{parse_set, SessionVariables};
+
+    #[test]
+    fn test_edit() {
+        let mut variables = SessionVariables::default();
+        variables.record(parse_set("SET timezone = 'UTC'").unwrap());
+        variables.record(parse_set("SET work_mem = '64MB'").unwrap());
+        let mut component = SessionVariablesComponent::new(KeyConfig::default());
+        component
+            .open(&variables, vec!["search_path", "timezone"])
+            .unwrap();
+        assert_eq!(component.rows.len(), 3);
+        assert_eq!(component.rows[0].value, None);
+
+        component.event(Key::Down).unwrap();
+        component.event(Key::Enter).unwrap();
+        assert_eq!(
+            component.take_edit(),
+            Some(("timezone".to_string(), "'UTC'".to_string()))
+        );
+        assert!(!component.visible);
+    }
+}
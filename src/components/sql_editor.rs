@@ -1,21 +1,36 @@
 use super::{
-    compute_character_width, CompletionComponent, Component, EventState, MovableComponent,
-    StatefulDrawableComponent, TableComponent,
+    completion::CompletionContext,
+    compute_character_width,
+    find_replace::{self, FindReplaceAction},
+    CompletionComponent, Component, DrawableComponent, EventState, FindReplaceComponent,
+    MovableComponent, StatefulDrawableComponent, TableComponent,
 };
-use crate::components::command::CommandInfo;
+use crate::components::command::{self, CommandInfo};
 use crate::config::KeyConfig;
-use crate::database::{ExecuteResult, Pool};
 use crate::event::Key;
+use crate::index_advice;
+use crate::result_schema::{self, ResultColumn};
+use crate::session_variables::{self, SessionVariables};
+use crate::snippet::Snippet;
+use crate::template;
 use crate::ui::stateful_paragraph::{ParagraphState, StatefulParagraph};
-use anyhow::Result;
+use crate::undo::{self, SessionChange, UndoEntry};
+use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Tabs, Wrap},
     Frame,
 };
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use unicode_width::UnicodeWidthStr;
+use zhobo::database::{
+    error_position, is_binary_type, top_level_keywords, ErrorPosition, ExecuteResult, FailedQuery,
+    Pool,
+};
 
 struct QueryResult {
     updated_rows: u64,
@@ -32,6 +47,71 @@ pub enum Focus {
     Table,
 }
 
+/// Editing mode of the editor when `vim_mode` is enabled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Insert,
+    Normal,
+    Visual,
+}
+
+impl Mode {
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Insert => " INSERT ",
+            Self::Normal => " NORMAL ",
+            Self::Visual => " VISUAL ",
+        }
+    }
+}
+
+const MAX_HISTORY: usize = 100;
+
+/// Kind of the last edit, used to group consecutive typing or deleting into one undo step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    Typing,
+    Deleting,
+}
+
+#[derive(PartialEq)]
+enum CharClass {
+    Space,
+    Word,
+    Punctuation,
+}
+
+const BRACKETS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+const QUOTES: &[char] = &['\'', '"', '`'];
+
+/// Display width of `c`. A newline has no width.
+fn char_width(c: char) -> u16 {
+    if c == '\n' {
+        0
+    } else {
+        compute_character_width(c)
+    }
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Space
+    } else if c.is_alphanumeric() || c == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// Tab stops of an expanded snippet as `(start, length)`, in the order Tab visits them.
+struct SnippetSession {
+    stops: Vec<(usize, usize)>,
+    current: usize,
+    /// The cursor was moved to the current stop and nothing was typed yet, so typing replaces
+    /// its default text.
+    fresh: bool,
+}
+
 pub struct SqlEditorComponent {
     input: Vec<char>,
     input_cursor_position_x: u16,
@@ -39,43 +119,1006 @@ pub struct SqlEditorComponent {
     table: TableComponent,
     query_result: Option<QueryResult>,
     completion: CompletionComponent,
+    find_replace: FindReplaceComponent,
     key_config: KeyConfig,
     paragraph_state: ParagraphState,
     focus: Focus,
+    last_change: Option<UndoEntry>,
+    /// The statement run last, until the app takes it.
+    executed: Option<String>,
+    /// The time the statement run last took, whether it failed or not, until the app takes it.
+    finished_in: Option<Duration>,
+    notice: Option<String>,
+    row_limit: Option<usize>,
+    max_cell_length: usize,
+    masked: bool,
+    /// The query and the limit its result was truncated to.
+    truncated: Option<(String, usize)>,
+    session_variables: SessionVariables,
+    dedicated_connection: bool,
+    /// Whether a statement changed the state of the session, such as a variable, the database or
+    /// temporary tables, since connecting.
+    session_changed: bool,
+    /// Whether a transaction started by a statement is open.
+    in_transaction: bool,
+    /// Whether statements run on the scratch database instead of the connection.
+    scratch: bool,
+    /// The values of `{{name}}` in statements.
+    variables: BTreeMap<String, String>,
+    /// The range of the buffer the last syntax error points to, until the buffer is edited.
+    error_range: Option<(usize, usize)>,
+    vim_mode: bool,
+    mode: Mode,
+    register: String,
+    pending_operator: Option<Key>,
+    visual_anchor: usize,
+    undo_history: Vec<(Vec<char>, usize)>,
+    redo_history: Vec<(Vec<char>, usize)>,
+    last_edit: Option<Edit>,
+    auto_close_brackets: bool,
+    pinned: Vec<TableComponent>,
+    /// The columns of the latest result and of each pinned result.
+    result_columns: Vec<ResultColumn>,
+    pinned_columns: Vec<Vec<ResultColumn>>,
+    /// Index of the pinned result being shown, or `None` for the latest result.
+    pinned_index: Option<usize>,
+    editor_percentage: u16,
+    snippets: Vec<Snippet>,
+    snippet_session: Option<SnippetSession>,
 }
 
 impl SqlEditorComponent {
-    pub fn new(key_config: KeyConfig) -> Self {
+    pub fn new(key_config: KeyConfig, vim_mode: bool, auto_close_brackets: bool) -> Self {
         Self {
             input: Vec::new(),
             input_idx: 0,
             input_cursor_position_x: 0,
             table: TableComponent::new(key_config.clone()),
             completion: CompletionComponent::new(key_config.clone(), "", true),
+            find_replace: FindReplaceComponent::new(key_config.clone()),
             focus: Focus::Editor,
             paragraph_state: ParagraphState::default(),
             query_result: None,
+            last_change: None,
+            executed: None,
+            finished_in: None,
+            notice: None,
+            row_limit: None,
+            max_cell_length: usize::MAX,
+            masked: false,
+            truncated: None,
+            session_variables: SessionVariables::default(),
+            dedicated_connection: false,
+            session_changed: false,
+            in_transaction: false,
+            scratch: false,
+            variables: BTreeMap::new(),
+            error_range: None,
+            vim_mode,
+            mode: if vim_mode { Mode::Normal } else { Mode::Insert },
+            register: String::new(),
+            pending_operator: None,
+            visual_anchor: 0,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+            last_edit: None,
+            auto_close_brackets,
+            pinned: Vec::new(),
+            result_columns: Vec::new(),
+            pinned_columns: Vec::new(),
+            pinned_index: None,
+            editor_percentage: 50,
+            snippets: Snippet::defaults(),
+            snippet_session: None,
             key_config,
         }
     }
 
-    fn update_completion(&mut self) {
-        let input = &self
-            .input
+    pub fn set_options(&mut self, vim_mode: bool, auto_close_brackets: bool) {
+        if vim_mode != self.vim_mode {
+            self.mode = if vim_mode { Mode::Normal } else { Mode::Insert };
+            self.pending_operator = None;
+        }
+        self.vim_mode = vim_mode;
+        self.auto_close_brackets = auto_close_brackets;
+    }
+
+    /// Caps the rows a `SELECT` reads to `row_limit`, or reads them all with `None`.
+    pub fn set_row_limit(&mut self, row_limit: Option<usize>) {
+        self.row_limit = row_limit;
+    }
+
+    /// Sets the number of characters of a value shown in the cells of the results.
+    pub fn set_max_cell_length(&mut self, max_cell_length: usize) {
+        self.max_cell_length = max_cell_length;
+        self.table.set_max_cell_length(max_cell_length);
+        for table in &mut self.pinned {
+            table.set_max_cell_length(max_cell_length);
+        }
+    }
+
+    /// Draws the values of the results as a mask.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.table.set_masked(masked);
+        for table in &mut self.pinned {
+            table.set_masked(masked);
+        }
+    }
+
+    /// Uses the built-in snippets and `snippets`, which replace built-in ones of the same name.
+    pub fn set_snippets(&mut self, snippets: Vec<Snippet>) {
+        let mut defaults = Snippet::defaults();
+        defaults.retain(|default| !snippets.iter().any(|s| s.name == default.name));
+        self.snippets = defaults.into_iter().chain(snippets).collect();
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.table.set_key_config(key_config.clone());
+        self.completion.set_key_config(key_config.clone());
+        self.find_replace.set_key_config(key_config.clone());
+        for table in &mut self.pinned {
+            table.set_key_config(key_config.clone());
+        }
+        self.key_config = key_config;
+    }
+
+    /// Saves the buffer before an edit. Consecutive edits of the same kind share one entry.
+    fn save_history(&mut self, edit: Option<Edit>) {
+        self.error_range = None;
+        if edit.is_none() || edit != self.last_edit {
+            self.undo_history.push((self.input.clone(), self.input_idx));
+            if self.undo_history.len() > MAX_HISTORY {
+                self.undo_history.remove(0);
+            }
+            self.redo_history.clear();
+        }
+        self.last_edit = edit;
+    }
+
+    fn undo(&mut self) {
+        self.snippet_session = None;
+        self.error_range = None;
+        if let Some((input, idx)) = self.undo_history.pop() {
+            let current = std::mem::replace(&mut self.input, input);
+            self.redo_history.push((current, self.input_idx));
+            self.restore_cursor(idx);
+        }
+    }
+
+    fn redo(&mut self) {
+        self.snippet_session = None;
+        self.error_range = None;
+        if let Some((input, idx)) = self.redo_history.pop() {
+            let current = std::mem::replace(&mut self.input, input);
+            self.undo_history.push((current, self.input_idx));
+            self.restore_cursor(idx);
+        }
+    }
+
+    fn restore_cursor(&mut self, idx: usize) {
+        if self.mode == Mode::Insert {
+            self.move_cursor_to(idx);
+        } else {
+            self.move_cursor_to(idx.min(self.last_idx()));
+        }
+    }
+
+    fn move_cursor_to(&mut self, idx: usize) {
+        self.input_idx = idx.min(self.input.len());
+        self.input_cursor_position_x = self.input[..self.input_idx]
+            .iter()
+            .map(|c| char_width(*c))
+            .sum();
+        self.completion.update("");
+        self.last_edit = None;
+    }
+
+    /// The last index the cursor can be on in normal and visual mode.
+    fn last_idx(&self) -> usize {
+        self.input.len().saturating_sub(1)
+    }
+
+    fn next_word_start(&self) -> usize {
+        let mut idx = self.input_idx;
+        if let Some(c) = self.input.get(idx) {
+            let class = char_class(*c);
+            while class != CharClass::Space
+                && idx < self.input.len()
+                && char_class(self.input[idx]) == class
+            {
+                idx += 1;
+            }
+        }
+        while idx < self.input.len() && char_class(self.input[idx]) == CharClass::Space {
+            idx += 1;
+        }
+        idx
+    }
+
+    fn previous_word_start(&self) -> usize {
+        let mut idx = self.input_idx;
+        while idx > 0 && char_class(self.input[idx - 1]) == CharClass::Space {
+            idx -= 1;
+        }
+        if idx == 0 {
+            return 0;
+        }
+        let class = char_class(self.input[idx - 1]);
+        while idx > 0 && char_class(self.input[idx - 1]) == class {
+            idx -= 1;
+        }
+        idx
+    }
+
+    /// Start and end (excluding the newline) of the line containing `idx`.
+    fn line_bounds(&self, idx: usize) -> (usize, usize) {
+        let start = self.input[..idx]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |i| i + 1);
+        let end = self.input[idx..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(self.input.len(), |i| idx + i);
+        (start, end)
+    }
+
+    /// Index of the bracket under or just before the cursor, along with the index of the
+    /// bracket matching it.
+    fn matching_bracket(&self) -> Option<(usize, usize)> {
+        [Some(self.input_idx), self.input_idx.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .filter(|idx| *idx < self.input.len())
+            .find_map(|idx| self.find_match(idx).map(|m| (idx, m)))
+    }
+
+    fn find_match(&self, idx: usize) -> Option<usize> {
+        let c = self.input[idx];
+        let mut depth = 0;
+        if let Some((open, close)) = BRACKETS.iter().find(|(open, _)| *open == c) {
+            for (i, n) in self.input.iter().enumerate().skip(idx) {
+                if n == open {
+                    depth += 1;
+                } else if n == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+            }
+        } else if let Some((open, close)) = BRACKETS.iter().find(|(_, close)| *close == c) {
+            for i in (0..=idx).rev() {
+                if self.input[i] == *close {
+                    depth += 1;
+                } else if self.input[i] == *open {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Inserts `c` in insert mode, closing brackets and quotes when `auto_close_brackets`
+    /// is enabled. Typing a closing character in front of the same one moves over it.
+    fn insert_char(&mut self, c: char) {
+        let next = self.input.get(self.input_idx).copied();
+        let previous = self.input_idx.checked_sub(1).map(|i| self.input[i]);
+        if self.auto_close_brackets {
+            let closes = BRACKETS.iter().any(|(_, close)| *close == c) || QUOTES.contains(&c);
+            if closes && next == Some(c) {
+                self.input_idx += 1;
+                self.input_cursor_position_x += char_width(c);
+                self.last_edit = None;
+                return;
+            }
+        }
+        self.save_history(Some(Edit::Typing));
+        self.input.insert(self.input_idx, c);
+        self.input_idx += 1;
+        self.input_cursor_position_x += char_width(c);
+        if !self.auto_close_brackets {
+            return;
+        }
+        let closing = BRACKETS
+            .iter()
+            .find(|(open, _)| *open == c)
+            .map(|(_, close)| *close)
+            .or_else(|| {
+                (QUOTES.contains(&c) && !previous.is_some_and(|p| char_class(p) == CharClass::Word))
+                    .then_some(c)
+            });
+        if let Some(closing) = closing {
+            if !next.is_some_and(|n| char_class(n) == CharClass::Word) {
+                self.input.insert(self.input_idx, closing);
+            }
+        }
+    }
+
+    /// Expands the snippet named by the word before the cursor, or moves to the next tab stop of
+    /// the snippet being filled in.
+    fn expand_snippet(&mut self) -> bool {
+        if let Some(session) = self.snippet_session.as_mut() {
+            session.current += 1;
+            session.fresh = true;
+            match session.stops.get(session.current) {
+                Some((start, _)) => {
+                    let start = *start;
+                    self.move_cursor_to(start);
+                }
+                None => self.snippet_session = None,
+            }
+            return true;
+        }
+        let word_start = self.input[..self.input_idx]
+            .iter()
+            .rposition(|c| char_class(*c) != CharClass::Word)
+            .map_or(0, |i| i + 1);
+        let word = self.input[word_start..self.input_idx]
+            .iter()
+            .collect::<String>();
+        let Some(snippet) = self.snippets.iter().find(|s| s.name == word) else {
+            return false;
+        };
+        let (text, stops) = snippet.expand();
+        self.save_history(None);
+        let len = text.chars().count();
+        self.input.splice(word_start..self.input_idx, text.chars());
+        let stops = stops
+            .into_iter()
+            .map(|(start, len)| (word_start + start, len))
+            .collect::<Vec<(usize, usize)>>();
+        match stops.first() {
+            Some((start, _)) => {
+                self.move_cursor_to(*start);
+                self.snippet_session = Some(SnippetSession {
+                    stops,
+                    current: 0,
+                    fresh: true,
+                });
+            }
+            None => self.move_cursor_to(word_start + len),
+        }
+        true
+    }
+
+    /// Removes the default text of the tab stop the cursor was just moved to, so typing
+    /// replaces it. Returns false if there was nothing to remove.
+    fn clear_placeholder(&mut self) -> bool {
+        let Some(session) = self.snippet_session.as_mut() else {
+            return false;
+        };
+        let (start, len) = session.stops[session.current];
+        if !std::mem::take(&mut session.fresh) || self.input_idx != start || len == 0 {
+            return false;
+        }
+        session.stops[session.current].1 = 0;
+        for stop in session.stops.iter_mut() {
+            if stop.0 > start {
+                stop.0 -= len;
+            }
+        }
+        self.save_history(Some(Edit::Typing));
+        self.input.drain(start..start + len);
+        self.move_cursor_to(start);
+        true
+    }
+
+    /// Keeps the tab stops up to date after an edit at `idx` which changed the length of the
+    /// input from `old_len`. An edit outside the current tab stop ends the snippet.
+    fn track_snippet_edit(&mut self, idx: usize, old_len: usize) {
+        let Some(session) = self.snippet_session.as_mut() else {
+            return;
+        };
+        let delta = self.input.len() as isize - old_len as isize;
+        let (start, len) = session.stops[session.current];
+        if idx < start || idx > start + len {
+            self.snippet_session = None;
+            return;
+        }
+        session.fresh = false;
+        session.stops[session.current].1 = len.saturating_add_signed(delta);
+        for (i, stop) in session.stops.iter_mut().enumerate() {
+            if i != session.current && stop.0 > idx {
+                stop.0 = stop.0.saturating_add_signed(delta);
+            }
+        }
+    }
+
+    /// Inserts a newline, keeping the indentation of the current line.
+    fn insert_new_line(&mut self) {
+        self.snippet_session = None;
+        self.save_history(None);
+        let (start, _) = self.line_bounds(self.input_idx);
+        let mut line = vec!['\n'];
+        line.extend(
+            self.input[start..self.input_idx]
+                .iter()
+                .take_while(|c| **c == ' ' || **c == '\t'),
+        );
+        let len = line.len();
+        self.input.splice(self.input_idx..self.input_idx, line);
+        self.move_cursor_to(self.input_idx + len);
+    }
+
+    /// Column and row of the cursor in an editor `width` cells wide.
+    fn cursor_position(&self, width: u16) -> (u16, u16) {
+        let width = width.max(1);
+        let (mut x, mut y) = (0, 0);
+        for c in &self.input[..self.input_idx] {
+            if *c == '\n' {
+                x = 0;
+                y += 1;
+                continue;
+            }
+            let w = char_width(*c);
+            if x + w > width {
+                x = 0;
+                y += 1;
+            }
+            x += w;
+        }
+        if x >= width {
+            x = 0;
+            y += 1;
+        }
+        (x, y)
+    }
+
+    /// Line and column of the cursor, both starting from 1.
+    fn line_and_column(&self) -> (usize, usize) {
+        let (start, _) = self.line_bounds(self.input_idx);
+        let line = self.input[..start].iter().filter(|c| **c == '\n').count() + 1;
+        (line, self.input_idx - start + 1)
+    }
+
+    fn line_count(&self) -> usize {
+        self.input.iter().filter(|c| **c == '\n').count() + 1
+    }
+
+    /// Wraps the input into rows `width` cells wide, in the same way as `cursor_position`,
+    /// and returns them with the line numbers of the gutter.
+    fn wrapped_lines(&self, width: u16) -> (Vec<Line<'static>>, Vec<Line<'static>>) {
+        let width = width.max(1);
+        let digits = self.line_count().to_string().len();
+        let selection = (self.mode == Mode::Visual).then(|| self.selection());
+        let brackets = self.matching_bracket();
+        let error = self.error_range;
+        let mut rows = vec![];
+        let mut gutter = vec![Line::from(format!("{:>digits$} ", 1))];
+        let mut spans = vec![];
+        let (mut x, mut line) = (0, 1);
+        for (i, c) in self.input.iter().enumerate() {
+            if *c == '\n' {
+                rows.push(Line::from(std::mem::take(&mut spans)));
+                line += 1;
+                gutter.push(Line::from(format!("{:>digits$} ", line)));
+                x = 0;
+                continue;
+            }
+            let w = char_width(*c);
+            if x + w > width {
+                rows.push(Line::from(std::mem::take(&mut spans)));
+                gutter.push(Line::from(""));
+                x = 0;
+            }
+            let style = if selection.is_some_and(|(start, end)| start <= i && i < end) {
+                Style::default().bg(Color::Blue)
+            } else if error.is_some_and(|(start, end)| start <= i && i < end) {
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::UNDERLINED | Modifier::BOLD)
+            } else if brackets.is_some_and(|(a, b)| a == i || b == i) {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(c.to_string(), style));
+            x += w;
+        }
+        rows.push(Line::from(spans));
+        (rows, gutter)
+    }
+
+    /// The range selected in visual mode, including the character under the cursor.
+    fn selection(&self) -> (usize, usize) {
+        let start = self.visual_anchor.min(self.input_idx);
+        let end = (self.visual_anchor.max(self.input_idx) + 1).min(self.input.len());
+        (start.min(end), end)
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        self.snippet_session = None;
+        self.save_history(None);
+        self.register = self.input.drain(start..end).collect();
+        self.move_cursor_to(start.min(self.last_idx()));
+    }
+
+    fn paste(&mut self) {
+        let idx = if self.input.is_empty() {
+            0
+        } else {
+            self.input_idx + 1
+        };
+        self.snippet_session = None;
+        self.save_history(None);
+        let register: Vec<char> = self.register.chars().collect();
+        let len = register.len();
+        self.input.splice(idx..idx, register);
+        self.move_cursor_to((idx + len).saturating_sub(1));
+    }
+
+    /// Handles a key in normal and visual mode.
+    fn vim_event(&mut self, key: Key) -> EventState {
+        if let Some(operator) = self.pending_operator.take() {
+            let (start, end) = self.line_bounds(self.input_idx);
+            if key == operator && operator == self.key_config.vim_delete {
+                if end < self.input.len() {
+                    self.delete_range(start, end + 1);
+                } else {
+                    self.delete_range(start.saturating_sub(1), end);
+                }
+                self.register = self.register.trim_matches('\n').to_string();
+            } else if key == operator && operator == self.key_config.copy {
+                self.register = self.input[start..end].iter().collect();
+            }
+            return EventState::Consumed;
+        }
+
+        if key == self.key_config.exit_popup {
+            if self.mode == Mode::Visual {
+                self.mode = Mode::Normal;
+            } else {
+                self.focus = Focus::Table;
+            }
+        } else if key == self.key_config.vim_undo || key == self.key_config.undo_edit {
+            self.undo();
+        } else if key == self.key_config.redo_edit {
+            self.redo();
+        } else if key == self.key_config.vim_insert {
+            self.mode = Mode::Insert;
+        } else if key == self.key_config.vim_visual {
+            self.mode = if self.mode == Mode::Visual {
+                Mode::Normal
+            } else {
+                self.visual_anchor = self.input_idx;
+                Mode::Visual
+            };
+        } else if key == self.key_config.scroll_left || key == Key::Left {
+            self.move_cursor_to(self.input_idx.saturating_sub(1));
+        } else if key == self.key_config.scroll_right || key == Key::Right {
+            self.move_cursor_to((self.input_idx + 1).min(self.last_idx()));
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.move_line(true);
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.move_line(false);
+        } else if key == self.key_config.vim_word_forward {
+            self.move_cursor_to(self.next_word_start().min(self.last_idx()));
+        } else if key == self.key_config.vim_word_backward {
+            self.move_cursor_to(self.previous_word_start());
+        } else if key == self.key_config.vim_line_start
+            || key == self.key_config.move_to_head_of_line
+        {
+            self.move_cursor_to(self.line_bounds(self.input_idx).0);
+        } else if key == self.key_config.move_to_tail_of_line {
+            let (start, end) = self.line_bounds(self.input_idx);
+            self.move_cursor_to(end.saturating_sub(1).max(start));
+        } else if key == self.key_config.vim_delete || key == self.key_config.copy {
+            if self.mode == Mode::Visual {
+                let (start, end) = self.selection();
+                if key == self.key_config.vim_delete {
+                    self.delete_range(start, end);
+                } else {
+                    self.register = self.input[start..end].iter().collect();
+                    self.move_cursor_to(start);
+                }
+                self.mode = Mode::Normal;
+            } else {
+                self.pending_operator = Some(key);
+            }
+        } else if key == self.key_config.vim_paste {
+            self.paste();
+        } else if [
+            self.key_config.enter,
+            self.key_config.run_without_limit,
+            self.key_config.suggest_index,
+            self.key_config.exit,
+        ]
+        .contains(&key)
+        {
+            // Running the query and exiting are handled outside of the editing modes.
+            return EventState::NotConsumed;
+        }
+        // Other keys do nothing rather than reaching the rest of the app, such as to quit.
+        EventState::Consumed
+    }
+
+    /// Moves the cursor to the same column of the next or previous line, or to the end of the
+    /// line when it is shorter.
+    fn move_line(&mut self, down: bool) {
+        let (start, end) = self.line_bounds(self.input_idx);
+        let line = if down && end < self.input.len() {
+            self.line_bounds(end + 1)
+        } else if !down && start > 0 {
+            self.line_bounds(start - 1)
+        } else {
+            return;
+        };
+        let column = self.input_idx - start;
+        self.move_cursor_to((line.0 + column).min(line.1.saturating_sub(1).max(line.0)));
+    }
+
+    fn result_table(&mut self) -> &mut TableComponent {
+        match self.pinned_index {
+            Some(index) => &mut self.pinned[index],
+            None => &mut self.table,
+        }
+    }
+
+    /// Replaces the input with `query` and focuses the editor. The previous input can be
+    /// restored with undo.
+    pub fn set_query(&mut self, query: &str) {
+        self.save_history(None);
+        self.input = query.chars().collect();
+        self.move_cursor_to(self.input.len());
+        self.focus = Focus::Editor;
+    }
+
+    pub fn set_editor_percentage(&mut self, percentage: u16) {
+        self.editor_percentage = percentage;
+    }
+
+    /// The result grid shown when the result is focused.
+    pub fn focused_result(&self) -> Option<&TableComponent> {
+        match (&self.focus, self.pinned_index) {
+            (Focus::Table, Some(index)) => self.pinned.get(index),
+            (Focus::Table, None) if self.query_result.is_none() => Some(&self.table),
+            _ => None,
+        }
+    }
+
+    /// The pinned results and the latest result, named as their tabs.
+    pub fn results(&self) -> Vec<(String, &TableComponent)> {
+        self.pinned
             .iter()
             .enumerate()
-            .filter(|(i, _)| i < &self.input_idx)
-            .map(|(_, i)| i)
-            .collect::<String>()
-            .split(' ')
-            .map(|i| i.to_string())
-            .collect::<Vec<String>>();
+            .map(|(i, table)| (format!("Pinned {}", i + 1), table))
+            .chain((!self.table.headers.is_empty()).then(|| ("Latest".to_string(), &self.table)))
+            .collect()
+    }
+
+    /// Moves the latest result to the pinned results so the next execution opens a new grid.
+    fn pin_result(&mut self) {
+        if self.pinned_index.is_some() || self.table.headers.is_empty() {
+            return;
+        }
+        let mut latest = TableComponent::new(self.key_config.clone());
+        latest.set_max_cell_length(self.max_cell_length);
+        latest.set_masked(self.masked);
+        let table = std::mem::replace(&mut self.table, latest);
+        self.pinned.push(table);
+        self.pinned_columns
+            .push(std::mem::take(&mut self.result_columns));
+        self.pinned_index = Some(self.pinned.len() - 1);
+    }
+
+    /// The columns of the result being shown.
+    pub fn result_columns(&self) -> &[ResultColumn] {
+        match self.pinned_index {
+            Some(index) => &self.pinned_columns[index],
+            None => &self.result_columns,
+        }
+    }
+
+    /// Cycles through the pinned results and the latest result.
+    fn switch_result(&mut self, forward: bool) {
+        if self.pinned.is_empty() {
+            return;
+        }
+        let len = self.pinned.len() + 1;
+        let current = self.pinned_index.unwrap_or(self.pinned.len());
+        let next = if forward {
+            (current + 1) % len
+        } else {
+            (current + len - 1) % len
+        };
+        self.pinned_index = (next < self.pinned.len()).then_some(next);
+    }
+
+    pub fn take_executed(&mut self) -> Option<String> {
+        self.executed.take()
+    }
+
+    pub fn take_finished_in(&mut self) -> Option<Duration> {
+        self.finished_in.take()
+    }
+
+    pub fn take_change(&mut self) -> Option<UndoEntry> {
+        self.last_change.take()
+    }
+
+    /// Forgets the session state of the previous connection. A dedicated connection keeps its
+    /// session state, so the session variables are not applied again. Applying them in a failed
+    /// transaction would fail the `ROLLBACK` too.
+    pub fn set_connection(&mut self, dedicated_connection: bool) {
+        self.session_variables = SessionVariables::default();
+        self.dedicated_connection = dedicated_connection;
+        self.session_changed = false;
+        self.in_transaction = false;
+    }
+
+    /// Switches to the scratch database, whose only connection keeps its session state like a
+    /// dedicated connection, or back to the connection.
+    pub fn set_scratch(&mut self, scratch: bool) {
+        self.scratch = scratch;
+    }
+
+    pub fn set_variables(&mut self, variables: BTreeMap<String, String>) {
+        self.variables = variables;
+    }
+
+    fn session_statements(&self) -> Vec<String> {
+        if self.dedicated_connection || self.scratch {
+            return vec![];
+        }
+        self.session_variables.statements()
+    }
+
+    pub fn session_variables(&self) -> &SessionVariables {
+        &self.session_variables
+    }
+
+    /// A message about the last index suggestion, shown once.
+    pub fn take_notice(&mut self) -> Option<String> {
+        self.notice.take()
+    }
+
+    /// Runs `EXPLAIN` for the query, shows the plan, and appends a `CREATE INDEX` statement for
+    /// each table the plan scans fully.
+    async fn suggest_indexes(&mut self, pool: &dyn Pool) -> Result<()> {
+        let query = self.query().trim().trim_end_matches(';').to_string();
+        if query.is_empty() {
+            return Ok(());
+        }
+        let explain = pool.explain_query(&query);
+        let ExecuteResult::Read {
+            headers,
+            rows,
+            database,
+            table,
+            types,
+        } = pool
+            .execute_in_session(&self.session_statements(), &explain)
+            .await?
+        else {
+            return Ok(());
+        };
+        self.result_columns = result_schema::describe(&explain, &headers, &types);
+        let scans = index_advice::full_scans(&headers, &rows);
+        let suggestions = index_advice::suggest(&query, &scans);
+        let count = Some(rows.len());
+        self.table
+            .update(rows, count, headers, database, table, false);
+        self.query_result = None;
+        self.pinned_index = None;
+        self.notice = Some(if scans.is_empty() {
+            "No full table scans found".to_string()
+        } else if suggestions.is_empty() {
+            format!(
+                "Full table scan on {}, but no columns to index were found",
+                scans.join(", ")
+            )
+        } else {
+            format!("Suggested {} index(es) for review", suggestions.len())
+        });
+        if !suggestions.is_empty() {
+            self.save_history(None);
+            let mut text = self.query().trim_end().to_string();
+            for suggestion in suggestions {
+                text = format!(
+                    "{}\n\n-- Full table scan on {}\n{}",
+                    text,
+                    suggestion.table,
+                    suggestion.statement(|identifier| pool.quote_identifier(identifier))
+                );
+            }
+            self.input = text.chars().collect();
+            self.move_cursor_to(self.input.len());
+        }
+        Ok(())
+    }
+
+    pub fn find_replace_visible(&self) -> bool {
+        self.find_replace.is_visible()
+    }
+
+    /// Whether a completion is selected, which Enter accepts rather than running the statement.
+    pub fn completing(&self) -> bool {
+        self.completion.selected_candidate().is_some()
+    }
+
+    fn run_find_replace(&mut self, action: FindReplaceAction) {
+        let pattern = match self.find_replace.pattern() {
+            Ok(Some(pattern)) => pattern,
+            Ok(None) => return,
+            Err(e) => return self.find_replace.set_message(e.to_string()),
+        };
+        let text = self.query();
+        let replacement = self.find_replace.replacement();
+        let expand = self.find_replace.is_regex();
+        let from = match action {
+            FindReplaceAction::FindNext => self.input_idx + 1,
+            FindReplaceAction::ReplaceOne => {
+                match find_replace::replace_at(
+                    &text,
+                    &pattern,
+                    &replacement,
+                    expand,
+                    self.input_idx,
+                ) {
+                    Some((replaced, end)) => {
+                        self.save_history(None);
+                        self.input = replaced.chars().collect();
+                        end
+                    }
+                    None => self.input_idx,
+                }
+            }
+            FindReplaceAction::ReplaceAll => {
+                let (replaced, count) =
+                    find_replace::replace_all(&text, &pattern, &replacement, expand);
+                if count > 0 {
+                    self.save_history(None);
+                    self.input = replaced.chars().collect();
+                    self.move_cursor_to(self.input_idx);
+                }
+                return self.find_replace.set_message(format!("{} replaced", count));
+            }
+        };
+        match find_replace::find_next(&self.query(), &pattern, from) {
+            Some(start) => self.move_cursor_to(start),
+            None => self.find_replace.set_message("No match".to_string()),
+        }
+    }
+
+    pub fn editor_focused(&self) -> bool {
+        matches!(self.focus, Focus::Editor)
+    }
+
+    pub fn query(&self) -> String {
+        self.input.iter().collect()
+    }
+
+    pub async fn execute(&mut self, query: String, pool: &dyn Pool) -> Result<()> {
+        self.run(query, pool, self.row_limit).await
+    }
+
+    /// Runs the last truncated query again without the row limit.
+    async fn execute_without_limit(&mut self, pool: &dyn Pool) -> Result<()> {
+        if let Some((query, _)) = self.truncated.take() {
+            self.run(query, pool, None).await?;
+        }
+        Ok(())
+    }
+
+    async fn run(
+        &mut self,
+        query: String,
+        pool: &dyn Pool,
+        row_limit: Option<usize>,
+    ) -> Result<()> {
+        self.error_range = None;
+        let query = template::substitute(&query, &self.variables)?;
+        // Changes to the scratch database are not recorded, because reverting them would run on
+        // the connection. Neither are changes after the session state changed or in a
+        // transaction, because the rows are read and the change is reverted outside the session.
+        let inverse = if self.scratch || self.session_changed || self.in_transaction {
+            None
+        } else {
+            undo::inverse_statements(pool, &query).await.unwrap_or(None)
+        };
+        // One more row than the limit is fetched to tell whether the result was truncated.
+        let limited =
+            row_limit.and_then(|limit| with_row_limit(&query, limit + 1).map(|q| (q, limit)));
+        let sent = limited.as_ref().map_or(&query, |(query, _)| query);
+        let started_at = Instant::now();
+        let result = pool
+            .execute_in_session(&self.session_statements(), sent)
+            .await;
+        self.finished_in = Some(started_at.elapsed());
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                self.error_range = error_position(&err)
+                    .and_then(|position| error_range(sent, &position))
+                    .and_then(|range| range_in_buffer(&self.input, sent, range));
+                if let Some((start, _)) = self.error_range {
+                    self.move_cursor_to(start);
+                    self.focus = Focus::Editor;
+                }
+                return Err(err).with_context(|| FailedQuery(query.clone()));
+            }
+        };
+        self.truncated = None;
+        self.executed = Some(query.clone());
+        if !self.scratch {
+            match undo::session_change(&query) {
+                SessionChange::State => self.session_changed = true,
+                SessionChange::Begin => self.in_transaction = true,
+                SessionChange::End => self.in_transaction = false,
+                SessionChange::None => {}
+            }
+        }
+        self.session_variables.end_statement();
+        if let Some(variable) = session_variables::parse_set(&query) {
+            self.session_variables.record(variable);
+        }
+        match result {
+            ExecuteResult::Read {
+                headers,
+                types,
+                rows,
+                database,
+                table,
+            } => {
+                self.result_columns = result_schema::describe(&query, &headers, &types);
+                let mut rows = rows;
+                if let Some((_, limit)) = limited {
+                    if rows.len() > limit {
+                        rows.truncate(limit);
+                        self.truncated = Some((query, limit));
+                    }
+                }
+                let count = Some(rows.len());
+                self.table
+                    .update(rows, count, headers, database, table, false);
+                self.table
+                    .set_binary_columns(types.iter().map(|t| is_binary_type(t)).collect());
+                self.focus = Focus::Table;
+                self.query_result = None;
+                self.pinned_index = None;
+            }
+            ExecuteResult::Write { updated_rows } => {
+                self.query_result = Some(QueryResult { updated_rows });
+                self.pinned_index = None;
+                self.last_change = inverse.map(|inverse| UndoEntry {
+                    statement: query,
+                    inverse,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn update_completion(&mut self) {
+        let before = self.input[..self.input_idx].iter().collect::<String>();
+        let word = before
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .to_string();
         self.completion
-            .update(input.last().unwrap_or(&String::new()));
+            .set_context(CompletionContext::from_preceding(
+                &before[..before.len() - word.len()],
+            ));
+        self.completion.update(word);
+    }
+
+    /// Sets the table and column names suggested while typing.
+    pub fn set_completion_names(&mut self, tables: Vec<String>, columns: Vec<String>) {
+        self.completion.set_tables(tables);
+        self.completion.set_columns(columns);
     }
 
     fn complete(&mut self) -> anyhow::Result<EventState> {
         if let Some(candidate) = self.completion.selected_candidate() {
+            self.completion.push_recent(&candidate);
+            self.snippet_session = None;
+            self.save_history(None);
             let mut input = Vec::new();
             let first = self
                 .input
@@ -139,24 +1182,181 @@ impl SqlEditorComponent {
     }
 }
 
+/// Appends `LIMIT limit` to `query` if it is a `SELECT`, also after `WITH`, whose own rows are
+/// not limited, locked or written `INTO` a table. A limit in a subquery, a literal or a comment is
+/// not its own. The limit goes on a new line so a trailing line comment can't hide it.
+fn with_row_limit(query: &str, limit: usize) -> Option<String> {
+    let query = query.trim().trim_end_matches(';').trim_end();
+    let words = top_level_keywords(query)?;
+    let has = |keywords: &[&str]| words.iter().any(|word| keywords.contains(&word.as_str()));
+    let select = match words.first().map(String::as_str) {
+        Some("SELECT") => true,
+        Some("WITH") => has(&["SELECT"]) && !has(&["INSERT", "UPDATE", "DELETE", "MERGE"]),
+        _ => false,
+    };
+    (select && !has(&["LIMIT", "FETCH", "OFFSET", "INTO", "FOR", "LOCK"]))
+        .then(|| format!("{}\nLIMIT {}", query, limit))
+}
+
+/// The range of characters of `sent`, the statement as it was sent, `position` points to: the
+/// word or the symbol there, or the last character when the error is at the end.
+fn error_range(sent: &str, position: &ErrorPosition) -> Option<(usize, usize)> {
+    let chars = sent.chars().collect::<Vec<char>>();
+    let start = match position {
+        ErrorPosition::Offset(offset) => *offset,
+        ErrorPosition::Near { text, .. } if text.is_empty() => {
+            sent.trim_end().chars().count().saturating_sub(1)
+        }
+        ErrorPosition::Near { text, line } => {
+            let found = sent
+                .match_indices(text.as_str())
+                .map(|(idx, _)| idx)
+                .collect::<Vec<usize>>();
+            let idx = found
+                .iter()
+                .find(|idx| Some(sent[..**idx].matches('\n').count() + 1) == *line)
+                .or(found.first())?;
+            sent[..*idx].chars().count()
+        }
+    };
+    let class = char_class(*chars.get(start)?);
+    let len = if class == CharClass::Word {
+        chars[start..]
+            .iter()
+            .take_while(|c| char_class(**c) == CharClass::Word)
+            .count()
+    } else {
+        1
+    };
+    Some((start, start + len))
+}
+
+/// Maps `range` of `sent` to the buffer. The statement matches the buffer apart from
+/// surrounding whitespace unless it was rewritten, in which case the first occurrence of the
+/// text in the range is taken.
+fn range_in_buffer(
+    buffer: &[char],
+    sent: &str,
+    (start, end): (usize, usize),
+) -> Option<(usize, usize)> {
+    let sent = sent.chars().collect::<Vec<char>>();
+    let sent_lead = sent.iter().take_while(|c| c.is_whitespace()).count();
+    let buffer_lead = buffer.iter().take_while(|c| c.is_whitespace()).count();
+    if start >= sent_lead && buffer[buffer_lead..].starts_with(&sent[sent_lead..end]) {
+        return Some((
+            start - sent_lead + buffer_lead,
+            end - sent_lead + buffer_lead,
+        ));
+    }
+    let text = &sent[start..end];
+    buffer
+        .windows(text.len())
+        .position(|window| window == text)
+        .map(|idx| (idx, idx + text.len()))
+}
+
 impl StatefulDrawableComponent for SqlEditorComponent {
     fn draw(&mut self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
         let layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(if matches!(self.focus, Focus::Table) {
-                vec![Constraint::Length(7), Constraint::Min(1)]
-            } else {
-                vec![Constraint::Percentage(50), Constraint::Min(1)]
-            })
+            .constraints([
+                Constraint::Percentage(self.editor_percentage),
+                Constraint::Min(1),
+            ])
             .split(area);
 
-        let editor = StatefulParagraph::new(self.input.iter().collect::<String>())
-            .wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL));
+        let (line, column) = self.line_and_column();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title_bottom(Line::from(format!(" {}:{} ", line, column)).right_aligned());
+        let block = if self.vim_mode {
+            block.title(self.mode.title())
+        } else {
+            block
+        };
+        let block = if self.scratch {
+            block.title(
+                Line::from(Span::styled(
+                    " scratch database ",
+                    Style::default().fg(Color::Yellow),
+                ))
+                .right_aligned(),
+            )
+        } else {
+            block
+        };
+        let block = match &self.truncated {
+            Some((_, limit)) => block.title_bottom(
+                Line::from(Span::styled(
+                    format!(
+                        " Truncated to {} rows, [{}] to run without the limit ",
+                        limit, self.key_config.run_without_limit
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ))
+                .left_aligned(),
+            ),
+            None => block,
+        };
+        let inner = block.inner(layout[0]);
+        f.render_widget(block, layout[0]);
+
+        let gutter_width = self.line_count().to_string().len() as u16 + 1;
+        let editor_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(gutter_width), Constraint::Min(1)])
+            .split(inner);
+        let (gutter_area, text_area) = (editor_layout[0], editor_layout[1]);
+        let (rows, gutter) = self.wrapped_lines(text_area.width);
+        let (cursor_x, cursor_y) = self.cursor_position(text_area.width);
+
+        let mut scroll = self.paragraph_state.scroll();
+        if cursor_y < scroll.y {
+            scroll.y = cursor_y;
+        } else if cursor_y >= scroll.y + text_area.height.max(1) {
+            scroll.y = cursor_y + 1 - text_area.height.max(1);
+        }
+        self.paragraph_state.set_scroll(scroll);
+
+        f.render_widget(
+            Paragraph::new(gutter)
+                .style(Style::default().fg(Color::DarkGray))
+                .scroll((scroll.y, 0)),
+            gutter_area,
+        );
+        f.render_stateful_widget(
+            StatefulParagraph::new(Text::from(rows)),
+            text_area,
+            &mut self.paragraph_state,
+        );
 
-        f.render_stateful_widget(editor, layout[0], &mut self.paragraph_state);
+        let result_area = if self.pinned.is_empty() {
+            layout[1]
+        } else {
+            let result_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(1)])
+                .split(layout[1]);
+            let titles = (1..=self.pinned.len())
+                .map(|i| format!("Pinned {}", i))
+                .chain(std::iter::once("Latest".to_string()))
+                .map(Line::from)
+                .collect::<Vec<_>>();
+            f.render_widget(
+                Tabs::new(titles)
+                    .select(self.pinned_index.unwrap_or(self.pinned.len()))
+                    .style(Style::default().fg(Color::DarkGray))
+                    .highlight_style(
+                        Style::default()
+                            .fg(Color::Reset)
+                            .add_modifier(Modifier::UNDERLINED),
+                    ),
+                result_layout[0],
+            );
+            result_layout[1]
+        };
 
-        if let Some(result) = self.query_result.as_ref() {
+        if let (Some(result), None) = (self.query_result.as_ref(), self.pinned_index) {
             let result = Paragraph::new(result.result_str())
                 .block(Block::default().borders(Borders::ALL).style(
                     if focused && matches!(self.focus, Focus::Editor) {
@@ -166,92 +1366,175 @@ impl StatefulDrawableComponent for SqlEditorComponent {
                     },
                 ))
                 .wrap(Wrap { trim: true });
-            f.render_widget(result, layout[1]);
+            f.render_widget(result, result_area);
         } else {
-            self.table
-                .draw(f, layout[1], focused && matches!(self.focus, Focus::Table))?;
+            let focused = focused && matches!(self.focus, Focus::Table);
+            self.result_table().draw(f, result_area, focused)?;
         }
 
+        let cursor_y = cursor_y - scroll.y;
         if focused && matches!(self.focus, Focus::Editor) {
             f.set_cursor(
-                (layout[0].x + 1)
-                    .saturating_add(
-                        self.input_cursor_position_x % layout[0].width.saturating_sub(2),
-                    )
+                text_area
+                    .x
+                    .saturating_add(cursor_x)
                     .min(area.right().saturating_sub(2)),
-                (layout[0].y
-                    + 1
-                    + self.input_cursor_position_x / layout[0].width.saturating_sub(2))
-                .min(layout[0].bottom()),
+                (text_area.y + cursor_y).min(layout[0].bottom()),
             )
         }
 
         if focused && matches!(self.focus, Focus::Editor) {
-            self.completion.draw(
-                f,
-                area,
-                false,
-                self.input_cursor_position_x % layout[0].width.saturating_sub(2) + 1,
-                self.input_cursor_position_x / layout[0].width.saturating_sub(2),
-            )?;
+            self.completion
+                .draw(f, area, false, gutter_width + cursor_x + 1, cursor_y)?;
         };
+        self.find_replace.draw(f, layout[0], focused)?;
         Ok(())
     }
 }
 
 #[async_trait]
 impl Component for SqlEditorComponent {
-    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+    fn commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(command::find_replace(&self.key_config)));
+        out.push(CommandInfo::new(command::suggest_index(&self.key_config)));
+        out.push(CommandInfo::new(command::show_session_variables(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::show_result_schema(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::toggle_scratch_database(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::save_result_to_scratch(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::run_without_limit(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::pin_result(&self.key_config)));
+        out.push(CommandInfo::new(command::switch_result(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_result_as_csv(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::export_results(&self.key_config)));
+    }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
         let input_str: String = self.input.iter().collect();
 
+        if self.find_replace.event(key)?.is_consumed() {
+            if let Some(action) = self.find_replace.take_action() {
+                self.run_find_replace(action);
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if matches!(self.focus, Focus::Table) {
+            if key == self.key_config.pin_result {
+                self.pin_result();
+                return Ok(EventState::Consumed);
+            } else if key == self.key_config.next_result || key == self.key_config.previous_result {
+                self.switch_result(key == self.key_config.next_result);
+                return Ok(EventState::Consumed);
+            }
+        }
+
         if key == self.key_config.focus_above && matches!(self.focus, Focus::Table) {
             self.focus = Focus::Editor
         } else if key == self.key_config.enter {
             return self.complete();
         }
 
+        if key == self.key_config.find_replace && matches!(self.focus, Focus::Editor) {
+            self.find_replace.show()?;
+            return Ok(EventState::Consumed);
+        }
+
+        if self.vim_mode && self.mode != Mode::Insert && matches!(self.focus, Focus::Editor) {
+            return Ok(self.vim_event(key));
+        }
+
+        if matches!(self.focus, Focus::Editor) {
+            if key == self.key_config.new_line {
+                self.insert_new_line();
+                return Ok(EventState::Consumed);
+            } else if key == self.key_config.undo_edit {
+                self.undo();
+                return Ok(EventState::Consumed);
+            } else if key == self.key_config.redo_edit {
+                self.redo();
+                return Ok(EventState::Consumed);
+            }
+        }
+
         match key {
             Key::Char(c) if matches!(self.focus, Focus::Editor) => {
-                self.input.insert(self.input_idx, c);
-                self.input_idx += 1;
-                self.input_cursor_position_x += compute_character_width(c);
+                self.clear_placeholder();
+                let (idx, len) = (self.input_idx, self.input.len());
+                self.insert_char(c);
+                self.track_snippet_edit(idx, len);
                 self.update_completion();
 
                 return Ok(EventState::Consumed);
             }
-            Key::Esc if matches!(self.focus, Focus::Editor) => self.focus = Focus::Table,
+            Key::Esc if matches!(self.focus, Focus::Editor) => {
+                if self.vim_mode {
+                    self.mode = Mode::Normal;
+                    self.move_cursor_to(self.input_idx.saturating_sub(1));
+                } else {
+                    self.focus = Focus::Table;
+                }
+            }
+            Key::Tab if matches!(self.focus, Focus::Editor) && self.expand_snippet() => {
+                return Ok(EventState::Consumed);
+            }
             Key::Delete | Key::Backspace if matches!(self.focus, Focus::Editor) => {
+                if self.clear_placeholder() {
+                    return Ok(EventState::Consumed);
+                }
+                let (idx, len) = (self.input_idx, self.input.len());
                 if input_str.width() > 0 && !self.input.is_empty() && self.input_idx > 0 {
+                    self.save_history(Some(Edit::Deleting));
+                    if self.auto_close_brackets && self.input_idx < self.input.len() {
+                        let pair = (self.input[self.input_idx - 1], self.input[self.input_idx]);
+                        if BRACKETS.contains(&pair)
+                            || (QUOTES.contains(&pair.0) && pair.0 == pair.1)
+                        {
+                            self.input.remove(self.input_idx);
+                        }
+                    }
                     let last_c = self.input.remove(self.input_idx - 1);
                     self.input_idx -= 1;
-                    self.input_cursor_position_x -= compute_character_width(last_c);
+                    self.input_cursor_position_x -= char_width(last_c);
                     self.completion.update("");
+                    self.track_snippet_edit(idx - 1, len);
                 }
 
                 return Ok(EventState::Consumed);
             }
             Key::Left if matches!(self.focus, Focus::Editor) => {
+                self.last_edit = None;
                 if !self.input.is_empty() && self.input_idx > 0 {
                     self.input_idx -= 1;
                     self.input_cursor_position_x = self
                         .input_cursor_position_x
-                        .saturating_sub(compute_character_width(self.input[self.input_idx]));
+                        .saturating_sub(char_width(self.input[self.input_idx]));
                     self.completion.update("");
                 }
                 return Ok(EventState::Consumed);
             }
             Key::Right if matches!(self.focus, Focus::Editor) => {
+                self.last_edit = None;
                 if self.input_idx < self.input.len() {
                     let next_c = self.input[self.input_idx];
                     self.input_idx += 1;
-                    self.input_cursor_position_x += compute_character_width(next_c);
+                    self.input_cursor_position_x += char_width(next_c);
                     self.completion.update("");
                 }
                 return Ok(EventState::Consumed);
             }
-            key if matches!(self.focus, Focus::Table) => return self.table.event(key),
+            key if matches!(self.focus, Focus::Table) => return self.result_table().event(key),
             _ => (),
         }
         Ok(EventState::NotConsumed)
@@ -259,28 +1542,623 @@ impl Component for SqlEditorComponent {
 
     async fn async_event(&mut self, key: Key, pool: &Box<dyn Pool>) -> Result<EventState> {
         if key == self.key_config.enter && matches!(self.focus, Focus::Editor) {
-            let query = self.input.iter().collect();
-            let result = pool.execute(&query).await?;
-            match result {
-                ExecuteResult::Read {
-                    headers,
-                    rows,
-                    database,
-                    table,
-                } => {
-                    let count = Some(rows.len());
-                    self.table
-                        .update(rows, count, headers, database, table, false);
-                    self.focus = Focus::Table;
-                    self.query_result = None;
-                }
-                ExecuteResult::Write { updated_rows } => {
-                    self.query_result = Some(QueryResult { updated_rows })
-                }
-            }
+            self.execute(self.query(), pool.as_ref()).await?;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.run_without_limit && self.truncated.is_some() {
+            self.execute_without_limit(pool.as_ref()).await?;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.suggest_index && matches!(self.focus, Focus::Editor) {
+            self.suggest_indexes(pool.as_ref()).await?;
             return Ok(EventState::Consumed);
         }
 
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        error_range, range_in_buffer, with_row_limit, Focus, Mode, Snippet, SqlEditorComponent,
+    };
+    use crate::components::Component;
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use zhobo::database::ErrorPosition;
+
+    fn editor(input: &str) -> SqlEditorComponent {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), true, true);
+        editor.event(Key::Char('i')).unwrap();
+        for c in input.chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+        editor.event(Key::Esc).unwrap();
+        editor
+    }
+
+    fn input(editor: &SqlEditorComponent) -> String {
+        editor.input.iter().collect()
+    }
+
+    #[test]
+    fn test_mode_switch() {
+        let mut editor = editor("select");
+        assert_eq!(editor.mode, Mode::Normal);
+        assert_eq!(editor.input_idx, 5);
+        assert_eq!(editor.input_cursor_position_x, 5);
+        assert!(editor.event(Key::Char('x')).unwrap().is_consumed());
+        assert_eq!(input(&editor), "select");
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let mut editor = editor("select id, name from users");
+        editor.event(Key::Char('0')).unwrap();
+        editor.event(Key::Char('w')).unwrap();
+        assert_eq!(editor.input_idx, 7);
+        editor.event(Key::Char('w')).unwrap();
+        assert_eq!(editor.input_idx, 9);
+        editor.event(Key::Char('w')).unwrap();
+        assert_eq!(editor.input_idx, 11);
+        editor.event(Key::Char('b')).unwrap();
+        assert_eq!(editor.input_idx, 9);
+        editor.event(Key::Char('$')).unwrap();
+        assert_eq!(editor.input_idx, 25);
+        editor.event(Key::Char('b')).unwrap();
+        assert_eq!(editor.input_idx, 21);
+    }
+
+    #[test]
+    fn test_line_operators() {
+        let mut editor = editor("select 1");
+        editor.event(Key::Char('y')).unwrap();
+        editor.event(Key::Char('y')).unwrap();
+        editor.event(Key::Char('p')).unwrap();
+        assert_eq!(input(&editor), "select 1select 1");
+        editor.event(Key::Char('d')).unwrap();
+        editor.event(Key::Char('d')).unwrap();
+        assert_eq!(input(&editor), "");
+        assert_eq!(editor.register, "select 1select 1");
+        assert_eq!(editor.input_cursor_position_x, 0);
+    }
+
+    #[test]
+    fn test_visual_mode() {
+        let mut editor = editor("select id from users");
+        editor.event(Key::Char('0')).unwrap();
+        editor.event(Key::Char('w')).unwrap();
+        editor.event(Key::Char('v')).unwrap();
+        editor.event(Key::Char('l')).unwrap();
+        assert_eq!(editor.mode, Mode::Visual);
+        assert_eq!(editor.selection(), (7, 9));
+        editor.event(Key::Char('d')).unwrap();
+        assert_eq!(editor.mode, Mode::Normal);
+        assert_eq!(input(&editor), "select  from users");
+        assert_eq!(editor.register, "id");
+        editor.event(Key::Char('$')).unwrap();
+        editor.event(Key::Char('p')).unwrap();
+        assert_eq!(input(&editor), "select  from usersid");
+    }
+
+    fn type_keys(editor: &mut SqlEditorComponent, input: &str) {
+        for c in input.chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_auto_close_brackets() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, true);
+        type_keys(&mut editor, "count(");
+        assert_eq!(input(&editor), "count()");
+        type_keys(&mut editor, "'a')");
+        assert_eq!(input(&editor), "count('a')");
+        assert_eq!(editor.input_idx, 10);
+        type_keys(&mut editor, " it's");
+        assert_eq!(input(&editor), "count('a') it's");
+
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, true);
+        type_keys(&mut editor, "[");
+        editor.event(Key::Backspace).unwrap();
+        assert_eq!(input(&editor), "");
+
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        type_keys(&mut editor, "('");
+        assert_eq!(input(&editor), "('");
+    }
+
+    #[test]
+    fn test_matching_bracket() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        type_keys(&mut editor, "f((a), b)");
+        assert_eq!(editor.matching_bracket(), Some((8, 1)));
+        editor.move_cursor_to(1);
+        assert_eq!(editor.matching_bracket(), Some((1, 8)));
+        editor.move_cursor_to(4);
+        assert_eq!(editor.matching_bracket(), Some((4, 2)));
+        editor.move_cursor_to(7);
+        assert_eq!(editor.matching_bracket(), None);
+    }
+
+    #[test]
+    fn test_new_line() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        type_keys(&mut editor, "  select");
+        editor.event(Key::Ctrl('j')).unwrap();
+        type_keys(&mut editor, "id");
+        assert_eq!(input(&editor), "  select\n  id");
+        assert_eq!(editor.cursor_position(20), (4, 1));
+        assert_eq!(editor.cursor_position(5), (4, 2));
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        type_keys(&mut editor, "select");
+        editor.event(Key::Ctrl('j')).unwrap();
+        type_keys(&mut editor, "from users");
+        assert_eq!(editor.line_and_column(), (2, 11));
+        editor.move_cursor_to(3);
+        assert_eq!(editor.line_and_column(), (1, 4));
+
+        let (rows, gutter) = editor.wrapped_lines(6);
+        assert_eq!(
+            rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            vec!["select", "from u", "sers"]
+        );
+        assert_eq!(
+            gutter.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            vec!["1 ", "2 ", ""]
+        );
+    }
+
+    #[test]
+    fn test_pin_result() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        editor.focus = Focus::Table;
+        editor.event(Key::Ctrl('p')).unwrap();
+        assert!(editor.pinned.is_empty());
+
+        editor.table.headers = vec!["a".to_string()];
+        editor.event(Key::Ctrl('p')).unwrap();
+        assert_eq!(editor.pinned.len(), 1);
+        assert_eq!(editor.pinned_index, Some(0));
+        assert!(editor.table.headers.is_empty());
+
+        editor.table.headers = vec!["b".to_string()];
+        editor.event(Key::Char(']')).unwrap();
+        assert_eq!(editor.pinned_index, None);
+        assert_eq!(editor.result_table().headers, vec!["b".to_string()]);
+        editor.event(Key::Ctrl('p')).unwrap();
+        assert_eq!(editor.pinned.len(), 2);
+        editor.event(Key::Char('[')).unwrap();
+        assert_eq!(editor.pinned_index, Some(0));
+        assert_eq!(editor.result_table().headers, vec!["a".to_string()]);
+        editor.event(Key::Char('[')).unwrap();
+        assert_eq!(editor.pinned_index, None);
+    }
+
+    #[test]
+    fn test_set_query() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        type_keys(&mut editor, "select 1");
+        editor.focus = Focus::Table;
+        editor.set_query("SELECT * FROM `users` LIMIT 0, 200");
+        assert!(matches!(editor.focus, Focus::Editor));
+        assert_eq!(editor.input_idx, 34);
+        assert_eq!(editor.input_cursor_position_x, 34);
+        editor.event(Key::Ctrl('z')).unwrap();
+        assert_eq!(input(&editor), "select 1");
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, true);
+        for c in "select".chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+        editor.event(Key::Left).unwrap();
+        editor.event(Key::Right).unwrap();
+        for c in " 1".chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+        editor.event(Key::Backspace).unwrap();
+        assert_eq!(input(&editor), "select ");
+
+        editor.event(Key::Ctrl('z')).unwrap();
+        assert_eq!(input(&editor), "select 1");
+        editor.event(Key::Ctrl('z')).unwrap();
+        assert_eq!(input(&editor), "select");
+        assert_eq!(editor.input_cursor_position_x, 6);
+        editor.event(Key::Ctrl('z')).unwrap();
+        assert_eq!(input(&editor), "");
+        editor.event(Key::Ctrl('y')).unwrap();
+        assert_eq!(input(&editor), "select");
+
+        editor.event(Key::Char('x')).unwrap();
+        editor.event(Key::Ctrl('y')).unwrap();
+        assert_eq!(input(&editor), "selectx");
+    }
+
+    #[test]
+    fn test_find_replace() {
+        let mut editor = editor("select id from t where id = 1");
+        editor.move_cursor_to(0);
+        editor.event(Key::Ctrl('f')).unwrap();
+        type_keys(&mut editor, "id");
+        editor.event(Key::Enter).unwrap();
+        assert_eq!(editor.input_idx, 7);
+        editor.event(Key::Enter).unwrap();
+        assert_eq!(editor.input_idx, 23);
+
+        editor.event(Key::Tab).unwrap();
+        type_keys(&mut editor, "user_id");
+        editor.event(Key::Enter).unwrap();
+        assert_eq!(input(&editor), "select id from t where user_id = 1");
+        assert_eq!(editor.input_idx, 7);
+
+        editor.event(Key::Tab).unwrap();
+        editor.event(Key::Ctrl('r')).unwrap();
+        type_keys(&mut editor, "|\\d+");
+        editor.event(Key::Alt('a')).unwrap();
+        assert_eq!(
+            input(&editor),
+            "select user_id from t where user_user_id = user_id"
+        );
+        editor.event(Key::Esc).unwrap();
+        assert!(!editor.find_replace_visible());
+        editor.event(Key::Char('u')).unwrap();
+        assert_eq!(input(&editor), "select id from t where user_id = 1");
+    }
+
+    #[test]
+    fn test_snippet() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), false, false);
+        editor.set_snippets(vec![Snippet {
+            name: "cnt".to_string(),
+            body: "SELECT COUNT(*) FROM ${1:table} WHERE ${2:condition};$0".to_string(),
+        }]);
+        type_keys(&mut editor, "x cnt");
+        editor.event(Key::Tab).unwrap();
+        assert_eq!(
+            input(&editor),
+            "x SELECT COUNT(*) FROM table WHERE condition;"
+        );
+        assert_eq!(editor.input_idx, 23);
+
+        type_keys(&mut editor, "users");
+        editor.event(Key::Backspace).unwrap();
+        type_keys(&mut editor, "s");
+        editor.event(Key::Tab).unwrap();
+        assert_eq!(editor.input_idx, 35);
+        editor.event(Key::Backspace).unwrap();
+        assert_eq!(input(&editor), "x SELECT COUNT(*) FROM users WHERE ;");
+        type_keys(&mut editor, "id = 1");
+        editor.event(Key::Tab).unwrap();
+        assert_eq!(input(&editor), "x SELECT COUNT(*) FROM users WHERE id = 1;");
+        assert_eq!(editor.input_idx, 42);
+
+        editor.event(Key::Tab).unwrap();
+        assert!(editor.snippet_session.is_none());
+        type_keys(&mut editor, " sel");
+        editor.event(Key::Tab).unwrap();
+        assert!(input(&editor).ends_with("; SELECT * FROM table WHERE condition"));
+    }
+
+    #[test]
+    fn test_vim_line_motions() {
+        let mut editor = editor("select id,\nname\nfrom users");
+        assert_eq!(editor.input_idx, 25);
+        editor.event(Key::Char('k')).unwrap();
+        assert_eq!(editor.input_idx, 14);
+        editor.event(Key::Up).unwrap();
+        assert_eq!(editor.input_idx, 3);
+        editor.event(Key::Char('k')).unwrap();
+        assert_eq!(editor.input_idx, 3);
+        editor.event(Key::Char('0')).unwrap();
+        editor.event(Key::Char('j')).unwrap();
+        assert_eq!(editor.input_idx, 11);
+        editor.event(Key::Down).unwrap();
+        assert_eq!(editor.input_idx, 16);
+        editor.event(Key::Char('j')).unwrap();
+        assert_eq!(editor.input_idx, 16);
+    }
+
+    #[test]
+    fn test_vim_consumes_keys() {
+        let mut editor = editor("select 1");
+        for key in [Key::Char('q'), Key::Char('1'), Key::Char('z')] {
+            assert!(editor.event(key).unwrap().is_consumed());
+        }
+        assert_eq!(input(&editor), "select 1");
+        assert!(!editor.event(Key::Ctrl('c')).unwrap().is_consumed());
+
+        editor.event(Key::Char('v')).unwrap();
+        assert!(editor.event(Key::Char('q')).unwrap().is_consumed());
+        assert_eq!(editor.mode, Mode::Visual);
+        editor.event(Key::Esc).unwrap();
+        assert_eq!(editor.mode, Mode::Normal);
+        editor.event(Key::Esc).unwrap();
+        assert!(matches!(editor.focus, Focus::Table));
+    }
+
+    #[test]
+    fn test_vim_undo() {
+        let mut editor = editor("select 1");
+        editor.event(Key::Char('d')).unwrap();
+        editor.event(Key::Char('d')).unwrap();
+        assert_eq!(input(&editor), "");
+        editor.event(Key::Char('u')).unwrap();
+        assert_eq!(input(&editor), "select 1");
+        assert_eq!(editor.input_idx, 7);
+        editor.event(Key::Ctrl('y')).unwrap();
+        assert_eq!(input(&editor), "");
+    }
+
+    #[tokio::test]
+    async fn test_suggest_indexes() {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use zhobo::database::{Pool, SqlitePool, MAX_CONNECTIONS};
+
+        let database =
+            std::env::temp_dir().join(format!("zhobo_explain_{}.db", std::process::id()));
+        let pool: Box<dyn Pool> = Box::new(
+            SqlitePool::new(
+                SqliteConnectOptions::new()
+                    .filename(&database)
+                    .create_if_missing(true),
+                200,
+                5,
+                MAX_CONNECTIONS,
+            )
+            .await
+            .unwrap(),
+        );
+        pool.execute(&"CREATE TABLE users (id INTEGER, email TEXT)".to_string())
+            .await
+            .unwrap();
+        let mut editor = editor("SELECT * FROM users WHERE email = 'a';");
+        editor.async_event(Key::Alt('i'), &pool).await.unwrap();
+        assert_eq!(
+            input(&editor),
+            "SELECT * FROM users WHERE email = 'a';\n\n-- Full table scan on users\nCREATE INDEX `idx_users_email` ON users (`email`);"
+        );
+        assert!(editor.take_notice().is_some());
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+    }
+
+    #[test]
+    fn test_with_row_limit() {
+        assert_eq!(
+            with_row_limit("select * from users -- all;", 10),
+            Some("select * from users -- all\nLIMIT 10".to_string())
+        );
+        assert_eq!(
+            with_row_limit("SELECT * FROM users WHERE name = 'limit';", 10),
+            Some("SELECT * FROM users WHERE name = 'limit'\nLIMIT 10".to_string())
+        );
+        assert_eq!(with_row_limit("SELECT * FROM users LIMIT 5", 10), None);
+        assert_eq!(with_row_limit("SELECT * FROM users FOR UPDATE", 10), None);
+        assert_eq!(
+            with_row_limit("SELECT * FROM users LOCK IN SHARE MODE", 10),
+            None
+        );
+        assert_eq!(
+            with_row_limit("SELECT * FROM users FETCH FIRST 5 ROWS ONLY", 10),
+            None
+        );
+        assert_eq!(
+            with_row_limit(
+                "SELECT * FROM users OFFSET 5 ROWS FETCH NEXT 5 ROWS ONLY",
+                10
+            ),
+            None
+        );
+        assert_eq!(with_row_limit("SELECT * INTO copy FROM users", 10), None);
+        assert_eq!(with_row_limit("DELETE FROM users", 10), None);
+        assert_eq!(
+            with_row_limit(
+                "WITH d AS (DELETE FROM users RETURNING *) DELETE FROM d",
+                10
+            ),
+            None
+        );
+        // A limit which is not of the statement itself still caps it.
+        assert_eq!(
+            with_row_limit("SELECT * FROM (SELECT * FROM users LIMIT 5) AS u", 10),
+            Some("SELECT * FROM (SELECT * FROM users LIMIT 5) AS u\nLIMIT 10".to_string())
+        );
+        assert_eq!(
+            with_row_limit("SELECT * FROM users /* LIMIT 5 */", 10),
+            Some("SELECT * FROM users /* LIMIT 5 */\nLIMIT 10".to_string())
+        );
+        assert_eq!(
+            with_row_limit("-- all users\nSELECT * FROM users", 10),
+            Some("-- all users\nSELECT * FROM users\nLIMIT 10".to_string())
+        );
+        assert_eq!(
+            with_row_limit("WITH u AS (SELECT * FROM users) SELECT * FROM u", 10),
+            Some("WITH u AS (SELECT * FROM users) SELECT * FROM u\nLIMIT 10".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_row_limit() {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use zhobo::database::{Pool, SqlitePool, MAX_CONNECTIONS};
+
+        let database =
+            std::env::temp_dir().join(format!("zhobo_row_limit_{}.db", std::process::id()));
+        let pool: Box<dyn Pool> = Box::new(
+            SqlitePool::new(
+                SqliteConnectOptions::new()
+                    .filename(&database)
+                    .create_if_missing(true),
+                200,
+                5,
+                MAX_CONNECTIONS,
+            )
+            .await
+            .unwrap(),
+        );
+        pool.execute(&"CREATE TABLE users (id INTEGER)".to_string())
+            .await
+            .unwrap();
+        pool.execute(&"INSERT INTO users VALUES (1), (2), (3)".to_string())
+            .await
+            .unwrap();
+        let mut editor = editor("SELECT * FROM users");
+        editor.set_row_limit(Some(2));
+        editor.execute(editor.query(), pool.as_ref()).await.unwrap();
+        assert_eq!(editor.table.rows.len(), 2);
+        assert!(editor.truncated.is_some());
+
+        editor.async_event(Key::Alt('r'), &pool).await.unwrap();
+        assert_eq!(editor.table.rows.len(), 3);
+        assert!(editor.truncated.is_none());
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scratch_change_not_recorded() {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use zhobo::database::{Pool, SqlitePool, MAX_CONNECTIONS};
+
+        let database =
+            std::env::temp_dir().join(format!("zhobo_scratch_undo_{}.db", std::process::id()));
+        let pool: Box<dyn Pool> = Box::new(
+            SqlitePool::new(
+                SqliteConnectOptions::new()
+                    .filename(&database)
+                    .create_if_missing(true),
+                200,
+                5,
+                MAX_CONNECTIONS,
+            )
+            .await
+            .unwrap(),
+        );
+        pool.execute(&"CREATE TABLE users (id INTEGER PRIMARY KEY)".to_string())
+            .await
+            .unwrap();
+        pool.execute(&"INSERT INTO users VALUES (1), (2), (3)".to_string())
+            .await
+            .unwrap();
+        let mut editor = editor("DELETE FROM users WHERE id = 1");
+        editor.set_scratch(true);
+        editor.execute(editor.query(), pool.as_ref()).await.unwrap();
+        assert_eq!(editor.take_change(), None);
+
+        editor.set_scratch(false);
+        editor.set_query("DELETE FROM users WHERE id = 2");
+        editor.execute(editor.query(), pool.as_ref()).await.unwrap();
+        assert!(editor.take_change().is_some());
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_change_not_recorded() {
+        use sqlx::sqlite::SqliteConnectOptions;
+        use zhobo::database::{Pool, SqlitePool};
+
+        let database =
+            std::env::temp_dir().join(format!("zhobo_session_undo_{}.db", std::process::id()));
+        // One connection, like a dedicated editor connection, which keeps the transaction.
+        let pool: Box<dyn Pool> = Box::new(
+            SqlitePool::new(
+                SqliteConnectOptions::new()
+                    .filename(&database)
+                    .create_if_missing(true),
+                200,
+                5,
+                1,
+            )
+            .await
+            .unwrap(),
+        );
+        pool.execute(&"CREATE TABLE users (id INTEGER PRIMARY KEY)".to_string())
+            .await
+            .unwrap();
+        pool.execute(&"INSERT INTO users VALUES (1), (2), (3)".to_string())
+            .await
+            .unwrap();
+        let mut editor = editor("BEGIN");
+        for (query, recorded) in [
+            ("BEGIN", false),
+            ("DELETE FROM users WHERE id = 1", false),
+            ("ROLLBACK", false),
+            ("DELETE FROM users WHERE id = 2", true),
+            ("ATTACH DATABASE ':memory:' AS other", false),
+            ("DELETE FROM users WHERE id = 3", false),
+        ] {
+            editor.set_query(query);
+            editor.execute(editor.query(), pool.as_ref()).await.unwrap();
+            assert_eq!(editor.take_change().is_some(), recorded, "{}", query);
+        }
+
+        editor.set_connection(true);
+        editor.set_query("DELETE FROM users");
+        editor.execute(editor.query(), pool.as_ref()).await.unwrap();
+        assert!(editor.take_change().is_some());
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+    }
+
+    #[test]
+    fn test_error_range() {
+        assert_eq!(
+            error_range("SELECT * FORM users", &ErrorPosition::Offset(9)),
+            Some((9, 13))
+        );
+        assert_eq!(
+            error_range(
+                "SELECT 1;\nSELECT * FORM users",
+                &ErrorPosition::Near {
+                    text: "FORM users".to_string(),
+                    line: Some(2)
+                }
+            ),
+            Some((19, 23))
+        );
+        assert_eq!(
+            error_range(
+                "SELECT (1",
+                &ErrorPosition::Near {
+                    text: String::new(),
+                    line: Some(1)
+                }
+            ),
+            Some((8, 9))
+        );
+        assert_eq!(error_range("SELECT 1", &ErrorPosition::Offset(20)), None);
+
+        let buffer = "  SELECT * FORM users".chars().collect::<Vec<char>>();
+        assert_eq!(
+            range_in_buffer(&buffer, "SELECT * FORM users\nLIMIT 101", (9, 13)),
+            Some((11, 15))
+        );
+        assert_eq!(
+            range_in_buffer(&buffer, "SELECT * FORM users WHERE id = 42", (29, 31)),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_syntax_error_position() {
+        use zhobo::database::{Pool, SqlitePool};
+
+        let pool: Box<dyn Pool> = Box::new(SqlitePool::in_memory(100).await.unwrap());
+        let mut editor = editor("SELECT 1 FORM users");
+        editor.move_cursor_to(0);
+        assert!(editor.execute(editor.query(), pool.as_ref()).await.is_err());
+        assert_eq!(editor.error_range, Some((14, 19)));
+        assert_eq!(editor.input_idx, 14);
+
+        editor.event(Key::Char('i')).unwrap();
+        editor.event(Key::Char('x')).unwrap();
+        assert_eq!(editor.error_range, None);
+    }
+}
@@ -4,10 +4,10 @@ use crate::config::KeyConfig;
 use crate::event::Key;
 use anyhow::Result;
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::Line,
-    widgets::{Block, Borders, Tabs},
+    widgets::{block::Title, Block, Borders, Tabs},
     Frame,
 };
 use strum_macros::EnumIter;
@@ -17,6 +17,7 @@ pub enum Tab {
     Records,
     Properties,
     Sql,
+    Server,
 }
 
 impl std::fmt::Display for Tab {
@@ -27,6 +28,8 @@ impl std::fmt::Display for Tab {
 
 pub struct TabComponent {
     pub selected_tab: Tab,
+    /// The name and the accent color of the connection, drawn on the border.
+    accent: Option<(String, Color)>,
     key_config: KeyConfig,
 }
 
@@ -34,19 +37,29 @@ impl TabComponent {
     pub fn new(key_config: KeyConfig) -> Self {
         Self {
             selected_tab: Tab::Records,
+            accent: None,
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
     pub fn reset(&mut self) {
         self.selected_tab = Tab::Records;
     }
 
+    pub fn set_accent(&mut self, accent: Option<(String, Color)>) {
+        self.accent = accent;
+    }
+
     fn names(&self) -> Vec<String> {
         vec![
             command::tab_records(&self.key_config).name,
             command::tab_properties(&self.key_config).name,
             command::tab_sql_editor(&self.key_config).name,
+            command::tab_server(&self.key_config).name,
         ]
     }
 }
@@ -54,8 +67,18 @@ impl TabComponent {
 impl DrawableComponent for TabComponent {
     fn draw(&self, f: &mut Frame, area: Rect, _focused: bool) -> Result<()> {
         let titles: Vec<_> = self.names().iter().cloned().map(Line::from).collect();
+        let mut block = Block::default().borders(Borders::ALL);
+        if let Some((name, color)) = &self.accent {
+            block = block.border_style(Style::default().fg(*color)).title(
+                Title::from(Line::styled(
+                    format!(" {} ", name),
+                    Style::default().fg(*color).add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
         let tabs = Tabs::new(titles)
-            .block(Block::default().borders(Borders::ALL))
+            .block(block)
             .select(self.selected_tab as usize)
             .style(Style::default().fg(Color::DarkGray))
             .highlight_style(
@@ -81,6 +104,9 @@ impl Component for TabComponent {
         } else if key == self.key_config.tab_properties {
             self.selected_tab = Tab::Properties;
             return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_server {
+            self.selected_tab = Tab::Server;
+            return Ok(EventState::Consumed);
         }
         Ok(EventState::NotConsumed)
     }
@@ -1,22 +1,30 @@
 use super::PropertyTrait;
 use super::{
     utils::scroll_vertical::VerticalScroll, Component, DrawableComponent, EventState,
-    StatefulDrawableComponent, TableStatusComponent, TableValueComponent,
+    StatefulDrawableComponent, TableStatusComponent,
 };
 use crate::components::command::{self, CommandInfo};
 use crate::config::KeyConfig;
 use crate::event::Key;
-use crate::tree::{Database, Table as DTable};
 use anyhow::Result;
 use ratatui::layout::Flex;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    text::{Line, Text},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
+use std::borrow::Cow;
 use std::convert::From;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
+use zhobo::database::geometry_summary;
+use zhobo::tree::{Database, Table as DTable};
+
+/// What a value is drawn as in privacy mode, the same for every value so its length is hidden too.
+pub const MASK: &str = "•••••";
 
 #[derive(Debug, PartialEq)]
 struct Order {
@@ -46,17 +54,14 @@ impl Order {
     }
 }
 
-#[derive(PartialEq)]
-struct OrderManager {
+/// The columns the records are sorted by, in the order they were picked.
+#[derive(PartialEq, Default)]
+pub struct OrderManager {
     orders: Vec<Order>,
 }
 
 impl OrderManager {
-    fn new() -> Self {
-        Self { orders: vec![] }
-    }
-
-    fn generate_order_query(&mut self) -> Option<String> {
+    pub fn generate_order_query(&self) -> Option<String> {
         let order_query = self
             .orders
             .iter()
@@ -70,7 +75,7 @@ impl OrderManager {
         None
     }
 
-    fn generate_header_icons(&mut self, header_length: usize) -> Vec<String> {
+    pub fn generate_header_icons(&self, header_length: usize) -> Vec<String> {
         let mut header_icons = vec![String::new(); header_length];
         for (index, order) in self.orders.iter().enumerate() {
             let arrow = if order.is_asc { "↑" } else { "↓" };
@@ -83,7 +88,7 @@ impl OrderManager {
         header_icons
     }
 
-    fn add_order(&mut self, selected_column: usize) {
+    pub fn add_order(&mut self, selected_column: usize) {
         let selected_column_number = selected_column + 1;
         if let Some(position) = self
             .orders
@@ -102,18 +107,46 @@ impl OrderManager {
     }
 }
 
+/// Cursor and scroll position of a table, kept to restore it when the table is opened again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TablePosition {
+    row: usize,
+    column: usize,
+    offset: usize,
+}
+
 pub struct TableComponent {
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub total_row_count: Option<usize>,
     pub eod: bool,
     pub selected_row: TableState,
-    orders: OrderManager,
     table: Option<(Database, DTable)>,
     selected_column: usize,
     selection_area_corner: Option<(usize, usize)>,
     column_page_start: std::cell::Cell<usize>,
+    /// The end of the columns drawn last time, or `None` before the table is drawn.
+    column_page_end: std::cell::Cell<Option<usize>>,
     scroll: VerticalScroll,
+    header_focused: bool,
+    row_numbers: bool,
+    /// Whether each column is numeric according to the column types. Without them, columns
+    /// whose values all look like numbers are numeric.
+    numeric_columns: Option<Vec<bool>>,
+    /// Whether each column has a binary type, whose values are copied as binary literals.
+    binary_columns: Vec<bool>,
+    /// Whether each column is spatial, whose WKT values are shown as a summary in the cells.
+    geometry_columns: Vec<bool>,
+    /// The number of characters of a value shown in a cell and the value line. The rest is
+    /// replaced with a marker, and the full value is opened in the pager.
+    max_cell_length: usize,
+    /// Whether the values are drawn as a mask, leaving the headers, for privacy while sharing
+    /// the screen.
+    masked: bool,
+    /// The dimmed line drawn below the first column of each row, such as a column comment.
+    notes: Vec<String>,
+    /// The lines drawn in place of the rows when there are none, such as what to press next.
+    empty_hint: Vec<String>,
     key_config: KeyConfig,
 }
 
@@ -124,21 +157,109 @@ impl TableComponent {
             headers: vec![],
             rows: vec![],
             total_row_count: None,
-            orders: OrderManager::new(),
             table: None,
             selected_column: 0,
             selection_area_corner: None,
             column_page_start: std::cell::Cell::new(0),
+            column_page_end: std::cell::Cell::new(None),
             scroll: VerticalScroll::new(false, false),
             eod: false,
+            header_focused: false,
+            row_numbers: true,
+            numeric_columns: None,
+            binary_columns: vec![],
+            geometry_columns: vec![],
+            max_cell_length: usize::MAX,
+            masked: false,
+            notes: vec![],
+            empty_hint: vec![],
             key_config,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    /// Shows or hides the gutter with the number of each fetched row.
+    pub fn set_row_numbers(&mut self, row_numbers: bool) {
+        self.row_numbers = row_numbers;
+    }
+
+    /// Sets which columns are numeric from the column types, which are right-aligned.
+    pub fn set_numeric_columns(&mut self, numeric_columns: Vec<bool>) {
+        self.numeric_columns = Some(numeric_columns);
+    }
+
+    /// Sets which columns have a binary type from the column types.
+    pub fn set_binary_columns(&mut self, binary_columns: Vec<bool>) {
+        self.binary_columns = binary_columns;
+    }
+
+    /// Sets which columns are spatial from the column types.
+    pub fn set_geometry_columns(&mut self, geometry_columns: Vec<bool>) {
+        self.geometry_columns = geometry_columns;
+    }
+
+    pub fn set_max_cell_length(&mut self, max_cell_length: usize) {
+        self.max_cell_length = max_cell_length;
+    }
+
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+
+    /// The value as it is shown in a cell of the column. The value line shows it in full, up to
+    /// the maximum length.
+    fn cell_value<'a>(&self, column_index: usize, value: &'a str) -> Cow<'a, str> {
+        if self.masked {
+            Cow::Borrowed(MASK)
+        } else if self
+            .geometry_columns
+            .get(column_index)
+            .copied()
+            .unwrap_or(false)
+        {
+            Cow::Owned(shorten(&geometry_summary(value), self.max_cell_length).into_owned())
+        } else {
+            shorten(value, self.max_cell_length)
+        }
+    }
+
+    /// Sets the lines drawn in place of the rows when there are none.
+    pub fn set_empty_hint(&mut self, hint: Vec<String>) {
+        self.empty_hint = hint;
+    }
+
+    /// Sets the dimmed line drawn below the first column of each row. Empty notes are not drawn.
+    pub fn set_notes(&mut self, notes: Vec<String>) {
+        self.notes = notes;
+    }
+
+    pub fn is_numeric_column(&self, column_index: usize) -> bool {
+        match &self.numeric_columns {
+            Some(numeric_columns) => numeric_columns.get(column_index).copied().unwrap_or(false),
+            None => {
+                let mut values = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column_index))
+                    .filter(|value| !value.is_empty() && value.as_str() != "NULL")
+                    .peekable();
+                values.peek().is_some() && values.all(|value| is_number(value))
+            }
+        }
+    }
+
     fn title(&self) -> String {
-        self.table.as_ref().map_or(" - ".to_string(), |table| {
+        let title = self.table.as_ref().map_or(" - ".to_string(), |table| {
             format!("{}.{}", table.0.name, table.1.name)
-        })
+        });
+        if self.masked {
+            format!("{} [privacy]", title)
+        } else {
+            title
+        }
     }
 
     pub fn update(
@@ -164,38 +285,71 @@ impl TableComponent {
         };
         self.selection_area_corner = None;
         self.column_page_start = std::cell::Cell::new(0);
+        self.column_page_end = std::cell::Cell::new(None);
         self.scroll = VerticalScroll::new(false, false);
         self.eod = false;
+        self.numeric_columns = None;
+        self.binary_columns = vec![];
+        self.geometry_columns = vec![];
+        self.notes = vec![];
         self.table = Some((database, table));
     }
 
+    /// The database and the table the rows were read from.
+    pub fn source(&self) -> Option<&(Database, DTable)> {
+        self.table.as_ref()
+    }
+
     pub fn reset(&mut self) {
         self.selected_row.select(None);
         self.headers = Vec::new();
         self.rows = Vec::new();
-        self.orders = OrderManager::new();
         self.selected_column = 0;
         self.selection_area_corner = None;
         self.column_page_start = std::cell::Cell::new(0);
+        self.column_page_end = std::cell::Cell::new(None);
         self.scroll = VerticalScroll::new(false, false);
         self.eod = false;
+        self.numeric_columns = None;
+        self.binary_columns = vec![];
+        self.geometry_columns = vec![];
+        self.notes = vec![];
         self.table = None;
     }
 
-    fn reset_selection(&mut self) {
-        self.selection_area_corner = None;
+    pub fn position(&self) -> Option<TablePosition> {
+        Some(TablePosition {
+            row: self.selected_row.selected()?,
+            column: self.selected_column,
+            offset: self.selected_row.offset(),
+        })
     }
 
-    pub fn add_order(&mut self) {
-        self.orders.add_order(self.selected_column)
+    /// Moves the cursor to `position`, as far as the fetched rows and headers allow.
+    pub fn restore_position(&mut self, position: TablePosition) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let row = position.row.min(self.rows.len() - 1);
+        self.selected_row.select(Some(row));
+        *self.selected_row.offset_mut() = position.offset.min(row);
+        self.selected_column = position.column.min(self.headers.len().saturating_sub(1));
     }
 
-    pub fn generate_order_query(&mut self) -> Option<String> {
-        self.orders.generate_order_query()
+    fn reset_selection(&mut self) {
+        self.selection_area_corner = None;
     }
 
-    pub fn generate_header_icons(&mut self, header_length: usize) -> Vec<String> {
-        self.orders.generate_header_icons(header_length)
+    pub fn header_focused(&self) -> bool {
+        self.header_focused
+    }
+
+    /// In header focus, left and right pick the column to sort by.
+    pub fn set_header_focus(&mut self, focused: bool) {
+        self.header_focused = focused;
+        if focused {
+            self.reset_selection();
+        }
     }
 
     pub fn end(&mut self) {
@@ -352,7 +506,99 @@ impl TableComponent {
             .map(|cell| cell.to_string())
     }
 
-    fn selected_column_index(&self) -> usize {
+    /// The column, the value and whether the column is binary of the selected cell.
+    pub fn selected_cell(&self) -> Option<(&str, &str, bool)> {
+        let value = self
+            .rows
+            .get(self.selected_row.selected()?)?
+            .get(self.selected_column)?;
+        Some((
+            self.headers.get(self.selected_column)?,
+            value,
+            self.binary_columns
+                .get(self.selected_column)
+                .copied()
+                .unwrap_or(false),
+        ))
+    }
+
+    /// The selected cells as SQL literals made by `literal` from each value and whether its
+    /// column is binary, separated by commas and lines like `content`.
+    pub fn content_as_literals(&self, literal: impl Fn(&str, bool) -> String) -> Option<String> {
+        let (columns, rows) = self.selected_area()?;
+        Some(
+            self.rows
+                .get(rows)?
+                .iter()
+                .map(|row| {
+                    columns
+                        .clone()
+                        .filter_map(|i| {
+                            let binary = self.binary_columns.get(i).copied().unwrap_or(false);
+                            row.get(i).map(|value| literal(value, binary))
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    /// The columns drawn last time, or all of them before the table is drawn.
+    pub fn visible_columns(&self) -> Range<usize> {
+        let end = self
+            .column_page_end
+            .get()
+            .unwrap_or(self.headers.len())
+            .min(self.headers.len());
+        self.column_page_start.get().min(end)..end
+    }
+
+    /// The columns and the rows of the selection area, or of the selected cell without one.
+    pub fn selected_area(&self) -> Option<(Range<usize>, Range<usize>)> {
+        let selected_row_index = self.selected_row.selected()?;
+        let (x, y) = self
+            .selection_area_corner
+            .unwrap_or((self.selected_column, selected_row_index));
+        Some((
+            x.min(self.selected_column)..x.max(self.selected_column) + 1,
+            y.min(selected_row_index)..y.max(selected_row_index) + 1,
+        ))
+    }
+
+    /// The headers and the rows cut to `columns` and `rows`.
+    pub fn slice(
+        &self,
+        columns: Range<usize>,
+        rows: Range<usize>,
+    ) -> (Vec<String>, Vec<Vec<String>>) {
+        let columns = columns.start.min(self.headers.len())..columns.end.min(self.headers.len());
+        let rows = rows.start.min(self.rows.len())..rows.end.min(self.rows.len());
+        (
+            self.headers[columns.clone()].to_vec(),
+            self.rows[rows]
+                .iter()
+                .map(|row| row[columns.start.min(row.len())..columns.end.min(row.len())].to_vec())
+                .collect(),
+        )
+    }
+
+    /// The headers and all rows as CSV.
+    pub fn csv(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(self.rows.iter())
+            .map(|row| {
+                row.iter()
+                    .map(|value| csv_value(value))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn selected_column_index(&self) -> usize {
         if let Some((x, _)) = self.selection_area_corner {
             return x;
         }
@@ -398,19 +644,44 @@ impl TableComponent {
     }
 
     fn rows(&self, left: usize, right: usize) -> Vec<Vec<String>> {
-        let rows = self
+        let mut new_rows: Vec<Vec<String>> = self
             .rows
             .iter()
-            .map(|row| row.to_vec())
-            .collect::<Vec<Vec<String>>>();
-        let mut new_rows: Vec<Vec<String>> =
-            rows.iter().map(|row| row[left..right].to_vec()).collect();
+            .map(|row| {
+                row[left..right]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| self.cell_value(left + i, value).into_owned())
+                    .collect()
+            })
+            .collect();
         for (index, row) in new_rows.iter_mut().enumerate() {
             row.insert(0, (index + 1).to_string())
         }
         new_rows
     }
 
+    /// The width of the widest cell or header of the column, between 3 and 20.
+    fn column_width(&self, column_index: usize) -> usize {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.get(column_index).map_or(0, |cell| {
+                    display_width(&self.cell_value(column_index, cell))
+                })
+            })
+            .max()
+            .map_or(3, |width| {
+                width
+                    .max(
+                        self.headers
+                            .get(column_index)
+                            .map_or(3, |header| display_width(header)),
+                    )
+                    .clamp(3, 20)
+            })
+    }
+
     fn calculate_cell_widths(
         &self,
         area_width: u16,
@@ -424,29 +695,14 @@ impl TableComponent {
 
         let far_right_column_index = self.selected_column_index();
         let mut column_index = self.selected_column_index();
-        let number_column_width = (self.rows.len() + 1).to_string().width() as u16;
+        let number_column_width = if self.row_numbers {
+            (self.rows.len() + 1).to_string().width() as u16
+        } else {
+            0
+        };
         let mut widths = Vec::new();
         loop {
-            let length = self
-                .rows
-                .iter()
-                .map(|row| {
-                    row.get(column_index)
-                        .map_or(String::new(), |cell| cell.to_string())
-                        .width()
-                })
-                .collect::<Vec<usize>>()
-                .iter()
-                .max()
-                .map_or(3, |v| {
-                    *v.max(
-                        &self
-                            .headers
-                            .get(column_index)
-                            .map_or(3, |header| header.to_string().width()),
-                    )
-                    .clamp(&3, &20)
-                });
+            let length = self.column_width(column_index);
             if widths.iter().map(|(_, width)| width).sum::<usize>() + length + widths.len() + 1
                 >= area_width.saturating_sub(number_column_width) as usize
             {
@@ -467,28 +723,7 @@ impl TableComponent {
         while widths.iter().map(|(_, width)| width).sum::<usize>() + widths.len()
             < area_width.saturating_sub(number_column_width) as usize
         {
-            let length = self
-                .rows
-                .iter()
-                .map(|row| {
-                    row.get(column_index)
-                        .map_or(String::new(), |cell| cell.to_string())
-                        .width()
-                })
-                .collect::<Vec<usize>>()
-                .iter()
-                .max()
-                .map_or(3, |v| {
-                    *v.max(
-                        self.headers
-                            .iter()
-                            .map(|header| header.to_string().width())
-                            .collect::<Vec<usize>>()
-                            .get(column_index)
-                            .unwrap_or(&3),
-                    )
-                    .clamp(&3, &20)
-                });
+            let length = self.column_width(column_index);
             match self.headers.get(column_index) {
                 Some(header) => {
                     widths.push((header.to_string(), length));
@@ -514,6 +749,8 @@ impl TableComponent {
         }
         constraints.insert(0, Constraint::Length(number_column_width));
         self.column_page_start.set(far_left_column_index);
+        self.column_page_end
+            .set(Some(far_right_column_index.min(self.headers.len())));
 
         (
             self.selection_area_corner
@@ -533,6 +770,82 @@ impl TableComponent {
     }
 }
 
+/// Replaces characters which move the cursor of the terminal, such as tabs, so they can't shift
+/// the following columns.
+fn sanitize(content: &str) -> String {
+    content
+        .chars()
+        .map(|c| match c {
+            '\n' => c,
+            '\t' => ' ',
+            c if c.is_control() => '\u{fffd}',
+            c => c,
+        })
+        .collect()
+}
+
+/// `value` cut to `max` characters, followed by `…(+N chars)` with the number of characters left
+/// out.
+fn shorten(value: &str, max: usize) -> Cow<'_, str> {
+    if value.len() <= max {
+        return Cow::Borrowed(value);
+    }
+    match value.char_indices().nth(max) {
+        Some((end, _)) => Cow::Owned(format!(
+            "{}…(+{} chars)",
+            &value[..end],
+            value[end..].chars().count()
+        )),
+        None => Cow::Borrowed(value),
+    }
+}
+
+/// The width of the widest line of `content`, counted by grapheme clusters the same way they are
+/// rendered.
+fn display_width(content: &str) -> usize {
+    sanitize(content)
+        .lines()
+        .map(|line| line.graphemes(true).map(|g| g.width()).sum::<usize>())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Fits each line of `content` into `width`, ending a cut line with `…`. A wide character is
+/// never split, so it can't overflow into the next column.
+fn truncate(content: &str, width: usize) -> String {
+    sanitize(content)
+        .split('\n')
+        .map(|line| {
+            if line.width() <= width {
+                return line.to_string();
+            }
+            if width == 0 {
+                return String::new();
+            }
+            let mut truncated = String::new();
+            let mut truncated_width = 0;
+            for grapheme in line.graphemes(true) {
+                if truncated_width + grapheme.width() + 1 > width {
+                    break;
+                }
+                truncated_width += grapheme.width();
+                truncated.push_str(grapheme);
+            }
+            truncated.push('…');
+            truncated
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn is_number(value: &str) -> bool {
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    (!integer.is_empty() || !fraction.is_empty())
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
+
 impl PropertyTrait for TableComponent {
     fn draw(&mut self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
         StatefulDrawableComponent::draw(self, f, area, focused)
@@ -592,23 +905,61 @@ impl StatefulDrawableComponent for TableComponent {
         let block = Block::default().borders(Borders::NONE);
         let (selected_column_index, headers, rows, constraints) =
             self.calculate_cell_widths(block.inner(chunks[0]).width);
+        // The first column is the gutter, which is followed by the columns from
+        // `column_page_start`.
+        let numeric = (0..headers.len())
+            .map(|column_index| {
+                column_index > 0
+                    && self.is_numeric_column(self.column_page_start.get() + column_index - 1)
+            })
+            .collect::<Vec<bool>>();
+        let aligned = |content: &str, column_index: usize| {
+            let text = Text::from(match constraints.get(column_index) {
+                Some(Constraint::Length(width)) => truncate(content, *width as usize),
+                _ => sanitize(content),
+            });
+            if numeric[column_index] {
+                text.alignment(Alignment::Right)
+            } else {
+                text
+            }
+        };
         let header_cells = headers.iter().enumerate().map(|(column_index, h)| {
-            Cell::from(h.to_string()).style(if selected_column_index == column_index {
-                Style::default().add_modifier(Modifier::BOLD)
+            Cell::from(aligned(h, column_index)).style(if selected_column_index == column_index {
+                if self.header_focused {
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default().add_modifier(Modifier::BOLD)
+                }
             } else {
                 Style::default()
             })
         });
         let header = Row::new(header_cells).height(1).bottom_margin(1);
         let rows = rows.iter().enumerate().map(|(row_index, item)| {
+            let note = self.notes.get(row_index).filter(|note| !note.is_empty());
             let height = item
                 .iter()
                 .map(|content| content.chars().filter(|c| *c == '\n').count())
                 .max()
                 .unwrap_or(0)
-                + 1;
+                + 1
+                + usize::from(note.is_some());
             let cells = item.iter().enumerate().map(|(column_index, c)| {
-                Cell::from(c.to_string()).style(
+                let mut text = aligned(c, column_index);
+                if let (1, Some(note)) = (column_index, note) {
+                    text.lines.push(Line::styled(
+                        aligned(note, column_index)
+                            .lines
+                            .first()
+                            .map(|line| line.to_string())
+                            .unwrap_or_default(),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Cell::from(text).style(
                     if self.is_selected_cell(row_index, column_index, selected_column_index) {
                         Style::default().bg(Color::Blue)
                     } else if self.is_number_column(row_index, column_index) {
@@ -641,7 +992,40 @@ impl StatefulDrawableComponent for TableComponent {
             },
         );
 
-        TableValueComponent::new(self.content().unwrap_or_default()).draw(f, chunks[0], focused)?;
+        if self.rows.is_empty() && !self.empty_hint.is_empty() {
+            // Below the header and its margin, when there is one.
+            let offset = if self.headers.is_empty() { 0 } else { 2 };
+            let hint_area = Rect {
+                y: chunks[1].y + offset.min(chunks[1].height),
+                height: chunks[1].height.saturating_sub(offset),
+                ..chunks[1]
+            };
+            f.render_widget(
+                Paragraph::new(
+                    self.empty_hint
+                        .iter()
+                        .map(|line| Line::from(line.as_str()))
+                        .collect::<Vec<Line>>(),
+                )
+                .style(Style::default().fg(Color::DarkGray))
+                .wrap(Wrap { trim: false }),
+                hint_area,
+            );
+        }
+
+        f.render_widget(
+            Paragraph::new(match self.content() {
+                Some(_) if self.masked => MASK.to_string(),
+                content => shorten(&content.unwrap_or_default(), self.max_cell_length).into_owned(),
+            })
+            .block(Block::default().borders(Borders::BOTTOM))
+            .style(if focused {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }),
+            chunks[0],
+        );
 
         TableStatusComponent::new(
             if self.rows.is_empty() {
@@ -679,6 +1063,15 @@ impl Component for TableComponent {
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.header_focused {
+            if key == Key::Left {
+                self.previous_column();
+                return Ok(EventState::Consumed);
+            } else if key == Key::Right {
+                self.next_column();
+                return Ok(EventState::Consumed);
+            }
+        }
         if key == self.key_config.scroll_left {
             self.previous_column();
             return Ok(EventState::Consumed);
@@ -727,10 +1120,21 @@ impl Component for TableComponent {
     }
 }
 
+pub fn csv_value(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{KeyConfig, Order, OrderManager, TableComponent};
-    use ratatui::layout::Constraint;
+    use super::{
+        display_width, shorten, truncate, Component, Key, KeyConfig, Order, OrderManager,
+        StatefulDrawableComponent, TableComponent,
+    };
+    use ratatui::{backend::TestBackend, layout::Constraint, Terminal};
 
     #[test]
     fn test_headers() {
@@ -739,6 +1143,60 @@ mod test {
         assert_eq!(component.headers(1, 2), vec!["", "b"])
     }
 
+    #[test]
+    fn test_csv() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = vec!["id".to_string(), "note".to_string()];
+        component.rows = vec![
+            vec!["1".to_string(), "a,b".to_string()],
+            vec!["2".to_string(), "say \"hi\"\nbye".to_string()],
+        ];
+        assert_eq!(
+            component.csv(),
+            "id,note\n1,\"a,b\"\n2,\"say \"\"hi\"\"\nbye\""
+        );
+    }
+
+    #[test]
+    fn test_restore_position() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["a", "b", "c"].iter().map(|h| h.to_string()).collect();
+        component.rows = (0..10)
+            .map(|i| vec![i.to_string(), "b".to_string(), "c".to_string()])
+            .collect();
+        component.selected_row.select(Some(7));
+        *component.selected_row.offset_mut() = 5;
+        component.selected_column = 2;
+        let position = component.position().unwrap();
+
+        component.reset();
+        assert_eq!(component.position(), None);
+        component.headers = ["a", "b"].iter().map(|h| h.to_string()).collect();
+        component.rows = (0..4)
+            .map(|i| vec![i.to_string(), "b".to_string()])
+            .collect();
+        component.restore_position(position);
+        assert_eq!(component.selected_row.selected(), Some(3));
+        assert_eq!(component.selected_row.offset(), 3);
+        assert_eq!(component.selected_column, 1);
+    }
+
+    #[test]
+    fn test_header_focus() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["a", "b", "c"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![["d", "e", "f"].iter().map(|h| h.to_string()).collect()];
+        component.selected_row.select(Some(0));
+
+        assert!(!component.event(Key::Right).unwrap().is_consumed());
+        component.set_header_focus(true);
+        assert!(component.event(Key::Right).unwrap().is_consumed());
+        assert!(component.event(Key::Right).unwrap().is_consumed());
+        assert_eq!(component.selected_column, 2);
+        component.event(Key::Left).unwrap();
+        assert_eq!(component.selected_column, 1);
+    }
+
     #[test]
     fn test_rows() {
         let mut component = TableComponent::new(KeyConfig::default());
@@ -919,6 +1377,40 @@ mod test {
         assert_eq!(component.content(), Some("a,b\nd,e".to_string()));
     }
 
+    #[test]
+    fn test_slice_selected_area_and_visible_columns() {
+        //    1  2  3
+        // 1  a |b  c|
+        // 2  d |e  f|
+
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["1", "2", "3"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![
+            ["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
+            ["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+        ];
+        component.selected_row.select(Some(1));
+        component.selected_column = 2;
+        assert_eq!(component.selected_area(), Some((2..3, 1..2)));
+        component.selection_area_corner = Some((1, 0));
+        let (columns, rows) = component.selected_area().unwrap();
+        assert_eq!(
+            component.slice(columns, rows),
+            (
+                vec!["2".to_string(), "3".to_string()],
+                vec![
+                    vec!["b".to_string(), "c".to_string()],
+                    vec!["e".to_string(), "f".to_string()]
+                ]
+            )
+        );
+
+        assert_eq!(component.visible_columns(), 0..3);
+        component.column_page_start.set(1);
+        component.column_page_end.set(Some(2));
+        assert_eq!(component.visible_columns(), 1..2);
+    }
+
     #[test]
     fn test_is_selected_cell_when_one_cell_selected() {
         //    1  2 3
@@ -1028,6 +1520,137 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("ab\n日本語です"), 10);
+        assert_eq!(display_width("a\tb"), 3);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate("abcdef\nab", 4), "abc…\nab");
+        assert_eq!(truncate("a\u{7}b", 3), "a\u{fffd}b");
+        assert_eq!(truncate("1", 0), "");
+    }
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(shorten("abc", 3), "abc");
+        assert_eq!(shorten("aéb", 3), "aéb");
+        assert_eq!(shorten("abcdef", 2), "ab…(+4 chars)");
+        assert_eq!(shorten("ééé", 1), "é…(+2 chars)");
+    }
+
+    #[test]
+    fn test_geometry_columns() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["id", "area"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![["1", "POLYGON((0 0,1 0,1 1,0 0))"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect()];
+        component.set_geometry_columns(vec![false, true]);
+        component.selected_row.select(Some(0));
+        component.selected_column = 1;
+        assert_eq!(component.rows(0, 2), [["1", "1", "POLYGON [4 points]"]]);
+        assert_eq!(component.content().unwrap(), "POLYGON((0 0,1 0,1 1,0 0))");
+    }
+
+    #[test]
+    fn test_masked() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["id", "email"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![["1", "secret@example.com"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect()];
+        component.selected_row.select(Some(0));
+        component.selected_column = 1;
+        component.set_masked(true);
+        assert_eq!(component.rows(0, 2), [["1", "•••••", "•••••"]]);
+        assert_eq!(component.content().unwrap(), "secret@example.com");
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|f| {
+                StatefulDrawableComponent::draw(&mut component, f, f.size(), true).unwrap();
+            })
+            .unwrap();
+        let screen = (0..10)
+            .map(|y| {
+                (0..40)
+                    .map(|x| terminal.backend().buffer().get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert!(screen.contains("email"), "{}", screen);
+        assert!(screen.contains("[privacy]"), "{}", screen);
+        assert!(!screen.contains("secret"), "{}", screen);
+    }
+
+    #[test]
+    fn test_numeric_columns() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.headers = ["id", "price", "name"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.rows = vec![
+            ["1", "-1.5", "foo"].iter().map(|h| h.to_string()).collect(),
+            ["22", "NULL", "42"].iter().map(|h| h.to_string()).collect(),
+        ];
+        assert!(component.is_numeric_column(0));
+        assert!(component.is_numeric_column(1));
+        assert!(!component.is_numeric_column(2));
+
+        component.set_numeric_columns(vec![false, true, false]);
+        assert!(!component.is_numeric_column(0));
+        assert!(component.is_numeric_column(1));
+
+        component.set_row_numbers(false);
+        component.selected_row.select(Some(0));
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal
+            .draw(|f| {
+                StatefulDrawableComponent::draw(&mut component, f, f.size(), true).unwrap();
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines = (0..10)
+            .map(|y| {
+                (0..30)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        assert!(lines[5].contains(" 1    -1.5 foo "), "{}", lines[5]);
+    }
+
+    #[test]
+    fn test_calculate_cell_widths_without_row_numbers() {
+        let mut component = TableComponent::new(KeyConfig::default());
+        component.set_row_numbers(false);
+        component.headers = ["1", "2"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![["aaaaa", "bbbbb"].iter().map(|h| h.to_string()).collect()];
+        let (_, _, _, constraints) = component.calculate_cell_widths(20);
+        assert_eq!(
+            constraints,
+            vec![
+                Constraint::Length(0),
+                Constraint::Length(5),
+                Constraint::Length(5),
+            ]
+        );
+    }
+
     #[test]
     fn test_calculate_cell_widths_when_sum_of_cell_widths_is_less_than_table_width() {
         let mut component = TableComponent::new(KeyConfig::default());
@@ -1110,7 +1733,7 @@ mod test {
 
     #[test]
     fn test_generate_order_query() {
-        let mut order_manager = OrderManager::new();
+        let mut order_manager = OrderManager::default();
 
         // If orders is empty, it should return None.
         assert_eq!(order_manager.generate_order_query(), None);
@@ -1126,7 +1749,7 @@ mod test {
 
     #[test]
     fn test_generate_header_icons() {
-        let mut order_manager = OrderManager::new();
+        let mut order_manager = OrderManager::default();
         assert_eq!(order_manager.generate_header_icons(1), vec![String::new()]);
 
         order_manager.add_order(1);
@@ -1149,7 +1772,7 @@ mod test {
 
     #[test]
     fn test_add_order() {
-        let mut order_manager = OrderManager::new();
+        let mut order_manager = OrderManager::default();
 
         // press first time, condition is asc.
         order_manager.add_order(1);
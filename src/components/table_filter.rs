@@ -5,8 +5,8 @@ use super::{
 use crate::components::command::CommandInfo;
 use crate::config::KeyConfig;
 use crate::event::Key;
-use crate::tree::Table;
-use anyhow::Result;
+use crate::filter_history::{FilterEntry, FilterHistory};
+use anyhow::{anyhow, Result};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -15,14 +15,32 @@ use ratatui::{
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
+use zhobo::database::Pool;
+use zhobo::tree::{Database, Table};
 
 pub struct TableFilterComponent {
     key_config: KeyConfig,
     pub table: Option<Table>,
+    pub database: Option<Database>,
+    /// The display name of the connection the table is on.
+    pub connection: Option<String>,
+    pub history: FilterHistory,
+    history_index: Option<usize>,
+    draft: FilterEntry,
     input: Vec<char>,
     input_idx: usize,
     input_cursor_position: u16,
     completion: CompletionComponent,
+    regex: bool,
+    ignore_case: bool,
+}
+
+/// The key state of `table` is kept by across tables and runs, such as `app.public.users`.
+pub fn table_key(database: &Database, table: &Table) -> String {
+    match &table.schema {
+        Some(schema) => format!("{}.{}.{}", database.name, schema, table.name),
+        None => format!("{}.{}", database.name, table.name),
+    }
 }
 
 impl TableFilterComponent {
@@ -30,22 +48,156 @@ impl TableFilterComponent {
         Self {
             key_config: key_config.clone(),
             table: None,
+            database: None,
+            connection: None,
+            history: FilterHistory::default(),
+            history_index: None,
+            draft: FilterEntry::default(),
             input: Vec::new(),
             input_idx: 0,
             input_cursor_position: 0,
             completion: CompletionComponent::new(key_config, "", false),
+            regex: false,
+            ignore_case: false,
         }
     }
 
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.completion.set_key_config(key_config.clone());
+        self.key_config = key_config;
+    }
+
     pub fn input_str(&self) -> String {
         self.input.iter().collect()
     }
 
+    /// Returns the condition for the WHERE clause.
+    /// In regex mode or when ignoring case, the input is `<column> <pattern>` and is translated
+    /// for the backend.
+    pub fn condition(&self, pool: &dyn Pool) -> Result<Option<String>> {
+        let input = self.input_str();
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+        if !self.regex && !self.ignore_case {
+            return Ok(Some(input));
+        }
+        match input.trim().split_once(' ') {
+            Some((column, pattern)) if !pattern.trim().is_empty() => Ok(Some(if self.regex {
+                pool.regex_condition(column, pattern.trim(), self.ignore_case)
+            } else {
+                pool.ignore_case_condition(column, pattern.trim())
+            })),
+            _ if self.regex => Err(anyhow!(
+                "Regex filter must be `<column> <pattern>`, e.g. `name ^foo`"
+            )),
+            _ => Err(anyhow!(
+                "Filter ignoring case must be `<column> <value>`, e.g. `name foo%`"
+            )),
+        }
+    }
+
+    /// Replaces the input with `current`, the condition the input was translated to with its
+    /// variables replaced, and `condition` added with AND. A regex filter or a filter ignoring
+    /// case turns plain.
+    pub fn and_condition(&mut self, current: Option<String>, condition: &str) {
+        self.regex = false;
+        self.ignore_case = false;
+        let current = current.unwrap_or_default();
+        // Parenthesized, so that an OR or another operator in it binds as it did on its own.
+        let input = match current.trim() {
+            "" => condition.to_string(),
+            current => format!("({}) AND {}", current, condition),
+        };
+        self.set_input(&input);
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<String>) {
+        self.completion.set_columns(columns);
+    }
+
     pub fn reset(&mut self) {
         self.table = None;
+        self.database = None;
+        self.completion.set_columns(vec![]);
         self.input = Vec::new();
         self.input_idx = 0;
         self.input_cursor_position = 0;
+        self.history_index = None;
+    }
+
+    /// `connection/database.table`, or `connection/database.schema.table` for tables in a
+    /// schema, so that tables of the same name on other connections are told apart.
+    pub fn table_key(&self) -> Option<String> {
+        Some(format!(
+            "{}/{}",
+            self.connection.as_ref()?,
+            table_key(self.database.as_ref()?, self.table.as_ref()?)
+        ))
+    }
+
+    /// The input with the mode it is applied in.
+    fn entry(&self) -> FilterEntry {
+        FilterEntry {
+            filter: self.input_str(),
+            regex: self.regex,
+            ignore_case: self.ignore_case,
+        }
+    }
+
+    /// Records the current input as the latest filter of the table.
+    pub fn push_history(&mut self) -> Result<()> {
+        self.history_index = None;
+        let entry = self.entry();
+        match self.table_key() {
+            Some(key) if !entry.filter.trim().is_empty() => self.history.push(&key, entry),
+            _ => Ok(()),
+        }
+    }
+
+    fn recall_history(&mut self, older: bool) {
+        let filters = match self.table_key() {
+            Some(key) => self.history.filters(&key).to_vec(),
+            None => return,
+        };
+        if filters.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, older) {
+            (None, true) => {
+                self.draft = self.entry();
+                Some(filters.len() - 1)
+            }
+            (None, false) => return,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i + 1 < filters.len() => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.history_index = index;
+        let entry = index.map_or(self.draft.clone(), |i| filters[i].clone());
+        self.regex = entry.regex;
+        self.ignore_case = entry.ignore_case;
+        self.set_input(&entry.filter);
+    }
+
+    fn set_input(&mut self, input: &str) {
+        self.input = input.chars().collect();
+        self.input_idx = self.input.len();
+        self.input_cursor_position = input.width() as u16;
+        self.completion.update("");
+    }
+
+    fn mode_label(&self) -> &'static str {
+        match (self.regex, self.ignore_case) {
+            (true, true) => " [regex, ignore case]",
+            (true, false) => " [regex]",
+            (false, true) => " [ignore case]",
+            (false, false) => "",
+        }
+    }
+
+    fn mode_label_width(&self) -> u16 {
+        self.mode_label().width() as u16
     }
 
     fn update_completion(&mut self) {
@@ -137,10 +289,16 @@ impl StatefulDrawableComponent for TableFilterComponent {
                     .map_or("-".to_string(), |table| table.name.to_string()),
                 Style::default().fg(Color::Blue),
             ),
+            Span::styled(self.mode_label(), Style::default().fg(Color::Yellow)),
             Span::from(format!(
                 " {}",
                 if focused || !self.input.is_empty() {
                     self.input.iter().collect::<String>()
+                } else if self.regex {
+                    "Enter a column name and a regular expression to filter records".to_string()
+                } else if self.ignore_case {
+                    "Enter a column name and a value, with % and _ wildcards, to match ignoring case"
+                        .to_string()
                 } else {
                     "Enter a SQL expression in WHERE clause to filter records".to_string()
                 }
@@ -164,6 +322,7 @@ impl StatefulDrawableComponent for TableFilterComponent {
                     .as_ref()
                     .map_or(String::new(), |table| format!("{} ", table.name))
                     .width() as u16)
+                    .saturating_add(self.mode_label_width())
                     .saturating_add(self.input_cursor_position),
                 0,
             )?;
@@ -178,6 +337,7 @@ impl StatefulDrawableComponent for TableFilterComponent {
                         .map_or(String::new(), |table| table.name.to_string())
                         .width()
                         + 1) as u16)
+                    .saturating_add(self.mode_label_width())
                     .saturating_add(self.input_cursor_position)
                     .min(area.right().saturating_sub(2)),
                 area.y + 1,
@@ -193,15 +353,36 @@ impl Component for TableFilterComponent {
     fn event(&mut self, key: Key) -> Result<EventState> {
         let input_str: String = self.input.iter().collect();
 
+        if key == self.key_config.toggle_regex_filter {
+            self.regex = !self.regex;
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.key_config.toggle_ignore_case_filter {
+            self.ignore_case = !self.ignore_case;
+            return Ok(EventState::Consumed);
+        }
+
         // apply completion candidates
         if key == self.key_config.enter {
             return self.complete();
         }
 
+        if self.completion.selected_candidate().is_none() {
+            if key == self.key_config.move_up {
+                self.recall_history(true);
+                return Ok(EventState::Consumed);
+            } else if key == self.key_config.move_down {
+                self.recall_history(false);
+                return Ok(EventState::Consumed);
+            }
+        }
+
         self.completion.selected_candidate();
 
         match key {
             Key::Char(c) => {
+                self.history_index = None;
                 self.input.insert(self.input_idx, c);
                 self.input_idx += 1;
                 self.input_cursor_position += compute_character_width(c);
@@ -258,7 +439,11 @@ impl Component for TableFilterComponent {
 
 #[cfg(test)]
 mod test {
-    use super::{KeyConfig, TableFilterComponent};
+    use super::{Component, KeyConfig, TableFilterComponent};
+    use crate::event::Key;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use zhobo::database::{Pool, SqlitePool, MAX_CONNECTIONS};
+    use zhobo::tree::{Database, Table};
 
     #[test]
     fn test_complete() {
@@ -295,4 +480,121 @@ mod test {
         assert!(filter.complete().is_ok());
         assert_eq!(filter.input, vec!['a', 'n', ' ', 'c', 'd', 'e', 'f', 'g']);
     }
+
+    #[tokio::test]
+    async fn test_regex_condition() {
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new().in_memory(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        let mut filter = TableFilterComponent::new(KeyConfig::default());
+        filter.input = "name ^fo'o".chars().collect();
+        assert_eq!(
+            filter.condition(&pool).unwrap(),
+            Some("name ^fo'o".to_string())
+        );
+
+        assert!(filter.event(Key::Ctrl('r')).unwrap().is_consumed());
+        assert!(filter.regex);
+        assert_eq!(
+            filter.condition(&pool).unwrap(),
+            Some("name REGEXP '^fo''o'".to_string())
+        );
+
+        assert!(filter.event(Key::Ctrl('t')).unwrap().is_consumed());
+        assert_eq!(
+            filter.condition(&pool).unwrap(),
+            Some("name REGEXP '(?i)^fo''o'".to_string())
+        );
+
+        filter.event(Key::Ctrl('r')).unwrap();
+        filter.input = "name fo'o".chars().collect();
+        assert_eq!(
+            filter.condition(&pool).unwrap(),
+            Some("name = 'fo''o' COLLATE NOCASE".to_string())
+        );
+        filter.input = "name fo%".chars().collect();
+        assert_eq!(
+            filter.condition(&pool).unwrap(),
+            Some("name LIKE 'fo%'".to_string())
+        );
+
+        filter.input = "name".chars().collect();
+        assert!(filter.condition(&pool).is_err());
+        pool.close().await;
+    }
+
+    #[test]
+    fn test_and_condition() {
+        let mut filter = TableFilterComponent::new(KeyConfig::default());
+        filter.and_condition(None, "\"name\" IS NULL");
+        assert_eq!(filter.input_str(), "\"name\" IS NULL");
+        filter.and_condition(Some(filter.input_str()), "\"id\" != '1'");
+        assert_eq!(filter.input_str(), "(\"name\" IS NULL) AND \"id\" != '1'");
+        filter.and_condition(Some("a = 1 OR\nb = 2".to_string()), "c = 3");
+        assert_eq!(filter.input_str(), "(a = 1 OR\nb = 2) AND c = 3");
+        filter.and_condition(Some("x = 1 or(y = 2)".to_string()), "c = 3");
+        assert_eq!(filter.input_str(), "(x = 1 or(y = 2)) AND c = 3");
+
+        filter.and_condition(Some("id = 1 or id = 2".to_string()), "\"name\" IS NOT NULL");
+        assert_eq!(
+            filter.input_str(),
+            "(id = 1 or id = 2) AND \"name\" IS NOT NULL"
+        );
+
+        filter.set_input("name ^foo");
+        filter.regex = true;
+        filter.and_condition(Some("name REGEXP '^foo'".to_string()), "id IS NULL");
+        assert!(!filter.regex);
+        assert_eq!(filter.input_str(), "(name REGEXP '^foo') AND id IS NULL");
+    }
+
+    #[test]
+    fn test_recall_history() {
+        let mut filter = TableFilterComponent::new(KeyConfig::default());
+        filter.connection = Some("local".to_string());
+        filter.database = Some(Database::new("db".to_string(), vec![]));
+        filter.table = Some(Table {
+            name: "users".to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            parent: None,
+            schema: None,
+        });
+        for input in ["id = 1", "name ^a"] {
+            filter.set_input(input);
+            filter.regex = input.contains('^');
+            filter.push_history().unwrap();
+        }
+        assert_eq!(
+            filter.history.filters("local/db.users")[1].filter,
+            "name ^a"
+        );
+        filter.regex = false;
+        filter.set_input("draft");
+
+        assert!(filter.event(Key::Up).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "name ^a");
+        assert!(filter.regex);
+        assert!(filter.event(Key::Up).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "id = 1");
+        assert!(!filter.regex);
+        assert!(filter.event(Key::Up).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "id = 1");
+        assert!(filter.event(Key::Down).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "name ^a");
+        assert!(filter.event(Key::Down).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "draft");
+        assert!(!filter.regex);
+
+        filter.connection = Some("staging".to_string());
+        assert!(filter.event(Key::Up).unwrap().is_consumed());
+        assert_eq!(filter.input_str(), "draft");
+        assert_eq!(filter.input_cursor_position, 5);
+    }
 }
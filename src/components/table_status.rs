@@ -1,7 +1,6 @@
 use super::{Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
 use crate::event::Key;
-use crate::tree::Table;
 use anyhow::Result;
 use ratatui::{
     layout::Rect,
@@ -10,6 +9,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use zhobo::tree::Table;
 
 #[derive(Default)]
 pub struct TableStatusComponent {
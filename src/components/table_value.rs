@@ -1,34 +1,54 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{ClipboardComponent, Component, EventState, PropertyTrait, StatefulDrawableComponent};
 use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
 use crate::event::Key;
 use anyhow::Result;
-use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
-    Frame,
-};
+use ratatui::{layout::Rect, widgets::Clear, Frame};
+use zhobo::tree::{Database, Table as DTable};
 
+/// The full value of the selected cell, searched with the filter key and moved through the
+/// matches with the next and previous match keys like the definition viewer.
 pub struct TableValueComponent {
-    value: String,
+    viewer: ClipboardComponent,
+    visible: bool,
+    key_config: KeyConfig,
 }
 
 impl TableValueComponent {
-    pub fn new(value: String) -> Self {
-        Self { value }
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            viewer: ClipboardComponent::new(key_config.clone()),
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.viewer.set_key_config(key_config.clone());
+        self.key_config = key_config;
+    }
+
+    /// Shows `value`, a cell of `table`.
+    pub fn open(&mut self, value: String, database: Database, table: DTable) -> Result<()> {
+        self.viewer.reset();
+        self.viewer.update(value, database, table);
+        self.show()
     }
 }
 
-impl DrawableComponent for TableValueComponent {
-    fn draw(&self, f: &mut Frame, area: Rect, focused: bool) -> Result<()> {
-        let paragraph = Paragraph::new(self.value.clone())
-            .block(Block::default().borders(Borders::BOTTOM))
-            .style(if focused {
-                Style::default()
-            } else {
-                Style::default().fg(Color::DarkGray)
-            });
-        f.render_widget(paragraph, area);
+impl StatefulDrawableComponent for TableValueComponent {
+    fn draw(&mut self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let size = f.size();
+            let area = Rect::new(
+                size.width / 10,
+                size.height / 10,
+                size.width - size.width / 5,
+                size.height - size.height / 5,
+            );
+            f.render_widget(Clear, area);
+            self.viewer.draw(f, area, true)?;
+        }
         Ok(())
     }
 }
@@ -36,7 +56,86 @@ impl DrawableComponent for TableValueComponent {
 impl Component for TableValueComponent {
     fn commands(&self, _out: &mut Vec<CommandInfo>) {}
 
-    fn event(&mut self, _key: Key) -> Result<EventState> {
-        todo!("scroll");
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if !self.viewer.searching() && key == self.key_config.exit_popup {
+                self.hide();
+            } else {
+                self.viewer.event(key)?;
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TableValueComponent;
+    use crate::components::{Component, StatefulDrawableComponent};
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+    use zhobo::tree::{Database, Table};
+
+    /// The lines of the screen holding the current match.
+    fn current_match_lines(popup: &mut TableValueComponent) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        terminal
+            .draw(|f| popup.draw(f, f.size(), true).unwrap())
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..20)
+            .filter(|y| (0..40).any(|x| buffer.get(x, *y).bg == Color::LightRed))
+            .map(|y| {
+                (0..40)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+                    .trim_matches(|c: char| c == ' ' || c == '║')
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_search() {
+        let mut popup = TableValueComponent::new(KeyConfig::default());
+        assert!(!popup.event(Key::Char('/')).unwrap().is_consumed());
+        popup
+            .open(
+                "{\n  \"id\": 1,\n  \"name\": \"a\",\n  \"id\": 2\n}".to_string(),
+                Database::new("db".to_string(), vec![]),
+                Table {
+                    name: "users".to_string(),
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    parent: None,
+                    schema: None,
+                },
+            )
+            .unwrap();
+        assert!(current_match_lines(&mut popup).is_empty());
+
+        for key in [Key::Char('/'), Key::Char('i'), Key::Char('d'), Key::Enter] {
+            assert!(popup.event(key).unwrap().is_consumed());
+        }
+        assert_eq!(current_match_lines(&mut popup), ["\"id\": 1,"]);
+        popup.event(Key::Char('n')).unwrap();
+        assert_eq!(current_match_lines(&mut popup), ["\"id\": 2"]);
+        popup.event(Key::Char('N')).unwrap();
+        assert_eq!(current_match_lines(&mut popup), ["\"id\": 1,"]);
+
+        popup.event(Key::Esc).unwrap();
+        assert!(!popup.event(Key::Char('n')).unwrap().is_consumed());
     }
 }
@@ -0,0 +1,91 @@
+use super::DrawableComponent;
+use anyhow::Result;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+/// A short message drawn in the bottom right corner, which disappears by itself.
+#[derive(Default)]
+pub struct ToastComponent {
+    message: String,
+    shown_at: Option<Instant>,
+}
+
+impl ToastComponent {
+    const DURATION: Duration = Duration::from_secs(4);
+
+    pub fn show_message(&mut self, message: String) {
+        self.message = message;
+        self.shown_at = Some(Instant::now());
+    }
+
+    /// Whether the toast is on screen, or has just expired and has to be cleared.
+    pub fn is_shown(&self) -> bool {
+        self.shown_at.is_some()
+    }
+
+    /// Forgets the message once it has been shown for long enough.
+    pub fn expire(&mut self) {
+        if !self.visible() {
+            self.shown_at = None;
+        }
+    }
+
+    fn visible(&self) -> bool {
+        self.shown_at
+            .is_some_and(|shown_at| shown_at.elapsed() < Self::DURATION)
+    }
+}
+
+impl DrawableComponent for ToastComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible() {
+            let size = f.size();
+            let width = (self.message.width() as u16 + 4).min(size.width);
+            let height = 3.min(size.height);
+            let area = Rect::new(size.width - width, size.height - height, width, height);
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.message.as_str()).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .style(Style::default().fg(Color::Green)),
+                ),
+                area,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ToastComponent;
+
+    #[test]
+    fn test_show_message() {
+        let mut toast = ToastComponent::default();
+        assert!(!toast.visible());
+        toast.show_message("Connected".to_string());
+        assert!(toast.visible());
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut toast = ToastComponent::default();
+        toast.show_message("Connected".to_string());
+        toast.expire();
+        assert!(toast.is_shown());
+        toast.shown_at = toast
+            .shown_at
+            .and_then(|shown_at| shown_at.checked_sub(ToastComponent::DURATION));
+        assert!(toast.is_shown());
+        toast.expire();
+        assert!(!toast.is_shown());
+    }
+}
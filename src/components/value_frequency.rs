@@ -0,0 +1,244 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::components::table::MASK;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use anyhow::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// The most frequent values of a column with their counts, one of which can be chosen to filter
+/// the records by.
+pub struct ValueFrequencyComponent {
+    column: String,
+    /// Each value and the number of records holding it, the most frequent first.
+    values: Vec<(String, String)>,
+    state: ListState,
+    selected: Option<(String, String)>,
+    /// Whether the values are drawn as a mask, leaving their counts, in privacy mode.
+    masked: bool,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ValueFrequencyComponent {
+    const WIDTH: u16 = 60;
+    const HEIGHT: u16 = 16;
+    /// How many of the most frequent values are listed.
+    pub const LIMIT: usize = 20;
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            column: String::new(),
+            values: vec![],
+            state: ListState::default(),
+            selected: None,
+            masked: false,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_key_config(&mut self, key_config: KeyConfig) {
+        self.key_config = key_config;
+    }
+
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+
+    /// Lists `values`, rows of a value and its count, of `column`.
+    pub fn open(&mut self, column: String, values: Vec<Vec<String>>) -> Result<()> {
+        self.column = column;
+        self.values = values
+            .into_iter()
+            .filter_map(|row| {
+                let mut row = row.into_iter();
+                Some((row.next()?, row.next()?))
+            })
+            .collect();
+        self.state.select((!self.values.is_empty()).then_some(0));
+        self.show()
+    }
+
+    /// The column and the value chosen to filter by.
+    pub fn take_selected(&mut self) -> Option<(String, String)> {
+        self.selected.take()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(if down {
+                (i + 1).min(self.values.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            }));
+        }
+    }
+}
+
+impl DrawableComponent for ValueFrequencyComponent {
+    fn draw(&self, f: &mut Frame, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let area = Rect::new(
+                (f.size().width.saturating_sub(Self::WIDTH)) / 2,
+                (f.size().height.saturating_sub(Self::HEIGHT)) / 2,
+                Self::WIDTH.min(f.size().width),
+                Self::HEIGHT.min(f.size().height),
+            );
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+                .split(area);
+
+            let count_width = self
+                .values
+                .iter()
+                .map(|(_, count)| count.len())
+                .max()
+                .unwrap_or(0);
+            let items = if self.values.is_empty() {
+                vec![ListItem::new("No records.")]
+            } else {
+                self.values
+                    .iter()
+                    .map(|(value, count)| {
+                        ListItem::new(format!(
+                            "{:>width$}  {}",
+                            count,
+                            if self.masked {
+                                MASK.to_string()
+                            } else {
+                                value.replace('\n', " ")
+                            },
+                            width = count_width
+                        ))
+                    })
+                    .collect::<Vec<ListItem>>()
+            };
+            let mut state = self.state.clone();
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title(format!("Top values of {}", self.column))
+                    .borders(Borders::ALL),
+                area,
+            );
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::Blue)),
+                chunks[0],
+                &mut state,
+            );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Press [{}] to filter by the value, [{}] to close.",
+                    self.key_config.enter, self.key_config.exit_popup
+                ))
+                .alignment(Alignment::Right),
+                chunks[1],
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ValueFrequencyComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.enter {
+                if let Some((value, _)) = self.state.selected().and_then(|i| self.values.get(i)) {
+                    self.selected = Some((self.column.clone(), value.clone()));
+                    self.hide();
+                }
+            } else if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == Key::Down || key == self.key_config.scroll_down {
+                self.move_selection(true);
+            } else if key == Key::Up || key == self.key_config.scroll_up {
+                self.move_selection(false);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ValueFrequencyComponent;
+    use crate::components::{Component, DrawableComponent};
+    use crate::config::KeyConfig;
+    use crate::event::Key;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_choose_value() {
+        let mut popup = ValueFrequencyComponent::new(KeyConfig::default());
+        assert!(!popup.event(Key::Enter).unwrap().is_consumed());
+
+        popup
+            .open(
+                "status".to_string(),
+                vec![
+                    vec!["paid".to_string(), "12".to_string()],
+                    vec!["NULL".to_string(), "3".to_string()],
+                ],
+            )
+            .unwrap();
+        popup.event(Key::Down).unwrap();
+        popup.event(Key::Down).unwrap();
+        popup.event(Key::Enter).unwrap();
+        assert_eq!(
+            popup.take_selected(),
+            Some(("status".to_string(), "NULL".to_string()))
+        );
+        assert!(!popup.event(Key::Enter).unwrap().is_consumed());
+
+        popup.open("status".to_string(), vec![]).unwrap();
+        assert!(popup.event(Key::Enter).unwrap().is_consumed());
+        assert_eq!(popup.take_selected(), None);
+    }
+
+    #[test]
+    fn test_masked() {
+        let mut popup = ValueFrequencyComponent::new(KeyConfig::default());
+        popup.set_masked(true);
+        popup
+            .open(
+                "email".to_string(),
+                vec![vec!["secret@example.com".to_string(), "12".to_string()]],
+            )
+            .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(70, 20)).unwrap();
+        terminal
+            .draw(|f| popup.draw(f, f.size(), true).unwrap())
+            .unwrap();
+        let screen = (0..20)
+            .map(|y| {
+                (0..70)
+                    .map(|x| terminal.backend().buffer().get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert!(screen.contains("12  •••••"), "{}", screen);
+        assert!(!screen.contains("secret"), "{}", screen);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::jobs::JobProgress;
+use anyhow::anyhow;
+use std::ops::Range;
+use std::sync::Arc;
+use zhobo::database::{insert_statements, Pool, QueryBuilder};
+
+/// Rows read and inserted per statement while copying.
+const ROWS_PER_BATCH: usize = 500;
+
+/// A column of the source table and the column of the target table its values go to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMapping {
+    pub source: String,
+    pub target: String,
+}
+
+/// The table rows are copied to, as typed in the prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyTarget {
+    /// The table name, with its schema or database when one is given.
+    pub table: Vec<String>,
+    pub columns: Vec<ColumnMapping>,
+}
+
+/// The rows to copy: those `query` reads, or only `rows` of them, the positions of the selected
+/// records. They are read again as they are stored rather than as they are shown.
+pub struct CopySource {
+    pub pool: Box<dyn Pool>,
+    pub query: Box<QueryBuilder>,
+    pub rows: Option<Range<usize>>,
+}
+
+/// Parses `[schema.]table [(column, column AS target, ...)]`. Without the list, every column of
+/// `source_columns` is copied to the column of the same name.
+pub fn parse_target(input: &str, source_columns: &[String]) -> anyhow::Result<CopyTarget> {
+    let input = input.trim();
+    let (table, list) = match input.find('(') {
+        Some(open) => {
+            let list = input[open + 1..]
+                .trim_end()
+                .strip_suffix(')')
+                .ok_or_else(|| anyhow!("the column list is not closed with `)`"))?;
+            (input[..open].trim(), Some(list))
+        }
+        None => (input, None),
+    };
+    if table.is_empty() {
+        return Err(anyhow!("enter the table to copy the rows to"));
+    }
+    let table = table
+        .split('.')
+        .map(|part| part.trim().to_string())
+        .collect::<Vec<String>>();
+    if table.len() > 2 || table.iter().any(|part| part.is_empty()) {
+        return Err(anyhow!("`{}` is not a table name", input));
+    }
+
+    let columns = match list {
+        None => source_columns
+            .iter()
+            .map(|column| ColumnMapping {
+                source: column.clone(),
+                target: column.clone(),
+            })
+            .collect(),
+        Some(list) => list
+            .split(',')
+            .map(|item| parse_mapping(item.trim(), source_columns))
+            .collect::<anyhow::Result<Vec<ColumnMapping>>>()?,
+    };
+    if columns.is_empty() {
+        return Err(anyhow!("no column to copy"));
+    }
+    Ok(CopyTarget { table, columns })
+}
+
+fn parse_mapping(item: &str, source_columns: &[String]) -> anyhow::Result<ColumnMapping> {
+    let words = item.split_whitespace().collect::<Vec<&str>>();
+    let (source, target) = match words.as_slice() {
+        [source] => (*source, *source),
+        [source, keyword, target] if keyword.eq_ignore_ascii_case("AS") => (*source, *target),
+        _ => return Err(anyhow!("`{}` is not `column` or `column AS target`", item)),
+    };
+    let source = source_columns
+        .iter()
+        .find(|column| column.eq_ignore_ascii_case(source))
+        .ok_or_else(|| anyhow!("the source table has no column `{}`", source))?;
+    Ok(ColumnMapping {
+        source: source.clone(),
+        target: target.to_string(),
+    })
+}
+
+/// Inserts the rows of `source` into the table of `target_name` on `target`, a batch per
+/// statement. Rows inserted before a failure or cancellation are kept.
+pub async fn copy_rows(
+    source: CopySource,
+    target: Box<dyn Pool>,
+    target_name: CopyTarget,
+    progress: Arc<JobProgress>,
+) -> anyhow::Result<()> {
+    let table = target_name
+        .table
+        .iter()
+        .map(|part| target.quote_identifier(part))
+        .collect::<Vec<String>>()
+        .join(".");
+    let columns = target_name
+        .columns
+        .iter()
+        .map(|column| target.quote_identifier(&column.target))
+        .collect::<Vec<String>>();
+    let pool = source.pool;
+    // The records are read as they are shown and the columns picked from them, since the sort
+    // order refers to the shown columns by their position.
+    let source_columns = target_name
+        .columns
+        .iter()
+        .map(|column| pool.quote_identifier(&column.source))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let (mut offset, end) = match source.rows {
+        Some(rows) => (rows.start, Some(rows.end)),
+        None => (0, None),
+    };
+    loop {
+        if progress.is_cancelled() {
+            return Err(anyhow!("cancelled"));
+        }
+        let limit = end.map_or(ROWS_PER_BATCH, |end| {
+            end.saturating_sub(offset).min(ROWS_PER_BATCH)
+        });
+        if limit == 0 {
+            break;
+        }
+        let records = source
+            .query
+            .as_ref()
+            .clone()
+            .limit(limit)
+            .offset(offset)
+            .select(pool.as_ref());
+        let (_, rows) = pool
+            .fetch_values(&format!(
+                "SELECT {} FROM ({}) AS records",
+                source_columns, records
+            ))
+            .await?;
+        let literals = rows
+            .iter()
+            .map(|row| row.iter().map(|value| target.literal(value)).collect())
+            .collect::<Vec<Vec<String>>>();
+        for statement in insert_statements(&table, &columns, &literals) {
+            target.execute(&statement).await?;
+        }
+        progress.add(rows.len(), 0);
+        if rows.len() < limit {
+            break;
+        }
+        offset += limit;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{copy_rows, parse_target, ColumnMapping, CopySource, CopyTarget};
+    use crate::jobs::JobProgress;
+    use std::sync::Arc;
+    use zhobo::database::{ExecuteResult, Pool, QueryBuilder, SqlitePool};
+    use zhobo::tree::{Database, Table};
+
+    fn mapping(source: &str, target: &str) -> ColumnMapping {
+        ColumnMapping {
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_target() {
+        let columns = ["id".to_string(), "name".to_string()];
+        assert_eq!(
+            parse_target(" users ", &columns).unwrap(),
+            CopyTarget {
+                table: vec!["users".to_string()],
+                columns: vec![mapping("id", "id"), mapping("name", "name")],
+            }
+        );
+        assert_eq!(
+            parse_target("staging.users (NAME as full_name, id)", &columns).unwrap(),
+            CopyTarget {
+                table: vec!["staging".to_string(), "users".to_string()],
+                columns: vec![mapping("name", "full_name"), mapping("id", "id")],
+            }
+        );
+        assert_eq!(
+            parse_target("users (email)", &columns)
+                .unwrap_err()
+                .to_string(),
+            "the source table has no column `email`"
+        );
+        assert!(parse_target("users (id", &columns).is_err());
+        assert!(parse_target("a.b.c", &columns).is_err());
+        assert!(parse_target("", &columns).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_rows() {
+        let source = SqlitePool::in_memory(100).await.unwrap();
+        source
+            .execute(&"CREATE TABLE users (id INTEGER, name TEXT, data BLOB)".to_string())
+            .await
+            .unwrap();
+        source
+            .execute(
+                &"INSERT INTO users VALUES (1, 'a', NULL), (2, NULL, X'0A'), (3, 'NULL', NULL), (4, 'o''neil', NULL)"
+                    .to_string(),
+            )
+            .await
+            .unwrap();
+        let target = SqlitePool::in_memory(100).await.unwrap();
+        target
+            .execute(
+                &"CREATE TABLE people (person_id INTEGER, full_name TEXT, data BLOB)".to_string(),
+            )
+            .await
+            .unwrap();
+        let query = QueryBuilder::new(
+            Database::new("main".to_string(), vec![]),
+            Table {
+                name: "users".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        )
+        .filter(Some("id > 1".to_string()))
+        .orders(Some("ORDER BY 1 DESC".to_string()));
+        let target_name = CopyTarget {
+            table: vec!["people".to_string()],
+            columns: vec![
+                mapping("id", "person_id"),
+                mapping("name", "full_name"),
+                mapping("data", "data"),
+            ],
+        };
+        let progress = Arc::new(JobProgress::default());
+        copy_rows(
+            CopySource {
+                pool: source.clone_box(),
+                query: Box::new(query.clone()),
+                rows: Some(0..2),
+            },
+            target.clone_box(),
+            target_name.clone(),
+            progress.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(progress.rows(), 2);
+        copy_rows(
+            CopySource {
+                pool: Box::new(source),
+                query: Box::new(query),
+                rows: Some(2..5),
+            },
+            target.clone_box(),
+            target_name,
+            Arc::new(JobProgress::default()),
+        )
+        .await
+        .unwrap();
+        let ExecuteResult::Read { rows, .. } = target
+            .execute(&"SELECT person_id, full_name, hex(data) FROM people".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            rows,
+            [["4", "o'neil", ""], ["3", "NULL", ""], ["2", "NULL", "0A"]]
+        );
+        let ExecuteResult::Read { rows, .. } = target
+            .execute(&"SELECT count(*) FROM people WHERE full_name IS NULL".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["1"]]);
+    }
+}
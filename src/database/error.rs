@@ -0,0 +1,242 @@
+use regex::Regex;
+#[cfg(feature = "mysql")]
+use sqlx::mysql::MySqlDatabaseError;
+#[cfg(feature = "postgres")]
+use sqlx::postgres::{PgDatabaseError, PgErrorPosition};
+use std::fmt;
+
+/// The statement an error happened on, attached to the error as its context.
+#[derive(Debug, Clone)]
+pub struct FailedQuery(pub String);
+
+impl fmt::Display for FailedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to run: {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    Connection,
+    Timeout,
+    Syntax,
+    Permission,
+    Other,
+}
+
+impl ErrorKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Connection => "Connection error",
+            Self::Timeout => "Timeout",
+            Self::Syntax => "SQL syntax error",
+            Self::Permission => "Permission denied",
+            Self::Other => "Error",
+        }
+    }
+
+    /// What is likely to fix the error.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Connection => Some(
+                "Check that the server is running, and the host, port and credentials of the connection.",
+            ),
+            Self::Timeout => Some(
+                "The server did not answer in time. Raise `timeout_second` of the connection, or narrow the query.",
+            ),
+            Self::Syntax => Some("Check the statement near where the message points to."),
+            Self::Permission => Some(
+                "The user of the connection lacks the privilege. Connect as another user or ask for a GRANT.",
+            ),
+            Self::Other => None,
+        }
+    }
+}
+
+/// An error split into what the error popup shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorDetails {
+    pub kind: ErrorKind,
+    /// The SQLSTATE or the error code of the database, with the MySQL error number.
+    pub code: Option<String>,
+    pub message: String,
+    /// The statement which failed, when the error is about one.
+    pub query: Option<String>,
+}
+
+impl ErrorDetails {
+    pub fn new(error: &anyhow::Error) -> Self {
+        let failed = error.downcast_ref::<FailedQuery>();
+        let context = failed.map(|failed| failed.to_string());
+        // The message of the error the query failed with, below its context.
+        let message = error
+            .chain()
+            .map(|cause| cause.to_string())
+            .find(|cause| Some(cause) != context.as_ref())
+            .unwrap_or_else(|| error.to_string());
+        let query = failed.map(|query| query.0.clone());
+        let (kind, code) = match error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<sqlx::Error>())
+        {
+            Some(sqlx::Error::Database(e)) => {
+                let code = e.code().map(|code| code.into_owned());
+                #[cfg(feature = "mysql")]
+                let code = match e.try_downcast_ref::<MySqlDatabaseError>() {
+                    Some(mysql) => Some(match code {
+                        Some(state) => format!("{} ({})", mysql.number(), state),
+                        None => mysql.number().to_string(),
+                    }),
+                    None => code,
+                };
+                (
+                    code.as_deref()
+                        .and_then(kind_of_code)
+                        .unwrap_or_else(|| kind_of_message(&message)),
+                    code,
+                )
+            }
+            Some(sqlx::Error::PoolTimedOut) => (ErrorKind::Timeout, None),
+            Some(
+                sqlx::Error::Io(_)
+                | sqlx::Error::Tls(_)
+                | sqlx::Error::Protocol(_)
+                | sqlx::Error::PoolClosed
+                | sqlx::Error::Configuration(_),
+            ) => (ErrorKind::Connection, None),
+            _ => (kind_of_message(&message), None),
+        };
+        Self {
+            kind,
+            code,
+            message,
+            query,
+        }
+    }
+}
+
+/// Where in the statement the database reports an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorPosition {
+    /// The index of the character, as PostgreSQL reports it.
+    Offset(usize),
+    /// The text the error is in front of, and its line as MySQL reports it. SQLite reports the
+    /// text alone.
+    Near { text: String, line: Option<usize> },
+}
+
+/// Where in the statement `error` happened, when the database tells.
+pub fn error_position(error: &anyhow::Error) -> Option<ErrorPosition> {
+    let database = error
+        .chain()
+        .find_map(|cause| match cause.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::Database(e)) => Some(e),
+            _ => None,
+        })?;
+    #[cfg(feature = "postgres")]
+    if let Some(PgErrorPosition::Original(position)) = database
+        .try_downcast_ref::<PgDatabaseError>()
+        .and_then(|e| e.position())
+    {
+        return Some(ErrorPosition::Offset(position.saturating_sub(1)));
+    }
+    let message = database.message();
+    if let Some(captures) = Regex::new(r"(?s)near '(.*)' at line (\d+)")
+        .unwrap()
+        .captures(message)
+    {
+        return Some(ErrorPosition::Near {
+            text: captures[1].to_string(),
+            line: captures[2].parse().ok(),
+        });
+    }
+    Regex::new(r#"near "(.+?)": syntax error"#)
+        .unwrap()
+        .captures(message)
+        .map(|captures| ErrorPosition::Near {
+            text: captures[1].to_string(),
+            line: None,
+        })
+}
+
+/// The kind of a PostgreSQL SQLSTATE, a MySQL error number as formatted by `ErrorDetails`, or
+/// a SQLite result code.
+fn kind_of_code(code: &str) -> Option<ErrorKind> {
+    let number = code.split_whitespace().next().unwrap_or_default();
+    match number {
+        "42601" | "1064" | "1149" => Some(ErrorKind::Syntax),
+        "42501" | "1044" | "1045" | "1142" | "1143" | "1227" | "23" => Some(ErrorKind::Permission),
+        "57014" | "1205" | "3024" => Some(ErrorKind::Timeout),
+        "2002" | "2003" | "2006" | "2013" => Some(ErrorKind::Connection),
+        _ if number.len() == 5 && number.starts_with("08") => Some(ErrorKind::Connection),
+        _ if number.len() == 5 && number.starts_with("28") => Some(ErrorKind::Permission),
+        _ => None,
+    }
+}
+
+fn kind_of_message(message: &str) -> ErrorKind {
+    let message = message.to_lowercase();
+    if message.contains("syntax error") {
+        ErrorKind::Syntax
+    } else if message.contains("permission denied")
+        || message.contains("access denied")
+        || message.contains("not authorized")
+    {
+        ErrorKind::Permission
+    } else if message.contains("timed out") || message.contains("timeout") {
+        ErrorKind::Timeout
+    } else if message.contains("connection refused") || message.contains("could not connect") {
+        ErrorKind::Connection
+    } else {
+        ErrorKind::Other
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod test {
+    use super::{
+        error_position, kind_of_code, ErrorDetails, ErrorKind, ErrorPosition, FailedQuery,
+    };
+    use crate::database::{Pool, SqlitePool};
+    use anyhow::Context;
+
+    #[tokio::test]
+    async fn test_error_details() {
+        let pool = SqlitePool::in_memory(100).await.unwrap();
+        let query = "SELEC 1".to_string();
+        let error = pool
+            .execute(&query)
+            .await
+            .map(|_| ())
+            .with_context(|| FailedQuery(query.clone()))
+            .unwrap_err();
+        let details = ErrorDetails::new(&error);
+        assert_eq!(details.kind, ErrorKind::Syntax);
+        assert_eq!(details.code.as_deref(), Some("1"));
+        assert!(details.message.contains("syntax error"));
+        assert_eq!(details.query, Some(query));
+        assert_eq!(
+            error_position(&error),
+            Some(ErrorPosition::Near {
+                text: "SELEC".to_string(),
+                line: None
+            })
+        );
+
+        let details = ErrorDetails::new(&anyhow::Error::new(sqlx::Error::PoolTimedOut));
+        assert_eq!(details.kind, ErrorKind::Timeout);
+        assert_eq!(details.query, None);
+
+        let details = ErrorDetails::new(&anyhow::anyhow!("No connection is selected"));
+        assert_eq!(details.kind, ErrorKind::Other);
+        assert_eq!(details.message, "No connection is selected");
+    }
+
+    #[test]
+    fn test_kind_of_code() {
+        assert_eq!(kind_of_code("42601"), Some(ErrorKind::Syntax));
+        assert_eq!(kind_of_code("1142 (42000)"), Some(ErrorKind::Permission));
+        assert_eq!(kind_of_code("08006"), Some(ErrorKind::Connection));
+        assert_eq!(kind_of_code("23505"), None);
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::anyhow;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPathStep {
+    Key(String),
+    Index(usize),
+}
+
+/// A path into a JSON value, typed as `$.user.id`, `user.tags[0]` or `->'user'->>'id'`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath(Vec<JsonPathStep>);
+
+impl JsonPath {
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let input = input.trim();
+        let steps = match input.strip_prefix('$').unwrap_or(input) {
+            path if path.starts_with("->") => parse_arrows(path)?,
+            path if path.is_empty() || path.starts_with(['.', '[']) => parse_dots(path)?,
+            path => parse_dots(&format!(".{}", path))?,
+        };
+        if steps.is_empty() {
+            return Err(anyhow!("enter a path such as $.user.id"));
+        }
+        Ok(Self(steps))
+    }
+
+    /// The path as MySQL and SQLite take it, such as `$.user."first name"[0]`.
+    pub fn to_json_path(&self) -> String {
+        let mut path = "$".to_string();
+        for step in &self.0 {
+            match step {
+                JsonPathStep::Key(key) if is_plain_key(key) => path = format!("{}.{}", path, key),
+                JsonPathStep::Key(key) => {
+                    path = format!("{}.\"{}\"", path, key.replace('"', "\\\""))
+                }
+                JsonPathStep::Index(index) => path = format!("{}[{}]", path, index),
+            }
+        }
+        path
+    }
+
+    /// The path as the text array `#>>` of PostgreSQL takes, such as `{user,"first name",0}`.
+    pub fn to_text_array(&self) -> String {
+        let elements = self
+            .0
+            .iter()
+            .map(|step| match step {
+                JsonPathStep::Key(key) if is_plain_key(key) => key.clone(),
+                JsonPathStep::Key(key) => {
+                    format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+                }
+                JsonPathStep::Index(index) => index.to_string(),
+            })
+            .collect::<Vec<String>>();
+        format!("{{{}}}", elements.join(","))
+    }
+}
+
+/// The path without `$`, such as `.user.id`, to name the column of the extracted value after.
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.to_json_path()[1..])
+    }
+}
+
+fn is_plain_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Parses `.user."first name"[0]`.
+fn parse_dots(input: &str) -> anyhow::Result<Vec<JsonPathStep>> {
+    let mut steps = vec![];
+    let mut rest = input;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (index, after) = after
+                .split_once(']')
+                .ok_or_else(|| anyhow!("`[` is not closed with `]`"))?;
+            steps.push(JsonPathStep::Index(
+                index
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("`{}` is not an array index", index))?,
+            ));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(".\"") {
+            let (key, after) = after
+                .split_once('"')
+                .ok_or_else(|| anyhow!("`\"` is not closed"))?;
+            steps.push(JsonPathStep::Key(key.to_string()));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = after[..end].trim();
+            if key.is_empty() {
+                return Err(anyhow!("a key is missing in `{}`", input));
+            }
+            steps.push(JsonPathStep::Key(key.to_string()));
+            rest = &after[end..];
+        } else {
+            return Err(anyhow!("`{}` is not a path such as $.user.id", input));
+        }
+    }
+    Ok(steps)
+}
+
+/// Parses `->'user'->>'id'` or `-> 'tags' -> 0`.
+fn parse_arrows(input: &str) -> anyhow::Result<Vec<JsonPathStep>> {
+    input
+        .split("->")
+        .skip(1)
+        .map(|step| {
+            let step = step.strip_prefix('>').unwrap_or(step).trim();
+            match step.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(key) => Ok(JsonPathStep::Key(key.replace("''", "'"))),
+                None => step
+                    .parse()
+                    .map(JsonPathStep::Index)
+                    .map_err(|_| anyhow!("`{}` is not a quoted key or an index", step)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JsonPath, JsonPathStep};
+
+    fn key(key: &str) -> JsonPathStep {
+        JsonPathStep::Key(key.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        let path = JsonPath(vec![key("user"), key("tags"), JsonPathStep::Index(0)]);
+        assert_eq!(JsonPath::parse("$.user.tags[0]").unwrap(), path);
+        assert_eq!(JsonPath::parse(" user.tags[0] ").unwrap(), path);
+        assert_eq!(JsonPath::parse("->'user'->'tags'->>0").unwrap(), path);
+        assert_eq!(
+            JsonPath::parse("$.\"first name\"").unwrap(),
+            JsonPath(vec![key("first name")])
+        );
+        assert!(JsonPath::parse("$").is_err());
+        assert!(JsonPath::parse("$.user[x]").is_err());
+        assert!(JsonPath::parse("$.user..id").is_err());
+        assert!(JsonPath::parse("->user").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let path = JsonPath(vec![key("user"), key("first name"), JsonPathStep::Index(2)]);
+        assert_eq!(path.to_json_path(), "$.user.\"first name\"[2]");
+        assert_eq!(path.to_text_array(), "{user,\"first name\",2}");
+        assert_eq!(path.to_string(), ".user.\"first name\"[2]");
+    }
+}
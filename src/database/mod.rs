@@ -1,38 +1,78 @@
+pub mod error;
+pub mod json_path;
+#[cfg(feature = "mysql")]
 pub mod mysql;
+#[cfg(feature = "postgres")]
 pub mod postgres;
+pub mod query_builder;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+pub use error::{error_position, ErrorDetails, ErrorKind, ErrorPosition, FailedQuery};
+pub use json_path::JsonPath;
+#[cfg(feature = "mysql")]
 pub use mysql::MySqlPool;
+#[cfg(feature = "postgres")]
 pub use postgres::PostgresPool;
+pub use query_builder::QueryBuilder;
+#[cfg(feature = "sqlite")]
 pub use sqlite::SqlitePool;
 
+const ROWS_PER_STATEMENT: usize = 100;
+
+/// The size of the pool used for browsing, which is the default of sqlx.
+pub const MAX_CONNECTIONS: u32 = 10;
+
+use crate::charset::Charsets;
 use crate::tree::{Child, Database, Table};
 use async_trait::async_trait;
+use std::sync::Mutex;
 
 #[async_trait]
 pub trait Pool: Send + Sync {
     async fn execute(&self, query: &String) -> anyhow::Result<ExecuteResult>;
-    async fn get_databases(&self) -> anyhow::Result<Vec<Database>>;
+    /// Executes `session` and then `query` on the same connection.
+    async fn execute_in_session(
+        &self,
+        session: &[String],
+        query: &str,
+    ) -> anyhow::Result<ExecuteResult>;
+    /// Reads the rows of `query` as they are stored, so that they can be written back as they
+    /// were with `literal`. Fails when a value can't be decoded, rather than writing back a
+    /// placeholder.
+    async fn fetch_values(&self, query: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<Value>>)>;
+    /// Executes `statements` in a transaction, which is rolled back when one of them fails, and
+    /// returns the rows each of them returns as `fetch_values` reads them.
+    async fn execute_in_transaction(
+        &self,
+        statements: &[String],
+    ) -> anyhow::Result<Vec<Vec<Vec<Value>>>>;
+    /// The columns of the primary key of `table`, or of a unique key when it has none, where
+    /// `table` is the name as written in a statement. `None` when it has neither.
+    async fn get_key_columns(&self, table: &str) -> anyhow::Result<Option<Vec<String>>>;
+    async fn get_database_names(&self) -> anyhow::Result<Vec<String>>;
+    /// The databases with their tables, listed one database after another. A database whose
+    /// tables could not be listed is kept with the error.
+    async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
+        let mut databases = vec![];
+        for database in self.get_database_names().await? {
+            let tables = self.get_tables(database.clone()).await;
+            databases.push(Database::from_tables(database, tables));
+        }
+        Ok(databases)
+    }
     async fn get_tables(&self, database: String) -> anyhow::Result<Vec<Child>>;
+    /// Reads a page of the records of `query`, from its offset.
     async fn get_records(
         &self,
-        database: &Database,
-        table: &Table,
-        page: u16,
-        filter: Option<String>,
-        orders: Option<String>,
+        query: &QueryBuilder,
     ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)>;
     async fn get_columns(
         &self,
         database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
-    async fn get_total_row_count(
-        &self,
-        database: &Database,
-        table: &Table,
-        filter: Option<String>,
-    ) -> anyhow::Result<usize>;
+    async fn get_total_row_count(&self, query: &QueryBuilder) -> anyhow::Result<usize>;
     async fn get_constraints(
         &self,
         database: &Database,
@@ -49,12 +89,94 @@ pub trait Pool: Send + Sync {
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
     async fn get_definition(&self, database: &Database, table: &Table) -> anyhow::Result<String>;
+    async fn get_locks(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
+    async fn get_variables(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
+    async fn get_version(&self) -> anyhow::Result<String>;
+    /// The character sets and collations of `table`, its database, and its text columns.
+    async fn get_charsets(&self, database: &Database, table: &Table) -> anyhow::Result<Charsets>;
+    /// The comment on `table`, or `None` if it has none or the database has no comments.
+    async fn get_table_comment(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Option<String>>;
+    /// The tables `table` inherits from or is a partition of, and the tables inheriting from it.
+    async fn get_inheritance(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Inheritance>;
+    /// The statement setting the comment on `table`, or on its `column`. An empty comment
+    /// removes it.
+    async fn comment_statement(
+        &self,
+        database: &Database,
+        table: &Table,
+        column: Option<&str>,
+        comment: &str,
+    ) -> anyhow::Result<String>;
+    /// The journal mode of SQLite, or `None` for other databases.
+    async fn get_journal_mode(&self) -> anyhow::Result<Option<String>>;
+    /// The query `get_records` executes.
+    fn records_query(&self, query: &QueryBuilder) -> String;
+    /// The clause reading `limit` rows from `offset`.
+    fn limit_clause(&self, limit: usize, offset: usize) -> String;
+    /// The session variables the SQL editor lists even before they are set.
+    fn session_variable_names(&self) -> Vec<&'static str>;
+    /// The statement showing the query plan of `query`.
+    fn explain_query(&self, query: &str) -> String;
+    fn quote_identifier(&self, identifier: &str) -> String;
+    /// The clause appended to an `INSERT` to return `columns` of the inserted rows, or `None`
+    /// when the database can't return them.
+    fn returning_clause(&self, columns: &[String]) -> Option<String>;
+    fn table_identifier(&self, database: &Database, table: &Table) -> String;
+    /// The literal of the binary value whose hex digits are `hex`, to be used in a condition.
+    fn binary_literal(&self, hex: &str) -> String;
+    /// `value` written as a literal of the database.
+    fn literal(&self, value: &Value) -> String {
+        match value {
+            Value::Null => "NULL".to_string(),
+            Value::Number(number) => number.clone(),
+            Value::Text(text) => string_literal(text),
+            Value::Binary(bytes) => self.binary_literal(&hex_string(bytes)[2..]),
+        }
+    }
+    /// `value`, as shown in the records, as a literal of the database, where `NULL` is the null
+    /// value and the hex digits of a value of a `binary` column are the bytes.
+    fn cell_literal(&self, value: &str, binary: bool) -> String {
+        match binary_hex(value).filter(|_| binary) {
+            Some(hex) => self.binary_literal(hex),
+            None if value == "NULL" => "NULL".to_string(),
+            None => self.literal(&Value::Text(value.to_string())),
+        }
+    }
+    /// The value at `path` in the JSON of `column`, as text.
+    fn json_path_expression(&self, column: &str, path: &JsonPath) -> String;
+    fn regex_condition(&self, column: &str, pattern: &str, ignore_case: bool) -> String;
+    /// A condition matching `column` to `pattern` ignoring case, where `%` and `_` are
+    /// wildcards as in `LIKE`.
+    fn ignore_case_condition(&self, column: &str, pattern: &str) -> String;
+    /// A handle to the same pool, which can be moved to a background task.
+    fn clone_box(&self) -> Box<dyn Pool>;
     async fn close(&self);
 }
 
+/// A value as it is stored, read to be written back, possibly to another database.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    /// A number as the database writes it, such as `-1.5`.
+    Number(String),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteResult {
     Read {
         headers: Vec<String>,
+        /// The type of each column as the driver reports it.
+        types: Vec<String>,
         rows: Vec<Vec<String>>,
         database: Database,
         table: Table,
@@ -64,14 +186,641 @@ pub enum ExecuteResult {
     },
 }
 
+/// How a table is related to other tables by inheritance or partitioning.
+#[derive(Debug, Default, PartialEq)]
+pub struct Inheritance {
+    pub parents: Vec<String>,
+    /// The bounds of the partition, if the table is a partition of its parent.
+    pub partition_bound: Option<String>,
+    /// The partition key, if the table is partitioned.
+    pub partition_key: Option<String>,
+    pub children: Vec<String>,
+}
+
+impl Inheritance {
+    /// One line for the parents and one for the children, if there are any.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if !self.parents.is_empty() {
+            lines.push(match &self.partition_bound {
+                Some(bound) => format!("partition of {} {}", self.parents.join(", "), bound),
+                None => format!("inherits from {}", self.parents.join(", ")),
+            });
+        }
+        match &self.partition_key {
+            Some(key) => lines.push(format!(
+                "partitioned by {}: {}",
+                key,
+                if self.children.is_empty() {
+                    "no partitions".to_string()
+                } else {
+                    self.children.join(", ")
+                }
+            )),
+            None if !self.children.is_empty() => {
+                lines.push(format!("inherited by {}", self.children.join(", ")))
+            }
+            None => {}
+        }
+        lines
+    }
+}
+
 pub trait TableRow: std::marker::Send {
     fn fields(&self) -> Vec<String>;
     fn columns(&self) -> Vec<String>;
 }
 
+/// Quotes `value` as a string literal, even when it is `NULL`.
+pub fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The parts of `name`, a table name as written in a statement such as `"app"."users"`, without
+/// their quotes.
+pub fn identifier_parts(name: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut quote = None;
+    for c in name.trim().chars() {
+        match (quote, c) {
+            (Some(end), c) if c == end => quote = None,
+            (Some(_), c) => parts.last_mut().unwrap().push(c),
+            (None, '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '.') => parts.push(String::new()),
+            (None, c) if !c.is_whitespace() => parts.last_mut().unwrap().push(c),
+            (None, _) => {}
+        }
+    }
+    parts
+}
+
+/// Formats `literals`, values already written as literals of the database, as an SQL condition
+/// on `column`, which must already be quoted, such as `column IN (...)`. `NULL`, which `IN` never
+/// matches, becomes `column IS NULL`, and no values become a condition which is always false.
+pub fn in_list(column: &str, literals: &[String]) -> String {
+    let null = literals.iter().any(|literal| literal == "NULL");
+    let values = literals
+        .iter()
+        .filter(|literal| literal.as_str() != "NULL")
+        .cloned()
+        .collect::<Vec<String>>();
+    match (values.is_empty(), null) {
+        (true, true) => format!("{} IS NULL", column),
+        (true, false) => "1 = 0".to_string(),
+        (false, true) => format!(
+            "({} IN ({}) OR {} IS NULL)",
+            column,
+            values.join(", "),
+            column
+        ),
+        (false, false) => format!("{} IN ({})", column, values.join(", ")),
+    }
+}
+
+/// Builds multi-row INSERT statements. `table` and `columns` must already be quoted.
+pub fn insert_statements(table: &str, columns: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    rows.chunks(ROWS_PER_STATEMENT)
+        .map(|chunk| {
+            format!(
+                "INSERT INTO {} ({}) VALUES {}",
+                table,
+                columns.join(", "),
+                chunk
+                    .iter()
+                    .map(|row| format!("({})", row.join(", ")))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })
+        .collect()
+}
+
+/// The names of the generated columns among `columns` listed by `get_columns`, which can't be
+/// written to.
+pub fn generated_columns(columns: &[Box<dyn TableRow>]) -> Vec<String> {
+    columns
+        .iter()
+        .filter_map(|column| {
+            let values = column.columns();
+            let generated = column
+                .fields()
+                .iter()
+                .position(|field| field == "generated")
+                .and_then(|index| values.get(index))?;
+            (!generated.is_empty()).then(|| values.first().cloned().unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Built-in functions which change data, the server or the session, so a `SELECT` calling them is
+/// treated as a write.
+const WRITING_FUNCTIONS: [&str; 19] = [
+    "NEXTVAL",
+    "SETVAL",
+    "SET_CONFIG",
+    "PG_TERMINATE_BACKEND",
+    "PG_CANCEL_BACKEND",
+    "PG_RELOAD_CONF",
+    "PG_ROTATE_LOGFILE",
+    "PG_SWITCH_WAL",
+    "PG_PROMOTE",
+    "PG_FILE_WRITE",
+    "PG_LOGICAL_EMIT_MESSAGE",
+    "LO_IMPORT",
+    "LO_EXPORT",
+    "LO_UNLINK",
+    "DBLINK_EXEC",
+    "GET_LOCK",
+    "RELEASE_LOCK",
+    "RELEASE_ALL_LOCKS",
+    "LOAD_EXTENSION",
+];
+/// Prefixes of the names of built-in functions which change data, the server or the session.
+const WRITING_FUNCTION_PREFIXES: [&str; 4] =
+    ["PG_ADVISORY_", "PG_STAT_RESET", "PG_CREATE_", "PG_DROP_"];
+
+/// Returns true if `query` only reads data, so it can run without confirmation on a protected
+/// connection. Unknown statements, more than one statement, `SELECT ... INTO`, locking reads and
+/// calls to built-in functions which write, such as `nextval` or `pg_terminate_backend`, are
+/// treated as writes. Functions defined in the database are not known, so a `SELECT` calling
+/// one which writes is still treated as a read.
+pub fn is_read_only(query: &str) -> bool {
+    let Some(words) = keywords(query) else {
+        return false;
+    };
+    let read = matches!(
+        words.first().map(String::as_str),
+        Some("SELECT" | "SHOW" | "DESCRIBE" | "DESC")
+    );
+    read && !words.iter().any(|word| word == "INTO")
+        && !words.iter().any(|word| {
+            WRITING_FUNCTIONS.contains(&word.as_str())
+                || WRITING_FUNCTION_PREFIXES
+                    .iter()
+                    .any(|prefix| word.starts_with(prefix))
+        })
+        && !words.windows(2).any(|pair| {
+            matches!(
+                [pair[0].as_str(), pair[1].as_str()],
+                ["FOR", "UPDATE" | "SHARE" | "NO" | "KEY"] | ["LOCK", "IN"]
+            )
+        })
+}
+
+/// The upper-cased words of `query` outside literals, quoted identifiers and comments, or `None`
+/// if it holds more than one statement.
+fn keywords(query: &str) -> Option<Vec<String>> {
+    Some(
+        nested_keywords(query)?
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect(),
+    )
+}
+
+/// The words of `query` as `keywords` reads them which are not in parentheses, such as in a
+/// subquery or a function call, or `None` if it holds more than one statement.
+pub fn top_level_keywords(query: &str) -> Option<Vec<String>> {
+    Some(
+        nested_keywords(query)?
+            .into_iter()
+            .filter(|(_, depth)| *depth == 0)
+            .map(|(word, _)| word)
+            .collect(),
+    )
+}
+
+/// The words of `query` as `keywords` reads them, with how many parentheses each is in.
+fn nested_keywords(query: &str) -> Option<Vec<(String, usize)>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut depth = 0usize;
+    let mut ended = false;
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            if ended {
+                return None;
+            }
+            word.push(c.to_ascii_uppercase());
+            continue;
+        }
+        if !word.is_empty() {
+            words.push((std::mem::take(&mut word), depth));
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                if ended {
+                    return None;
+                }
+                // A doubled quote escapes itself, so it is read as two quoted parts.
+                for inner in chars.by_ref() {
+                    if inner == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for inner in chars.by_ref() {
+                    if inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for inner in chars.by_ref() {
+                    if previous == '*' && inner == '/' {
+                        break;
+                    }
+                    previous = inner;
+                }
+            }
+            ';' => ended = true,
+            c if c.is_whitespace() => {}
+            _ if ended => return None,
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    if !word.is_empty() {
+        words.push((word, depth));
+    }
+    Some(words)
+}
+
+/// Returns true if `type_name`, as listed in the column properties, is a numeric type.
+pub fn is_numeric_type(type_name: &str) -> bool {
+    let type_name = type_name.trim().to_lowercase();
+    let base = type_name
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    matches!(
+        base,
+        "tinyint"
+            | "smallint"
+            | "mediumint"
+            | "int"
+            | "integer"
+            | "bigint"
+            | "int2"
+            | "int4"
+            | "int8"
+            | "serial"
+            | "smallserial"
+            | "bigserial"
+            | "decimal"
+            | "dec"
+            | "numeric"
+            | "real"
+            | "float"
+            | "float4"
+            | "float8"
+            | "double"
+            | "money"
+    )
+}
+
+/// Returns true if `type_name`, as listed in the column properties or reported by the driver,
+/// is a binary type.
+pub fn is_binary_type(type_name: &str) -> bool {
+    let type_name = type_name.trim().to_lowercase();
+    let base = type_name
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    matches!(
+        base,
+        "bytea" | "binary" | "varbinary" | "blob" | "tinyblob" | "mediumblob" | "longblob"
+    )
+}
+
+/// Returns true if `type_name`, as listed in the column properties, is `json` or `jsonb`.
+pub fn is_json_type(type_name: &str) -> bool {
+    let type_name = type_name.trim().to_lowercase();
+    matches!(type_name.as_str(), "json" | "jsonb")
+}
+
+/// Returns true if `type_name`, as listed in the column properties, is a spatial type of
+/// PostGIS or MySQL.
+pub fn is_geometry_type(type_name: &str) -> bool {
+    let type_name = type_name.trim().to_lowercase();
+    let base = type_name
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    matches!(
+        base,
+        "geometry"
+            | "geography"
+            | "point"
+            | "linestring"
+            | "polygon"
+            | "multipoint"
+            | "multilinestring"
+            | "multipolygon"
+            | "geometrycollection"
+            | "geomcollection"
+    )
+}
+
+/// The select list which reads the spatial columns of `columns`, the names and types of the
+/// columns of a table in order, as WKT with `ST_AsText`, or `None` if there is no spatial column.
+pub fn geometry_select_list(
+    columns: &[(String, String)],
+    quote_identifier: impl Fn(&str) -> String,
+) -> Option<String> {
+    if !columns.iter().any(|(_, r#type)| is_geometry_type(r#type)) {
+        return None;
+    }
+    Some(
+        columns
+            .iter()
+            .map(|(name, r#type)| {
+                let name = quote_identifier(name);
+                if is_geometry_type(r#type) {
+                    format!("ST_AsText({name}) AS {name}")
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", "),
+    )
+}
+
+/// `query` with the spatial columns of its table read as WKT, so that they are not shown as
+/// binary.
+pub async fn with_geometry_as_text(
+    pool: &dyn Pool,
+    query: &QueryBuilder,
+) -> anyhow::Result<QueryBuilder> {
+    let columns = pool
+        .get_columns(&query.database, &query.table)
+        .await?
+        .iter()
+        .filter_map(|column| {
+            let columns = column.columns();
+            Some((columns.first()?.clone(), columns.get(1)?.clone()))
+        })
+        .collect::<Vec<(String, String)>>();
+    Ok(
+        match geometry_select_list(&columns, |name| pool.quote_identifier(name)) {
+            Some(list) => query.clone().columns(list),
+            None => query.clone(),
+        },
+    )
+}
+
+/// A short form of the WKT value of a spatial column for a table cell. Points are shown as they
+/// are, and the other geometries by their type and the number of their points.
+pub fn geometry_summary(wkt: &str) -> String {
+    let Some(open) = wkt.find('(') else {
+        return wkt.to_string();
+    };
+    let kind = wkt[..open].trim();
+    if kind
+        .split_whitespace()
+        .next()
+        .is_some_and(|kind| kind.eq_ignore_ascii_case("POINT"))
+    {
+        return wkt.to_string();
+    }
+    format!("{} [{} points]", kind, wkt[open..].split(',').count())
+}
+
+/// The hex digits of a binary value as it is shown, `\x0a1b` for PostgreSQL and `0x0A1B` for the
+/// others.
+pub fn binary_hex(value: &str) -> Option<&str> {
+    let hex = value
+        .strip_prefix("\\x")
+        .or_else(|| value.strip_prefix("0x"))?;
+    (hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
+}
+
+/// Shows `bytes` as `0x` followed by the hex digits, as MySQL does.
+pub fn hex_string(bytes: &[u8]) -> String {
+    format!(
+        "0x{}",
+        bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<String>()
+    )
+}
+
+/// The errors of the values which failed to decode since they were last taken, each once.
+static DECODE_ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// How many distinct decode errors are kept until they are taken.
+const MAX_DECODE_ERRORS: usize = 20;
+
+/// The text shown in place of a value which failed to decode, so that the rest of the result is
+/// still shown. The error is kept for [`take_decode_errors`] and written to the log.
+pub fn decode_error_cell(column: &str, error: &anyhow::Error) -> String {
+    crate::debug!("failed to decode `{}`: {}", column, error);
+    let message = format!("`{}`: {}", column, error);
+    let mut errors = DECODE_ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+    if errors.len() < MAX_DECODE_ERRORS && !errors.contains(&message) {
+        errors.push(message);
+    }
+    format!("<decode error: {}>", error)
+}
+
+/// The decode errors since the last call, so they can be shown once the result is drawn.
+pub fn take_decode_errors() -> Vec<String> {
+    std::mem::take(&mut *DECODE_ERRORS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
 #[macro_export]
 macro_rules! get_or_null {
     ($value:expr) => {
         $value.map_or("NULL".to_string(), |v| v.to_string())
     };
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        binary_hex, decode_error_cell, geometry_select_list, geometry_summary, hex_string,
+        identifier_parts, in_list, insert_statements, is_binary_type, is_geometry_type,
+        is_json_type, is_numeric_type, is_read_only, string_literal, take_decode_errors,
+        Inheritance,
+    };
+
+    #[test]
+    fn test_inheritance_lines() {
+        assert!(Inheritance::default().lines().is_empty());
+        assert_eq!(
+            Inheritance {
+                parents: vec!["measurements".to_string()],
+                partition_bound: Some(
+                    "FOR VALUES FROM ('2025-01-01') TO ('2026-01-01')".to_string()
+                ),
+                partition_key: Some("RANGE (logged_on)".to_string()),
+                children: vec![],
+            }
+            .lines(),
+            [
+                "partition of measurements FOR VALUES FROM ('2025-01-01') TO ('2026-01-01')",
+                "partitioned by RANGE (logged_on): no partitions"
+            ]
+        );
+        assert_eq!(
+            Inheritance {
+                parents: vec!["cities".to_string(), "places".to_string()],
+                children: vec!["capitals".to_string()],
+                ..Inheritance::default()
+            }
+            .lines(),
+            ["inherits from cities, places", "inherited by capitals"]
+        );
+    }
+
+    #[test]
+    fn test_identifier_parts() {
+        assert_eq!(identifier_parts("users"), ["users"]);
+        assert_eq!(identifier_parts(r#""app"."my.users""#), ["app", "my.users"]);
+        assert_eq!(identifier_parts("`app` . [users]"), ["app", "users"]);
+        assert_eq!(string_literal("NULL"), "'NULL'");
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let error = anyhow::anyhow!("invalid utf-8");
+        assert_eq!(
+            decode_error_cell("decoded_name", &error),
+            "<decode error: invalid utf-8>"
+        );
+        decode_error_cell("decoded_name", &error);
+        let errors = take_decode_errors();
+        assert_eq!(
+            errors
+                .iter()
+                .filter(|e| *e == "`decoded_name`: invalid utf-8")
+                .count(),
+            1
+        );
+        assert!(!take_decode_errors().contains(&errors[0]));
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        assert!(is_read_only("select * from users"));
+        assert!(is_read_only("  (SELECT 1) UNION (SELECT 2)"));
+        assert!(is_read_only("show tables"));
+        assert!(!is_read_only("UPDATE users SET name = 'a'"));
+        assert!(!is_read_only("selection"));
+        assert!(!is_read_only(
+            "WITH d AS (DELETE FROM users RETURNING *) SELECT * FROM d"
+        ));
+        assert!(!is_read_only(""));
+    }
+
+    #[test]
+    fn test_is_read_only_statements() {
+        assert!(is_read_only("SELECT 1;"));
+        assert!(is_read_only("SELECT 1; -- done"));
+        assert!(is_read_only(
+            "SELECT ';' AS a, \"into\" FROM t /* for update */"
+        ));
+        assert!(!is_read_only("SELECT 1; DELETE FROM users"));
+        assert!(!is_read_only("select 1;delete from users"));
+        assert!(!is_read_only("SELECT * INTO backup FROM users"));
+        assert!(!is_read_only(
+            "SELECT id FROM users INTO OUTFILE '/tmp/users'"
+        ));
+        assert!(!is_read_only("SELECT * FROM users FOR UPDATE"));
+        assert!(!is_read_only("select * from users for share"));
+        assert!(!is_read_only("SELECT * FROM users FOR NO KEY UPDATE"));
+        assert!(!is_read_only("SELECT * FROM users LOCK IN SHARE MODE"));
+        assert!(!is_read_only("SELECT pg_terminate_backend(42)"));
+        assert!(!is_read_only("SELECT nextval('users_id_seq')"));
+        assert!(!is_read_only("SELECT pg_advisory_lock(1)"));
+        assert!(!is_read_only("SELECT GET_LOCK('job', 10)"));
+        assert!(is_read_only("SELECT \"nextval\" FROM counters"));
+    }
+
+    #[test]
+    fn test_is_numeric_type() {
+        assert!(is_numeric_type("int(11) unsigned"));
+        assert!(is_numeric_type("decimal(10,2)"));
+        assert!(is_numeric_type("double precision"));
+        assert!(is_numeric_type("INTEGER"));
+        assert!(!is_numeric_type("interval"));
+        assert!(!is_numeric_type("varchar(255)"));
+        assert!(!is_numeric_type(""));
+    }
+
+    #[test]
+    fn test_binary_values() {
+        assert!(is_binary_type("varbinary(16)"));
+        assert!(is_binary_type("BYTEA"));
+        assert!(!is_binary_type("varchar(16)"));
+        assert!(is_json_type("JSONB"));
+        assert!(!is_json_type("text"));
+        assert_eq!(hex_string(&[0, 10, 255]), "0x000AFF");
+        assert_eq!(binary_hex("0x000AFF"), Some("000AFF"));
+        assert_eq!(binary_hex(r"\x0aff"), Some("0aff"));
+        assert_eq!(binary_hex("0x0g"), None);
+        assert_eq!(binary_hex("deadbeef"), None);
+    }
+
+    #[test]
+    fn test_geometry_values() {
+        assert!(is_geometry_type("point"));
+        assert!(is_geometry_type("geometry(Polygon,4326)"));
+        assert!(!is_geometry_type("text"));
+        let columns = [
+            ("id".to_string(), "int".to_string()),
+            ("area".to_string(), "polygon".to_string()),
+        ];
+        assert_eq!(
+            geometry_select_list(&columns, |name| format!("`{}`", name)).as_deref(),
+            Some("`id`, ST_AsText(`area`) AS `area`")
+        );
+        assert_eq!(geometry_select_list(&columns[..1], str::to_string), None);
+        assert_eq!(geometry_summary("POINT(1 2)"), "POINT(1 2)");
+        assert_eq!(
+            geometry_summary("POLYGON((0 0,1 0,1 1,0 0))"),
+            "POLYGON [4 points]"
+        );
+        assert_eq!(
+            geometry_summary("MULTIPOINT((0 0),(1 1))"),
+            "MULTIPOINT [2 points]"
+        );
+        assert_eq!(geometry_summary("LINESTRING EMPTY"), "LINESTRING EMPTY");
+    }
+
+    #[test]
+    fn test_in_list() {
+        assert_eq!(
+            in_list("id", &["'1'".to_string(), "'it''s'".to_string()]),
+            "id IN ('1', 'it''s')"
+        );
+        assert_eq!(
+            in_list(
+                "id",
+                &["'1'".to_string(), "NULL".to_string(), "'NULL'".to_string()]
+            ),
+            "(id IN ('1', 'NULL') OR id IS NULL)"
+        );
+        assert_eq!(
+            in_list("id", &["NULL".to_string(), "NULL".to_string()]),
+            "id IS NULL"
+        );
+        assert_eq!(in_list("id", &[]), "1 = 0");
+    }
+
+    #[test]
+    fn test_insert_statements() {
+        let rows = vec![vec!["'1'".to_string()]; 150];
+        let statements = insert_statements("`users`", &["`id`".to_string()], &rows);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[1].starts_with("INSERT INTO `users` (`id`) VALUES ('1'), "));
+    }
+}
@@ -1,14 +1,23 @@
 use crate::get_or_null;
 
-use super::{ExecuteResult, Pool, TableRow};
+use super::{
+    decode_error_cell, hex_string, identifier_parts, is_binary_type, is_geometry_type,
+    with_geometry_as_text, ExecuteResult, Inheritance, JsonPath, Pool, QueryBuilder, TableRow,
+    Value,
+};
+use crate::charset::{Charset, Charsets};
 use crate::tree::{Child, Database, Table};
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use futures::TryStreamExt;
-use sqlx::mysql::{MySqlColumn, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::mysql::{MySqlColumn, MySqlConnectOptions, MySqlPoolOptions, MySqlRow};
+use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
 use std::time::Duration;
 
+/// Appended to a value which does not decode as the type of its column, such as the zero date.
+const INVALID_VALUE_MARKER: &str = " (!)";
+
+#[derive(Clone)]
 pub struct MySqlPool {
     pool: sqlx::mysql::MySqlPool,
     limit_size: usize,
@@ -16,14 +25,16 @@ pub struct MySqlPool {
 
 impl MySqlPool {
     pub async fn new(
-        database_url: &str,
+        options: MySqlConnectOptions,
         limit_size: usize,
         timeout_second: u64,
+        max_connections: u32,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             pool: MySqlPoolOptions::new()
+                .max_connections(max_connections)
                 .acquire_timeout(Duration::from_secs(timeout_second))
-                .connect(database_url)
+                .connect_with(options)
                 .await?,
             limit_size,
         })
@@ -51,6 +62,8 @@ pub struct Column {
     null: Option<String>,
     default: Option<String>,
     comment: Option<String>,
+    /// The expression of a generated column.
+    generated: Option<String>,
 }
 
 impl TableRow for Column {
@@ -61,6 +74,7 @@ impl TableRow for Column {
             "null".to_string(),
             "default".to_string(),
             "comment".to_string(),
+            "generated".to_string(),
         ]
     }
 
@@ -81,6 +95,9 @@ impl TableRow for Column {
             self.comment
                 .as_ref()
                 .map_or(String::new(), |comment| comment.to_string()),
+            self.generated
+                .as_ref()
+                .map_or(String::new(), |generated| generated.to_string()),
         ]
     }
 }
@@ -150,14 +167,113 @@ impl TableRow for Index {
     }
 }
 
+pub struct Lock {
+    session: Option<String>,
+    object: Option<String>,
+    r#type: Option<String>,
+    mode: Option<String>,
+    status: Option<String>,
+    blocked_by: Option<String>,
+    query: Option<String>,
+}
+
+impl TableRow for Lock {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "session".to_string(),
+            "object".to_string(),
+            "type".to_string(),
+            "mode".to_string(),
+            "status".to_string(),
+            "blocked_by".to_string(),
+            "query".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.session
+                .as_ref()
+                .map_or(String::new(), |session| session.to_string()),
+            self.object
+                .as_ref()
+                .map_or(String::new(), |object| object.to_string()),
+            self.r#type
+                .as_ref()
+                .map_or(String::new(), |r#type| r#type.to_string()),
+            self.mode
+                .as_ref()
+                .map_or(String::new(), |mode| mode.to_string()),
+            self.status
+                .as_ref()
+                .map_or(String::new(), |status| status.to_string()),
+            self.blocked_by
+                .as_ref()
+                .map_or(String::new(), |blocked_by| blocked_by.to_string()),
+            self.query
+                .as_ref()
+                .map_or(String::new(), |query| query.to_string()),
+        ]
+    }
+}
+
+pub struct Variable {
+    name: Option<String>,
+    value: Option<String>,
+    unit: Option<String>,
+    description: Option<String>,
+}
+
+impl TableRow for Variable {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "value".to_string(),
+            "unit".to_string(),
+            "description".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name
+                .as_ref()
+                .map_or(String::new(), |name| name.to_string()),
+            self.value
+                .as_ref()
+                .map_or(String::new(), |value| value.to_string()),
+            self.unit
+                .as_ref()
+                .map_or(String::new(), |unit| unit.to_string()),
+            self.description
+                .as_ref()
+                .map_or(String::new(), |description| description.to_string()),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for MySqlPool {
     async fn execute(&self, query: &String) -> anyhow::Result<ExecuteResult> {
+        self.execute_in_session(&[], query).await
+    }
+
+    async fn execute_in_session(
+        &self,
+        session: &[String],
+        query: &str,
+    ) -> anyhow::Result<ExecuteResult> {
         let query = query.trim();
+        let mut connection = self.pool.acquire().await?;
+        for statement in session {
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
 
-        if query.to_uppercase().starts_with("SELECT") {
-            let mut rows = sqlx::query(query).fetch(&self.pool);
+        let keyword = query.to_uppercase();
+        if keyword.starts_with("SELECT") || keyword.starts_with("EXPLAIN") {
+            let mut rows = sqlx::query(query).fetch(&mut *connection);
             let mut headers = vec![];
+            let mut types = vec![];
             let mut records = vec![];
             while let Some(row) = rows.try_next().await? {
                 headers = row
@@ -165,51 +281,118 @@ impl Pool for MySqlPool {
                     .iter()
                     .map(|column| column.name().to_string())
                     .collect();
+                types = row
+                    .columns()
+                    .iter()
+                    .map(|column| column.type_info().name().to_string())
+                    .collect();
                 let mut new_row = vec![];
                 for column in row.columns() {
-                    new_row.push(convert_column_value_to_string(&row, column)?)
+                    new_row.push(
+                        convert_column_value_to_string(&row, column)
+                            .unwrap_or_else(|e| decode_error_cell(column.name(), &e)),
+                    )
                 }
                 records.push(new_row)
             }
 
             return Ok(ExecuteResult::Read {
                 headers,
+                types,
                 rows: records,
-                database: Database {
-                    name: "-".to_string(),
-                    children: Vec::new(),
-                },
+                database: Database::new("-".to_string(), Vec::new()),
                 table: Table {
                     name: "-".to_string(),
                     create_time: None,
                     update_time: None,
                     engine: None,
+                    parent: None,
                     schema: None,
                 },
             });
         }
 
-        let result = sqlx::query(query).execute(&self.pool).await?;
+        let result = sqlx::query(query).execute(&mut *connection).await?;
         Ok(ExecuteResult::Write {
             updated_rows: result.rows_affected(),
         })
     }
 
-    async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
-        let databases = sqlx::query("SHOW DATABASES")
+    async fn fetch_values(&self, query: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        let rows = sqlx::query(query).fetch_all(&self.pool).await?;
+        let headers = rows.first().map_or_else(Vec::new, |row| {
+            row.columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect()
+        });
+        let rows = rows
+            .iter()
+            .map(row_values)
+            .collect::<anyhow::Result<Vec<Vec<Value>>>>()?;
+        Ok((headers, rows))
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        statements: &[String],
+    ) -> anyhow::Result<Vec<Vec<Vec<Value>>>> {
+        let mut transaction = self.pool.begin().await?;
+        let mut results = vec![];
+        for statement in statements {
+            let rows = sqlx::query(statement).fetch_all(&mut *transaction).await?;
+            results.push(
+                rows.iter()
+                    .map(row_values)
+                    .collect::<anyhow::Result<Vec<Vec<Value>>>>()?,
+            );
+        }
+        transaction.commit().await?;
+        Ok(results)
+    }
+
+    async fn get_key_columns(&self, table: &str) -> anyhow::Result<Option<Vec<String>>> {
+        let mut parts = identifier_parts(table);
+        let name = parts.pop().unwrap_or_default();
+        let rows = sqlx::query(
+            "
+        SELECT
+            INDEX_NAME,
+            COLUMN_NAME
+        FROM
+            information_schema.STATISTICS
+        WHERE
+            TABLE_SCHEMA = COALESCE(?, DATABASE())
+            AND TABLE_NAME = ?
+            AND NON_UNIQUE = 0
+        ORDER BY
+            INDEX_NAME = 'PRIMARY' DESC, INDEX_NAME, SEQ_IN_INDEX
+        ",
+        )
+        .bind(parts.pop())
+        .bind(&name)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut key = None;
+        let mut columns = vec![];
+        for row in &rows {
+            let index: String = row.try_get(0)?;
+            if *key.get_or_insert_with(|| index.clone()) != index {
+                break;
+            }
+            // The columns of an index on expressions have no name, which can't be a key.
+            columns.push(row.try_get::<Option<String>, _>(1)?);
+        }
+        Ok(key.and(columns.into_iter().collect()))
+    }
+
+    async fn get_database_names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(sqlx::query("SHOW DATABASES")
             .fetch_all(&self.pool)
             .await?
             .iter()
             .map(|table| table.get(0))
-            .collect::<Vec<String>>();
-        let mut list = vec![];
-        for db in databases {
-            list.push(Database::new(
-                db.clone(),
-                self.get_tables(db.clone()).await?,
-            ))
-        }
-        Ok(list)
+            .collect())
     }
 
     async fn get_tables(&self, database: String) -> anyhow::Result<Vec<Child>> {
@@ -222,6 +405,7 @@ impl Pool for MySqlPool {
                 create_time: row.try_get("Create_time")?,
                 update_time: row.try_get("Update_time")?,
                 engine: row.try_get("Engine")?,
+                parent: None,
                 schema: None,
             })
         }
@@ -230,49 +414,9 @@ impl Pool for MySqlPool {
 
     async fn get_records(
         &self,
-        database: &Database,
-        table: &Table,
-        page: u16,
-        filter: Option<String>,
-        orders: Option<String>,
+        query: &QueryBuilder,
     ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let (Some(filter), Some(orders)) = (&filter, &orders) {
-            format!(
-                "SELECT * FROM `{database}`.`{table}` WHERE {filter} {orders} LIMIT {page}, {limit}",
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = self.limit_size,
-                orders = orders
-            )
-        } else if let Some(filter) = filter {
-            format!(
-                "SELECT * FROM `{database}`.`{table}` WHERE {filter} LIMIT {page}, {limit}",
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = self.limit_size,
-            )
-        } else if let Some(orders) = orders {
-            format!(
-                "SELECT * FROM `{database}`.`{table}` {orders} LIMIT {page}, {limit}",
-                database = database.name,
-                table = table.name,
-                orders = orders,
-                page = page,
-                limit = self.limit_size,
-            )
-        } else {
-            format!(
-                "SELECT * FROM `{database}`.`{table}` LIMIT {page}, {limit}",
-                database = database.name,
-                table = table.name,
-                page = page,
-                limit = self.limit_size,
-            )
-        };
+        let query = self.records_query(&with_geometry_as_text(self, query).await?);
         let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
         let mut headers = vec![];
         let mut records = vec![];
@@ -284,34 +428,20 @@ impl Pool for MySqlPool {
                 .collect();
             let mut new_row = vec![];
             for column in row.columns() {
-                new_row.push(convert_column_value_to_string(&row, column)?)
+                new_row.push(
+                    convert_column_value_to_string(&row, column)
+                        .unwrap_or_else(|e| decode_error_cell(column.name(), &e)),
+                )
             }
             records.push(new_row)
         }
         Ok((headers, records))
     }
 
-    async fn get_total_row_count(
-        &self,
-        database: &Database,
-        table: &Table,
-        filter: Option<String>,
-    ) -> anyhow::Result<usize> {
-        let query = if let Some(filter) = &filter {
-            format!(
-                "SELECT COUNT(*) FROM `{database}`.`{table}` WHERE {filter}",
-                database = database.name,
-                table = table.name,
-                filter = filter
-            )
-        } else {
-            format!(
-                "SELECT COUNT(*) FROM `{database}`.`{table}`",
-                database = database.name,
-                table = table.name,
-            )
-        };
-        let res = sqlx::query(query.as_str()).fetch_one(&self.pool).await?;
+    async fn get_total_row_count(&self, query: &QueryBuilder) -> anyhow::Result<usize> {
+        let res = sqlx::query(query.count(self).as_str())
+            .fetch_one(&self.pool)
+            .await?;
         Ok(res.get::<i64, usize>(0) as usize)
     }
 
@@ -324,11 +454,51 @@ impl Pool for MySqlPool {
             "SHOW FULL COLUMNS FROM `{}`.`{}`",
             database.name, table.name
         );
-        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let rows = sqlx::query(query.as_str()).fetch_all(&self.pool).await?;
+        let mut generated = vec![];
+        if rows.iter().any(|row| {
+            row.try_get::<String, _>("Extra")
+                .is_ok_and(|extra| extra.contains("GENERATED"))
+        }) {
+            let mut generated_rows = sqlx::query(
+                "
+            SELECT
+                COLUMN_NAME,
+                EXTRA,
+                GENERATION_EXPRESSION
+            FROM
+                information_schema.COLUMNS
+            WHERE
+                TABLE_SCHEMA = ?
+                AND TABLE_NAME = ?
+                AND EXTRA LIKE '%GENERATED%'
+            ",
+            )
+            .bind(&database.name)
+            .bind(&table.name)
+            .fetch(&self.pool);
+            while let Some(row) = generated_rows.try_next().await? {
+                let extra: String = row.try_get("EXTRA")?;
+                let expression: String = row.try_get("GENERATION_EXPRESSION")?;
+                generated.push((
+                    row.try_get::<String, _>("COLUMN_NAME")?,
+                    format!(
+                        "AS ({}) {}",
+                        expression,
+                        extra.split_whitespace().next().unwrap_or_default()
+                    ),
+                ));
+            }
+        }
         let mut columns: Vec<Box<dyn TableRow>> = vec![];
-        while let Some(row) = rows.try_next().await? {
+        for row in rows {
+            let name: Option<String> = row.try_get("Field")?;
             columns.push(Box::new(Column {
-                name: row.try_get("Field")?,
+                generated: generated
+                    .iter()
+                    .find(|(column, _)| Some(column) == name.as_ref())
+                    .map(|(_, expression)| expression.clone()),
+                name,
                 r#type: row.try_get("Type")?,
                 null: row.try_get("Null")?,
                 default: row.try_get("Default")?,
@@ -441,6 +611,265 @@ impl Pool for MySqlPool {
         Ok(foreign_keys)
     }
 
+    async fn get_locks(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            CAST(l.THREAD_ID AS CHAR) AS session,
+            CONCAT_WS('.', l.OBJECT_SCHEMA, l.OBJECT_NAME) AS object,
+            l.LOCK_TYPE AS type,
+            l.LOCK_MODE AS mode,
+            l.LOCK_STATUS AS status,
+            CAST(GROUP_CONCAT(DISTINCT b.THREAD_ID) AS CHAR) AS blocked_by,
+            (
+                SELECT
+                    s.SQL_TEXT
+                FROM
+                    performance_schema.events_statements_current s
+                WHERE
+                    s.THREAD_ID = l.THREAD_ID
+                ORDER BY
+                    s.EVENT_ID DESC
+                LIMIT
+                    1
+            ) AS query
+        FROM
+            performance_schema.data_locks l
+            LEFT JOIN performance_schema.data_lock_waits w ON w.REQUESTING_ENGINE_LOCK_ID = l.ENGINE_LOCK_ID
+            LEFT JOIN performance_schema.data_locks b ON b.ENGINE_LOCK_ID = w.BLOCKING_ENGINE_LOCK_ID
+        GROUP BY
+            l.ENGINE_LOCK_ID,
+            l.THREAD_ID,
+            l.OBJECT_SCHEMA,
+            l.OBJECT_NAME,
+            l.LOCK_TYPE,
+            l.LOCK_MODE,
+            l.LOCK_STATUS
+        ORDER BY
+            l.THREAD_ID
+        ",
+        )
+        .fetch(&self.pool);
+        let mut locks: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            locks.push(Box::new(Lock {
+                session: row.try_get("session")?,
+                object: row.try_get("object")?,
+                r#type: row.try_get("type")?,
+                mode: row.try_get("mode")?,
+                status: row.try_get("status")?,
+                blocked_by: row.try_get("blocked_by")?,
+                query: row.try_get("query")?,
+            }))
+        }
+        Ok(locks)
+    }
+
+    async fn get_variables(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows = sqlx::query("SHOW GLOBAL VARIABLES").fetch(&self.pool);
+        let mut variables: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            variables.push(Box::new(Variable {
+                name: row.try_get("Variable_name")?,
+                value: row.try_get("Value")?,
+                unit: None,
+                description: None,
+            }))
+        }
+        Ok(variables)
+    }
+
+    fn records_query(&self, query: &QueryBuilder) -> String {
+        query.clone().limit(self.limit_size).select(self)
+    }
+
+    fn limit_clause(&self, limit: usize, offset: usize) -> String {
+        format!("LIMIT {}, {}", offset, limit)
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("`{}`", identifier)
+    }
+
+    fn returning_clause(&self, _columns: &[String]) -> Option<String> {
+        None
+    }
+
+    fn table_identifier(&self, database: &Database, table: &Table) -> String {
+        format!("`{}`.`{}`", database.name, table.name)
+    }
+
+    fn binary_literal(&self, hex: &str) -> String {
+        format!("UNHEX('{}')", hex)
+    }
+
+    fn literal(&self, value: &Value) -> String {
+        match value {
+            // Backslashes start escapes in MySQL strings unless NO_BACKSLASH_ESCAPES is set.
+            Value::Text(text) => format!("'{}'", text.replace('\\', "\\\\").replace('\'', "''")),
+            Value::Null => "NULL".to_string(),
+            Value::Number(number) => number.clone(),
+            Value::Binary(bytes) => self.binary_literal(&hex_string(bytes)[2..]),
+        }
+    }
+
+    fn json_path_expression(&self, column: &str, path: &JsonPath) -> String {
+        format!(
+            "JSON_UNQUOTE(JSON_EXTRACT({}, {}))",
+            self.quote_identifier(column),
+            self.literal(&Value::Text(path.to_json_path()))
+        )
+    }
+
+    fn session_variable_names(&self) -> Vec<&'static str> {
+        vec!["sql_mode", "time_zone"]
+    }
+
+    fn explain_query(&self, query: &str) -> String {
+        format!("EXPLAIN {}", query)
+    }
+
+    fn regex_condition(&self, column: &str, pattern: &str, ignore_case: bool) -> String {
+        // backslashes are escape characters in MySQL string literals
+        format!(
+            "{} REGEXP '{}{}'",
+            column,
+            if ignore_case { "(?i)" } else { "" },
+            pattern.replace('\\', "\\\\").replace('\'', "''")
+        )
+    }
+
+    fn ignore_case_condition(&self, column: &str, pattern: &str) -> String {
+        // LIKE follows the collation of the column, which may be case sensitive.
+        format!(
+            "LOWER({}) LIKE LOWER('{}')",
+            column,
+            pattern.replace('\\', "\\\\").replace('\'', "''")
+        )
+    }
+
+    async fn get_version(&self) -> anyhow::Result<String> {
+        let res = sqlx::query("SELECT VERSION()")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(res.try_get::<String, usize>(0)?)
+    }
+
+    async fn get_charsets(&self, database: &Database, table: &Table) -> anyhow::Result<Charsets> {
+        let database_collation: Option<String> = sqlx::query(
+            "SELECT DEFAULT_COLLATION_NAME FROM information_schema.SCHEMATA WHERE SCHEMA_NAME = ?",
+        )
+        .bind(&database.name)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|row| row.try_get(0))
+        .transpose()?
+        .flatten();
+        let table_collation: Option<String> = sqlx::query(
+            "SELECT TABLE_COLLATION FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|row| row.try_get(0))
+        .transpose()?
+        .flatten();
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            COLUMN_NAME,
+            COLLATION_NAME
+        FROM
+            information_schema.COLUMNS
+        WHERE
+            TABLE_SCHEMA = ?
+            AND TABLE_NAME = ?
+            AND COLLATION_NAME IS NOT NULL
+        ORDER BY
+            ORDINAL_POSITION
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            columns.push((
+                row.try_get("COLUMN_NAME")?,
+                Charset::from_collation(row.try_get("COLLATION_NAME")?),
+            ))
+        }
+        Ok(Charsets {
+            database: Charset::from_collation(database_collation),
+            table: Charset::from_collation(table_collation),
+            columns,
+        })
+    }
+
+    async fn get_table_comment(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Option<String>> {
+        let comment: Option<String> = sqlx::query(
+            "SELECT TABLE_COMMENT FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|row| row.try_get(0))
+        .transpose()?
+        .flatten();
+        Ok(comment.filter(|comment| !comment.is_empty()))
+    }
+
+    async fn get_inheritance(
+        &self,
+        _database: &Database,
+        _table: &Table,
+    ) -> anyhow::Result<Inheritance> {
+        Ok(Inheritance::default())
+    }
+
+    async fn comment_statement(
+        &self,
+        database: &Database,
+        table: &Table,
+        column: Option<&str>,
+        comment: &str,
+    ) -> anyhow::Result<String> {
+        let table_identifier = self.table_identifier(database, table);
+        match column {
+            None => Ok(format!(
+                "ALTER TABLE {} COMMENT = {}",
+                table_identifier,
+                quote_comment(comment)
+            )),
+            Some(column) => {
+                // MODIFY COLUMN replaces the whole definition, which is taken from the table.
+                let definition = self.get_definition(database, table).await?;
+                let column_definition = column_definition(&definition, column)
+                    .ok_or_else(|| anyhow::anyhow!("column `{}` is not found", column))?;
+                Ok(format!(
+                    "ALTER TABLE {} MODIFY COLUMN {} COMMENT {}",
+                    table_identifier,
+                    column_definition,
+                    quote_comment(comment)
+                ))
+            }
+        }
+    }
+
+    async fn get_journal_mode(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn clone_box(&self) -> Box<dyn Pool> {
+        Box::new(self.clone())
+    }
+
     async fn close(&self) {
         self.pool.close().await;
     }
@@ -452,8 +881,46 @@ impl Pool for MySqlPool {
     }
 }
 
+/// The values of `row`. Spatial values are read as the binary MySQL stores them, and values which
+/// only decode leniently, such as the zero date, as stored.
+fn row_values(row: &MySqlRow) -> anyhow::Result<Vec<Value>> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            let i = column.ordinal();
+            if row.try_get_raw(i)?.is_null() {
+                return Ok(Value::Null);
+            }
+            let type_name = column.type_info().name();
+            if is_binary_type(type_name) || is_geometry_type(type_name) {
+                return Ok(Value::Binary(row.try_get_unchecked(i)?));
+            }
+            let value = convert_column_value_to_string(row, column)?;
+            Ok(Value::Text(match row.try_get::<String, _>(i) {
+                Ok(_) => value,
+                Err(_) => value
+                    .strip_suffix(INVALID_VALUE_MARKER)
+                    .map_or(value.clone(), str::to_string),
+            }))
+        })
+        .collect()
+}
+
 fn convert_column_value_to_string(row: &MySqlRow, column: &MySqlColumn) -> anyhow::Result<String> {
     let column_name = column.name();
+    match column.type_info().name() {
+        // DECIMAL is sent as text, which `Decimal` would round beyond 28 significant digits.
+        "DECIMAL" => {
+            let value: Option<&str> = row.try_get_unchecked(column_name)?;
+            return Ok(get_or_null!(value));
+        }
+        // `f32`, which is tried first for FLOAT, would truncate a DOUBLE.
+        "DOUBLE" => {
+            let value: Option<f64> = row.try_get(column_name)?;
+            return Ok(get_or_null!(value));
+        }
+        _ => {}
+    }
 
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<String> = value;
@@ -512,6 +979,20 @@ fn convert_column_value_to_string(row: &MySqlRow, column: &MySqlColumn) -> anyho
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<bool> = value;
         Ok(get_or_null!(value))
+    } else if let Ok(value) = row.try_get(column_name) {
+        let value: Option<&[u8]> = value;
+        Ok(value.map_or("NULL".to_string(), hex_string))
+    } else if let Ok(value) = row.try_get_unchecked(column_name) {
+        // Zero dates and invalid enum values are stored by MySQL but rejected by the decoders, so
+        // they are shown as stored instead of failing the whole row.
+        let value: Option<&[u8]> = value;
+        Ok(value.map_or("NULL".to_string(), |raw| {
+            format!(
+                "{}{}",
+                lenient_value(raw, column.type_info().name()),
+                INVALID_VALUE_MARKER
+            )
+        }))
     } else {
         anyhow::bail!(
             "column type not implemented: `{}` {}",
@@ -520,3 +1001,127 @@ fn convert_column_value_to_string(row: &MySqlRow, column: &MySqlColumn) -> anyho
         )
     }
 }
+
+/// The literal of `raw`, a value of the type `type_name` which does not decode as that type.
+/// Dates are read from the binary protocol without checking that they exist, so `0000-00-00`
+/// and `2024-02-00` are shown as MySQL shows them.
+fn lenient_value(raw: &[u8], type_name: &str) -> String {
+    let binary_date = matches!(type_name, "DATE" | "DATETIME" | "TIMESTAMP")
+        && matches!(raw.first(), Some(0 | 4 | 7 | 11))
+        && raw.len() == raw[0] as usize + 1;
+    if !binary_date {
+        return String::from_utf8_lossy(raw).to_string();
+    }
+    let field = |i: usize| raw.get(i).copied().unwrap_or_default();
+    let year = u16::from_le_bytes([field(1), field(2)]);
+    let date = format!("{:04}-{:02}-{:02}", year, field(3), field(4));
+    if type_name == "DATE" {
+        return date;
+    }
+    let time = format!("{} {:02}:{:02}:{:02}", date, field(5), field(6), field(7));
+    if raw.len() == 12 {
+        let micros = u32::from_le_bytes([field(8), field(9), field(10), field(11)]);
+        format!("{}.{:06}", time, micros)
+    } else {
+        time
+    }
+}
+
+fn quote_comment(comment: &str) -> String {
+    format!("'{}'", comment.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+/// The definition of `column` in the `CREATE TABLE` statement `definition`, without its comment.
+fn column_definition(definition: &str, column: &str) -> Option<String> {
+    let prefix = format!("`{}` ", column.replace('`', "``"));
+    let line = definition
+        .lines()
+        .map(|line| line.trim().trim_end_matches(','))
+        .find(|line| line.starts_with(&prefix))?;
+    Some(match line.find(" COMMENT '") {
+        Some(start) => {
+            let mut chars = line[start + 10..].char_indices().peekable();
+            let mut end = line.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '\'' if chars.peek().map(|(_, c)| *c) == Some('\'') => {
+                        chars.next();
+                    }
+                    '\'' => {
+                        end = start + 10 + i + 1;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            format!("{}{}", &line[..start], &line[end..])
+        }
+        None => line.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{column_definition, lenient_value, quote_comment, MySqlPool, Pool};
+    use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions};
+
+    #[tokio::test]
+    async fn test_cell_literal() {
+        // Never connects, since literals are made without the database.
+        let pool = MySqlPool {
+            pool: MySqlPoolOptions::new().connect_lazy_with(MySqlConnectOptions::new()),
+            limit_size: 0,
+        };
+        assert_eq!(pool.cell_literal(r"a\", false), r"'a\\'");
+        assert_eq!(
+            pool.cell_literal(r"\' OR 1 = 1 -- ", false),
+            r"'\\'' OR 1 = 1 -- '"
+        );
+        assert_eq!(pool.cell_literal("NULL", false), "NULL");
+        assert_eq!(pool.cell_literal("0x0A1B", true), "UNHEX('0A1B')");
+    }
+
+    #[test]
+    fn test_column_definition() {
+        let definition = "CREATE TABLE `users` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `name` varchar(255) NOT NULL DEFAULT '' COMMENT 'the user''s \\'name\\'',
+  `email` varchar(255) DEFAULT NULL COMMENT 'login' /*!80023 INVISIBLE */,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB";
+        assert_eq!(
+            column_definition(definition, "id"),
+            Some("`id` int NOT NULL AUTO_INCREMENT".to_string())
+        );
+        assert_eq!(
+            column_definition(definition, "name"),
+            Some("`name` varchar(255) NOT NULL DEFAULT ''".to_string())
+        );
+        assert_eq!(
+            column_definition(definition, "email"),
+            Some("`email` varchar(255) DEFAULT NULL /*!80023 INVISIBLE */".to_string())
+        );
+        assert_eq!(column_definition(definition, "age"), None);
+        assert_eq!(quote_comment(r"it's a \ path"), r"'it''s a \\ path'");
+    }
+
+    #[test]
+    fn test_lenient_value() {
+        assert_eq!(lenient_value(&[0], "DATE"), "0000-00-00");
+        assert_eq!(lenient_value(&[4, 232, 7, 2, 0], "DATE"), "2024-02-00");
+        assert_eq!(lenient_value(&[0], "DATETIME"), "0000-00-00 00:00:00");
+        assert_eq!(
+            lenient_value(&[7, 0, 0, 0, 0, 13, 5, 9], "TIMESTAMP"),
+            "0000-00-00 13:05:09"
+        );
+        assert_eq!(
+            lenient_value(&[11, 0, 0, 1, 0, 0, 0, 0, 64, 226, 1, 0], "DATETIME"),
+            "0000-01-00 00:00:00.123456"
+        );
+        assert_eq!(lenient_value(b"0000-00-00", "DATE"), "0000-00-00");
+        assert_eq!(lenient_value(b"unknown", "ENUM"), "unknown");
+    }
+}
@@ -1,15 +1,21 @@
 use crate::get_or_null;
 
-use super::{ExecuteResult, Pool, TableRow};
+use super::{
+    decode_error_cell, string_literal, with_geometry_as_text, ExecuteResult, Inheritance, JsonPath,
+    Pool, QueryBuilder, TableRow, Value,
+};
+use crate::charset::{Charset, Charsets};
 use crate::tree::{Child, Database, Schema, Table};
+use anyhow::anyhow;
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use futures::TryStreamExt;
 use itertools::Itertools;
-use sqlx::postgres::{PgColumn, PgPool, PgPoolOptions, PgRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::postgres::{PgColumn, PgConnectOptions, PgPool, PgPoolOptions, PgRow, PgValueFormat};
+use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
 use std::time::Duration;
 
+#[derive(Clone)]
 pub struct PostgresPool {
     pool: PgPool,
     limit_size: usize,
@@ -17,14 +23,16 @@ pub struct PostgresPool {
 
 impl PostgresPool {
     pub async fn new(
-        database_url: &str,
+        options: PgConnectOptions,
         limit_size: usize,
         timeout_second: u64,
+        max_connections: u32,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             pool: PgPoolOptions::new()
+                .max_connections(max_connections)
                 .acquire_timeout(Duration::from_secs(timeout_second))
-                .connect(database_url)
+                .connect_with(options)
                 .await?,
             limit_size,
         })
@@ -52,6 +60,8 @@ pub struct Column {
     null: Option<String>,
     default: Option<String>,
     comment: Option<String>,
+    /// The expression of a generated column.
+    generated: Option<String>,
 }
 
 impl TableRow for Column {
@@ -62,6 +72,7 @@ impl TableRow for Column {
             "null".to_string(),
             "default".to_string(),
             "comment".to_string(),
+            "generated".to_string(),
         ]
     }
 
@@ -82,6 +93,9 @@ impl TableRow for Column {
             self.comment
                 .as_ref()
                 .map_or(String::new(), |comment| comment.to_string()),
+            self.generated
+                .as_ref()
+                .map_or(String::new(), |generated| generated.to_string()),
         ]
     }
 }
@@ -151,13 +165,112 @@ impl TableRow for Index {
     }
 }
 
+pub struct Lock {
+    session: Option<String>,
+    object: Option<String>,
+    r#type: Option<String>,
+    mode: Option<String>,
+    status: Option<String>,
+    blocked_by: Option<String>,
+    query: Option<String>,
+}
+
+impl TableRow for Lock {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "session".to_string(),
+            "object".to_string(),
+            "type".to_string(),
+            "mode".to_string(),
+            "status".to_string(),
+            "blocked_by".to_string(),
+            "query".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.session
+                .as_ref()
+                .map_or(String::new(), |session| session.to_string()),
+            self.object
+                .as_ref()
+                .map_or(String::new(), |object| object.to_string()),
+            self.r#type
+                .as_ref()
+                .map_or(String::new(), |r#type| r#type.to_string()),
+            self.mode
+                .as_ref()
+                .map_or(String::new(), |mode| mode.to_string()),
+            self.status
+                .as_ref()
+                .map_or(String::new(), |status| status.to_string()),
+            self.blocked_by
+                .as_ref()
+                .map_or(String::new(), |blocked_by| blocked_by.to_string()),
+            self.query
+                .as_ref()
+                .map_or(String::new(), |query| query.to_string()),
+        ]
+    }
+}
+
+pub struct Variable {
+    name: Option<String>,
+    value: Option<String>,
+    unit: Option<String>,
+    description: Option<String>,
+}
+
+impl TableRow for Variable {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "value".to_string(),
+            "unit".to_string(),
+            "description".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name
+                .as_ref()
+                .map_or(String::new(), |name| name.to_string()),
+            self.value
+                .as_ref()
+                .map_or(String::new(), |value| value.to_string()),
+            self.unit
+                .as_ref()
+                .map_or(String::new(), |unit| unit.to_string()),
+            self.description
+                .as_ref()
+                .map_or(String::new(), |description| description.to_string()),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for PostgresPool {
     async fn execute(&self, query: &String) -> anyhow::Result<ExecuteResult> {
+        self.execute_in_session(&[], query).await
+    }
+
+    async fn execute_in_session(
+        &self,
+        session: &[String],
+        query: &str,
+    ) -> anyhow::Result<ExecuteResult> {
         let query = query.trim();
-        if query.to_uppercase().starts_with("SELECT") {
-            let mut rows = sqlx::query(query).fetch(&self.pool);
+        let mut connection = self.pool.acquire().await?;
+        for statement in session {
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
+        let keyword = query.to_uppercase();
+        if keyword.starts_with("SELECT") || keyword.starts_with("EXPLAIN") {
+            let mut rows = sqlx::query(query).fetch(&mut *connection);
             let mut headers = vec![];
+            let mut types = vec![];
             let mut records = vec![];
             while let Some(row) = rows.try_next().await? {
                 headers = row
@@ -165,57 +278,142 @@ impl Pool for PostgresPool {
                     .iter()
                     .map(|column| column.name().to_string())
                     .collect();
+                types = row
+                    .columns()
+                    .iter()
+                    .map(|column| column.type_info().name().to_string())
+                    .collect();
                 let mut new_row = vec![];
                 for column in row.columns() {
-                    new_row.push(convert_column_value_to_string(&row, column)?)
+                    new_row.push(
+                        convert_column_value_to_string(&row, column)
+                            .unwrap_or_else(|e| decode_error_cell(column.name(), &e)),
+                    )
                 }
                 records.push(new_row)
             }
             return Ok(ExecuteResult::Read {
                 headers,
+                types,
                 rows: records,
-                database: Database {
-                    name: "-".to_string(),
-                    children: Vec::new(),
-                },
+                database: Database::new("-".to_string(), Vec::new()),
                 table: Table {
                     name: "-".to_string(),
                     create_time: None,
                     update_time: None,
                     engine: None,
+                    parent: None,
                     schema: None,
                 },
             });
         }
 
-        let result = sqlx::query(query).execute(&self.pool).await?;
+        let result = sqlx::query(query).execute(&mut *connection).await?;
         Ok(ExecuteResult::Write {
             updated_rows: result.rows_affected(),
         })
     }
 
-    async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
-        let databases = sqlx::query("SELECT datname FROM pg_database")
+    async fn fetch_values(&self, query: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        let rows = sqlx::query(query).fetch_all(&self.pool).await?;
+        let headers = rows.first().map_or_else(Vec::new, |row| {
+            row.columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect()
+        });
+        let rows = rows
+            .iter()
+            .map(row_values)
+            .collect::<anyhow::Result<Vec<Vec<Value>>>>()?;
+        Ok((headers, rows))
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        statements: &[String],
+    ) -> anyhow::Result<Vec<Vec<Vec<Value>>>> {
+        let mut transaction = self.pool.begin().await?;
+        let mut results = vec![];
+        for statement in statements {
+            let rows = sqlx::query(statement).fetch_all(&mut *transaction).await?;
+            results.push(
+                rows.iter()
+                    .map(row_values)
+                    .collect::<anyhow::Result<Vec<Vec<Value>>>>()?,
+            );
+        }
+        transaction.commit().await?;
+        Ok(results)
+    }
+
+    async fn get_key_columns(&self, table: &str) -> anyhow::Result<Option<Vec<String>>> {
+        // The columns of an index on expressions have no attribute, which can't be a key.
+        Ok(sqlx::query(
+            "
+        SELECT a.attname::text
+        FROM pg_index i
+        CROSS JOIN LATERAL unnest(i.indkey::int2[]) WITH ORDINALITY AS k(attnum, n)
+        LEFT JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+        WHERE i.indexrelid = (
+            SELECT indexrelid FROM pg_index
+            WHERE indrelid = $1::text::regclass AND indisunique AND indpred IS NULL
+            ORDER BY indisprimary DESC, indexrelid
+            LIMIT 1
+        )
+        AND k.n <= i.indnkeyatts
+        ORDER BY k.n
+        ",
+        )
+        .bind(table)
+        .fetch_all(&self.pool)
+        .await?
+        .iter()
+        .map(|row| row.try_get(0))
+        .collect::<Result<Vec<Option<String>>, _>>()?
+        .into_iter()
+        .collect::<Option<Vec<String>>>()
+        .filter(|columns| !columns.is_empty()))
+    }
+
+    async fn get_database_names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(sqlx::query("SELECT datname FROM pg_database")
             .fetch_all(&self.pool)
             .await?
             .iter()
             .map(|table| table.get(0))
-            .collect::<Vec<String>>();
-        let mut list = vec![];
-        for db in databases {
-            list.push(Database::new(
-                db.clone(),
-                self.get_tables(db.clone()).await?,
-            ))
-        }
-        Ok(list)
+            .collect())
     }
 
     async fn get_tables(&self, database: String) -> anyhow::Result<Vec<Child>> {
-        let mut rows =
-            sqlx::query("SELECT * FROM information_schema.tables WHERE table_catalog = $1")
-                .bind(database)
-                .fetch(&self.pool);
+        // The first parent in the same schema of each inheriting table and partition.
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            t.table_name,
+            t.table_schema,
+            (
+                SELECT
+                    p.relname::text
+                FROM
+                    pg_inherits i
+                    JOIN pg_class p ON p.oid = i.inhparent
+                    JOIN pg_namespace pn ON pn.oid = p.relnamespace
+                WHERE
+                    i.inhrelid = format('%I.%I', t.table_schema, t.table_name)::regclass
+                    AND pn.nspname = t.table_schema
+                ORDER BY
+                    i.inhseqno
+                LIMIT 1
+            ) AS parent
+        FROM
+            information_schema.tables t
+        WHERE
+            t.table_catalog = $1
+        ",
+        )
+        .bind(database)
+        .fetch(&self.pool);
         let mut tables = Vec::new();
         while let Some(row) = rows.try_next().await? {
             tables.push(Table {
@@ -223,6 +421,7 @@ impl Pool for PostgresPool {
                 create_time: None,
                 update_time: None,
                 engine: None,
+                parent: row.try_get("parent")?,
                 schema: row.try_get("table_schema")?,
             })
         }
@@ -247,54 +446,10 @@ impl Pool for PostgresPool {
 
     async fn get_records(
         &self,
-        database: &Database,
-        table: &Table,
-        page: u16,
-        filter: Option<String>,
-        orders: Option<String>,
+        query: &QueryBuilder,
     ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let (Some(filter), Some(orders)) = (&filter, &orders) {
-            format!(
-                r#"SELECT * FROM "{database}"."{table_schema}"."{table}" WHERE {filter} {orders} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                orders = orders,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else if let Some(filter) = &filter {
-            format!(
-                r#"SELECT * FROM "{database}"."{table_schema}"."{table}" WHERE {filter} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else if let Some(orders) = &orders {
-            format!(
-                r#"SELECT * FROM "{database}"."{table_schema}"."{table}" {orders} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                orders = orders,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else {
-            format!(
-                r#"SELECT * FROM "{database}"."{table_schema}"."{table}" LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        };
-        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let records_query = self.records_query(&with_geometry_as_text(self, query).await?);
+        let mut rows = sqlx::query(records_query.as_str()).fetch(&self.pool);
         let mut headers = vec![];
         let mut records = vec![];
         let mut json_records = None;
@@ -310,16 +465,7 @@ impl Pool for PostgresPool {
                     Ok(v) => new_row.push(v),
                     Err(_) => {
                         if json_records.is_none() {
-                            json_records = Some(
-                                self.get_json_records(
-                                    database,
-                                    table,
-                                    page,
-                                    filter.clone(),
-                                    orders.clone(),
-                                )
-                                .await?,
-                            );
+                            json_records = Some(self.get_json_records(query).await?);
                         }
                         if let Some(json_records) = &json_records {
                             match json_records
@@ -335,13 +481,10 @@ impl Pool for PostgresPool {
                                 }
                                 serde_json::Value::Number(v) => new_row.push(v.to_string()),
                                 serde_json::Value::Bool(v) => new_row.push(v.to_string()),
-                                others => {
-                                    panic!(
-                                        "column type not implemented: `{}` {}",
-                                        column.name(),
-                                        others
-                                    )
-                                }
+                                others => new_row.push(decode_error_cell(
+                                    column.name(),
+                                    &anyhow::anyhow!("column type not implemented: {}", others),
+                                )),
                             }
                         }
                     }
@@ -352,29 +495,10 @@ impl Pool for PostgresPool {
         Ok((headers, records))
     }
 
-    async fn get_total_row_count(
-        &self,
-        database: &Database,
-        table: &Table,
-        filter: Option<String>,
-    ) -> anyhow::Result<usize> {
-        let query = if let Some(filter) = &filter {
-            format!(
-                r#"SELECT COUNT(*) FROM "{database}"."{table_schema}"."{table}" WHERE {filter}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-            )
-        } else {
-            format!(
-                r#"SELECT COUNT(*) FROM "{database}"."{table_schema}"."{table}""#,
-                database = database.name,
-                table = table.name,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-            )
-        };
-        let res = sqlx::query(query.as_str()).fetch_one(&self.pool).await?;
+    async fn get_total_row_count(&self, query: &QueryBuilder) -> anyhow::Result<usize> {
+        let res = sqlx::query(query.count(self).as_str())
+            .fetch_one(&self.pool)
+            .await?;
         Ok(res.get::<i64, usize>(0) as usize)
     }
 
@@ -388,7 +512,7 @@ impl Pool for PostgresPool {
             .as_ref()
             .map_or("public", |schema| schema.as_str());
         let mut rows = sqlx::query(
-            "SELECT * FROM information_schema.columns WHERE table_catalog = $1 AND table_schema = $2 AND table_name = $3"
+            "SELECT *, col_description(format('%I.%I', table_schema, table_name)::regclass::oid, ordinal_position::int) AS column_comment FROM information_schema.columns WHERE table_catalog = $1 AND table_schema = $2 AND table_name = $3 ORDER BY ordinal_position"
         )
         .bind(&database.name).bind(table_schema).bind(&table.name)
         .fetch(&self.pool);
@@ -396,10 +520,20 @@ impl Pool for PostgresPool {
         while let Some(row) = rows.try_next().await? {
             columns.push(Box::new(Column {
                 name: row.try_get("column_name")?,
-                r#type: row.try_get("data_type")?,
+                // Types of extensions such as PostGIS `geometry` are `USER-DEFINED`.
+                r#type: match row.try_get::<String, _>("data_type")?.as_str() {
+                    "USER-DEFINED" => row.try_get("udt_name")?,
+                    data_type => Some(data_type.to_string()),
+                },
                 null: row.try_get("is_nullable")?,
                 default: row.try_get("column_default")?,
-                comment: None,
+                comment: row.try_get("column_comment")?,
+                // `generation_expression` is missing before PostgreSQL 12.
+                generated: row
+                    .try_get::<Option<String>, _>("generation_expression")
+                    .ok()
+                    .flatten()
+                    .map(|expression| format!("AS ({})", expression)),
             }))
         }
         Ok(columns)
@@ -534,6 +668,288 @@ impl Pool for PostgresPool {
             .to_owned())
     }
 
+    async fn get_locks(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            l.pid::text AS session,
+            COALESCE(l.relation::regclass::text, l.transactionid::text, l.virtualxid) AS object,
+            l.locktype AS type,
+            l.mode,
+            CASE WHEN l.granted THEN 'GRANTED' ELSE 'WAITING' END AS status,
+            array_to_string(pg_blocking_pids(l.pid), ',') AS blocked_by,
+            a.query
+        FROM
+            pg_locks l
+            JOIN pg_stat_activity a ON a.pid = l.pid
+        WHERE
+            l.pid <> pg_backend_pid()
+        ORDER BY
+            l.granted,
+            l.pid
+        ",
+        )
+        .fetch(&self.pool);
+        let mut locks: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            locks.push(Box::new(Lock {
+                session: row.try_get("session")?,
+                object: row.try_get("object")?,
+                r#type: row.try_get("type")?,
+                mode: row.try_get("mode")?,
+                status: row.try_get("status")?,
+                blocked_by: row.try_get("blocked_by")?,
+                query: row.try_get("query")?,
+            }))
+        }
+        Ok(locks)
+    }
+
+    async fn get_variables(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows =
+            sqlx::query("SELECT name, setting, unit, short_desc FROM pg_settings ORDER BY name")
+                .fetch(&self.pool);
+        let mut variables: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            variables.push(Box::new(Variable {
+                name: row.try_get("name")?,
+                value: row.try_get("setting")?,
+                unit: row.try_get("unit")?,
+                description: row.try_get("short_desc")?,
+            }))
+        }
+        Ok(variables)
+    }
+
+    fn records_query(&self, query: &QueryBuilder) -> String {
+        query.clone().limit(self.limit_size).select(self)
+    }
+
+    fn limit_clause(&self, limit: usize, offset: usize) -> String {
+        format!("LIMIT {} OFFSET {}", limit, offset)
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!(r#""{}""#, identifier)
+    }
+
+    fn returning_clause(&self, columns: &[String]) -> Option<String> {
+        Some(format!("RETURNING {}", columns.join(", ")))
+    }
+
+    fn table_identifier(&self, database: &Database, table: &Table) -> String {
+        format!(
+            r#""{database}"."{table_schema}"."{table}""#,
+            database = database.name,
+            table = table.name,
+            table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string())
+        )
+    }
+
+    fn binary_literal(&self, hex: &str) -> String {
+        format!(r"'\x{}'", hex)
+    }
+
+    fn json_path_expression(&self, column: &str, path: &JsonPath) -> String {
+        format!(
+            "{}::jsonb #>> {}",
+            self.quote_identifier(column),
+            string_literal(&path.to_text_array())
+        )
+    }
+
+    fn session_variable_names(&self) -> Vec<&'static str> {
+        vec!["search_path", "timezone"]
+    }
+
+    fn explain_query(&self, query: &str) -> String {
+        format!("EXPLAIN {}", query)
+    }
+
+    fn regex_condition(&self, column: &str, pattern: &str, ignore_case: bool) -> String {
+        format!(
+            "{} {} '{}'",
+            column,
+            if ignore_case { "~*" } else { "~" },
+            pattern.replace('\'', "''")
+        )
+    }
+
+    fn ignore_case_condition(&self, column: &str, pattern: &str) -> String {
+        format!("{}::text ILIKE '{}'", column, pattern.replace('\'', "''"))
+    }
+
+    async fn get_version(&self) -> anyhow::Result<String> {
+        let res = sqlx::query("SHOW server_version")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(res.try_get::<String, usize>(0)?)
+    }
+
+    async fn get_charsets(&self, database: &Database, table: &Table) -> anyhow::Result<Charsets> {
+        let row = sqlx::query(
+            "SELECT pg_encoding_to_char(encoding)::text, datcollate::text FROM pg_database WHERE datname = $1",
+        )
+        .bind(&database.name)
+        .fetch_one(&self.pool)
+        .await?;
+        let database_charset = Charset::new(row.try_get(0)?, row.try_get(1)?);
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            column_name::text,
+            collation_name::text
+        FROM
+            information_schema.columns
+        WHERE
+            table_catalog = $1
+            AND table_schema = $2
+            AND table_name = $3
+            AND data_type IN ('text', 'character varying', 'character')
+        ORDER BY
+            ordinal_position
+        ",
+        )
+        .bind(&database.name)
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let collation: Option<String> = row.try_get(1)?;
+            columns.push((
+                row.try_get(0)?,
+                Charset::new(
+                    database_charset.charset.clone(),
+                    collation.or_else(|| database_charset.collation.clone()),
+                ),
+            ))
+        }
+        Ok(Charsets {
+            database: database_charset,
+            table: Charset::default(),
+            columns,
+        })
+    }
+
+    async fn get_table_comment(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Option<String>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        let comment: Option<String> = sqlx::query(
+            "
+        SELECT
+            obj_description(c.oid, 'pg_class')
+        FROM
+            pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE
+            n.nspname = $1
+            AND c.relname = $2
+        ",
+        )
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|row| row.try_get(0))
+        .transpose()?
+        .flatten();
+        Ok(comment.filter(|comment| !comment.is_empty()))
+    }
+
+    async fn get_inheritance(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Inheritance> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        let relation = "format('%I.%I', $1::text, $2::text)::regclass";
+        let row = sqlx::query(&format!(
+            "SELECT pg_get_partkeydef(c.oid), pg_get_expr(c.relpartbound, c.oid) FROM pg_class c WHERE c.oid = {}",
+            relation
+        ))
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch_one(&self.pool)
+        .await?;
+        let parents = sqlx::query(&format!(
+            "SELECT inhparent::regclass::text FROM pg_inherits WHERE inhrelid = {} ORDER BY inhseqno",
+            relation
+        ))
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch_all(&self.pool)
+        .await?
+        .iter()
+        .map(|row| row.try_get(0))
+        .collect::<Result<Vec<String>, _>>()?;
+        let children = sqlx::query(&format!(
+            "SELECT inhrelid::regclass::text FROM pg_inherits WHERE inhparent = {} ORDER BY 1",
+            relation
+        ))
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch_all(&self.pool)
+        .await?
+        .iter()
+        .map(|row| row.try_get(0))
+        .collect::<Result<Vec<String>, _>>()?;
+        Ok(Inheritance {
+            parents,
+            partition_bound: row.try_get(1)?,
+            partition_key: row.try_get(0)?,
+            children,
+        })
+    }
+
+    async fn comment_statement(
+        &self,
+        database: &Database,
+        table: &Table,
+        column: Option<&str>,
+        comment: &str,
+    ) -> anyhow::Result<String> {
+        let comment = if comment.is_empty() {
+            "NULL".to_string()
+        } else {
+            format!("'{}'", comment.replace('\'', "''"))
+        };
+        Ok(match column {
+            None => format!(
+                "COMMENT ON TABLE {} IS {}",
+                self.table_identifier(database, table),
+                comment
+            ),
+            Some(column) => format!(
+                "COMMENT ON COLUMN {}.{} IS {}",
+                self.table_identifier(database, table),
+                self.quote_identifier(column),
+                comment
+            ),
+        })
+    }
+
+    async fn get_journal_mode(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn clone_box(&self) -> Box<dyn Pool> {
+        Box::new(self.clone())
+    }
+
     async fn close(&self) {
         self.pool.close().await;
     }
@@ -542,61 +958,54 @@ impl Pool for PostgresPool {
 impl PostgresPool {
     async fn get_json_records(
         &self,
-        database: &Database,
-        table: &Table,
-        page: u16,
-        filter: Option<String>,
-        orders: Option<String>,
+        query: &QueryBuilder,
     ) -> anyhow::Result<Vec<serde_json::Value>> {
-        let query = if let (Some(filter), Some(orders)) = (&filter, &orders) {
-            format!(
-                r#"SELECT to_json("{table}".*) FROM "{database}"."{table_schema}"."{table}" WHERE {filter} {orders} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                orders = orders,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else if let Some(filter) = filter {
-            format!(
-                r#"SELECT to_json("{table}".*) FROM "{database}"."{table_schema}"."{table}" WHERE {filter} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else if let Some(orders) = orders {
-            format!(
-                r#"SELECT to_json("{table}".*) FROM "{database}"."{table_schema}"."{table}" {orders} LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                orders = orders,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        } else {
-            format!(
-                r#"SELECT to_json("{table}".*) FROM "{database}"."{table_schema}"."{table}" LIMIT {limit} OFFSET {page}"#,
-                database = database.name,
-                table = table.name,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = self.limit_size
-            )
-        };
+        let query = self.records_query(&query.clone().columns(format!(
+            "to_json({}.*)",
+            self.quote_identifier(&query.table.name)
+        )));
         let json: Vec<(serde_json::Value,)> =
             sqlx::query_as(query.as_str()).fetch_all(&self.pool).await?;
         Ok(json.iter().map(|v| v.clone().0).collect())
     }
 }
 
+/// The values of `row`. Arrays are not read, since they are shown joined with commas.
+fn row_values(row: &PgRow) -> anyhow::Result<Vec<Value>> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            if row.try_get_raw(column.ordinal())?.is_null() {
+                return Ok(Value::Null);
+            }
+            let type_name = column.type_info().name();
+            if type_name == "BYTEA" {
+                return Ok(Value::Binary(row.try_get(column.ordinal())?));
+            }
+            if type_name.ends_with("[]") {
+                return Err(anyhow!(
+                    "`{}` is an array, which can't be written back",
+                    column.name()
+                ));
+            }
+            convert_column_value_to_string(row, column).map(Value::Text)
+        })
+        .collect()
+}
+
 fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Result<String> {
     let column_name = column.name();
+    if column.type_info().name() == "NUMERIC" {
+        // `Decimal` would round or reject numbers beyond 28 significant digits.
+        let value = row.try_get_raw(column_name)?;
+        if value.is_null() {
+            return Ok("NULL".to_string());
+        }
+        return match value.format() {
+            PgValueFormat::Binary => numeric_to_string(value.as_bytes().map_err(|e| anyhow!(e))?),
+            PgValueFormat::Text => Ok(value.as_str().map_err(|e| anyhow!(e))?.to_string()),
+        };
+    }
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<i16> = value;
         Ok(get_or_null!(value))
@@ -609,6 +1018,9 @@ fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Res
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<rust_decimal::Decimal> = value;
         Ok(get_or_null!(value))
+    } else if let Ok(value) = row.try_get(column_name) {
+        let value: Option<sqlx::types::Uuid> = value;
+        Ok(get_or_null!(value))
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<&[u8]> = value;
         Ok(value.map_or("NULL".to_string(), |values| {
@@ -658,3 +1070,101 @@ fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Res
         )
     }
 }
+
+/// The text of a NUMERIC in the binary format, which is a sign, the scale and the digits in base
+/// 10000, with every digit kept.
+fn numeric_to_string(buf: &[u8]) -> anyhow::Result<String> {
+    let field = |i: usize| -> anyhow::Result<u16> {
+        buf.get(i * 2..i * 2 + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .ok_or_else(|| anyhow!("invalid numeric of {} bytes", buf.len()))
+    };
+    let ndigits = field(0)? as usize;
+    let weight = field(1)? as i16 as i64;
+    let sign = field(2)?;
+    let scale = field(3)? as usize;
+    let digits = (0..ndigits)
+        .map(|i| field(4 + i))
+        .collect::<anyhow::Result<Vec<u16>>>()?;
+    match sign {
+        0xC000 => return Ok("NaN".to_string()),
+        0xD000 => return Ok("Infinity".to_string()),
+        0xF000 => return Ok("-Infinity".to_string()),
+        _ => {}
+    }
+    let digit = |i: i64| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let mut text = if sign == 0x4000 {
+        "-".to_string()
+    } else {
+        String::new()
+    };
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+        for i in 1..=weight {
+            text.push_str(&format!("{:04}", digit(i)));
+        }
+    }
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut i = weight + 1;
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(i)));
+            i += 1;
+        }
+        fraction.truncate(scale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::numeric_to_string;
+
+    fn numeric(weight: i16, sign: u16, scale: u16, digits: &[u16]) -> Vec<u8> {
+        [digits.len() as u16, weight as u16, sign, scale]
+            .iter()
+            .chain(digits)
+            .flat_map(|field| field.to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_numeric_to_string() {
+        // 12345678901234567890123456789012.3400
+        assert_eq!(
+            numeric_to_string(&numeric(
+                7,
+                0,
+                4,
+                &[1234, 5678, 9012, 3456, 7890, 1234, 5678, 9012, 3400]
+            ))
+            .unwrap(),
+            "12345678901234567890123456789012.3400"
+        );
+        assert_eq!(
+            numeric_to_string(&numeric(-2, 0x4000, 6, &[1200])).unwrap(),
+            "-0.000012"
+        );
+        assert_eq!(
+            numeric_to_string(&numeric(1, 0, 0, &[18])).unwrap(),
+            "180000"
+        );
+        assert_eq!(numeric_to_string(&numeric(0, 0, 2, &[])).unwrap(), "0.00");
+        assert_eq!(
+            numeric_to_string(&numeric(0, 0xC000, 0, &[])).unwrap(),
+            "NaN"
+        );
+        assert!(numeric_to_string(&[0, 1]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use super::{JsonPath, Pool};
+use crate::tree::{Database, Table};
+
+/// A `SELECT` on the records of a table, with the columns, filter, sort order, limit and offset
+/// they are read with. The pools render it with their own quoting and `LIMIT` syntax, so the
+/// records, their count, the shown query and exports all read the same rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryBuilder {
+    pub database: Database,
+    pub table: Table,
+    /// The select list, `*` when not set.
+    columns: Option<String>,
+    /// The columns of the table selected in place of `*`, in order.
+    table_columns: Option<Vec<String>>,
+    /// Values in JSON columns selected after `*` as columns of their own, named after the column
+    /// and the path.
+    json_columns: Vec<(String, JsonPath)>,
+    /// SQL expressions selected after the JSON columns, such as `price * quantity AS total`.
+    computed_columns: Vec<String>,
+    /// The condition of the `WHERE` clause.
+    filter: Option<String>,
+    /// The `ORDER BY` clause.
+    orders: Option<String>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+impl QueryBuilder {
+    pub fn new(database: Database, table: Table) -> Self {
+        Self {
+            database,
+            table,
+            columns: None,
+            table_columns: None,
+            json_columns: vec![],
+            computed_columns: vec![],
+            filter: None,
+            orders: None,
+            limit: None,
+            offset: 0,
+        }
+    }
+
+    pub fn columns(mut self, columns: String) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn table_columns(mut self, table_columns: Option<Vec<String>>) -> Self {
+        self.table_columns = table_columns;
+        self
+    }
+
+    pub fn json_columns(mut self, json_columns: Vec<(String, JsonPath)>) -> Self {
+        self.json_columns = json_columns;
+        self
+    }
+
+    pub fn computed_columns(mut self, computed_columns: Vec<String>) -> Self {
+        self.computed_columns = computed_columns;
+        self
+    }
+
+    pub fn filter(mut self, filter: Option<String>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn orders(mut self, orders: Option<String>) -> Self {
+        self.orders = orders;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The `SELECT` statement for `pool`.
+    pub fn select(&self, pool: &dyn Pool) -> String {
+        let columns = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => std::iter::once(match &self.table_columns {
+                Some(columns) => columns
+                    .iter()
+                    .map(|column| pool.quote_identifier(column))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                None => "*".to_string(),
+            })
+            .chain(self.json_columns.iter().map(|(column, path)| {
+                format!(
+                    "{} AS {}",
+                    pool.json_path_expression(column, path),
+                    pool.quote_identifier(&format!("{}{}", column, path))
+                )
+            }))
+            .chain(self.computed_columns.iter().cloned())
+            .collect::<Vec<String>>()
+            .join(", "),
+        };
+        let mut query = format!(
+            "SELECT {} FROM {}",
+            columns,
+            pool.table_identifier(&self.database, &self.table)
+        );
+        if let Some(filter) = &self.filter {
+            query = format!("{} WHERE {}", query, filter);
+        }
+        if let Some(orders) = &self.orders {
+            query = format!("{} {}", query, orders);
+        }
+        if let Some(limit) = self.limit {
+            query = format!("{} {}", query, pool.limit_clause(limit, self.offset));
+        }
+        query
+    }
+
+    /// The statement counting the rows the filter matches, regardless of the limit.
+    pub fn count(&self, pool: &dyn Pool) -> String {
+        let query = format!(
+            "SELECT COUNT(*) FROM {}",
+            pool.table_identifier(&self.database, &self.table)
+        );
+        match &self.filter {
+            Some(filter) => format!("{} WHERE {}", query, filter),
+            None => query,
+        }
+    }
+
+    /// The statement counting the records the filter matches for each of the `limit` most
+    /// frequent values of `column`, the most frequent first.
+    pub fn frequency(&self, pool: &dyn Pool, column: &str, limit: usize) -> String {
+        let column = pool.quote_identifier(column);
+        let mut query = format!(
+            "SELECT {}, COUNT(*) FROM {}",
+            column,
+            pool.table_identifier(&self.database, &self.table)
+        );
+        if let Some(filter) = &self.filter {
+            query = format!("{} WHERE {}", query, filter);
+        }
+        format!(
+            "{} GROUP BY {} ORDER BY 2 DESC, 1 {}",
+            query,
+            column,
+            pool.limit_clause(limit, 0)
+        )
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod test {
+    use super::QueryBuilder;
+    use crate::database::{ExecuteResult, JsonPath, Pool};
+    use crate::demo;
+    use crate::tree::{Database, Table};
+
+    #[tokio::test]
+    async fn test_json_and_computed_columns() {
+        let pool = demo::open(100).await.unwrap();
+        let query = QueryBuilder::new(
+            Database::new("main".to_string(), vec![]),
+            Table {
+                name: "products".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        )
+        .json_columns(vec![(
+            "attributes".to_string(),
+            JsonPath::parse("$.color").unwrap(),
+        )])
+        .computed_columns(vec!["stock * 2 AS double_stock".to_string()])
+        .filter(Some("id < 3".to_string()));
+        assert_eq!(
+            query.select(&pool),
+            "SELECT *, json_extract(`attributes`, '$.color') AS `attributes.color`, stock * 2 AS double_stock FROM `products` WHERE id < 3"
+        );
+        let (headers, rows) = pool.get_records(&query).await.unwrap();
+        assert_eq!(
+            headers[headers.len() - 2..],
+            ["attributes.color", "double_stock"]
+        );
+        assert_eq!(
+            rows.iter()
+                .map(|row| row[row.len() - 2..].join(" "))
+                .collect::<Vec<String>>(),
+            ["green 26", "blue 52"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_frequency() {
+        let pool = demo::open(100).await.unwrap();
+        let query = QueryBuilder::new(
+            Database::new("main".to_string(), vec![]),
+            Table {
+                name: "products".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        )
+        .filter(Some("id > 1".to_string()));
+        let frequency = query.frequency(&pool, "category", 2);
+        assert_eq!(
+            frequency,
+            "SELECT `category`, COUNT(*) FROM `products` WHERE id > 1 GROUP BY `category` ORDER BY 2 DESC, 1 LIMIT 0, 2"
+        );
+        let ExecuteResult::Read { rows, .. } = pool.execute(&frequency).await.unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["tools", "7"], ["books", "6"]]);
+    }
+}
@@ -1,14 +1,72 @@
 use crate::get_or_null;
 
-use super::{ExecuteResult, Pool, TableRow};
+use super::{
+    decode_error_cell, hex_string, identifier_parts, insert_statements, string_literal,
+    ExecuteResult, Inheritance, JsonPath, Pool, QueryBuilder, TableRow, Value,
+};
+use crate::charset::{Charset, Charsets};
 use crate::tree::{Child, Database, Table};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
 use futures::TryStreamExt;
-use sqlx::sqlite::{SqliteColumn, SqlitePoolOptions, SqliteRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::sqlite::{SqliteColumn, SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{Column as _, Row as _, TypeInfo as _, ValueRef as _};
 use std::time::Duration;
 
+const PRAGMAS: &[(&str, &str)] = &[
+    (
+        "application_id",
+        "The 32-bit application ID in the database header",
+    ),
+    (
+        "auto_vacuum",
+        "Auto-vacuum mode (0: none, 1: full, 2: incremental)",
+    ),
+    ("busy_timeout", "Busy timeout in milliseconds"),
+    (
+        "cache_size",
+        "Suggested maximum number of pages held in memory",
+    ),
+    ("encoding", "Text encoding of the database"),
+    (
+        "foreign_keys",
+        "Whether foreign key constraints are enforced",
+    ),
+    (
+        "freelist_count",
+        "Number of unused pages in the database file",
+    ),
+    ("journal_mode", "Journal mode of the database"),
+    ("locking_mode", "Database connection locking mode"),
+    ("page_count", "Total number of pages in the database file"),
+    ("page_size", "Page size of the database in bytes"),
+    (
+        "recursive_triggers",
+        "Whether recursive triggers are enabled",
+    ),
+    (
+        "secure_delete",
+        "Whether deleted content is overwritten with zeros",
+    ),
+    (
+        "synchronous",
+        "Synchronous flag (0: off, 1: normal, 2: full, 3: extra)",
+    ),
+    (
+        "temp_store",
+        "Storage location of temporary tables and indices",
+    ),
+    (
+        "user_version",
+        "The user version number in the database header",
+    ),
+    (
+        "wal_autocheckpoint",
+        "WAL auto-checkpoint interval in pages",
+    ),
+];
+
+#[derive(Clone)]
 pub struct SqlitePool {
     pool: sqlx::sqlite::SqlitePool,
     limit_size: usize,
@@ -16,14 +74,31 @@ pub struct SqlitePool {
 
 impl SqlitePool {
     pub async fn new(
-        database_url: &str,
+        options: SqliteConnectOptions,
         limit_size: usize,
         timeout_second: u64,
+        max_connections: u32,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             pool: SqlitePoolOptions::new()
+                .max_connections(max_connections)
                 .acquire_timeout(Duration::from_secs(timeout_second))
-                .connect(database_url)
+                .connect_with(options.with_regexp())
+                .await?,
+            limit_size,
+        })
+    }
+
+    /// A private in-memory database. Its only connection is never closed for being idle, since
+    /// the database is gone with it.
+    pub async fn in_memory(limit_size: usize) -> anyhow::Result<Self> {
+        Ok(Self {
+            pool: SqlitePoolOptions::new()
+                .max_connections(1)
+                .min_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+                .connect_with(SqliteConnectOptions::new().with_regexp())
                 .await?,
             limit_size,
         })
@@ -60,6 +135,8 @@ pub struct Column {
     null: Option<String>,
     default: Option<String>,
     comment: Option<String>,
+    /// The expression of a generated column.
+    generated: Option<String>,
 }
 
 impl TableRow for Column {
@@ -70,6 +147,7 @@ impl TableRow for Column {
             "null".to_string(),
             "default".to_string(),
             "comment".to_string(),
+            "generated".to_string(),
         ]
     }
 
@@ -90,6 +168,9 @@ impl TableRow for Column {
             self.comment
                 .as_ref()
                 .map_or(String::new(), |comment| comment.to_string()),
+            self.generated
+                .as_ref()
+                .map_or(String::new(), |generated| generated.to_string()),
         ]
     }
 }
@@ -154,13 +235,62 @@ impl TableRow for Index {
     }
 }
 
+pub struct Variable {
+    name: Option<String>,
+    value: Option<String>,
+    unit: Option<String>,
+    description: Option<String>,
+}
+
+impl TableRow for Variable {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "value".to_string(),
+            "unit".to_string(),
+            "description".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name
+                .as_ref()
+                .map_or(String::new(), |name| name.to_string()),
+            self.value
+                .as_ref()
+                .map_or(String::new(), |value| value.to_string()),
+            self.unit
+                .as_ref()
+                .map_or(String::new(), |unit| unit.to_string()),
+            self.description
+                .as_ref()
+                .map_or(String::new(), |description| description.to_string()),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for SqlitePool {
     async fn execute(&self, query: &String) -> anyhow::Result<ExecuteResult> {
+        self.execute_in_session(&[], query).await
+    }
+
+    async fn execute_in_session(
+        &self,
+        session: &[String],
+        query: &str,
+    ) -> anyhow::Result<ExecuteResult> {
         let query = query.trim();
-        if query.to_uppercase().starts_with("SELECT") {
-            let mut rows = sqlx::query(query).fetch(&self.pool);
+        let mut connection = self.pool.acquire().await?;
+        for statement in session {
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
+        let keyword = query.to_uppercase();
+        if keyword.starts_with("SELECT") || keyword.starts_with("EXPLAIN") {
+            let mut rows = sqlx::query(query).fetch(&mut *connection);
             let mut headers = vec![];
+            let mut types = vec![];
             let mut records = vec![];
             while let Some(row) = rows.try_next().await? {
                 headers = row
@@ -168,50 +298,125 @@ impl Pool for SqlitePool {
                     .iter()
                     .map(|column| column.name().to_string())
                     .collect();
+                types = row
+                    .columns()
+                    .iter()
+                    .map(|column| column.type_info().name().to_string())
+                    .collect();
                 let mut new_row = vec![];
                 for column in row.columns() {
-                    new_row.push(convert_column_value_to_string(&row, column)?)
+                    new_row.push(
+                        convert_column_value_to_string(&row, column)
+                            .unwrap_or_else(|e| decode_error_cell(column.name(), &e)),
+                    )
                 }
                 records.push(new_row)
             }
             return Ok(ExecuteResult::Read {
                 headers,
+                types,
                 rows: records,
-                database: Database {
-                    name: "-".to_string(),
-                    children: Vec::new(),
-                },
+                database: Database::new("-".to_string(), Vec::new()),
                 table: Table {
                     name: "-".to_string(),
                     create_time: None,
                     update_time: None,
                     engine: None,
+                    parent: None,
                     schema: None,
                 },
             });
         }
 
-        let result = sqlx::query(query).execute(&self.pool).await?;
+        let result = sqlx::query(query).execute(&mut *connection).await?;
         Ok(ExecuteResult::Write {
             updated_rows: result.rows_affected(),
         })
     }
 
-    async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
-        let databases = sqlx::query("SELECT name FROM pragma_database_list")
+    async fn fetch_values(&self, query: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<Value>>)> {
+        let rows = sqlx::query(query).fetch_all(&self.pool).await?;
+        let headers = rows.first().map_or_else(Vec::new, |row| {
+            row.columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect()
+        });
+        let rows = rows
+            .iter()
+            .map(row_values)
+            .collect::<anyhow::Result<Vec<Vec<Value>>>>()?;
+        Ok((headers, rows))
+    }
+
+    async fn execute_in_transaction(
+        &self,
+        statements: &[String],
+    ) -> anyhow::Result<Vec<Vec<Vec<Value>>>> {
+        let mut transaction = self.pool.begin().await?;
+        let mut results = vec![];
+        for statement in statements {
+            let rows = sqlx::query(statement).fetch_all(&mut *transaction).await?;
+            results.push(
+                rows.iter()
+                    .map(row_values)
+                    .collect::<anyhow::Result<Vec<Vec<Value>>>>()?,
+            );
+        }
+        transaction.commit().await?;
+        Ok(results)
+    }
+
+    async fn get_key_columns(&self, table: &str) -> anyhow::Result<Option<Vec<String>>> {
+        let mut parts = identifier_parts(table);
+        let name = parts.pop().unwrap_or_default();
+        let schema = parts.pop().unwrap_or_else(|| "main".to_string());
+        let primary_key =
+            sqlx::query("SELECT name FROM pragma_table_info(?, ?) WHERE pk > 0 ORDER BY pk")
+                .bind(&name)
+                .bind(&schema)
+                .fetch_all(&self.pool)
+                .await?
+                .iter()
+                .map(|row| row.try_get(0))
+                .collect::<Result<Vec<String>, _>>()?;
+        if !primary_key.is_empty() {
+            return Ok(Some(primary_key));
+        }
+        let index: Option<String> = sqlx::query(
+            r#"SELECT name FROM pragma_index_list(?, ?) WHERE "unique" AND NOT partial ORDER BY name"#,
+        )
+        .bind(&name)
+        .bind(&schema)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|row| row.try_get(0))
+        .transpose()?;
+        let Some(index) = index else {
+            return Ok(None);
+        };
+        // The columns of an index on expressions have no name, which can't be a key.
+        Ok(
+            sqlx::query("SELECT name FROM pragma_index_info(?, ?) ORDER BY seqno")
+                .bind(&index)
+                .bind(&schema)
+                .fetch_all(&self.pool)
+                .await?
+                .iter()
+                .map(|row| row.try_get(0))
+                .collect::<Result<Vec<Option<String>>, _>>()?
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    async fn get_database_names(&self) -> anyhow::Result<Vec<String>> {
+        Ok(sqlx::query("SELECT name FROM pragma_database_list")
             .fetch_all(&self.pool)
             .await?
             .iter()
             .map(|table| table.get(0))
-            .collect::<Vec<String>>();
-        let mut list = vec![];
-        for db in databases {
-            list.push(Database::new(
-                db.clone(),
-                self.get_tables(db.clone()).await?,
-            ))
-        }
-        Ok(list)
+            .collect())
     }
 
     async fn get_tables(&self, _database: String) -> anyhow::Result<Vec<Child>> {
@@ -224,53 +429,27 @@ impl Pool for SqlitePool {
                 create_time: None,
                 update_time: None,
                 engine: None,
+                parent: None,
                 schema: None,
             })
         }
+        // sqlite_master has no row describing itself, so it is added to be browsed as well.
+        tables.push(Table {
+            name: "sqlite_master".to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            parent: None,
+            schema: None,
+        });
         Ok(tables.into_iter().map(|table| table.into()).collect())
     }
 
     async fn get_records(
         &self,
-        _database: &Database,
-        table: &Table,
-        page: u16,
-        filter: Option<String>,
-        orders: Option<String>,
+        query: &QueryBuilder,
     ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let (Some(filter), Some(orders)) = (&filter, &orders) {
-            format!(
-                "SELECT * FROM `{table}` WHERE {filter} {orders} LIMIT {page}, {limit}",
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = self.limit_size,
-                orders = orders
-            )
-        } else if let Some(filter) = filter {
-            format!(
-                "SELECT * FROM `{table}` WHERE {filter} LIMIT {page}, {limit}",
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = self.limit_size
-            )
-        } else if let Some(orders) = orders {
-            format!(
-                "SELECT * FROM `{table}`{orders} LIMIT {page}, {limit}",
-                table = table.name,
-                orders = orders,
-                page = page,
-                limit = self.limit_size
-            )
-        } else {
-            format!(
-                "SELECT * FROM `{table}` LIMIT {page}, {limit}",
-                table = table.name,
-                page = page,
-                limit = self.limit_size
-            )
-        };
+        let query = self.records_query(query);
         let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
         let mut headers = vec![];
         let mut records = vec![];
@@ -282,44 +461,60 @@ impl Pool for SqlitePool {
                 .collect();
             let mut new_row = vec![];
             for column in row.columns() {
-                new_row.push(convert_column_value_to_string(&row, column)?)
+                new_row.push(
+                    convert_column_value_to_string(&row, column)
+                        .unwrap_or_else(|e| decode_error_cell(column.name(), &e)),
+                )
             }
             records.push(new_row)
         }
         Ok((headers, records))
     }
 
-    async fn get_total_row_count(
-        &self,
-        _database: &Database,
-        table: &Table,
-        filter: Option<String>,
-    ) -> anyhow::Result<usize> {
-        let query = if let Some(filter) = &filter {
-            format!(
-                "SELECT COUNT(*) FROM `{table}` WHERE {filter}",
-                table = table.name,
-                filter = filter,
-            )
-        } else {
-            format!("SELECT COUNT(*) FROM `{table}`", table = table.name,)
-        };
-        let res = sqlx::query(query.as_str()).fetch_one(&self.pool).await?;
+    async fn get_total_row_count(&self, query: &QueryBuilder) -> anyhow::Result<usize> {
+        let res = sqlx::query(query.count(self).as_str())
+            .fetch_one(&self.pool)
+            .await?;
         Ok(res.get::<i64, usize>(0) as usize)
     }
 
     async fn get_columns(
         &self,
-        _database: &Database,
+        database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
-        let query = format!("SELECT * FROM pragma_table_info('{}');", table.name);
+        // Unlike `table_info`, `table_xinfo` lists generated columns, which are hidden 2 for
+        // virtual and 3 for stored ones.
+        let query = format!(
+            "SELECT * FROM pragma_table_xinfo('{}') WHERE hidden != 1;",
+            table.name
+        );
         let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let mut definition = None;
         let mut columns: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
             let null: Option<i16> = row.try_get("notnull")?;
+            let name: Option<String> = row.try_get("name")?;
+            let hidden: i64 = row.try_get("hidden")?;
+            let generated = match (hidden, &name) {
+                (2 | 3, Some(name)) => {
+                    if definition.is_none() {
+                        definition = Some(self.get_definition(database, table).await?);
+                    }
+                    Some(format!(
+                        "AS ({}) {}",
+                        definition
+                            .as_deref()
+                            .and_then(|definition| generated_expression(definition, name))
+                            .unwrap_or_default(),
+                        if hidden == 3 { "STORED" } else { "VIRTUAL" }
+                    ))
+                }
+                _ => None,
+            };
             columns.push(Box::new(Column {
-                name: row.try_get("name")?,
+                name,
+                generated,
                 r#type: row.try_get("type")?,
                 null: if matches!(null, Some(null) if null == 1) {
                     Some("✔︎".to_string())
@@ -424,14 +619,268 @@ impl Pool for SqlitePool {
     async fn get_definition(&self, _database: &Database, table: &Table) -> anyhow::Result<String> {
         let query = sqlx::query("SELECT sql FROM sqlite_master WHERE type='table' AND name=?;")
             .bind(&table.name);
-        let row = query.fetch_one(&self.pool).await?;
-        Ok(row.get::<String, usize>(0))
+        let row = query.fetch_optional(&self.pool).await?;
+        Ok(row
+            .and_then(|row| row.get::<Option<String>, usize>(0))
+            .unwrap_or_default())
+    }
+
+    async fn get_locks(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        // SQLite only has file-level locks, which cannot be inspected through SQL.
+        Ok(vec![])
+    }
+
+    async fn get_variables(&self) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut variables: Vec<Box<dyn TableRow>> = vec![];
+        for (name, description) in PRAGMAS {
+            let query = format!("PRAGMA {}", name);
+            let row = sqlx::query(query.as_str())
+                .fetch_optional(&self.pool)
+                .await?;
+            let value = match row {
+                Some(row) => row.columns().first().map(|column| {
+                    convert_column_value_to_string(&row, column)
+                        .unwrap_or_else(|e| decode_error_cell(column.name(), &e))
+                }),
+                None => None,
+            };
+            variables.push(Box::new(Variable {
+                name: Some(name.to_string()),
+                value,
+                unit: None,
+                description: Some(description.to_string()),
+            }))
+        }
+        Ok(variables)
+    }
+
+    fn records_query(&self, query: &QueryBuilder) -> String {
+        query.clone().limit(self.limit_size).select(self)
+    }
+
+    fn limit_clause(&self, limit: usize, offset: usize) -> String {
+        format!("LIMIT {}, {}", offset, limit)
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("`{}`", identifier)
+    }
+
+    fn returning_clause(&self, columns: &[String]) -> Option<String> {
+        Some(format!("RETURNING {}", columns.join(", ")))
+    }
+
+    fn table_identifier(&self, _database: &Database, table: &Table) -> String {
+        format!("`{}`", table.name)
+    }
+
+    fn binary_literal(&self, hex: &str) -> String {
+        format!("X'{}'", hex)
+    }
+
+    fn json_path_expression(&self, column: &str, path: &JsonPath) -> String {
+        format!(
+            "json_extract({}, {})",
+            self.quote_identifier(column),
+            string_literal(&path.to_json_path())
+        )
+    }
+
+    fn session_variable_names(&self) -> Vec<&'static str> {
+        vec![]
     }
+
+    fn explain_query(&self, query: &str) -> String {
+        format!("EXPLAIN QUERY PLAN {}", query)
+    }
+
+    fn regex_condition(&self, column: &str, pattern: &str, ignore_case: bool) -> String {
+        format!(
+            "{} REGEXP '{}{}'",
+            column,
+            if ignore_case { "(?i)" } else { "" },
+            pattern.replace('\'', "''")
+        )
+    }
+
+    fn ignore_case_condition(&self, column: &str, pattern: &str) -> String {
+        // LIKE ignores the case of ASCII letters, and so does `=` with NOCASE.
+        if pattern.contains(['%', '_']) {
+            format!("{} LIKE '{}'", column, pattern.replace('\'', "''"))
+        } else {
+            format!(
+                "{} = '{}' COLLATE NOCASE",
+                column,
+                pattern.replace('\'', "''")
+            )
+        }
+    }
+
+    async fn get_version(&self) -> anyhow::Result<String> {
+        let res = sqlx::query("SELECT sqlite_version()")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(res.try_get::<String, usize>(0)?)
+    }
+
+    async fn get_charsets(&self, _database: &Database, table: &Table) -> anyhow::Result<Charsets> {
+        let encoding: String = sqlx::query("PRAGMA encoding")
+            .fetch_one(&self.pool)
+            .await?
+            .try_get(0)?;
+        let query = format!(
+            "SELECT name, type FROM pragma_table_info('{}');",
+            table.name
+        );
+        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let r#type: String = row.try_get("type")?;
+            let r#type = r#type.to_uppercase();
+            if ["CHAR", "CLOB", "TEXT"]
+                .iter()
+                .any(|affinity| r#type.contains(affinity))
+            {
+                columns.push((
+                    row.try_get("name")?,
+                    Charset::new(Some(encoding.clone()), None),
+                ))
+            }
+        }
+        Ok(Charsets {
+            database: Charset::new(Some(encoding), None),
+            table: Charset::default(),
+            columns,
+        })
+    }
+
+    async fn get_table_comment(
+        &self,
+        _database: &Database,
+        _table: &Table,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn get_inheritance(
+        &self,
+        _database: &Database,
+        _table: &Table,
+    ) -> anyhow::Result<Inheritance> {
+        Ok(Inheritance::default())
+    }
+
+    async fn comment_statement(
+        &self,
+        _database: &Database,
+        _table: &Table,
+        _column: Option<&str>,
+        _comment: &str,
+    ) -> anyhow::Result<String> {
+        Err(anyhow::anyhow!("SQLite has no comments"))
+    }
+
+    async fn get_journal_mode(&self) -> anyhow::Result<Option<String>> {
+        let res = sqlx::query("PRAGMA journal_mode")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Some(res.try_get::<String, usize>(0)?))
+    }
+
+    fn clone_box(&self) -> Box<dyn Pool> {
+        Box::new(self.clone())
+    }
+
     async fn close(&self) {
         self.pool.close().await;
     }
 }
 
+/// The statements creating `table` with the columns of a result and copying its `rows` into it.
+/// The values of the `numeric` columns which are numbers are written as they are, and the others
+/// as strings. Empty and repeated headers are given a number to stay unique.
+pub fn copy_result_statements(
+    table: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+    numeric: &[bool],
+) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    for (i, header) in headers.iter().enumerate() {
+        let base = if header.is_empty() {
+            format!("column{}", i + 1)
+        } else {
+            header.clone()
+        };
+        let mut name = base.clone();
+        let mut n = 2;
+        while names.iter().any(|other| other.eq_ignore_ascii_case(&name)) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        names.push(name);
+    }
+    let columns = names
+        .iter()
+        .map(|name| format!("`{}`", name.replace('`', "``")))
+        .collect::<Vec<String>>();
+    let definitions = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let type_name = if numeric.get(i).copied().unwrap_or(false) {
+                "NUMERIC"
+            } else {
+                "TEXT"
+            };
+            format!("{} {}", column, type_name)
+        })
+        .collect::<Vec<String>>();
+    let values = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    if value == "NULL"
+                        || numeric.get(i).copied().unwrap_or(false) && value.parse::<f64>().is_ok()
+                    {
+                        value.clone()
+                    } else {
+                        string_literal(value)
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<String>>>();
+    let table = format!("`{}`", table.replace('`', "``"));
+    let mut statements = vec![format!(
+        "CREATE TABLE {} ({})",
+        table,
+        definitions.join(", ")
+    )];
+    statements.extend(insert_statements(&table, &columns, &values));
+    statements
+}
+
+/// The values of `row`, read by their storage class rather than the type of their column.
+fn row_values(row: &SqliteRow) -> anyhow::Result<Vec<Value>> {
+    (0..row.len())
+        .map(|i| {
+            let value = row.try_get_raw(i)?;
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            Ok(match value.type_info().name() {
+                "INTEGER" => Value::Number(row.try_get_unchecked::<i64, _>(i)?.to_string()),
+                "REAL" => Value::Number(row.try_get_unchecked::<f64, _>(i)?.to_string()),
+                "BLOB" => Value::Binary(row.try_get_unchecked(i)?),
+                _ => Value::Text(row.try_get_unchecked(i)?),
+            })
+        })
+        .collect()
+}
+
 fn convert_column_value_to_string(
     row: &SqliteRow,
     column: &SqliteColumn,
@@ -452,9 +901,6 @@ fn convert_column_value_to_string(
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<i64> = value;
         Ok(get_or_null!(value))
-    } else if let Ok(value) = row.try_get(column_name) {
-        let value: Option<f32> = value;
-        Ok(get_or_null!(value))
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<f64> = value;
         Ok(get_or_null!(value))
@@ -470,6 +916,9 @@ fn convert_column_value_to_string(
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<bool> = value;
         Ok(get_or_null!(value))
+    } else if let Ok(value) = row.try_get(column_name) {
+        let value: Option<&[u8]> = value;
+        Ok(value.map_or("NULL".to_string(), hex_string))
     } else {
         anyhow::bail!(
             "column type not implemented: `{}` {}",
@@ -478,3 +927,259 @@ fn convert_column_value_to_string(
         )
     }
 }
+
+/// The expression of the generated column `column` in the `CREATE TABLE` statement `definition`.
+fn generated_expression(definition: &str, column: &str) -> Option<String> {
+    let body = &definition[definition.find('(')? + 1..];
+    split_top_level(body)
+        .into_iter()
+        .find(|column_definition| {
+            first_identifier(column_definition)
+                .is_some_and(|name| name.eq_ignore_ascii_case(column))
+        })
+        .and_then(|column_definition| {
+            let upper = column_definition.to_uppercase();
+            let start = upper.match_indices("AS").map(|(i, _)| i).find(|i| {
+                upper[..*i].ends_with(char::is_whitespace)
+                    && upper[i + 2..].trim_start().starts_with('(')
+            })?;
+            let rest = &column_definition[start + 2..];
+            let open = rest.find('(')?;
+            split_top_level(&rest[open + 1..])
+                .first()
+                .map(|expression| expression.trim().to_string())
+        })
+}
+
+/// The items of a parenthesized list which `list` starts inside, up to its closing parenthesis.
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match (quote, c) {
+            (Some(end), c) if c == end => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => {
+                items.push(&list[start..i]);
+                return items;
+            }
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&list[start..]);
+    items
+}
+
+/// The first name in a column definition, without its quotes.
+fn first_identifier(definition: &str) -> Option<&str> {
+    let definition = definition.trim_start();
+    let end = match definition.chars().next()? {
+        '"' => '"',
+        '`' => '`',
+        '[' => ']',
+        _ => return definition.split_whitespace().next(),
+    };
+    let name = &definition[1..];
+    Some(&name[..name.find(end)?])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{copy_result_statements, generated_expression, SqlitePool};
+    use crate::database::{generated_columns, ExecuteResult, Pool, QueryBuilder, MAX_CONNECTIONS};
+    use crate::tree::{Database, Table};
+    use sqlx::sqlite::SqliteConnectOptions;
+
+    #[test]
+    fn test_generated_expression() {
+        let definition = r#"CREATE TABLE items (
+    id INTEGER PRIMARY KEY,
+    price REAL NOT NULL DEFAULT (0.0),
+    qty INTEGER,
+    "total price" REAL GENERATED ALWAYS AS (price * coalesce(qty, 1)) STORED,
+    label TEXT AS (printf('%d, %s', id, 'a)')),
+    CHECK (qty > 0)
+)"#;
+        assert_eq!(
+            generated_expression(definition, "total price"),
+            Some("price * coalesce(qty, 1)".to_string())
+        );
+        assert_eq!(
+            generated_expression(definition, "LABEL"),
+            Some("printf('%d, %s', id, 'a)')".to_string())
+        );
+        assert_eq!(generated_expression(definition, "price"), None);
+        assert_eq!(generated_expression(definition, "missing"), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_generated_columns() {
+        let path = std::env::temp_dir().join(format!("zhobo_generated_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&path)
+                .create_if_missing(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(
+            &"CREATE TABLE items (price REAL, qty INTEGER, total REAL AS (price * qty) STORED)"
+                .to_string(),
+        )
+        .await
+        .unwrap();
+        let database = Database::new("main".to_string(), vec![]);
+        let table = Table {
+            name: "items".to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            parent: None,
+            schema: None,
+        };
+        let columns = pool.get_columns(&database, &table).await.unwrap();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(
+            columns[2].columns().last().unwrap(),
+            "AS (price * qty) STORED"
+        );
+        assert_eq!(generated_columns(&columns), ["total"]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extreme_numbers() {
+        let path = std::env::temp_dir().join(format!("zhobo_numbers_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&path)
+                .create_if_missing(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(&"CREATE TABLE amounts (amount NUMERIC, count INTEGER)".to_string())
+            .await
+            .unwrap();
+        pool.execute(
+            &"INSERT INTO amounts VALUES (123456789.123, 9223372036854775807), \
+              (0.1, -9223372036854775808)"
+                .to_string(),
+        )
+        .await
+        .unwrap();
+        let ExecuteResult::Read { rows, .. } = pool
+            .execute(&"SELECT amount, count FROM amounts".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            rows,
+            [
+                ["123456789.123", "9223372036854775807"],
+                ["0.1", "-9223372036854775808"],
+            ]
+        );
+        pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_builder() {
+        let path = std::env::temp_dir().join(format!("zhobo_records_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&path)
+                .create_if_missing(true),
+            2,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(&"CREATE TABLE users (id INTEGER, name TEXT)".to_string())
+            .await
+            .unwrap();
+        pool.execute(
+            &"INSERT INTO users VALUES (1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')".to_string(),
+        )
+        .await
+        .unwrap();
+        let query = QueryBuilder::new(
+            Database::new("main".to_string(), vec![]),
+            Table {
+                name: "users".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        )
+        .filter(Some("id > 1".to_string()))
+        .orders(Some("ORDER BY 1 DESC".to_string()));
+        assert_eq!(
+            pool.records_query(&query),
+            "SELECT * FROM `users` WHERE id > 1 ORDER BY 1 DESC LIMIT 0, 2"
+        );
+        assert_eq!(
+            query.count(&pool),
+            "SELECT COUNT(*) FROM `users` WHERE id > 1"
+        );
+        let (_, rows) = pool.get_records(&query.clone().offset(2)).await.unwrap();
+        assert_eq!(rows, [["2", "b"]]);
+        assert_eq!(pool.get_total_row_count(&query).await.unwrap(), 3);
+        pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_copy_result_to_in_memory_database() {
+        let pool = SqlitePool::in_memory(100).await.unwrap();
+        let statements = copy_result_statements(
+            "result_1",
+            &["id".to_string(), "name".to_string(), "ID".to_string()],
+            &[
+                vec!["1".to_string(), "o'neil".to_string(), "x".to_string()],
+                vec!["NULL".to_string(), "NULL".to_string(), "2".to_string()],
+            ],
+            &[true, false, false],
+        );
+        assert_eq!(
+            statements,
+            [
+                "CREATE TABLE `result_1` (`id` NUMERIC, `name` TEXT, `ID_2` TEXT)",
+                "INSERT INTO `result_1` (`id`, `name`, `ID_2`) VALUES (1, 'o''neil', 'x'), (NULL, NULL, '2')",
+            ]
+        );
+        for statement in &statements {
+            pool.execute(statement).await.unwrap();
+        }
+        let ExecuteResult::Read { rows, .. } = pool
+            .execute(&"SELECT count(*), sum(id) FROM result_1".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["2", "1"]]);
+        pool.close().await;
+    }
+}
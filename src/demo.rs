@@ -0,0 +1,126 @@
+use crate::database::{Pool, SqlitePool};
+use anyhow::Context;
+
+/// The name of the connection `--demo` adds.
+pub const NAME: &str = "demo";
+
+/// The sample schema and its rows. Rows are generated from their ids rather than at random, so
+/// every demo database holds the same data.
+const STATEMENTS: &[&str] = &[
+    "CREATE TABLE customers (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        email TEXT UNIQUE,
+        country TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    )",
+    "CREATE TABLE products (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        category TEXT NOT NULL,
+        price REAL NOT NULL,
+        stock INTEGER NOT NULL DEFAULT 0,
+        attributes TEXT,
+        thumbnail BLOB
+    )",
+    "CREATE TABLE orders (
+        id INTEGER PRIMARY KEY,
+        customer_id INTEGER NOT NULL REFERENCES customers (id),
+        status TEXT NOT NULL CHECK (status IN ('pending', 'paid', 'shipped', 'cancelled')),
+        ordered_at TEXT NOT NULL,
+        note TEXT
+    )",
+    "CREATE TABLE order_items (
+        order_id INTEGER NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+        product_id INTEGER NOT NULL REFERENCES products (id),
+        quantity INTEGER NOT NULL,
+        unit_price REAL NOT NULL,
+        PRIMARY KEY (order_id, product_id)
+    )",
+    "CREATE INDEX orders_customer_id ON orders (customer_id)",
+    "CREATE INDEX orders_ordered_at ON orders (ordered_at)",
+    "CREATE VIEW order_totals AS
+        SELECT orders.id AS order_id, customers.name AS customer, orders.status,
+            SUM(order_items.quantity * order_items.unit_price) AS total
+        FROM orders
+        JOIN customers ON customers.id = orders.customer_id
+        JOIN order_items ON order_items.order_id = orders.id
+        GROUP BY orders.id",
+    "INSERT INTO customers (id, name, email, country, created_at)
+        WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 50)
+        SELECT i,
+            json_extract('[\"James\",\"Mary\",\"Haruto\",\"Yui\",\"Sota\",\"Linda\",\"Minato\"]', '$[' || (i % 7) || ']')
+                || ' ' || json_extract('[\"Smith\",\"Sato\",\"Brown\",\"Suzuki\",\"Garcia\"]', '$[' || (i % 5) || ']'),
+            CASE WHEN i % 10 = 0 THEN NULL ELSE 'customer' || i || '@example.com' END,
+            json_extract('[\"JP\",\"US\",\"GB\",\"DE\"]', '$[' || (i % 4) || ']'),
+            date('2023-01-01', '+' || (i * 7) || ' days')
+        FROM n",
+    "INSERT INTO products (id, name, category, price, stock, attributes, thumbnail)
+        WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 20)
+        SELECT i,
+            'Product ' || i,
+            json_extract('[\"books\",\"games\",\"tools\"]', '$[' || (i % 3) || ']'),
+            (i * 250 % 1000 + 99) / 100.0,
+            i * 13 % 40,
+            CASE WHEN i % 4 = 0 THEN NULL ELSE json_object('color', json_extract('[\"red\",\"green\",\"blue\"]', '$[' || (i % 3) || ']'), 'weight', i * 10) END,
+            CASE WHEN i % 5 = 0 THEN x'89504e470d0a1a0a' END
+        FROM n",
+    "INSERT INTO orders (id, customer_id, status, ordered_at, note)
+        WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 200)
+        SELECT i,
+            i * 7 % 50 + 1,
+            json_extract('[\"pending\",\"paid\",\"shipped\",\"cancelled\"]', '$[' || (i % 4) || ']'),
+            datetime('2024-01-01', '+' || (i * 37 % 365) || ' days', '+' || (i * 53 % 1440) || ' minutes'),
+            CASE WHEN i % 9 = 0 THEN 'gift wrapping' END
+        FROM n",
+    "INSERT INTO order_items (order_id, product_id, quantity, unit_price)
+        WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 400)
+        SELECT (i + 1) / 2, (i * 3 + i / 2) % 20 + 1, i % 3 + 1, products.price
+        FROM n JOIN products ON products.id = (i * 3 + i / 2) % 20 + 1",
+];
+
+/// Opens a new in-memory database holding the sample schema and rows.
+pub async fn open(limit_size: usize) -> anyhow::Result<SqlitePool> {
+    let pool = SqlitePool::in_memory(limit_size).await?;
+    for statement in STATEMENTS {
+        pool.execute(&statement.to_string())
+            .await
+            .with_context(|| format!("failed to create the demo database: {}", statement))?;
+    }
+    Ok(pool)
+}
+
+#[cfg(test)]
+mod test {
+    use super::open;
+    use crate::database::{ExecuteResult, Pool};
+
+    #[tokio::test]
+    async fn test_open() {
+        let pool = open(100).await.unwrap();
+        let ExecuteResult::Read { rows, .. } = pool
+            .execute(
+                &"SELECT (SELECT COUNT(*) FROM customers), (SELECT COUNT(*) FROM products),
+                    (SELECT COUNT(*) FROM orders), (SELECT COUNT(*) FROM order_items),
+                    (SELECT COUNT(*) FROM order_totals)"
+                    .to_string(),
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["50", "20", "200", "400", "200"]]);
+
+        let ExecuteResult::Read { rows, .. } = open(100)
+            .await
+            .unwrap()
+            .execute(&"SELECT name, email, country FROM customers WHERE id = 10".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["Yui Smith", "NULL", "GB"]]);
+    }
+}
@@ -1,12 +1,21 @@
 use crate::event::Key;
 use crossterm::event;
-use std::{sync::mpsc, thread, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
 
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
 pub struct EventConfig {
     pub exit_key: Key,
     pub tick_rate: Duration,
+    /// Tick rate while idle, when ticks only serve to notice changes of the config file.
+    pub idle_tick_rate: Duration,
 }
 
 impl Default for EventConfig {
@@ -14,6 +23,7 @@ impl Default for EventConfig {
         EventConfig {
             exit_key: Key::Ctrl('c'),
             tick_rate: Duration::from_millis(250),
+            idle_tick_rate: Duration::from_secs(2),
         }
     }
 }
@@ -21,12 +31,29 @@ impl Default for EventConfig {
 #[derive(Copy, Clone)]
 pub enum Event<I> {
     Input(I),
+    Resize,
     Tick,
 }
 
 pub struct Events {
     rx: mpsc::Receiver<Event<Key>>,
     _tx: mpsc::Sender<Event<Key>>,
+    idle: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    /// Held by the event thread while it reads the terminal.
+    input: Arc<Mutex<()>>,
+}
+
+/// Keeps the event thread from reading the terminal until dropped.
+pub struct Paused<'a> {
+    paused: &'a AtomicBool,
+    _input: MutexGuard<'a, ()>,
+}
+
+impl Drop for Paused<'_> {
+    fn drop(&mut self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
 }
 
 impl Events {
@@ -40,20 +67,63 @@ impl Events {
     pub fn with_config(config: EventConfig) -> Events {
         let (tx, rx) = mpsc::channel();
 
+        let idle = Arc::new(AtomicBool::new(false));
+        let paused = Arc::new(AtomicBool::new(false));
+        let input = Arc::new(Mutex::new(()));
+
         let event_tx = tx.clone();
+        let event_idle = idle.clone();
+        let event_paused = paused.clone();
+        let event_input = input.clone();
         thread::spawn(move || loop {
-            if event::poll(config.tick_rate).unwrap() {
-                if let event::Event::Key(key) = event::read().unwrap() {
-                    let key = Key::from(key);
+            if event_paused.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            let _input = event_input.lock().unwrap();
+            let tick_rate = if event_idle.load(Ordering::Relaxed) {
+                config.idle_tick_rate
+            } else {
+                config.tick_rate
+            };
+            if event::poll(tick_rate).unwrap() {
+                match event::read().unwrap() {
+                    event::Event::Key(key) => {
+                        let key = Key::from(key);
 
-                    event_tx.send(Event::Input(key)).unwrap();
+                        event_idle.store(false, Ordering::Relaxed);
+                        event_tx.send(Event::Input(key)).unwrap();
+                    }
+                    event::Event::Resize(_, _) => event_tx.send(Event::Resize).unwrap(),
+                    _ => (),
                 }
             }
 
             event_tx.send(Event::Tick).unwrap();
         });
 
-        Events { rx, _tx: tx }
+        Events {
+            rx,
+            _tx: tx,
+            idle,
+            paused,
+            input,
+        }
+    }
+
+    /// Stops reading the terminal, so that another program run in it gets the keys, until the
+    /// returned guard is dropped.
+    pub fn pause(&self) -> Paused<'_> {
+        self.paused.store(true, Ordering::Relaxed);
+        Paused {
+            paused: &self.paused,
+            _input: self.input.lock().unwrap(),
+        }
+    }
+
+    /// Slows the ticks down while nothing is going on. Input wakes the event thread regardless.
+    pub fn set_idle(&self, idle: bool) {
+        self.idle.store(idle, Ordering::Relaxed);
     }
 
     pub fn next(&self) -> Result<Event<Key>, mpsc::RecvError> {
@@ -0,0 +1,1158 @@
+use crate::components::table::{csv_value, is_number};
+use crate::jobs::JobProgress;
+use anyhow::{anyhow, bail};
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zhobo::database::{insert_statements, ExecuteResult, Pool, QueryBuilder};
+use zhobo::tree::{Database, Table};
+
+/// Rows fetched per query while exporting.
+const ROWS_PER_FETCH: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Parquet,
+    Xlsx,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportCompression {
+    None,
+    Gzip,
+    Zstd,
+    Snappy,
+}
+
+/// What of a table is exported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportScope {
+    Entire,
+    VisibleColumns,
+    Selection,
+}
+
+impl ExportScope {
+    pub const ALL: [ExportScope; 3] = [
+        ExportScope::Entire,
+        ExportScope::VisibleColumns,
+        ExportScope::Selection,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportScope::Entire => "entire result",
+            ExportScope::VisibleColumns => "visible columns",
+            ExportScope::Selection => "current selection",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub compression: ExportCompression,
+}
+
+impl ExportOptions {
+    /// Picks the format and the compression from the extensions of `path`. CSV and JSONL
+    /// (`.csv`, `.jsonl`, `.ndjson`) are compressed as a whole with `.gz` or `.zst` after
+    /// them, and the pages of Parquet with `.snappy`, `.gzip`, `.zstd` or `.uncompressed`
+    /// before `.parquet`, snappy by default. `.xlsx` is always compressed.
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        let mut extensions = name.rsplit('.');
+        let mut extension = extensions.next().unwrap_or_default();
+        let compression = match extension {
+            "gz" => ExportCompression::Gzip,
+            "zst" => ExportCompression::Zstd,
+            _ => ExportCompression::None,
+        };
+        if compression != ExportCompression::None {
+            extension = extensions.next().unwrap_or_default();
+        }
+        let format = match extension {
+            "csv" => ExportFormat::Csv,
+            "jsonl" | "ndjson" => ExportFormat::Jsonl,
+            "xlsx" if compression == ExportCompression::None => ExportFormat::Xlsx,
+            "parquet" if compression == ExportCompression::None => {
+                return Ok(Self {
+                    format: ExportFormat::Parquet,
+                    compression: match extensions.next().unwrap_or_default() {
+                        "uncompressed" => ExportCompression::None,
+                        "gzip" => ExportCompression::Gzip,
+                        "zstd" => ExportCompression::Zstd,
+                        _ => ExportCompression::Snappy,
+                    },
+                })
+            }
+            _ => bail!(
+                "{} is not a .csv, .jsonl, .parquet or .xlsx file (.csv.gz, .jsonl.zst, .zstd.parquet, ... to compress)",
+                path.display()
+            ),
+        };
+        Ok(Self {
+            format,
+            compression,
+        })
+    }
+}
+
+/// How values are written to CSV, JSONL and Parquet, set by the `[export]` table of the config.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExportEncoding {
+    /// What `NULL` is written as in CSV.
+    pub null: String,
+    /// The strftime format of dates, such as `%d/%m/%Y`.
+    pub date_format: Option<String>,
+    /// The strftime format of timestamps, such as `%Y-%m-%dT%H:%M:%S`.
+    pub datetime_format: Option<String>,
+    pub boolean: BooleanEncoding,
+    pub quote: QuotePolicy,
+    pub line_ending: LineEnding,
+}
+
+impl Default for ExportEncoding {
+    fn default() -> Self {
+        Self {
+            null: "NULL".to_string(),
+            date_format: None,
+            datetime_format: None,
+            boolean: BooleanEncoding::default(),
+            quote: QuotePolicy::default(),
+            line_ending: LineEnding::default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum BooleanEncoding {
+    #[default]
+    #[serde(rename = "true/false")]
+    TrueFalse,
+    #[serde(rename = "1/0")]
+    OneZero,
+}
+
+/// Which CSV values are quoted.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotePolicy {
+    /// Values with commas, quotes or line breaks.
+    #[default]
+    Minimal,
+    All,
+    NonNumeric,
+    Never,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+impl ExportEncoding {
+    fn validate(&self) -> anyhow::Result<()> {
+        for (name, format) in [
+            ("date_format", &self.date_format),
+            ("datetime_format", &self.datetime_format),
+        ] {
+            if let Some(format) = format {
+                if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                    bail!(
+                        "export.{} `{}` is not a valid strftime format",
+                        name,
+                        format
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `value` with the booleans, dates and timestamps written as configured. `NULL` is left
+    /// as is, since it is written differently in each format.
+    fn encode<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.boolean == BooleanEncoding::OneZero {
+            match value {
+                "true" => return Cow::Borrowed("1"),
+                "false" => return Cow::Borrowed("0"),
+                _ => {}
+            }
+        }
+        if let Some(format) = &self.date_format {
+            if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                return try_format(value, date.format(format));
+            }
+        }
+        if let Some(format) = &self.datetime_format {
+            if let Some(utc) = value.strip_suffix(" UTC") {
+                if let Ok(datetime) = NaiveDateTime::parse_from_str(utc, "%Y-%m-%d %H:%M:%S%.f") {
+                    return try_format(value, datetime.and_utc().format(format));
+                }
+            }
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f") {
+                return try_format(value, datetime.format(format));
+            }
+        }
+        Cow::Borrowed(value)
+    }
+}
+
+/// The formatted value, or `value` as is when the format needs what the value does not have,
+/// such as a time zone.
+fn try_format(value: &str, formatted: impl std::fmt::Display) -> Cow<'_, str> {
+    let mut text = String::new();
+    match write!(text, "{}", formatted) {
+        Ok(()) => Cow::Owned(text),
+        Err(_) => Cow::Borrowed(value),
+    }
+}
+
+/// Writes fetched rows in one of the formats.
+trait RowWriter {
+    /// Writes `rows`, and `headers` before the first rows, and returns the number of bytes
+    /// written before compression.
+    fn write_rows(&mut self, headers: &[String], rows: &[Vec<String>]) -> anyhow::Result<usize>;
+
+    fn finish(self: Box<Self>) -> anyhow::Result<()>;
+}
+
+struct TextWriter {
+    writer: Box<dyn Write + Send>,
+    format: ExportFormat,
+    encoding: ExportEncoding,
+    wrote_headers: bool,
+}
+
+impl RowWriter for TextWriter {
+    fn write_rows(&mut self, headers: &[String], rows: &[Vec<String>]) -> anyhow::Result<usize> {
+        let mut bytes = 0;
+        if self.format == ExportFormat::Csv && !self.wrote_headers {
+            bytes += write_line(&mut self.writer, headers, &self.encoding, false)?;
+        }
+        self.wrote_headers = true;
+        for row in rows {
+            bytes += match self.format {
+                ExportFormat::Csv => write_line(&mut self.writer, row, &self.encoding, true)?,
+                _ => write_json_line(&mut self.writer, headers, row, &self.encoding)?,
+            };
+        }
+        Ok(bytes)
+    }
+
+    fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct ParquetWriter {
+    writer: Option<ArrowWriter<File>>,
+    schema: SchemaRef,
+    file: Option<File>,
+    compression: Compression,
+    encoding: ExportEncoding,
+}
+
+impl RowWriter for ParquetWriter {
+    fn write_rows(&mut self, headers: &[String], rows: &[Vec<String>]) -> anyhow::Result<usize> {
+        if self.writer.is_none() {
+            // Values are fetched as text, so every column is a nullable string.
+            self.schema = Arc::new(Schema::new(
+                headers
+                    .iter()
+                    .map(|header| Field::new(header, DataType::Utf8, true))
+                    .collect::<Vec<Field>>(),
+            ));
+            let properties = WriterProperties::builder()
+                .set_compression(self.compression)
+                .build();
+            self.writer = Some(ArrowWriter::try_new(
+                self.file
+                    .take()
+                    .ok_or_else(|| anyhow!("the file is closed"))?,
+                self.schema.clone(),
+                Some(properties),
+            )?);
+        }
+        let writer = self.writer.as_mut().unwrap();
+        let columns = (0..headers.len())
+            .map(|i| {
+                Arc::new(StringArray::from(
+                    rows.iter()
+                        .map(|row| {
+                            row.get(i)
+                                .filter(|v| *v != "NULL")
+                                .map(|v| self.encoding.encode(v).into_owned())
+                        })
+                        .collect::<Vec<Option<String>>>(),
+                )) as ArrayRef
+            })
+            .collect::<Vec<ArrayRef>>();
+        writer.write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        Ok(rows.iter().flatten().map(|value| value.len()).sum())
+    }
+
+    fn finish(self: Box<Self>) -> anyhow::Result<()> {
+        if let Some(writer) = self.writer {
+            writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the rows in a worksheet, since a workbook is written at once.
+struct XlsxWriter {
+    workbook: Workbook,
+    path: PathBuf,
+    formats: CellFormats,
+    row: u32,
+}
+
+impl RowWriter for XlsxWriter {
+    fn write_rows(&mut self, headers: &[String], rows: &[Vec<String>]) -> anyhow::Result<usize> {
+        if self.row == 0 {
+            let worksheet = self.workbook.add_worksheet();
+            worksheet.set_name(sheet_name(
+                self.path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default(),
+            ))?;
+            write_headers(worksheet, headers, &self.formats)?;
+            self.row = 1;
+        }
+        let worksheet = self.workbook.worksheet_from_index(0)?;
+        for row in rows {
+            write_row(worksheet, self.row, row, &self.formats)?;
+            self.row += 1;
+        }
+        Ok(rows.iter().flatten().map(|value| value.len()).sum())
+    }
+
+    fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        if self.row == 0 {
+            self.workbook.add_worksheet();
+        }
+        self.workbook.save(&self.path)?;
+        Ok(())
+    }
+}
+
+fn row_writer(
+    path: &Path,
+    options: ExportOptions,
+    encoding: &ExportEncoding,
+) -> anyhow::Result<Box<dyn RowWriter + Send>> {
+    encoding.validate()?;
+    if options.format == ExportFormat::Xlsx {
+        return Ok(Box::new(XlsxWriter {
+            workbook: Workbook::new(),
+            path: path.to_path_buf(),
+            formats: CellFormats::default(),
+            row: 0,
+        }));
+    }
+    let file = File::create(path)?;
+    if options.format == ExportFormat::Parquet {
+        return Ok(Box::new(ParquetWriter {
+            writer: None,
+            schema: Arc::new(Schema::empty()),
+            file: Some(file),
+            compression: match options.compression {
+                ExportCompression::None => Compression::UNCOMPRESSED,
+                ExportCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+                ExportCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+                ExportCompression::Snappy => Compression::SNAPPY,
+            },
+            encoding: encoding.clone(),
+        }));
+    }
+    let file = BufWriter::new(file);
+    let writer: Box<dyn Write + Send> = match options.compression {
+        ExportCompression::Gzip => Box::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        )),
+        ExportCompression::Zstd => Box::new(zstd::Encoder::new(file, 0)?.auto_finish()),
+        _ => Box::new(file),
+    };
+    Ok(Box::new(TextWriter {
+        writer,
+        format: options.format,
+        encoding: encoding.clone(),
+        wrote_headers: false,
+    }))
+}
+
+/// Writes the rows of `query` to `path` in the format of `options`, fetching them page by page
+/// so the progress can be reported and the export can be cancelled. A cancelled export removes
+/// the partial file.
+pub async fn export(
+    pool: Box<dyn Pool>,
+    query: QueryBuilder,
+    path: PathBuf,
+    options: ExportOptions,
+    encoding: ExportEncoding,
+    progress: Arc<JobProgress>,
+) -> anyhow::Result<()> {
+    let mut writer = row_writer(&path, options, &encoding)?;
+    if let Err(e) = for_each_page(pool.as_ref(), &query, &progress, |headers, rows| {
+        writer.write_rows(headers, rows)
+    })
+    .await
+    {
+        drop(writer);
+        if progress.is_cancelled() {
+            std::fs::remove_file(&path)?;
+        }
+        return Err(e);
+    }
+    writer.finish()
+}
+
+/// Writes the definition of `table` and its rows as INSERT statements to `path`, in the same
+/// way as [`export`].
+pub async fn dump(
+    pool: Box<dyn Pool>,
+    database: Database,
+    table: Table,
+    path: PathBuf,
+    progress: Arc<JobProgress>,
+) -> anyhow::Result<()> {
+    let definition = pool.get_definition(&database, &table).await?;
+    let mut file = BufWriter::new(File::create(&path)?);
+    writeln!(file, "{};\n", definition.trim_end().trim_end_matches(';'))?;
+    let identifier = pool.table_identifier(&database, &table);
+    let query = QueryBuilder::new(database, table);
+    let result = async {
+        // The values are read as they are stored, so blobs are written as binary literals rather
+        // than as the hex they are shown as.
+        let mut offset = 0;
+        loop {
+            if progress.is_cancelled() {
+                return Err(anyhow!("cancelled"));
+            }
+            let (headers, rows) = pool
+                .fetch_values(
+                    &query
+                        .clone()
+                        .limit(ROWS_PER_FETCH)
+                        .offset(offset)
+                        .select(pool.as_ref()),
+                )
+                .await?;
+            let columns = headers
+                .iter()
+                .map(|header| pool.quote_identifier(header))
+                .collect::<Vec<String>>();
+            let literals = rows
+                .iter()
+                .map(|row| row.iter().map(|value| pool.literal(value)).collect())
+                .collect::<Vec<Vec<String>>>();
+            let mut bytes = 0;
+            for statement in insert_statements(&identifier, &columns, &literals) {
+                let line = format!("{};\n", statement);
+                file.write_all(line.as_bytes())?;
+                bytes += line.len();
+            }
+            progress.add(rows.len(), bytes);
+            if rows.len() < ROWS_PER_FETCH {
+                return Ok(());
+            }
+            offset += ROWS_PER_FETCH;
+        }
+    }
+    .await;
+    if let Err(e) = result {
+        drop(file);
+        if progress.is_cancelled() {
+            std::fs::remove_file(&path)?;
+        }
+        return Err(e);
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Fetches the rows of `query` page by page and passes each page to `write`, which returns the
+/// number of bytes it wrote. Fails once the job is cancelled.
+async fn for_each_page(
+    pool: &dyn Pool,
+    query: &QueryBuilder,
+    progress: &JobProgress,
+    mut write: impl FnMut(&[String], &[Vec<String>]) -> anyhow::Result<usize>,
+) -> anyhow::Result<()> {
+    let mut offset = 0;
+    loop {
+        if progress.is_cancelled() {
+            return Err(anyhow!("cancelled"));
+        }
+        let (headers, rows) = match pool
+            .execute(
+                &query
+                    .clone()
+                    .limit(ROWS_PER_FETCH)
+                    .offset(offset)
+                    .select(pool),
+            )
+            .await?
+        {
+            ExecuteResult::Read { headers, rows, .. } => (headers, rows),
+            ExecuteResult::Write { .. } => return Err(anyhow!("the query does not return rows")),
+        };
+        let bytes = write(&headers, &rows)?;
+        progress.add(rows.len(), bytes);
+        if rows.len() < ROWS_PER_FETCH {
+            return Ok(());
+        }
+        offset += ROWS_PER_FETCH;
+    }
+}
+
+/// Writes rows which are already fetched to `path` in the format of `options`.
+pub fn export_rows(
+    path: &Path,
+    options: ExportOptions,
+    encoding: &ExportEncoding,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> anyhow::Result<()> {
+    let mut writer = row_writer(path, options, encoding)?;
+    writer.write_rows(headers, rows)?;
+    writer.finish()
+}
+
+/// A sheet of the workbook written by [`write_xlsx`].
+pub struct Sheet<'a> {
+    pub name: String,
+    pub headers: &'a [String],
+    pub rows: &'a [Vec<String>],
+}
+
+/// Writes `sheets` to an Excel workbook at `path`.
+pub fn write_xlsx(path: &Path, sheets: &[Sheet]) -> anyhow::Result<()> {
+    let formats = CellFormats::default();
+    let mut workbook = Workbook::new();
+    let mut names: Vec<String> = vec![];
+    for sheet in sheets {
+        // Names of sheets have to be unique regardless of case.
+        let mut name = sheet_name(&sheet.name);
+        let mut suffix = 1;
+        while names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            suffix += 1;
+            let suffix = format!(" ({})", suffix);
+            name = format!(
+                "{}{}",
+                name.chars()
+                    .take(MAX_SHEET_NAME_LEN - suffix.len())
+                    .collect::<String>(),
+                suffix
+            );
+        }
+        names.push(name.clone());
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name)?;
+        write_headers(worksheet, sheet.headers, &formats)?;
+        for (i, row) in sheet.rows.iter().enumerate() {
+            write_row(worksheet, i as u32 + 1, row, &formats)?;
+        }
+    }
+    if sheets.is_empty() {
+        workbook.add_worksheet();
+    }
+    workbook.save(path)?;
+    Ok(())
+}
+
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+/// `name` without the characters which are not allowed in the name of a sheet, shortened to
+/// what Excel accepts.
+fn sheet_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(MAX_SHEET_NAME_LEN)
+        .collect::<String>();
+    let name = name.trim_matches('\'');
+    if name.is_empty() {
+        "Sheet1".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+struct CellFormats {
+    header: Format,
+    date: Format,
+    datetime: Format,
+    time: Format,
+}
+
+impl Default for CellFormats {
+    fn default() -> Self {
+        Self {
+            header: Format::new().set_bold(),
+            date: Format::new().set_num_format("yyyy-mm-dd"),
+            datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+            time: Format::new().set_num_format("hh:mm:ss"),
+        }
+    }
+}
+
+/// Writes `headers` in bold to the first row and freezes it.
+fn write_headers(
+    worksheet: &mut Worksheet,
+    headers: &[String],
+    formats: &CellFormats,
+) -> anyhow::Result<()> {
+    for (column, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(0, column as u16, header, &formats.header)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+/// Writes `values` to the `row`th row of `worksheet`, as numbers and dates when they look like
+/// ones, so they can be calculated with. `NULL` is an empty cell.
+fn write_row(
+    worksheet: &mut Worksheet,
+    row: u32,
+    values: &[String],
+    formats: &CellFormats,
+) -> anyhow::Result<()> {
+    for (column, value) in values.iter().enumerate() {
+        let column = column as u16;
+        match cell_value(value) {
+            CellValue::Empty => {}
+            CellValue::Number(number) => {
+                worksheet.write_number(row, column, number)?;
+            }
+            CellValue::Date(date) => {
+                worksheet.write_datetime_with_format(row, column, date, &formats.date)?;
+            }
+            CellValue::DateTime(datetime) => {
+                worksheet.write_datetime_with_format(row, column, datetime, &formats.datetime)?;
+            }
+            CellValue::Time(time) => {
+                worksheet.write_datetime_with_format(row, column, time, &formats.time)?;
+            }
+            CellValue::String => {
+                worksheet.write_string(row, column, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Significant digits kept by the numbers of Excel.
+const MAX_NUMBER_DIGITS: usize = 15;
+
+enum CellValue {
+    Empty,
+    Number(f64),
+    Date(ExcelDateTime),
+    DateTime(ExcelDateTime),
+    Time(ExcelDateTime),
+    String,
+}
+
+/// How `value` is written to a cell. Numbers which would lose digits or leading zeros, such as
+/// IDs and zip codes, stay strings, and so do dates Excel can not show.
+fn cell_value(value: &str) -> CellValue {
+    if value == "NULL" {
+        return CellValue::Empty;
+    }
+    if is_number(value) {
+        let digits = value.trim_start_matches('-');
+        let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+        let significant = digits
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .skip_while(|c| *c == '0')
+            .count();
+        if !leading_zero && significant <= MAX_NUMBER_DIGITS {
+            if let Ok(number) = value.parse::<f64>() {
+                return CellValue::Number(number);
+            }
+        }
+        return CellValue::String;
+    }
+    let datetime = |date: NaiveDate, time: NaiveTime| {
+        ExcelDateTime::from_ymd(date.year() as u16, date.month() as u8, date.day() as u8)
+            .and_then(|datetime| {
+                datetime.and_hms(time.hour() as u16, time.minute() as u8, seconds(time))
+            })
+            .ok()
+    };
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        if let Some(date) = datetime(date, NaiveTime::MIN) {
+            return CellValue::Date(date);
+        }
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(value) = NaiveDateTime::parse_from_str(value, format) {
+            if let Some(value) = datetime(value.date(), value.time()) {
+                return CellValue::DateTime(value);
+            }
+        }
+    }
+    if let Ok(time) = NaiveTime::parse_from_str(value, "%H:%M:%S%.f") {
+        if let Ok(time) =
+            ExcelDateTime::from_hms(time.hour() as u16, time.minute() as u8, seconds(time))
+        {
+            return CellValue::Time(time);
+        }
+    }
+    CellValue::String
+}
+
+fn seconds(time: NaiveTime) -> f64 {
+    time.second() as f64 + time.nanosecond() as f64 / 1_000_000_000.0
+}
+
+/// Writes `values` as a CSV line. `NULL` and the other `values` are encoded unless they are
+/// the headers.
+fn write_line(
+    writer: &mut impl Write,
+    values: &[String],
+    encoding: &ExportEncoding,
+    encode: bool,
+) -> anyhow::Result<usize> {
+    let line = format!(
+        "{}{}",
+        values
+            .iter()
+            .map(|value| {
+                if !encode {
+                    return quote(value, encoding.quote);
+                }
+                if value == "NULL" {
+                    return encoding.null.clone();
+                }
+                quote(&encoding.encode(value), encoding.quote)
+            })
+            .collect::<Vec<String>>()
+            .join(","),
+        encoding.line_ending.as_str()
+    );
+    writer.write_all(line.as_bytes())?;
+    Ok(line.len())
+}
+
+fn quote(value: &str, policy: QuotePolicy) -> String {
+    match policy {
+        QuotePolicy::Minimal => csv_value(value),
+        QuotePolicy::NonNumeric if is_number(value) => value.to_string(),
+        QuotePolicy::All | QuotePolicy::NonNumeric => {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        }
+        QuotePolicy::Never => value.to_string(),
+    }
+}
+
+/// Writes `values` as a JSON object keyed by `headers`, in the order of the columns. Values are
+/// strings, since they are fetched as text, and `NULL` is `null`.
+fn write_json_line(
+    writer: &mut impl Write,
+    headers: &[String],
+    values: &[String],
+    encoding: &ExportEncoding,
+) -> anyhow::Result<usize> {
+    let line = format!(
+        "{{{}}}{}",
+        headers
+            .iter()
+            .zip(values)
+            .map(|(header, value)| {
+                let value = if value == "NULL" {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::Value::String(encoding.encode(value).into_owned())
+                };
+                format!("{}:{}", serde_json::Value::String(header.clone()), value)
+            })
+            .collect::<Vec<String>>()
+            .join(","),
+        encoding.line_ending.as_str()
+    );
+    writer.write_all(line.as_bytes())?;
+    Ok(line.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        cell_value, dump, export, sheet_name, write_line, write_xlsx, BooleanEncoding, CellValue,
+        ExportCompression, ExportEncoding, ExportFormat, ExportOptions, LineEnding, QuotePolicy,
+        Sheet,
+    };
+    use crate::jobs::JobProgress;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use std::sync::Arc;
+    use zhobo::database::{Pool, QueryBuilder, SqlitePool, MAX_CONNECTIONS};
+    use zhobo::tree::{Database, Table};
+
+    fn users() -> QueryBuilder {
+        QueryBuilder::new(
+            Database::new("main".to_string(), vec![]),
+            Table {
+                name: "users".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                parent: None,
+                schema: None,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_export_csv() {
+        let database = std::env::temp_dir().join(format!("zhobo_export_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&database)
+                .create_if_missing(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(&"CREATE TABLE users (id INTEGER, name TEXT)".to_string())
+            .await
+            .unwrap();
+        pool.execute(&"INSERT INTO users VALUES (1, 'foo'), (2, 'b,ar')".to_string())
+            .await
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("zhobo_export_{}.csv", std::process::id()));
+        let progress = Arc::new(JobProgress::default());
+        export(
+            pool.clone_box(),
+            users().orders(Some("ORDER BY id".to_string())),
+            path.clone(),
+            ExportOptions::from_path(&path).unwrap(),
+            ExportEncoding::default(),
+            progress.clone(),
+        )
+        .await
+        .unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "id,name\n1,foo\n2,\"b,ar\"\n");
+        assert_eq!(progress.rows(), 2);
+        assert_eq!(progress.bytes(), contents.len());
+
+        progress.cancel();
+        assert!(export(
+            pool.clone_box(),
+            users(),
+            path.clone(),
+            ExportOptions::from_path(&path).unwrap(),
+            ExportEncoding::default(),
+            progress
+        )
+        .await
+        .is_err());
+        assert!(!path.exists());
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+    }
+
+    #[test]
+    fn test_export_options() {
+        let options = |path: &str| ExportOptions::from_path(std::path::Path::new(path)).unwrap();
+        assert_eq!(
+            options("users.CSV"),
+            ExportOptions {
+                format: ExportFormat::Csv,
+                compression: ExportCompression::None,
+            }
+        );
+        assert_eq!(
+            options("/tmp/users.jsonl.gz"),
+            ExportOptions {
+                format: ExportFormat::Jsonl,
+                compression: ExportCompression::Gzip,
+            }
+        );
+        assert_eq!(
+            options("users.ndjson.zst").compression,
+            ExportCompression::Zstd
+        );
+        assert_eq!(
+            options("users.parquet"),
+            ExportOptions {
+                format: ExportFormat::Parquet,
+                compression: ExportCompression::Snappy,
+            }
+        );
+        assert_eq!(
+            options("users.zstd.parquet").compression,
+            ExportCompression::Zstd
+        );
+        assert_eq!(
+            options("users.uncompressed.parquet").compression,
+            ExportCompression::None
+        );
+        assert_eq!(options("users.xlsx").format, ExportFormat::Xlsx);
+        assert!(ExportOptions::from_path(std::path::Path::new("users.txt")).is_err());
+        assert!(ExportOptions::from_path(std::path::Path::new("users.xlsx.gz")).is_err());
+        assert!(ExportOptions::from_path(std::path::Path::new("users.parquet.gz")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_and_parquet() {
+        use arrow_array::{Array, StringArray};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use std::io::Read;
+
+        let name = |extension: &str| {
+            std::env::temp_dir().join(format!(
+                "zhobo_export_formats_{}.{}",
+                std::process::id(),
+                extension
+            ))
+        };
+        let database = name("db");
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&database)
+                .create_if_missing(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(&"CREATE TABLE users (id INTEGER, name TEXT)".to_string())
+            .await
+            .unwrap();
+        pool.execute(&"INSERT INTO users VALUES (1, 'a\"b'), (2, NULL)".to_string())
+            .await
+            .unwrap();
+        let run = |path: std::path::PathBuf| {
+            export(
+                pool.clone_box(),
+                users().orders(Some("ORDER BY id".to_string())),
+                path.clone(),
+                ExportOptions::from_path(&path).unwrap(),
+                ExportEncoding::default(),
+                Arc::new(JobProgress::default()),
+            )
+        };
+
+        let jsonl = name("jsonl.gz");
+        run(jsonl.clone()).await.unwrap();
+        let mut contents = String::new();
+        flate2::read::GzDecoder::new(std::fs::File::open(&jsonl).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(
+            contents,
+            "{\"id\":\"1\",\"name\":\"a\\\"b\"}\n{\"id\":\"2\",\"name\":null}\n"
+        );
+
+        let parquet = name("zstd.parquet");
+        run(parquet.clone()).await.unwrap();
+        let batches =
+            ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&parquet).unwrap())
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema().field(1).name(), "name");
+        let names = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "a\"b");
+        assert!(names.is_null(1));
+
+        pool.close().await;
+        for path in [database, jsonl, parquet] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_cell_value() {
+        assert!(matches!(cell_value("NULL"), CellValue::Empty));
+        assert!(matches!(cell_value("-1.5"), CellValue::Number(n) if n == -1.5));
+        assert!(matches!(cell_value("0.25"), CellValue::Number(n) if n == 0.25));
+        assert!(matches!(cell_value("0"), CellValue::Number(n) if n == 0.0));
+        assert!(matches!(cell_value("007"), CellValue::String));
+        assert!(matches!(cell_value("12345678901234567"), CellValue::String));
+        assert!(matches!(cell_value("2024-02-29"), CellValue::Date(_)));
+        assert!(matches!(
+            cell_value("2024-02-29 12:30:00.5"),
+            CellValue::DateTime(_)
+        ));
+        assert!(matches!(
+            cell_value("2024-02-29T12:30:00"),
+            CellValue::DateTime(_)
+        ));
+        assert!(matches!(cell_value("12:30:00"), CellValue::Time(_)));
+        assert!(matches!(cell_value("2024-02-30"), CellValue::String));
+        assert!(matches!(cell_value("1899-01-01"), CellValue::String));
+        assert!(matches!(cell_value("foo"), CellValue::String));
+    }
+
+    #[test]
+    fn test_write_xlsx() {
+        assert_eq!(sheet_name("users [2024/01]"), "users 202401");
+        assert_eq!(sheet_name("?"), "Sheet1");
+
+        let path = std::env::temp_dir().join(format!("zhobo_export_{}.xlsx", std::process::id()));
+        let headers = vec!["id".to_string(), "created_at".to_string()];
+        let rows = vec![vec!["1".to_string(), "2024-01-01".to_string()]];
+        write_xlsx(
+            &path,
+            &[
+                Sheet {
+                    name: "Result".to_string(),
+                    headers: &headers,
+                    rows: &rows,
+                },
+                Sheet {
+                    name: "result".to_string(),
+                    headers: &headers,
+                    rows: &[],
+                },
+            ],
+        )
+        .unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"PK"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_export_encoding() {
+        let encoding: ExportEncoding = toml::from_str(
+            r#"
+            null = ""
+            date_format = "%d/%m/%Y"
+            datetime_format = "%Y-%m-%dT%H:%M:%S%z"
+            boolean = "1/0"
+            quote = "non_numeric"
+            line_ending = "crlf"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(encoding.boolean, BooleanEncoding::OneZero);
+        assert_eq!(encoding.quote, QuotePolicy::NonNumeric);
+        assert_eq!(encoding.line_ending, LineEnding::Crlf);
+        assert_eq!(encoding.encode("2024-01-31"), "31/01/2024");
+        assert_eq!(
+            encoding.encode("2024-01-31 12:30:00 UTC"),
+            "2024-01-31T12:30:00+0000"
+        );
+        // A timestamp without a time zone can not be written with %z.
+        assert_eq!(
+            encoding.encode("2024-01-31 12:30:00"),
+            "2024-01-31 12:30:00"
+        );
+
+        let values = ["1", "true", "NULL", "2024-01-31", "a\"b"].map(|v| v.to_string());
+        let mut line = vec![];
+        write_line(&mut line, &values, &encoding, true).unwrap();
+        assert_eq!(
+            String::from_utf8(line).unwrap(),
+            "1,1,,\"31/01/2024\",\"a\"\"b\"\r\n"
+        );
+        let mut line = vec![];
+        write_line(&mut line, &values, &ExportEncoding::default(), true).unwrap();
+        assert_eq!(
+            String::from_utf8(line).unwrap(),
+            "1,true,NULL,2024-01-31,\"a\"\"b\"\n"
+        );
+
+        let invalid = ExportEncoding {
+            date_format: Some("%Q".to_string()),
+            ..ExportEncoding::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dump() {
+        let database = std::env::temp_dir().join(format!("zhobo_dump_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new()
+                .filename(&database)
+                .create_if_missing(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        pool.execute(&"CREATE TABLE users (id INTEGER, name TEXT, data BLOB)".to_string())
+            .await
+            .unwrap();
+        pool.execute(
+            &"INSERT INTO users VALUES (1, 'o''neil', NULL), (2, NULL, X'0A1B')".to_string(),
+        )
+        .await
+        .unwrap();
+        let databases = pool.get_databases().await.unwrap();
+        let table = match &databases[0].children[0] {
+            zhobo::tree::Child::Table(table) => table.clone(),
+            _ => panic!("expected a table"),
+        };
+        let path = std::env::temp_dir().join(format!("zhobo_dump_{}.sql", std::process::id()));
+        let progress = Arc::new(JobProgress::default());
+        dump(
+            pool.clone_box(),
+            databases[0].clone(),
+            table,
+            path.clone(),
+            progress.clone(),
+        )
+        .await
+        .unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("CREATE TABLE users (id INTEGER, name TEXT, data BLOB);\n\n"));
+        assert!(contents.ends_with("VALUES (1, 'o''neil', NULL), (2, NULL, X'0A1B');\n"));
+        assert_eq!(progress.rows(), 2);
+        pool.close().await;
+        std::fs::remove_file(database).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
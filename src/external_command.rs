@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+const PLACEHOLDERS: [&str; 5] = [
+    "database",
+    "schema",
+    "table",
+    "connection_url",
+    "connection",
+];
+
+/// A command run from the external commands popup through `sh -c`. `{database}`, `{schema}`,
+/// `{table}`, `{connection_url}` and `{connection}` in `command` are replaced with the selected
+/// table and the connection, quoted for the shell.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ExternalCommand {
+    pub name: String,
+    pub command: String,
+}
+
+/// The values of the placeholders. `None` is a value which is not available, such as the table
+/// when none is selected.
+#[derive(Default)]
+pub struct Placeholders {
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub table: Option<String>,
+    pub connection_url: Option<String>,
+    pub connection: Option<String>,
+}
+
+impl Placeholders {
+    fn get(&self, name: &str) -> Option<&Option<String>> {
+        match name {
+            "database" => Some(&self.database),
+            "schema" => Some(&self.schema),
+            "table" => Some(&self.table),
+            "connection_url" => Some(&self.connection_url),
+            "connection" => Some(&self.connection),
+            _ => None,
+        }
+    }
+}
+
+impl ExternalCommand {
+    /// The command line with the placeholders replaced. Fails if the command refers to a value
+    /// which is not available.
+    pub fn expand(&self, placeholders: &Placeholders) -> Result<String> {
+        let mut expanded = String::new();
+        let mut rest = self.command.as_str();
+        while let Some(start) = rest.find('{') {
+            expanded.push_str(&rest[..start]);
+            let placeholder = rest[start + 1..]
+                .find('}')
+                .map(|end| &rest[start + 1..start + 1 + end])
+                .filter(|name| PLACEHOLDERS.contains(name));
+            match placeholder.and_then(|name| placeholders.get(name).map(|value| (name, value))) {
+                Some((name, value)) => {
+                    let value = value.as_ref().ok_or_else(|| {
+                        anyhow!("`{}` needs {{{}}}, which is not selected", self.name, name)
+                    })?;
+                    expanded.push_str(&shell_quote(value));
+                    rest = &rest[start + name.len() + 2..];
+                }
+                None => {
+                    expanded.push('{');
+                    rest = &rest[start + 1..];
+                }
+            }
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExternalCommand, Placeholders};
+
+    #[test]
+    fn test_expand() {
+        let command = ExternalCommand {
+            name: "dump".to_string(),
+            command: "pg_dump -t {schema}.{table} {connection_url} > ${HOME}/{table}.sql"
+                .to_string(),
+        };
+        let placeholders = Placeholders {
+            database: Some("app".to_string()),
+            schema: Some("public".to_string()),
+            table: Some("o'rders".to_string()),
+            connection_url: Some("postgres://root@localhost:5432/app".to_string()),
+            connection: None,
+        };
+        assert_eq!(
+            command.expand(&placeholders).unwrap(),
+            r"pg_dump -t 'public'.'o'\''rders' 'postgres://root@localhost:5432/app' > ${HOME}/'o'\''rders'.sql"
+        );
+
+        let placeholders = Placeholders {
+            table: None,
+            ..placeholders
+        };
+        assert_eq!(
+            command.expand(&placeholders).unwrap_err().to_string(),
+            "`dump` needs {table}, which is not selected"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+
+const MAX_FILTERS_PER_TABLE: usize = 30;
+
+/// A filter as it was entered, with the mode it was applied in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterEntry {
+    pub filter: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub ignore_case: bool,
+}
+
+/// Applied filters per table of a connection, from oldest to newest.
+#[derive(Default, Serialize, Deserialize)]
+pub struct FilterHistory {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(default)]
+    entries: HashMap<String, Vec<FilterEntry>>,
+}
+
+impl FilterHistory {
+    /// Loads the history from `path`. A missing or broken file results in an empty history.
+    pub fn load(path: PathBuf) -> Self {
+        let mut contents = String::new();
+        let mut history = File::open(&path)
+            .ok()
+            .and_then(|mut file| file.read_to_string(&mut contents).ok())
+            .and_then(|_| ron::from_str::<Self>(&contents).ok())
+            .unwrap_or_default();
+        history.path = Some(path);
+        history
+    }
+
+    pub fn filters(&self, table: &str) -> &[FilterEntry] {
+        self.entries
+            .get(table)
+            .map_or(&[], |filters| filters.as_slice())
+    }
+
+    pub fn push(&mut self, table: &str, filter: FilterEntry) -> anyhow::Result<()> {
+        let filters = self.entries.entry(table.to_string()).or_default();
+        filters.retain(|f| f != &filter);
+        filters.push(filter);
+        if filters.len() > MAX_FILTERS_PER_TABLE {
+            filters.remove(0);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.path {
+            fs::write(path, ron::ser::to_string_pretty(self, Default::default())?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FilterEntry, FilterHistory, MAX_FILTERS_PER_TABLE};
+
+    fn entry(filter: &str) -> FilterEntry {
+        FilterEntry {
+            filter: filter.to_string(),
+            ..FilterEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_push() {
+        let mut history = FilterHistory::default();
+        history.push("db.users", entry("id = 1")).unwrap();
+        history.push("db.users", entry("id = 2")).unwrap();
+        history.push("db.users", entry("id = 1")).unwrap();
+        assert_eq!(
+            history.filters("db.users"),
+            &[entry("id = 2"), entry("id = 1")]
+        );
+        assert!(history.filters("db.other").is_empty());
+
+        let regex = FilterEntry {
+            regex: true,
+            ..entry("id = 1")
+        };
+        history.push("db.users", regex.clone()).unwrap();
+        assert_eq!(history.filters("db.users").len(), 3);
+        assert_eq!(history.filters("db.users")[2], regex);
+
+        for i in 0..MAX_FILTERS_PER_TABLE {
+            history
+                .push("db.users", entry(&format!("id > {}", i)))
+                .unwrap();
+        }
+        assert_eq!(history.filters("db.users").len(), MAX_FILTERS_PER_TABLE);
+        assert_eq!(history.filters("db.users")[0], entry("id > 0"));
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let path =
+            std::env::temp_dir().join(format!("zhobo_filter_history_{}.ron", std::process::id()));
+        let mut history = FilterHistory::load(path.clone());
+        assert!(history.filters("db.users").is_empty());
+        history.push("db.users", entry("name LIKE 'a%'")).unwrap();
+
+        let history = FilterHistory::load(path.clone());
+        assert_eq!(history.filters("db.users"), &[entry("name LIKE 'a%'")]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
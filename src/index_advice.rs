@@ -0,0 +1,298 @@
+use regex::Regex;
+
+/// Indexes wider than this rarely pay off, so the remaining candidate columns are dropped.
+const MAX_INDEX_COLUMNS: usize = 3;
+
+const KEYWORDS: [&str; 24] = [
+    "WHERE", "ON", "USING", "JOIN", "LEFT", "RIGHT", "INNER", "OUTER", "CROSS", "FULL", "NATURAL",
+    "ORDER", "GROUP", "HAVING", "LIMIT", "OFFSET", "UNION", "AND", "OR", "NOT", "NULL", "SET",
+    "FOR", "WINDOW",
+];
+
+#[derive(Debug, PartialEq)]
+pub struct IndexSuggestion {
+    pub table: String,
+    pub columns: Vec<String>,
+}
+
+impl IndexSuggestion {
+    pub fn name(&self) -> String {
+        format!(
+            "idx_{}_{}",
+            self.table.rsplit('.').next().unwrap_or_default(),
+            self.columns.join("_")
+        )
+    }
+
+    pub fn statement(&self, quote: impl Fn(&str) -> String) -> String {
+        format!(
+            "CREATE INDEX {} ON {} ({});",
+            quote(&self.name()),
+            self.table,
+            self.columns
+                .iter()
+                .map(|column| quote(column))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Returns the tables, or their aliases, an `EXPLAIN` result of MySQL, PostgreSQL, or an
+/// `EXPLAIN QUERY PLAN` result of SQLite reads without an index.
+pub fn full_scans(headers: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    let position = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+    };
+    let mut scans: Vec<String> = vec![];
+    if let (Some(r#type), Some(table)) = (position("type"), position("table")) {
+        for row in rows {
+            if row.get(r#type).is_some_and(|r#type| r#type == "ALL") {
+                scans.extend(row.get(table).cloned());
+            }
+        }
+    } else if let Some(plan) = position("QUERY PLAN") {
+        let re = Regex::new(r"Seq Scan on (\S+)(?: (\w+))?").unwrap();
+        for row in rows {
+            if let Some(captures) = row.get(plan).and_then(|line| re.captures(line)) {
+                scans.extend(
+                    captures
+                        .get(2)
+                        .or(captures.get(1))
+                        .map(|m| m.as_str().to_string()),
+                );
+            }
+        }
+    } else if let Some(detail) = position("detail") {
+        let re = Regex::new(r"^SCAN (?:TABLE )?(\S+)(?: AS (\S+))?$").unwrap();
+        for row in rows {
+            if let Some(captures) = row.get(detail).and_then(|line| re.captures(line)) {
+                scans.extend(
+                    captures
+                        .get(2)
+                        .or(captures.get(1))
+                        .map(|m| m.as_str().to_string()),
+                );
+            }
+        }
+    }
+    let mut unique = vec![];
+    for scan in scans {
+        if !unique.contains(&scan) {
+            unique.push(scan);
+        }
+    }
+    unique
+}
+
+/// Suggests an index for each of `scans` from the columns `query` filters on, joins on, and
+/// sorts by, in that order. Equality conditions come before range conditions.
+pub fn suggest(query: &str, scans: &[String]) -> Vec<IndexSuggestion> {
+    let query = Regex::new(r"'(?:[^']|'')*'")
+        .unwrap()
+        .replace_all(query, "?")
+        .to_string();
+    let tables = tables(&query);
+    let references = references(&query);
+    let mut suggestions = vec![];
+    for scan in scans {
+        let Some((key, table)) = tables.iter().find(|(key, table)| {
+            key.eq_ignore_ascii_case(scan) || table.eq_ignore_ascii_case(scan)
+        }) else {
+            continue;
+        };
+        let mut columns: Vec<String> = vec![];
+        for (qualifier, column) in &references {
+            let matches = match qualifier {
+                Some(qualifier) => qualifier.eq_ignore_ascii_case(key),
+                None => tables.len() == 1,
+            };
+            if matches && !columns.contains(column) && columns.len() < MAX_INDEX_COLUMNS {
+                columns.push(column.clone());
+            }
+        }
+        let suggestion = IndexSuggestion {
+            table: table.clone(),
+            columns,
+        };
+        if !suggestion.columns.is_empty() && !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+    suggestions
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+/// The tables in `FROM` and `JOIN` as pairs of the name they are referred by and the table.
+pub fn tables(query: &str) -> Vec<(String, String)> {
+    let re = Regex::new(r#"(?i)\b(?:FROM|JOIN)\s+([\w.`"]+)(?:\s+(?:AS\s+)?(\w+))?"#).unwrap();
+    re.captures_iter(query)
+        .map(|captures| {
+            let table = captures[1].replace(['`', '"'], "");
+            let key = match captures.get(2) {
+                Some(alias) if !is_keyword(alias.as_str()) => alias.as_str().to_string(),
+                _ => table.rsplit('.').next().unwrap_or_default().to_string(),
+            };
+            (key, table)
+        })
+        .collect()
+}
+
+/// The columns referenced in `WHERE`, `ON`, and `ORDER BY` clauses, in order of priority.
+fn references(query: &str) -> Vec<(Option<String>, String)> {
+    let clause =
+        Regex::new(r"(?i)\b(SELECT|FROM|WHERE|ON|JOIN|ORDER\s+BY|GROUP\s+BY|HAVING|LIMIT|UNION)\b")
+            .unwrap();
+    let keywords = clause.find_iter(query).collect::<Vec<_>>();
+    let mut segments: Vec<(String, &str)> = vec![];
+    for (i, keyword) in keywords.iter().enumerate() {
+        let end = keywords.get(i + 1).map_or(query.len(), |next| next.start());
+        segments.push((
+            keyword
+                .as_str()
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+                .to_uppercase(),
+            &query[keyword.end()..end],
+        ));
+    }
+    let segments_of = |name: &str| -> Vec<&str> {
+        segments
+            .iter()
+            .filter(|(keyword, _)| keyword == name)
+            .map(|(_, segment)| *segment)
+            .collect()
+    };
+
+    let equality = Regex::new(r"(?i)((?:\w+\.)?\w+)\s*(?:=|\bIN\b|\bIS\b)").unwrap();
+    let range =
+        Regex::new(r"(?i)((?:\w+\.)?\w+)\s*(?:<>|!=|<=|>=|<|>|\bLIKE\b|\bBETWEEN\b)").unwrap();
+    let qualified = Regex::new(r"(\w+\.\w+)").unwrap();
+    let leading = Regex::new(r"^\s*((?:\w+\.)?\w+)").unwrap();
+
+    let mut references = vec![];
+    for re in [&equality, &range] {
+        for segment in segments_of("WHERE") {
+            references.extend(
+                re.captures_iter(segment)
+                    .map(|captures| captures[1].to_string()),
+            );
+        }
+    }
+    for segment in segments_of("ON") {
+        references.extend(qualified.captures_iter(segment).map(|c| c[1].to_string()));
+    }
+    for segment in segments_of("ORDER BY") {
+        for item in segment.split(',') {
+            references.extend(
+                leading
+                    .captures(item)
+                    .map(|captures| captures[1].to_string()),
+            );
+        }
+    }
+    references
+        .into_iter()
+        .filter_map(|reference| {
+            let (qualifier, column) = match reference.split_once('.') {
+                Some((qualifier, column)) => (Some(qualifier.to_string()), column.to_string()),
+                None => (None, reference),
+            };
+            (!is_keyword(&column) && !column.starts_with(|c: char| c.is_ascii_digit()))
+                .then_some((qualifier, column))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{full_scans, suggest, IndexSuggestion};
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_full_scans() {
+        let mysql = full_scans(
+            &strings(&["id", "select_type", "table", "type", "key"]),
+            &[
+                strings(&["1", "SIMPLE", "u", "ALL", "NULL"]),
+                strings(&["1", "SIMPLE", "o", "ref", "user_id"]),
+            ],
+        );
+        assert_eq!(mysql, vec!["u"]);
+
+        let postgres = full_scans(
+            &strings(&["QUERY PLAN"]),
+            &[
+                strings(&["Hash Join  (cost=1.00..2.00 rows=1 width=8)"]),
+                strings(&["  ->  Seq Scan on users u  (cost=0.00..1.00 rows=1 width=8)"]),
+                strings(&["  ->  Index Scan using orders_pkey on orders o"]),
+                strings(&["  ->  Seq Scan on items  (cost=0.00..1.00 rows=1 width=8)"]),
+            ],
+        );
+        assert_eq!(postgres, vec!["u", "items"]);
+
+        let sqlite = full_scans(
+            &strings(&["id", "parent", "notused", "detail"]),
+            &[
+                strings(&["2", "0", "0", "SCAN users"]),
+                strings(&["3", "0", "0", "SCAN orders USING COVERING INDEX idx"]),
+                strings(&["4", "0", "0", "SEARCH items USING INDEX idx (id=?)"]),
+            ],
+        );
+        assert_eq!(sqlite, vec!["users"]);
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(
+            suggest(
+                "SELECT * FROM users WHERE created_at > '2024-01-01' AND email = 'a=b' ORDER BY name",
+                &strings(&["users"]),
+            ),
+            vec![IndexSuggestion {
+                table: "users".to_string(),
+                columns: strings(&["email", "created_at", "name"]),
+            }]
+        );
+        assert_eq!(
+            suggest(
+                "SELECT * FROM users AS u JOIN orders o ON o.user_id = u.id WHERE o.status = 'paid'",
+                &strings(&["o", "u"]),
+            ),
+            vec![
+                IndexSuggestion {
+                    table: "orders".to_string(),
+                    columns: strings(&["status", "user_id"]),
+                },
+                IndexSuggestion {
+                    table: "users".to_string(),
+                    columns: strings(&["id"]),
+                },
+            ]
+        );
+        assert!(suggest("SELECT * FROM users", &strings(&["users"])).is_empty());
+    }
+
+    #[test]
+    fn test_statement() {
+        let suggestion = IndexSuggestion {
+            table: "public.users".to_string(),
+            columns: strings(&["email", "name"]),
+        };
+        assert_eq!(
+            suggestion.statement(|identifier| format!("\"{}\"", identifier)),
+            "CREATE INDEX \"idx_users_email_name\" ON public.users (\"email\", \"name\");"
+        );
+    }
+}
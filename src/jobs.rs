@@ -0,0 +1,184 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Progress shared between a background job and the UI.
+#[derive(Default)]
+pub struct JobProgress {
+    rows: AtomicUsize,
+    bytes: AtomicUsize,
+    total_rows: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl JobProgress {
+    pub fn set_total_rows(&self, total_rows: usize) {
+        self.total_rows.store(total_rows, Ordering::Relaxed);
+    }
+
+    pub fn add(&self, rows: usize, bytes: usize) {
+        self.rows.fetch_add(rows, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// `None` if the total is unknown.
+    pub fn total_rows(&self) -> Option<usize> {
+        Some(self.total_rows.load(Ordering::Relaxed)).filter(|total| *total > 0)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Jobs check this between steps and stop as soon as it is set.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed(_) => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+pub struct Job {
+    pub title: String,
+    pub progress: Arc<JobProgress>,
+    pub status: JobStatus,
+    started_at: Instant,
+    finished_in: Option<Duration>,
+    handle: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl Job {
+    /// Runs `task` on the tokio runtime. `task` reports to and checks cancellation on
+    /// `progress`.
+    pub fn spawn<F>(title: String, progress: Arc<JobProgress>, task: F) -> Self
+    where
+        F: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self {
+            title,
+            progress,
+            status: JobStatus::Running,
+            started_at: Instant::now(),
+            finished_in: None,
+            handle: Some(tokio::spawn(task)),
+        }
+    }
+
+    /// Updates the status if the task has finished.
+    pub fn poll(&mut self) {
+        if !self
+            .handle
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished())
+        {
+            return;
+        }
+        let result = self.handle.take().and_then(|handle| handle.now_or_never());
+        self.finished_in = Some(self.started_at.elapsed());
+        self.status = match result {
+            Some(Ok(Ok(()))) => JobStatus::Succeeded,
+            _ if self.progress.is_cancelled() => JobStatus::Cancelled,
+            Some(Ok(Err(e))) => JobStatus::Failed(e.to_string()),
+            Some(Err(e)) => JobStatus::Failed(e.to_string()),
+            None => JobStatus::Failed("the job was lost".to_string()),
+        };
+    }
+
+    /// Time the job has been running, or took to finish.
+    pub fn elapsed(&self) -> Duration {
+        self.finished_in
+            .unwrap_or_else(|| self.started_at.elapsed())
+    }
+
+    /// Estimated time until the job finishes, based on the rows processed so far.
+    pub fn eta(&self) -> Option<Duration> {
+        let rows = self.progress.rows();
+        let total_rows = self.progress.total_rows()?;
+        if rows == 0 || !matches!(self.status, JobStatus::Running) {
+            return None;
+        }
+        let elapsed = self.elapsed().as_secs_f64();
+        Some(Duration::from_secs_f64(
+            elapsed / rows as f64 * total_rows.saturating_sub(rows) as f64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Job, JobProgress, JobStatus};
+    use std::sync::Arc;
+
+    async fn wait(job: &mut Job) {
+        while matches!(job.status, JobStatus::Running) {
+            tokio::task::yield_now().await;
+            job.poll();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job() {
+        let progress = Arc::new(JobProgress::default());
+        let task_progress = progress.clone();
+        let mut job = Job::spawn("count".to_string(), progress, async move {
+            task_progress.set_total_rows(10);
+            task_progress.add(10, 100);
+            Ok(())
+        });
+        wait(&mut job).await;
+        assert_eq!(job.status, JobStatus::Succeeded);
+        let elapsed = job.elapsed();
+        assert_eq!(job.elapsed(), elapsed);
+        assert_eq!(job.progress.rows(), 10);
+        assert_eq!(job.progress.bytes(), 100);
+        assert_eq!(job.eta(), None);
+
+        let progress = Arc::new(JobProgress::default());
+        progress.cancel();
+        let task_progress = progress.clone();
+        let mut job = Job::spawn("cancelled".to_string(), progress, async move {
+            if task_progress.is_cancelled() {
+                return Err(anyhow::anyhow!("cancelled"));
+            }
+            Ok(())
+        });
+        wait(&mut job).await;
+        assert_eq!(job.status, JobStatus::Cancelled);
+
+        let mut job = Job::spawn(
+            "failed".to_string(),
+            Arc::new(JobProgress::default()),
+            async move { Err(anyhow::anyhow!("disk full")) },
+        );
+        wait(&mut job).await;
+        assert_eq!(job.status, JobStatus::Failed("disk full".to_string()));
+    }
+}
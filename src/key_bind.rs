@@ -45,8 +45,78 @@ pub struct KeyBind {
     pub tab_definition: Option<Key>,
     pub tab_sql_editor: Option<Key>,
     pub tab_properties: Option<Key>,
+    pub tab_server: Option<Key>,
+    pub tab_locks: Option<Key>,
+    pub tab_variables: Option<Key>,
+    pub refresh: Option<Key>,
+    pub undo_change: Option<Key>,
+    pub bulk_update: Option<Key>,
+    pub generate_data: Option<Key>,
+    pub paste_rows: Option<Key>,
+    pub copy_rows_to_connection: Option<Key>,
+    pub copy_distinct_values: Option<Key>,
+    pub copy_distinct_values_as_in_list: Option<Key>,
+    pub value_frequency: Option<Key>,
+    pub show_cell_value: Option<Key>,
+    pub copy_as_literal: Option<Key>,
+    pub extract_json_path: Option<Key>,
+    pub add_computed_column: Option<Key>,
+    pub exclude_cell_value: Option<Key>,
+    pub filter_null: Option<Key>,
+    pub filter_not_null: Option<Key>,
+    pub hide_column: Option<Key>,
+    pub edit_column_layout: Option<Key>,
+    pub next_match: Option<Key>,
+    pub previous_match: Option<Key>,
+    pub toggle_regex_filter: Option<Key>,
+    pub toggle_ignore_case_filter: Option<Key>,
+    pub vim_insert: Option<Key>,
+    pub vim_visual: Option<Key>,
+    pub vim_word_forward: Option<Key>,
+    pub vim_word_backward: Option<Key>,
+    pub vim_delete: Option<Key>,
+    pub vim_paste: Option<Key>,
+    pub vim_line_start: Option<Key>,
+    pub new_line: Option<Key>,
+    pub pin_result: Option<Key>,
+    pub next_result: Option<Key>,
+    pub previous_result: Option<Key>,
+    pub copy_result_as_csv: Option<Key>,
+    pub focus_header: Option<Key>,
+    pub show_query: Option<Key>,
+    pub switch_connection: Option<Key>,
+    pub test_connection: Option<Key>,
+    pub export_records: Option<Key>,
+    pub show_jobs: Option<Key>,
+    pub cancel_job: Option<Key>,
+    pub find_replace: Option<Key>,
+    pub replace_all: Option<Key>,
+    pub suggest_index: Option<Key>,
+    pub show_session_variables: Option<Key>,
+    pub show_result_schema: Option<Key>,
+    pub rerun_last_query: Option<Key>,
+    pub set_template_variable: Option<Key>,
+    pub toggle_scratch_database: Option<Key>,
+    pub save_result_to_scratch: Option<Key>,
+    pub run_without_limit: Option<Key>,
+    pub record_macro: Option<Key>,
+    pub play_macro: Option<Key>,
+    pub open_in_pager: Option<Key>,
+    pub open_shell: Option<Key>,
+    pub show_external_commands: Option<Key>,
+    pub toggle_privacy: Option<Key>,
+    pub context_menu: Option<Key>,
+    pub toggle_system_objects: Option<Key>,
+    pub copy_column_list: Option<Key>,
+    pub edit_comment: Option<Key>,
+    pub open_query_in_editor: Option<Key>,
+    pub undo_edit: Option<Key>,
+    pub redo_edit: Option<Key>,
+    pub vim_undo: Option<Key>,
     pub extend_or_shorten_widget_width_to_right: Option<Key>,
     pub extend_or_shorten_widget_width_to_left: Option<Key>,
+    pub extend_or_shorten_widget_height_to_top: Option<Key>,
+    pub extend_or_shorten_widget_height_to_bottom: Option<Key>,
 }
 
 impl KeyBind {
@@ -132,6 +202,77 @@ impl From<KeyBind> for KeyConfig {
         merge!(kc.tab_foreign_keys, kb.tab_foreign_keys);
         merge!(kc.tab_definition, kb.tab_definition);
         merge!(kc.tab_indexes, kb.tab_indexes);
+        merge!(kc.tab_server, kb.tab_server);
+        merge!(kc.tab_locks, kb.tab_locks);
+        merge!(kc.tab_variables, kb.tab_variables);
+        merge!(kc.refresh, kb.refresh);
+        merge!(kc.undo_change, kb.undo_change);
+        merge!(kc.bulk_update, kb.bulk_update);
+        merge!(kc.generate_data, kb.generate_data);
+        merge!(kc.paste_rows, kb.paste_rows);
+        merge!(kc.copy_rows_to_connection, kb.copy_rows_to_connection);
+        merge!(kc.copy_distinct_values, kb.copy_distinct_values);
+        merge!(
+            kc.copy_distinct_values_as_in_list,
+            kb.copy_distinct_values_as_in_list
+        );
+        merge!(kc.value_frequency, kb.value_frequency);
+        merge!(kc.show_cell_value, kb.show_cell_value);
+        merge!(kc.copy_as_literal, kb.copy_as_literal);
+        merge!(kc.extract_json_path, kb.extract_json_path);
+        merge!(kc.add_computed_column, kb.add_computed_column);
+        merge!(kc.exclude_cell_value, kb.exclude_cell_value);
+        merge!(kc.filter_null, kb.filter_null);
+        merge!(kc.filter_not_null, kb.filter_not_null);
+        merge!(kc.hide_column, kb.hide_column);
+        merge!(kc.edit_column_layout, kb.edit_column_layout);
+        merge!(kc.next_match, kb.next_match);
+        merge!(kc.previous_match, kb.previous_match);
+        merge!(kc.toggle_regex_filter, kb.toggle_regex_filter);
+        merge!(kc.toggle_ignore_case_filter, kb.toggle_ignore_case_filter);
+        merge!(kc.vim_insert, kb.vim_insert);
+        merge!(kc.vim_visual, kb.vim_visual);
+        merge!(kc.vim_word_forward, kb.vim_word_forward);
+        merge!(kc.vim_word_backward, kb.vim_word_backward);
+        merge!(kc.vim_delete, kb.vim_delete);
+        merge!(kc.vim_paste, kb.vim_paste);
+        merge!(kc.vim_line_start, kb.vim_line_start);
+        merge!(kc.new_line, kb.new_line);
+        merge!(kc.pin_result, kb.pin_result);
+        merge!(kc.next_result, kb.next_result);
+        merge!(kc.previous_result, kb.previous_result);
+        merge!(kc.copy_result_as_csv, kb.copy_result_as_csv);
+        merge!(kc.focus_header, kb.focus_header);
+        merge!(kc.show_query, kb.show_query);
+        merge!(kc.switch_connection, kb.switch_connection);
+        merge!(kc.test_connection, kb.test_connection);
+        merge!(kc.export_records, kb.export_records);
+        merge!(kc.show_jobs, kb.show_jobs);
+        merge!(kc.cancel_job, kb.cancel_job);
+        merge!(kc.find_replace, kb.find_replace);
+        merge!(kc.replace_all, kb.replace_all);
+        merge!(kc.suggest_index, kb.suggest_index);
+        merge!(kc.show_session_variables, kb.show_session_variables);
+        merge!(kc.show_result_schema, kb.show_result_schema);
+        merge!(kc.rerun_last_query, kb.rerun_last_query);
+        merge!(kc.set_template_variable, kb.set_template_variable);
+        merge!(kc.toggle_scratch_database, kb.toggle_scratch_database);
+        merge!(kc.save_result_to_scratch, kb.save_result_to_scratch);
+        merge!(kc.run_without_limit, kb.run_without_limit);
+        merge!(kc.record_macro, kb.record_macro);
+        merge!(kc.play_macro, kb.play_macro);
+        merge!(kc.open_in_pager, kb.open_in_pager);
+        merge!(kc.open_shell, kb.open_shell);
+        merge!(kc.show_external_commands, kb.show_external_commands);
+        merge!(kc.toggle_privacy, kb.toggle_privacy);
+        merge!(kc.context_menu, kb.context_menu);
+        merge!(kc.toggle_system_objects, kb.toggle_system_objects);
+        merge!(kc.copy_column_list, kb.copy_column_list);
+        merge!(kc.edit_comment, kb.edit_comment);
+        merge!(kc.open_query_in_editor, kb.open_query_in_editor);
+        merge!(kc.undo_edit, kb.undo_edit);
+        merge!(kc.redo_edit, kb.redo_edit);
+        merge!(kc.vim_undo, kb.vim_undo);
         merge!(
             kc.extend_or_shorten_widget_width_to_right,
             kb.extend_or_shorten_widget_width_to_right
@@ -140,6 +281,14 @@ impl From<KeyBind> for KeyConfig {
             kc.extend_or_shorten_widget_width_to_left,
             kb.extend_or_shorten_widget_width_to_left
         );
+        merge!(
+            kc.extend_or_shorten_widget_height_to_top,
+            kb.extend_or_shorten_widget_height_to_top
+        );
+        merge!(
+            kc.extend_or_shorten_widget_height_to_bottom,
+            kb.extend_or_shorten_widget_height_to_bottom
+        );
         kc
     }
 }
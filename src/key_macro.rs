@@ -0,0 +1,76 @@
+use crate::event::Key;
+
+/// A recorded sequence of keys, played back as if they were typed again.
+#[derive(Default)]
+pub struct KeyMacro {
+    recording: Option<Vec<Key>>,
+    keys: Vec<Key>,
+}
+
+impl KeyMacro {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start_recording(&mut self) {
+        self.recording = Some(vec![]);
+    }
+
+    /// Stops recording and keeps the recorded keys, unless none were recorded. Returns how many
+    /// keys were recorded.
+    pub fn stop_recording(&mut self) -> usize {
+        let keys = self.recording.take().unwrap_or_default();
+        let len = keys.len();
+        if !keys.is_empty() {
+            self.keys = keys;
+        }
+        len
+    }
+
+    pub fn record(&mut self, key: Key) {
+        if let Some(keys) = self.recording.as_mut() {
+            keys.push(key);
+        }
+    }
+
+    /// The recorded keys repeated `count` times.
+    pub fn playback(&self, count: usize) -> Vec<Key> {
+        self.keys.repeat(count)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::KeyMacro;
+    use crate::event::Key;
+
+    #[test]
+    fn test_record_and_playback() {
+        let mut key_macro = KeyMacro::default();
+        key_macro.record(Key::Char('j'));
+        assert_eq!(key_macro.len(), 0);
+
+        key_macro.start_recording();
+        key_macro.record(Key::Char('j'));
+        key_macro.record(Key::Char('y'));
+        assert!(key_macro.is_recording());
+        assert_eq!(key_macro.stop_recording(), 2);
+        assert_eq!(
+            key_macro.playback(2),
+            vec![
+                Key::Char('j'),
+                Key::Char('y'),
+                Key::Char('j'),
+                Key::Char('y')
+            ]
+        );
+
+        key_macro.start_recording();
+        assert_eq!(key_macro.stop_recording(), 0);
+        assert_eq!(key_macro.len(), 2);
+    }
+}
@@ -0,0 +1,15 @@
+//! The database abstraction of zhobo, for tools which browse databases the way zhobo does.
+//!
+//! [`connection::Connection`] is a connection as written in the config file, which
+//! [`connection::open_pool`] opens as a [`database::Pool`]. The pools list the databases,
+//! schemas and tables as the models of [`tree`], which backends outside this crate can
+//! implement [`database::Pool`] to return as well.
+
+#[macro_use]
+pub mod log;
+pub mod charset;
+pub mod connection;
+pub mod database;
+#[cfg(feature = "sqlite")]
+pub mod demo;
+pub mod tree;
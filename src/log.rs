@@ -1,5 +1,14 @@
 use serde::Deserialize;
 
+/// The file `debug!` appends to, in the cache directory, or in the temporary directory when there
+/// is none.
+pub fn log_file() -> std::path::PathBuf {
+    dirs_next::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("zhobo")
+        .join("zhobo.log")
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Deserialize)]
 pub enum LogLevel {
     Quiet,
@@ -65,17 +74,24 @@ macro_rules! outln {
 #[macro_export]
 macro_rules! debug {
     ($($expr:expr),+) => {
-        #[cfg(debug_assertions)]
+        // Tests don't write the log, which their errors would fill.
+        #[cfg(all(debug_assertions, not(test)))]
         {
             use std::io::{Write};
             use std::fs::OpenOptions;
-            let mut file = OpenOptions::new()
+            let path = $crate::log::log_file();
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            // The log is only for debugging, so failing to write it is not an error.
+            if let Ok(mut file) = OpenOptions::new()
                 .write(true)
                 .create(true)
                 .append(true)
-                .open("zhobo.log")
-                .unwrap();
-            writeln!(file, $($expr),+).expect("Can't write output");
+                .open(path)
+            {
+                let _ = writeln!(file, $($expr),+);
+            }
         }
     }
 }
@@ -1,22 +1,38 @@
 mod app;
+mod audit;
 mod cli;
 mod clipboard;
+mod column_layout;
 mod components;
 mod config;
-mod database;
+mod copy_rows;
 mod event;
+mod export;
+mod external_command;
+mod filter_history;
+mod index_advice;
+mod jobs;
 mod key_bind;
-mod tree;
+mod key_macro;
+mod mock_data;
+mod notification;
+mod pager;
+mod paste;
+mod result_schema;
+mod script;
+mod session;
+mod session_variables;
+mod shell;
+mod snippet;
+mod template;
 mod ui;
+mod undo;
 mod version;
 
-#[macro_use]
-mod log;
-
-use crate::app::App;
-use crate::config::Config;
+use crate::app::{App, External};
+use crate::config::{Config, ConfigWatcher};
 use crate::event::{Event, Key};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::execute;
 use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -25,45 +41,104 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::{self, stdout};
 use std::panic::{set_hook, take_hook};
+use std::time::{Duration, Instant};
+
+/// Ticks slow down after this long without input, unless something is still going on.
+const IDLE_AFTER: Duration = Duration::from_secs(5);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let value = crate::cli::parse();
-    let config = Config::new(&value.config)?;
+    let mut config = Config::new(&value.config)?;
+    config.conn.splice(0..0, value.connections()?);
+    if let Some(path) = &value.script {
+        let steps = script::parse(&std::fs::read_to_string(path)?)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        let mut app = App::new(config);
+        return script::run(&mut app, &steps, value.script_size, &mut stdout()).await;
+    }
     setup_terminal()?;
 
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
     let events = event::Events::new(250);
+    let mut watcher = ConfigWatcher::new(&value.config)?;
     let mut app = App::new(config.clone());
+    app.load_session()?;
+    app.load_filter_history()?;
+    app.load_column_layouts()?;
     terminal.clear()?;
 
+    let mut dirty = true;
+    let mut last_input = Instant::now();
     loop {
-        terminal.draw(|f| {
-            if let Err(err) = app.draw(f) {
-                shutdown_terminal();
-                let mut source = err.source();
-                while let Some(err) = source {
-                    eprintln!("Caused by: {}", err);
-                    source = err.source();
-                }
-                eprintln!("Failed by: {}", err);
+        let animating = app.is_animating();
+        if dirty || animating {
+            terminal.draw(|f| {
+                if let Err(err) = app.draw(f) {
+                    shutdown_terminal();
+                    let mut source = err.source();
+                    while let Some(err) = source {
+                        eprintln!("Caused by: {}", err);
+                        source = err.source();
+                    }
+                    eprintln!("Failed by: {}", err);
 
-                std::process::exit(1);
-            }
-        })?;
+                    std::process::exit(1);
+                }
+            })?;
+            dirty = false;
+        }
+        events.set_idle(!animating && last_input.elapsed() >= IDLE_AFTER);
         match events.next()? {
-            Event::Input(key) => match app.event(key).await {
-                Ok(state) => {
-                    if !state.is_consumed()
-                        && (key == app.config.key_config.quit || key == app.config.key_config.exit)
-                    {
-                        break;
+            Event::Input(key) => {
+                dirty = true;
+                last_input = Instant::now();
+                match app.event(key).await {
+                    Ok(state) => {
+                        if !state.is_consumed()
+                            && (key == app.config.key_config.quit
+                                || key == app.config.key_config.exit)
+                        {
+                            break;
+                        }
+                        app.show_decode_errors()?;
                     }
+                    Err(err) => app.error.set_error(&err)?,
                 }
-                Err(err) => app.error.set(err.to_string())?,
-            },
-            Event::Tick => (),
+                if let Some(external) = app.take_external() {
+                    let _paused = events.pause();
+                    shutdown_terminal();
+                    let result = match external {
+                        External::Pager(text) => pager::page(app.config.pager.as_deref(), &text),
+                        External::Shell(env) => shell::run(app.config.shell.as_deref(), &env),
+                        External::Command { command, env } => shell::run_and_wait(&command, &env),
+                    };
+                    enable_raw_mode()?;
+                    io::stdout().execute(EnterAlternateScreen)?;
+                    terminal.clear()?;
+                    if let Err(err) = result {
+                        app.error.set(err.to_string())?;
+                    }
+                }
+            }
+            Event::Resize => dirty = true,
+            Event::Tick => {
+                match app.disconnect_if_idle(last_input.elapsed()).await {
+                    Ok(disconnected) => dirty |= disconnected,
+                    Err(err) => app.error.set_error(&err)?,
+                }
+                if watcher.changed() {
+                    dirty = true;
+                    match Config::new(&value.config) {
+                        Ok(mut config) => {
+                            config.conn.splice(0..0, value.connections()?);
+                            app.reload_config(config)?
+                        }
+                        Err(err) => app.error.set(err.to_string())?,
+                    }
+                }
+            }
         }
     }
 
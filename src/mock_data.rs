@@ -0,0 +1,373 @@
+use anyhow::{anyhow, Context};
+use rand::{distributions::Alphanumeric, Rng};
+use std::fmt;
+use zhobo::database::Pool;
+
+const MAX_ROWS: usize = 10000;
+
+const FIRST_NAMES: &[&str] = &[
+    "James",
+    "Mary",
+    "John",
+    "Patricia",
+    "Robert",
+    "Jennifer",
+    "Michael",
+    "Linda",
+    "William",
+    "Elizabeth",
+    "David",
+    "Barbara",
+    "Richard",
+    "Susan",
+    "Joseph",
+    "Jessica",
+    "Thomas",
+    "Sarah",
+    "Charles",
+    "Karen",
+    "Haruto",
+    "Yui",
+    "Sota",
+    "Hina",
+    "Minato",
+    "Mio",
+];
+const LAST_NAMES: &[&str] = &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Hernandez",
+    "Lopez",
+    "Wilson",
+    "Anderson",
+    "Taylor",
+    "Moore",
+    "Sato",
+    "Suzuki",
+    "Takahashi",
+    "Tanaka",
+    "Watanabe",
+    "Ito",
+];
+const DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Generator {
+    Sequence(i64),
+    Int(i64, i64),
+    Text(usize),
+    Choice(Vec<String>),
+    Name,
+    FirstName,
+    LastName,
+    Email,
+    Null,
+}
+
+impl Generator {
+    /// Guesses a generator from the column name and type.
+    /// Returns `None` for columns which should be left to the database, such as `id`.
+    pub fn infer(name: &str, r#type: &str, default: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let r#type = r#type.to_lowercase();
+        if name == "id" || default.contains("nextval") {
+            return None;
+        }
+        if name.contains("email") {
+            Some(Self::Email)
+        } else if name.contains("first_name") {
+            Some(Self::FirstName)
+        } else if name.contains("last_name") {
+            Some(Self::LastName)
+        } else if name.contains("name") {
+            Some(Self::Name)
+        } else if r#type.contains("bool") || r#type.starts_with("tinyint(1)") {
+            Some(Self::Choice(vec!["0".to_string(), "1".to_string()]))
+        } else if is_integer_type(&r#type) {
+            Some(Self::Int(0, 1000))
+        } else if r#type.contains("char") || r#type.contains("text") {
+            Some(Self::Text(10))
+        } else {
+            None
+        }
+    }
+
+    fn generate(&self, index: usize, rng: &mut impl Rng) -> String {
+        match self {
+            Self::Sequence(start) => (start + index as i64).to_string(),
+            Self::Int(min, max) => rng.gen_range(*min..=*max).to_string(),
+            Self::Text(len) => rng
+                .sample_iter(&Alphanumeric)
+                .take(*len)
+                .map(char::from)
+                .collect(),
+            Self::Choice(choices) => choices[rng.gen_range(0..choices.len())].clone(),
+            Self::Name => format!(
+                "{} {}",
+                FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())],
+                LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())]
+            ),
+            Self::FirstName => FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_string(),
+            Self::LastName => LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())].to_string(),
+            Self::Email => format!(
+                "{}.{}{}@{}",
+                FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_lowercase(),
+                LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())].to_lowercase(),
+                index,
+                DOMAINS[rng.gen_range(0..DOMAINS.len())]
+            ),
+            Self::Null => "NULL".to_string(),
+        }
+    }
+}
+
+/// Returns true if `type_name`, in lower case, is an integer type such as `int(11) unsigned` or
+/// `bigint`, but not `point` or `interval`.
+fn is_integer_type(type_name: &str) -> bool {
+    let base = type_name
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    matches!(
+        base,
+        "tinyint"
+            | "smallint"
+            | "mediumint"
+            | "int"
+            | "integer"
+            | "bigint"
+            | "int2"
+            | "int4"
+            | "int8"
+    )
+}
+
+impl std::str::FromStr for Generator {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, args) = match s.split_once('(') {
+            Some((name, args)) => (
+                name,
+                Some(
+                    args.strip_suffix(')')
+                        .with_context(|| format!("missing `)` in `{}`", s))?,
+                ),
+            ),
+            None => (s, None),
+        };
+        let numbers = |args: &str| -> anyhow::Result<Vec<i64>> {
+            args.split(',')
+                .map(|n| {
+                    n.trim()
+                        .parse::<i64>()
+                        .with_context(|| format!("invalid number in `{}`", s))
+                })
+                .collect()
+        };
+        match (name, args) {
+            ("seq", None) => Ok(Self::Sequence(1)),
+            ("seq", Some(args)) => Ok(Self::Sequence(numbers(args)?[0])),
+            ("int", None) => Ok(Self::Int(0, 1000)),
+            ("int", Some(args)) => match numbers(args)?.as_slice() {
+                [min, max] if min <= max => Ok(Self::Int(*min, *max)),
+                _ => Err(anyhow!("`int` expects `int(min,max)`, got `{}`", s)),
+            },
+            ("string", None) => Ok(Self::Text(10)),
+            ("string", Some(args)) => Ok(Self::Text(
+                args.trim()
+                    .parse::<usize>()
+                    .with_context(|| format!("invalid length in `{}`", s))?,
+            )),
+            ("choice", Some(args)) if !args.is_empty() => Ok(Self::Choice(
+                args.split('|').map(|choice| choice.to_string()).collect(),
+            )),
+            ("name", None) => Ok(Self::Name),
+            ("first_name", None) => Ok(Self::FirstName),
+            ("last_name", None) => Ok(Self::LastName),
+            ("email", None) => Ok(Self::Email),
+            ("null", None) => Ok(Self::Null),
+            _ => Err(anyhow!("unknown generator `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sequence(start) => write!(f, "seq({})", start),
+            Self::Int(min, max) => write!(f, "int({},{})", min, max),
+            Self::Text(len) => write!(f, "string({})", len),
+            Self::Choice(choices) => write!(f, "choice({})", choices.join("|")),
+            Self::Name => write!(f, "name"),
+            Self::FirstName => write!(f, "first_name"),
+            Self::LastName => write!(f, "last_name"),
+            Self::Email => write!(f, "email"),
+            Self::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// A row count followed by `column=generator` pairs, e.g. `10 name=name age=int(18,65)`.
+/// Columns which are not listed are left to their default values.
+#[derive(Debug, PartialEq)]
+pub struct MockSpec {
+    pub rows: usize,
+    pub columns: Vec<(String, Generator)>,
+}
+
+impl MockSpec {
+    pub fn infer(rows: usize, columns: &[Vec<String>]) -> Self {
+        Self {
+            rows,
+            columns: columns
+                .iter()
+                .filter_map(|column| {
+                    let get = |i: usize| column.get(i).map_or("", |c| c.as_str());
+                    Generator::infer(get(0), get(1), get(3)).map(|g| (get(0).to_string(), g))
+                })
+                .collect(),
+        }
+    }
+
+    /// The generated values as literals of `pool`.
+    pub fn generate_rows(&self, pool: &dyn Pool) -> Vec<Vec<String>> {
+        let mut rng = rand::thread_rng();
+        (0..self.rows)
+            .map(|index| {
+                self.columns
+                    .iter()
+                    .map(|(_, generator)| {
+                        pool.cell_literal(&generator.generate(index, &mut rng), false)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl std::str::FromStr for MockSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let rows = tokens
+            .next()
+            .context("the number of rows is required")?
+            .parse::<usize>()
+            .context("the first value must be the number of rows")?;
+        if rows == 0 || rows > MAX_ROWS {
+            return Err(anyhow!("the number of rows must be 1 to {}", MAX_ROWS));
+        }
+        let columns = tokens
+            .map(|token| {
+                let (column, generator) = token
+                    .split_once('=')
+                    .with_context(|| format!("expected `column=generator`, got `{}`", token))?;
+                Ok((column.to_string(), generator.parse::<Generator>()?))
+            })
+            .collect::<anyhow::Result<Vec<(String, Generator)>>>()?;
+        if columns.is_empty() {
+            return Err(anyhow!("at least one `column=generator` is required"));
+        }
+        Ok(Self { rows, columns })
+    }
+}
+
+impl fmt::Display for MockSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.rows)?;
+        for (column, generator) in &self.columns {
+            write!(f, " {}={}", column, generator)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Generator, MockSpec};
+    use sqlx::sqlite::SqliteConnectOptions;
+    use zhobo::database::{SqlitePool, MAX_CONNECTIONS};
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = "3 id=seq(10) age=int(18,65) role=choice(admin|user) email=email"
+            .parse::<MockSpec>()
+            .unwrap();
+        assert_eq!(
+            spec,
+            MockSpec {
+                rows: 3,
+                columns: vec![
+                    ("id".to_string(), Generator::Sequence(10)),
+                    ("age".to_string(), Generator::Int(18, 65)),
+                    (
+                        "role".to_string(),
+                        Generator::Choice(vec!["admin".to_string(), "user".to_string()])
+                    ),
+                    ("email".to_string(), Generator::Email),
+                ],
+            }
+        );
+        assert_eq!(
+            spec.to_string(),
+            "3 id=seq(10) age=int(18,65) role=choice(admin|user) email=email"
+        );
+
+        assert!("0 id=seq".parse::<MockSpec>().is_err());
+        assert!("3".parse::<MockSpec>().is_err());
+        assert!("3 id=unknown".parse::<MockSpec>().is_err());
+        assert!("3 age=int(65,18)".parse::<MockSpec>().is_err());
+    }
+
+    #[test]
+    fn test_infer_spec() {
+        let columns = vec![
+            vec!["id".to_string(), "int".to_string()],
+            vec!["user_name".to_string(), "varchar(255)".to_string()],
+            vec!["email".to_string(), "text".to_string()],
+            vec!["age".to_string(), "bigint".to_string()],
+            vec!["created_at".to_string(), "datetime".to_string()],
+            vec!["location".to_string(), "point".to_string()],
+            vec!["duration".to_string(), "interval".to_string()],
+            vec!["score".to_string(), "int(11) unsigned".to_string()],
+        ];
+        assert_eq!(
+            MockSpec::infer(10, &columns).to_string(),
+            "10 user_name=name email=email age=int(0,1000) score=int(0,1000)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_rows() {
+        let pool = SqlitePool::new(
+            SqliteConnectOptions::new().in_memory(true),
+            200,
+            5,
+            MAX_CONNECTIONS,
+        )
+        .await
+        .unwrap();
+        let spec = "3 id=seq(5) note=null code=string(4)"
+            .parse::<MockSpec>()
+            .unwrap();
+        let rows = spec.generate_rows(&pool);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows.iter()
+                .map(|row| row[0].clone())
+                .collect::<Vec<String>>(),
+            vec!["'5'", "'6'", "'7'"]
+        );
+        assert!(rows.iter().all(|row| row[1] == "NULL" && row[2].len() == 6));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How zhobo tells that a long query or job finished.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Notification {
+    /// The terminal bell.
+    #[default]
+    Bell,
+    /// A desktop notification through OSC 9, which terminals such as iTerm2, kitty and Windows
+    /// Terminal show.
+    Desktop,
+}
+
+impl Notification {
+    /// The escape sequence notifying `message`.
+    fn sequence(&self, message: &str) -> String {
+        match self {
+            Self::Bell => "\x07".to_string(),
+            Self::Desktop => format!(
+                "\x1b]9;{}\x07",
+                message.replace(|c: char| c.is_control(), " ")
+            ),
+        }
+    }
+
+    /// Notifies `message` if `elapsed` is `after_second` or longer.
+    pub fn notify_if_long(
+        &self,
+        after_second: Option<u64>,
+        elapsed: Duration,
+        message: &str,
+    ) -> io::Result<()> {
+        if after_second.is_some_and(|second| elapsed >= Duration::from_secs(second)) {
+            let mut stdout = io::stdout();
+            write!(stdout, "{}", self.sequence(message))?;
+            stdout.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Notification;
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(Notification::Bell.sequence("query finished"), "\x07");
+        assert_eq!(
+            Notification::Desktop.sequence("export\nfinished"),
+            "\x1b]9;export finished\x07"
+        );
+    }
+}
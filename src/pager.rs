@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const DEFAULT_PAGER: &str = "less";
+
+/// The pager command and its arguments: `configured`, `$PAGER`, or `less`, in that order.
+pub fn pager_command(configured: Option<&str>, env: Option<String>) -> Vec<String> {
+    let command = configured
+        .map(|command| command.to_string())
+        .or(env)
+        .filter(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    command.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+/// Pipes `text` to the pager and waits until it is closed. The terminal has to be handed over
+/// to the pager beforehand.
+pub fn page(configured: Option<&str>, text: &str) -> Result<()> {
+    let args = pager_command(configured, std::env::var("PAGER").ok());
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+
+    let mut process = command
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+
+    // The pager may quit before reading everything, which is not an error.
+    if let Some(mut stdin) = process.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    process
+        .wait()
+        .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::pager_command;
+
+    #[test]
+    fn test_pager_command() {
+        assert_eq!(
+            pager_command(Some("bat -l sql"), Some("more".to_string())),
+            vec!["bat", "-l", "sql"]
+        );
+        assert_eq!(pager_command(None, Some("more".to_string())), vec!["more"]);
+        assert_eq!(pager_command(None, Some(" ".to_string())), vec!["less"]);
+        assert_eq!(pager_command(None, None), vec!["less"]);
+    }
+}
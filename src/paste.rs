@@ -0,0 +1,223 @@
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+
+#[derive(Debug, PartialEq)]
+pub struct PastedRows {
+    /// Table columns the pasted values are inserted into.
+    pub columns: Vec<String>,
+    /// Pasted headers which do not exist in the table.
+    pub ignored: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Parses clipboard text as a JSON array of objects, TSV or CSV, and maps the values to
+/// `table_columns`. For TSV and CSV, the first line is used as the header if any value is a
+/// column name; otherwise values are mapped to the table columns by position, which is
+/// the format produced by copying a selection.
+pub fn parse(text: &str, table_columns: &[String]) -> anyhow::Result<PastedRows> {
+    let text = text.trim_end_matches(['\r', '\n']);
+    if text.trim().is_empty() {
+        return Err(anyhow!("clipboard is empty"));
+    }
+    let (headers, rows) = if text.trim_start().starts_with('[') {
+        let (headers, rows) = parse_json(text)?;
+        (Some(headers), rows)
+    } else {
+        let delimiter = if text.lines().next().unwrap_or_default().contains('\t') {
+            '\t'
+        } else {
+            ','
+        };
+        let mut records = parse_delimited(text, delimiter);
+        let is_header = records.first().is_some_and(|first| {
+            first.iter().any(|h| {
+                table_columns
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(h.trim()))
+            })
+        });
+        if is_header {
+            let headers = records.remove(0);
+            (Some(headers), records)
+        } else {
+            (None, records)
+        }
+    };
+
+    let indexes = match &headers {
+        Some(headers) => headers
+            .iter()
+            .map(|h| {
+                table_columns
+                    .iter()
+                    .position(|c| c.eq_ignore_ascii_case(h.trim()))
+            })
+            .collect::<Vec<Option<usize>>>(),
+        None => {
+            let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+            if width > table_columns.len() {
+                return Err(anyhow!(
+                    "pasted rows have {} values but the table has {} columns",
+                    width,
+                    table_columns.len()
+                ));
+            }
+            (0..width).map(Some).collect()
+        }
+    };
+    if indexes.iter().all(|i| i.is_none()) {
+        return Err(anyhow!("no pasted header matches a column of the table"));
+    }
+
+    Ok(PastedRows {
+        columns: indexes
+            .iter()
+            .flatten()
+            .map(|i| table_columns[*i].clone())
+            .collect(),
+        ignored: headers.map_or(vec![], |headers| {
+            headers
+                .iter()
+                .zip(indexes.iter())
+                .filter(|(_, i)| i.is_none())
+                .map(|(h, _)| h.clone())
+                .collect()
+        }),
+        rows: rows
+            .iter()
+            .map(|row| {
+                indexes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, i)| i.is_some())
+                    .map(|(n, _)| row.get(n).cloned().unwrap_or_else(|| "NULL".to_string()))
+                    .collect()
+            })
+            .collect(),
+    })
+}
+
+fn parse_json(text: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
+    let values: Vec<serde_json::Map<String, Value>> =
+        serde_json::from_str(text).context("clipboard is not a JSON array of objects")?;
+    let mut headers: Vec<String> = vec![];
+    for object in &values {
+        for key in object.keys() {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+    }
+    let rows = values
+        .iter()
+        .map(|object| {
+            headers
+                .iter()
+                .map(|h| match object.get(h) {
+                    None | Some(Value::Null) => "NULL".to_string(),
+                    Some(Value::String(s)) => s.clone(),
+                    Some(v) => v.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    Ok((headers, rows))
+}
+
+/// Splits `text` into records, handling double-quoted values which contain the delimiter,
+/// escaped quotes or newlines.
+fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if value.is_empty() => quoted = true,
+            c if c == delimiter && !quoted => record.push(std::mem::take(&mut value)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut value));
+                records.push(std::mem::take(&mut record));
+            }
+            c => value.push(c),
+        }
+    }
+    record.push(value);
+    records.push(record);
+    records
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, parse_delimited, PastedRows};
+
+    fn columns() -> Vec<String> {
+        vec!["id".to_string(), "name".to_string(), "age".to_string()]
+    }
+
+    #[test]
+    fn test_parse_delimited() {
+        assert_eq!(
+            parse_delimited("a,\"b,c\",\"d \"\"e\"\"\"\r\n1,\"x\ny\",", ','),
+            vec![
+                vec!["a".to_string(), "b,c".to_string(), "d \"e\"".to_string()],
+                vec!["1".to_string(), "x\ny".to_string(), "".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        assert_eq!(
+            parse("NAME,unknown,id\nfoo,x,1\n", &columns()).unwrap(),
+            PastedRows {
+                columns: vec!["name".to_string(), "id".to_string()],
+                ignored: vec!["unknown".to_string()],
+                rows: vec![vec!["foo".to_string(), "1".to_string()]],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_tsv_without_header() {
+        assert_eq!(
+            parse("1\tfoo\n2\tbar", &columns()).unwrap(),
+            PastedRows {
+                columns: vec!["id".to_string(), "name".to_string()],
+                ignored: vec![],
+                rows: vec![
+                    vec!["1".to_string(), "foo".to_string()],
+                    vec!["2".to_string(), "bar".to_string()]
+                ],
+            }
+        );
+        assert!(parse("1,2,3,4", &columns()).is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            parse(
+                r#"[{"id": 1, "name": "foo", "extra": true}, {"id": 2, "name": null}]"#,
+                &columns()
+            )
+            .unwrap(),
+            PastedRows {
+                columns: vec!["id".to_string(), "name".to_string()],
+                ignored: vec!["extra".to_string()],
+                rows: vec![
+                    vec!["1".to_string(), "foo".to_string()],
+                    vec!["2".to_string(), "NULL".to_string()]
+                ],
+            }
+        );
+        assert!(parse(r#"[{"other": 1}]"#, &columns()).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::index_advice;
+use crate::undo::{find_keyword, split_top_level, strip_keyword, unquote_identifier};
+use regex::Regex;
+
+/// Words which look like a column in a select list but are not read from a table.
+const LITERALS: [&str; 6] = [
+    "NULL",
+    "TRUE",
+    "FALSE",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+];
+
+/// The clauses which end the `FROM` clause.
+const CLAUSES: [&str; 7] = [
+    "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "UNION", "WINDOW",
+];
+
+/// A column of a query result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultColumn {
+    pub name: String,
+    /// The type of the column as the driver reports it.
+    pub type_name: String,
+    /// The table the column is read from. None of the drivers report it, so it is inferred
+    /// from the select list, and is `None` for expressions.
+    pub origin: Option<String>,
+}
+
+/// The columns of the result of `query`, whose headers and types are `headers` and `types`.
+pub fn describe(query: &str, headers: &[String], types: &[String]) -> Vec<ResultColumn> {
+    let origins = origins(query, headers.len());
+    headers
+        .iter()
+        .enumerate()
+        .map(|(i, name)| ResultColumn {
+            name: name.clone(),
+            type_name: types.get(i).cloned().unwrap_or_default(),
+            origin: origins.get(i).cloned().flatten(),
+        })
+        .collect()
+}
+
+/// The table each of the `len` columns selected by `query` is read from. The positions of the
+/// columns are only known when the select list has no `*`, or is a `*` on a single table.
+fn origins(query: &str, len: usize) -> Vec<Option<String>> {
+    let Some(rest) = strip_keyword(query.trim(), "SELECT") else {
+        return vec![];
+    };
+    let rest = strip_keyword(rest, "DISTINCT").unwrap_or(rest);
+    let Some(from) = find_keyword(rest, "FROM") else {
+        return vec![];
+    };
+    let clause = &rest[from..];
+    let clause = CLAUSES
+        .iter()
+        .filter_map(|keyword| find_keyword(clause, keyword))
+        .min()
+        .map_or(clause, |end| &clause[..end]);
+    let tables = index_advice::tables(clause);
+    let single = match tables.as_slice() {
+        [(_, table)] if split_top_level(clause, ',').len() == 1 => Some(table.clone()),
+        _ => None,
+    };
+    let items = split_top_level(&rest[..from], ',');
+    if items == ["*"] {
+        return vec![single; len];
+    }
+    if items.iter().any(|item| item.ends_with('*')) {
+        return vec![];
+    }
+
+    let column =
+        Regex::new(r#"^(?:([\w"`]+)\.)?([\w"`]+)(?:\s+(?:(?i:AS)\s+)?[\w"`]+)?$"#).unwrap();
+    items
+        .iter()
+        .map(|item| {
+            let captures = column.captures(item)?;
+            let name = unquote_identifier(&captures[2]);
+            if LITERALS
+                .iter()
+                .any(|literal| literal.eq_ignore_ascii_case(&name))
+                || name.starts_with(|c: char| c.is_ascii_digit())
+            {
+                return None;
+            }
+            match captures.get(1) {
+                Some(qualifier) => {
+                    let qualifier = unquote_identifier(qualifier.as_str());
+                    tables
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(&qualifier))
+                        .map(|(_, table)| table.clone())
+                }
+                None => single.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{describe, origins, ResultColumn};
+
+    #[test]
+    fn test_origins() {
+        assert_eq!(
+            origins(
+                "SELECT u.id, u.name AS user_name, o.total, count(*), 1, NULL \
+                 FROM users u JOIN orders AS o ON o.user_id = u.id",
+                6
+            ),
+            [
+                Some("users".to_string()),
+                Some("users".to_string()),
+                Some("orders".to_string()),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(
+            origins("select * from public.users", 2),
+            [
+                Some("public.users".to_string()),
+                Some("public.users".to_string())
+            ]
+        );
+        assert_eq!(
+            origins("SELECT id, name FROM users, orders", 2),
+            [None, None]
+        );
+        assert!(origins("SELECT u.*, o.id FROM users u JOIN orders o", 3).is_empty());
+        assert!(origins("EXPLAIN SELECT id FROM users", 1).is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(
+                "SELECT DISTINCT \"name\", length(name) FROM users",
+                &["name".to_string(), "length(name)".to_string()],
+                &["TEXT".to_string(), "INTEGER".to_string()]
+            ),
+            [
+                ResultColumn {
+                    name: "name".to_string(),
+                    type_name: "TEXT".to_string(),
+                    origin: Some("users".to_string()),
+                },
+                ResultColumn {
+                    name: "length(name)".to_string(),
+                    type_name: "INTEGER".to_string(),
+                    origin: None,
+                },
+            ]
+        );
+    }
+}
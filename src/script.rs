@@ -0,0 +1,188 @@
+use crate::app::App;
+use crate::event::Key;
+use anyhow::{anyhow, Context};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// How often the screen is drawn while waiting, which also polls jobs and filters.
+const TICK: Duration = Duration::from_millis(50);
+
+/// A step of a script run by `--script`.
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    /// `key Ctrl('d')`, a key written as in key_bind.ron.
+    Key(Key),
+    /// `type SELECT 1`, typing the rest of the line.
+    Type(String),
+    /// `wait 500`, waiting for the milliseconds so jobs and filters in the background can finish.
+    Wait(Duration),
+    /// `snapshot [name]`, printing the screen.
+    Snapshot(String),
+}
+
+/// Parses a script of a step per line. Blank lines and lines starting with `#` are skipped.
+pub fn parse(script: &str) -> anyhow::Result<Vec<Step>> {
+    script
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| parse_step(line.trim_start()).with_context(|| format!("line {}", i + 1)))
+        .collect()
+}
+
+fn parse_step(line: &str) -> anyhow::Result<Step> {
+    let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+    match command {
+        "key" => ron::from_str(rest.trim()).map(Step::Key).map_err(|_| {
+            anyhow!(
+                "`{}` is not a key such as Char('j'), Enter or Ctrl('d')",
+                rest
+            )
+        }),
+        "type" => Ok(Step::Type(rest.to_string())),
+        "wait" => rest
+            .trim()
+            .parse()
+            .map(|millis| Step::Wait(Duration::from_millis(millis)))
+            .map_err(|_| anyhow!("`{}` is not milliseconds", rest)),
+        "snapshot" => Ok(Step::Snapshot(rest.trim().to_string())),
+        _ => Err(anyhow!(
+            "`{}` is not one of key, type, wait or snapshot",
+            command
+        )),
+    }
+}
+
+/// Runs `steps` on `app` drawn to a screen of `width` x `height` which is not shown, and writes
+/// the screen to `out` at each snapshot. Errors are shown in the error popup as when typing.
+/// The script stops at a key which quits.
+pub async fn run(
+    app: &mut App,
+    steps: &[Step],
+    (width, height): (u16, u16),
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let mut snapshots = 0;
+    for step in steps {
+        let keys = match step {
+            Step::Key(key) => vec![*key],
+            Step::Type(text) => text.chars().map(Key::Char).collect(),
+            Step::Wait(duration) => {
+                let deadline = Instant::now() + *duration;
+                while Instant::now() < deadline {
+                    tokio::time::sleep(TICK.min(deadline - Instant::now())).await;
+                    draw(&mut terminal, app)?;
+                }
+                vec![]
+            }
+            Step::Snapshot(name) => {
+                draw(&mut terminal, app)?;
+                snapshots += 1;
+                writeln!(out, "--- snapshot {} {}", snapshots, name)?;
+                write!(out, "{}", screen(terminal.backend().buffer()))?;
+                vec![]
+            }
+        };
+        for key in keys {
+            match app.event(key).await {
+                Ok(state) => {
+                    if !state.is_consumed()
+                        && (key == app.config.key_config.quit || key == app.config.key_config.exit)
+                    {
+                        return Ok(());
+                    }
+                }
+                Err(err) => app.error.set_error(&err)?,
+            }
+            draw(&mut terminal, app)?;
+        }
+    }
+    Ok(())
+}
+
+fn draw(terminal: &mut Terminal<TestBackend>, app: &mut App) -> anyhow::Result<()> {
+    let mut result = Ok(());
+    terminal.draw(|f| result = app.draw(f))?;
+    result
+}
+
+/// The text of `buffer`, a line per row without trailing spaces.
+fn screen(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for y in 0..buffer.area.height {
+        let line = (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect::<String>();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Parses `--script-size`, such as `120x40`.
+pub fn parse_size(size: &str) -> anyhow::Result<(u16, u16)> {
+    size.split_once('x')
+        .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+        .ok_or_else(|| anyhow!("`{}` is not a size such as 120x40", size))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, parse_size, run, Step};
+    use crate::app::App;
+    use crate::config::{Config, Connection};
+    use crate::event::Key;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("# connect\nkey Enter\n\nkey Ctrl('d')\ntype SELECT 1\nwait 10\nsnapshot tables")
+                .unwrap(),
+            [
+                Step::Key(Key::Enter),
+                Step::Key(Key::Ctrl('d')),
+                Step::Type("SELECT 1".to_string()),
+                Step::Wait(Duration::from_millis(10)),
+                Step::Snapshot("tables".to_string()),
+            ]
+        );
+        assert_eq!(
+            format!("{:#}", parse("key Enter\nkey j").unwrap_err()),
+            "line 2: `j` is not a key such as Char('j'), Enter or Ctrl('d')"
+        );
+        assert!(parse("press Enter").is_err());
+        assert_eq!(parse_size("100x30").unwrap(), (100, 30));
+        assert!(parse_size("100").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let mut app = App::new(Config {
+            conn: vec![Connection::demo()],
+            ..Config::default()
+        });
+        let steps = parse(
+            "key Enter
+            key Enter
+            snapshot tables
+            key Char('/')
+            type order
+            wait 500
+            snapshot filtered",
+        )
+        .unwrap();
+        let mut out = vec![];
+        run(&mut app, &steps, (100, 30), &mut out).await.unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let (tables, filtered) = out.split_once("--- snapshot 2 filtered\n").unwrap();
+        assert!(tables.starts_with("--- snapshot 1 tables\n"));
+        assert!(tables.contains("customers"));
+        assert!(tables.contains("products"));
+        assert!(filtered.contains("order_items"));
+        assert!(!filtered.contains("customers"));
+        assert_eq!(filtered.lines().count(), 30);
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+
+/// UI state which is kept between runs.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Width of the database tree in percent.
+    pub left_main_chunk_percentage: u16,
+    /// Height of the SQL editor above its result in percent.
+    pub sql_editor_percentage: u16,
+    /// SQL expressions shown as columns of the records, by the table key.
+    pub computed_columns: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            path: None,
+            left_main_chunk_percentage: 15,
+            sql_editor_percentage: 50,
+            computed_columns: BTreeMap::new(),
+        }
+    }
+}
+
+impl Session {
+    /// Loads the session from `path`. A missing or broken file results in the default session.
+    pub fn load(path: PathBuf) -> Self {
+        let mut contents = String::new();
+        let mut session = File::open(&path)
+            .ok()
+            .and_then(|mut file| file.read_to_string(&mut contents).ok())
+            .and_then(|_| ron::from_str::<Self>(&contents).ok())
+            .unwrap_or_default();
+        session.path = Some(path);
+        session
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.path {
+            fs::write(path, ron::ser::to_string_pretty(self, Default::default())?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Session;
+
+    #[test]
+    fn test_load_and_save() {
+        let path = std::env::temp_dir().join(format!("zhobo_session_{}.ron", std::process::id()));
+        std::fs::write(&path, "(sql_editor_percentage: 70)").unwrap();
+        let mut session = Session::load(path.clone());
+        assert_eq!(session.left_main_chunk_percentage, 15);
+        assert_eq!(session.sql_editor_percentage, 70);
+
+        session.left_main_chunk_percentage = 30;
+        session.computed_columns.insert(
+            "main.orders".to_string(),
+            vec!["price * quantity AS total".to_string()],
+        );
+        session.save().unwrap();
+        let session = Session::load(path.clone());
+        assert_eq!(session.left_main_chunk_percentage, 30);
+        assert_eq!(
+            session.computed_columns["main.orders"],
+            ["price * quantity AS total"]
+        );
+        assert_eq!(session.sql_editor_percentage, 70);
+        std::fs::remove_file(path).unwrap();
+    }
+}
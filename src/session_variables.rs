@@ -0,0 +1,147 @@
+use regex::Regex;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionVariable {
+    pub name: String,
+    pub value: String,
+    /// Set with `SET LOCAL`, so it only lasts until the end of the transaction.
+    pub local: bool,
+}
+
+impl SessionVariable {
+    pub fn statement(&self) -> String {
+        format!(
+            "SET {}{} = {}",
+            if self.local { "LOCAL " } else { "" },
+            self.name,
+            self.value
+        )
+    }
+}
+
+/// Parses a `SET` statement changing a session variable. `SET GLOBAL`, user variables, and
+/// statements such as `SET NAMES` are not session variables zhobo tracks.
+pub fn parse_set(query: &str) -> Option<SessionVariable> {
+    let query = query.trim().trim_end_matches(';').trim_end();
+    let time_zone = Regex::new(r"(?is)^SET\s+(?:(SESSION|LOCAL)\s+)?TIME\s+ZONE\s+(.+)$").unwrap();
+    if let Some(captures) = time_zone.captures(query) {
+        return Some(SessionVariable {
+            name: "timezone".to_string(),
+            value: captures[2].to_string(),
+            local: captures
+                .get(1)
+                .is_some_and(|scope| scope.as_str().eq_ignore_ascii_case("LOCAL")),
+        });
+    }
+    let re = Regex::new(
+        r"(?is)^SET\s+(?:(SESSION|LOCAL)\s+|@@(?:SESSION\.|LOCAL\.)?)?(\w+)\s*(?:=|\sTO\s)\s*(.+)$",
+    )
+    .unwrap();
+    let captures = re.captures(query)?;
+    let name = captures[2].to_lowercase();
+    if matches!(name.as_str(), "global" | "persist" | "transaction") {
+        return None;
+    }
+    Some(SessionVariable {
+        name,
+        value: captures[3].trim().to_string(),
+        local: captures
+            .get(1)
+            .is_some_and(|scope| scope.as_str().eq_ignore_ascii_case("LOCAL")),
+    })
+}
+
+/// Session variables set through the SQL editor. They are applied again before each statement
+/// because the statements may run on different connections of the pool.
+#[derive(Default)]
+pub struct SessionVariables {
+    variables: Vec<SessionVariable>,
+}
+
+impl SessionVariables {
+    /// Tracks `variable`, which replaces the previous value. Setting `DEFAULT` stops tracking it.
+    pub fn record(&mut self, variable: SessionVariable) {
+        self.variables.retain(|v| v.name != variable.name);
+        if !variable.value.eq_ignore_ascii_case("DEFAULT") {
+            self.variables.push(variable);
+        }
+    }
+
+    /// Forgets `SET LOCAL` variables, whose transaction ends with each statement.
+    pub fn end_statement(&mut self) {
+        self.variables.retain(|variable| !variable.local);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SessionVariable> {
+        self.variables.iter().find(|variable| variable.name == name)
+    }
+
+    pub fn variables(&self) -> &[SessionVariable] {
+        &self.variables
+    }
+
+    /// The statements restoring the session state on another connection.
+    pub fn statements(&self) -> Vec<String> {
+        self.variables
+            .iter()
+            .filter(|variable| !variable.local)
+            .map(|variable| variable.statement())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_set, SessionVariable, SessionVariables};
+
+    fn variable(name: &str, value: &str, local: bool) -> Option<SessionVariable> {
+        Some(SessionVariable {
+            name: name.to_string(),
+            value: value.to_string(),
+            local,
+        })
+    }
+
+    #[test]
+    fn test_parse_set() {
+        assert_eq!(
+            parse_set("SET search_path TO app, public;"),
+            variable("search_path", "app, public", false)
+        );
+        assert_eq!(
+            parse_set("set local statement_timeout = '5s'"),
+            variable("statement_timeout", "'5s'", true)
+        );
+        assert_eq!(
+            parse_set("SET SESSION sql_mode = 'STRICT_ALL_TABLES'"),
+            variable("sql_mode", "'STRICT_ALL_TABLES'", false)
+        );
+        assert_eq!(
+            parse_set("SET @@session.time_zone = '+09:00'"),
+            variable("time_zone", "'+09:00'", false)
+        );
+        assert_eq!(
+            parse_set("SET TIME ZONE 'UTC'"),
+            variable("timezone", "'UTC'", false)
+        );
+        assert_eq!(parse_set("SET GLOBAL sql_mode = ''"), None);
+        assert_eq!(parse_set("SET @count = 1"), None);
+        assert_eq!(parse_set("SET NAMES utf8mb4"), None);
+        assert_eq!(parse_set("SELECT 1"), None);
+    }
+
+    #[test]
+    fn test_session_variables() {
+        let mut variables = SessionVariables::default();
+        variables.record(parse_set("SET search_path TO app").unwrap());
+        variables.record(parse_set("SET LOCAL work_mem = '64MB'").unwrap());
+        variables.record(parse_set("SET search_path = public").unwrap());
+        assert_eq!(variables.variables().len(), 2);
+        assert_eq!(variables.statements(), vec!["SET search_path = public"]);
+
+        variables.end_statement();
+        assert!(variables.get("work_mem").is_none());
+        variables.record(parse_set("SET search_path TO DEFAULT").unwrap());
+        assert!(variables.variables().is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::{anyhow, Result};
+use std::io::{BufRead, Write};
+use std::process::Command;
+
+const DEFAULT_SHELL: &str = "sh";
+
+/// Runs `configured` through `sh -c`, so it can refer to the variables in `env`, or `$SHELL`
+/// when no command is configured, and waits until it exits. The terminal has to be handed over
+/// beforehand.
+pub fn run(configured: Option<&str>, env: &[(String, String)]) -> Result<()> {
+    let mut command = match configured.filter(|command| !command.trim().is_empty()) {
+        Some(configured) => {
+            let mut command = Command::new(DEFAULT_SHELL);
+            command.arg("-c").arg(configured);
+            command
+        }
+        None => Command::new(
+            std::env::var("SHELL")
+                .ok()
+                .filter(|shell| !shell.is_empty())
+                .unwrap_or_else(|| DEFAULT_SHELL.to_string()),
+        ),
+    };
+    command.envs(env.iter().map(|(name, value)| (name, value)));
+
+    command
+        .status()
+        .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+    Ok(())
+}
+
+/// Runs `command` through `sh -c` and waits for Enter afterwards, so its output can be read
+/// before zhobo is drawn over it.
+pub fn run_and_wait(command: &str, env: &[(String, String)]) -> Result<()> {
+    println!("$ {}", command);
+    run(Some(command), env)?;
+    print!("\nPress Enter to return to zhobo");
+    std::io::stdout().flush()?;
+    std::io::stdin().lock().read_line(&mut String::new())?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use serde::Deserialize;
+
+/// A query template expanded in the SQL editor by typing `name` and pressing Tab.
+/// `body` may contain tab stops: `${1:default}`, `${1}` or `$1`, visited in order, and `$0`
+/// for the final cursor position.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Snippet {
+    pub name: String,
+    pub body: String,
+}
+
+impl Snippet {
+    fn new(name: &str, body: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    /// Built-in snippets, which snippets in the config with the same name replace.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("sel", "SELECT ${1:*} FROM ${2:table} WHERE ${3:condition}"),
+            Self::new(
+                "ins",
+                "INSERT INTO ${1:table} (${2:columns}) VALUES (${3:values})",
+            ),
+            Self::new(
+                "upd",
+                "UPDATE ${1:table} SET ${2:column} = ${3:value} WHERE ${4:condition}",
+            ),
+            Self::new("del", "DELETE FROM ${1:table} WHERE ${2:condition}"),
+            Self::new(
+                "join",
+                "SELECT ${1:*} FROM ${2:table} JOIN ${3:other} ON ${4:condition}",
+            ),
+        ]
+    }
+
+    /// The text to insert and its tab stops as `(start, length)` in characters, in the order
+    /// they are visited. Only the first occurrence of a number is a tab stop.
+    pub fn expand(&self) -> (String, Vec<(usize, usize)>) {
+        let chars = self.body.chars().collect::<Vec<char>>();
+        let mut text = String::new();
+        let mut len = 0;
+        let mut stops: Vec<(usize, usize, usize)> = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            if let Some((number, default, next)) = parse_stop(&chars, i) {
+                if !stops.iter().any(|(n, _, _)| *n == number) {
+                    stops.push((number, len, default.chars().count()));
+                }
+                len += default.chars().count();
+                text.push_str(&default);
+                i = next;
+            } else {
+                text.push(chars[i]);
+                len += 1;
+                i += 1;
+            }
+        }
+        stops.sort_by_key(|(number, _, _)| if *number == 0 { usize::MAX } else { *number });
+        (
+            text,
+            stops
+                .into_iter()
+                .map(|(_, start, len)| (start, len))
+                .collect(),
+        )
+    }
+}
+
+/// Parses a tab stop at `i`, returning its number, default text and the index after it.
+fn parse_stop(chars: &[char], i: usize) -> Option<(usize, String, usize)> {
+    if chars.get(i) != Some(&'$') {
+        return None;
+    }
+    let digits = |from: usize| {
+        chars[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+    };
+    if chars.get(i + 1) == Some(&'{') {
+        let number = digits(i + 2);
+        let mut j = i + 2 + number.len();
+        if number.is_empty() {
+            return None;
+        }
+        let mut default = String::new();
+        if chars.get(j) == Some(&':') {
+            j += 1;
+            while chars.get(j).is_some_and(|c| *c != '}') {
+                default.push(chars[j]);
+                j += 1;
+            }
+        }
+        if chars.get(j) != Some(&'}') {
+            return None;
+        }
+        Some((number.parse().ok()?, default, j + 1))
+    } else {
+        let number = digits(i + 1);
+        if number.is_empty() {
+            return None;
+        }
+        Some((number.parse().ok()?, String::new(), i + 1 + number.len()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Snippet;
+
+    #[test]
+    fn test_expand() {
+        let snippet = Snippet {
+            name: "sel".to_string(),
+            body: "SELECT ${2:*} FROM ${1:table} WHERE $3;$0 -- $ ${x}".to_string(),
+        };
+        assert_eq!(
+            snippet.expand(),
+            (
+                "SELECT * FROM table WHERE ; -- $ ${x}".to_string(),
+                vec![(14, 5), (7, 1), (26, 0), (27, 0)]
+            )
+        );
+    }
+}
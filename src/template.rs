@@ -0,0 +1,95 @@
+use anyhow::anyhow;
+use std::collections::BTreeMap;
+
+/// Replaces `{{name}}` in `text` with the value of the variable `name` as it is, so a string
+/// value is written as `'{{name}}'`. Fails if a variable is not defined.
+pub fn substitute(text: &str, variables: &BTreeMap<String, String>) -> anyhow::Result<String> {
+    let mut substituted = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + end].trim();
+        if !is_name(name) {
+            substituted.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let value = variables
+            .get(name)
+            .ok_or_else(|| anyhow!("{{{{{}}}}} is not defined for this connection", name))?;
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(value);
+        rest = &rest[start + 2 + end + 2..];
+    }
+    substituted.push_str(rest);
+    Ok(substituted)
+}
+
+/// Parses `name = value` typed to set a variable. The value is `None` when it is empty, which
+/// removes the variable.
+pub fn parse_assignment(input: &str) -> anyhow::Result<(String, Option<String>)> {
+    let (name, value) = input
+        .split_once('=')
+        .ok_or_else(|| anyhow!("enter the variable as `name = value`"))?;
+    let name = name.trim();
+    if !is_name(name) {
+        return Err(anyhow!("`{}` is not a variable name", name));
+    }
+    let value = value.trim();
+    Ok((
+        name.to_string(),
+        (!value.is_empty()).then(|| value.to_string()),
+    ))
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_assignment, substitute};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_substitute() {
+        let variables = BTreeMap::from([
+            ("tenant_id".to_string(), "42".to_string()),
+            ("region".to_string(), "eu".to_string()),
+        ]);
+        assert_eq!(
+            substitute(
+                "SELECT * FROM users WHERE tenant_id = {{tenant_id}} AND region = '{{ region }}'",
+                &variables
+            )
+            .unwrap(),
+            "SELECT * FROM users WHERE tenant_id = 42 AND region = 'eu'"
+        );
+        assert_eq!(
+            substitute("SELECT '{{}}', '{{a b}}', '{{'", &variables).unwrap(),
+            "SELECT '{{}}', '{{a b}}', '{{'"
+        );
+        assert_eq!(
+            substitute("id = {{user_id}}", &variables)
+                .unwrap_err()
+                .to_string(),
+            "{{user_id}} is not defined for this connection"
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment(" tenant_id = 42 ").unwrap(),
+            ("tenant_id".to_string(), Some("42".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("region =").unwrap(),
+            ("region".to_string(), None)
+        );
+        assert!(parse_assignment("tenant id = 1").is_err());
+        assert!(parse_assignment("tenant_id").is_err());
+    }
+}
@@ -25,7 +25,7 @@ pub struct VisualSelection {
 }
 
 /// wraps `DatabaseTreeItems` as a datastore and adds selection functionality
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct DatabaseTree {
     items: DatabaseTreeItems,
     pub selection: Option<usize>,
@@ -56,29 +56,118 @@ impl DatabaseTree {
 
     /// iterates visible elements starting from `start_index_visual`
     pub fn iterate(&self, start_index_visual: usize, max_amount: usize) -> TreeIterator<'_> {
-        let start = self
-            .visual_index_to_absolute(start_index_visual)
-            .unwrap_or_default();
-        TreeIterator::new(self.items.iterate(start, max_amount), self.selection)
+        TreeIterator::new(
+            self.items.iterate(start_index_visual, max_amount),
+            self.selection,
+        )
     }
 
     pub const fn visual_selection(&self) -> Option<&VisualSelection> {
         self.visual_selection.as_ref()
     }
 
+    /// Names of the tables in `database`.
+    pub fn table_names(&self, database: &str) -> Vec<String> {
+        self.items
+            .tree_items
+            .iter()
+            .filter_map(|item| match item.kind() {
+                DatabaseTreeItemKind::Table {
+                    database: d, table, ..
+                } if d.name == database => Some(table.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The name of the selected database, schema or table.
+    pub fn selected_name(&self) -> Option<String> {
+        self.selection
+            .map(|index| self.items.tree_items[index].kind().name())
+    }
+
+    /// Why the tables of the selected database could not be listed, if they could not.
+    pub fn selected_database_error(&self) -> Option<String> {
+        self.selection.and_then(|index| {
+            self.items.tree_items[index]
+                .kind()
+                .database_error()
+                .map(str::to_string)
+        })
+    }
+
+    /// Why the columns of the selected table could not be read, if they could not.
+    pub fn selected_table_error(&self) -> Option<String> {
+        self.selection.and_then(|index| {
+            self.items.tree_items[index]
+                .kind()
+                .table_error()
+                .map(str::to_string)
+        })
+    }
+
+    /// Marks `table` in `database` as one whose columns could not be read, or clears the mark
+    /// with `None`.
+    pub fn set_table_error(&mut self, database: &str, table: &Table, error: Option<String>) {
+        for item in self.items.tree_items.iter_mut() {
+            if matches!(
+                item.kind(),
+                DatabaseTreeItemKind::Table { database: d, table: t, .. }
+                    if d.name == database && t.name == table.name && t.schema == table.schema
+            ) {
+                item.set_table_error(error.clone());
+            }
+        }
+    }
+
     pub fn selected_table(&self) -> Option<(Database, Table)> {
         self.selection.and_then(|index| {
             let item = &self.items.tree_items[index];
             match item.kind() {
                 DatabaseTreeItemKind::Database { .. } => None,
-                DatabaseTreeItemKind::Table { table, database } => {
-                    Some((database.clone(), table.clone()))
-                }
+                DatabaseTreeItemKind::Table {
+                    table, database, ..
+                } => Some((database.clone(), table.clone())),
                 DatabaseTreeItemKind::Schema { .. } => None,
             }
         })
     }
 
+    /// Selects `schema` in `database`, or in the first database which has it when `database`
+    /// is `None`, or `database` itself without `schema`, and expands them. Returns whether it
+    /// was found.
+    pub fn select(&mut self, database: Option<&str>, schema: Option<&str>) -> bool {
+        let in_database = |name: &str| database.is_none() || database == Some(name);
+        let Some(index) = self
+            .items
+            .tree_items
+            .iter()
+            .position(|item| match item.kind() {
+                DatabaseTreeItemKind::Database { name, .. } => {
+                    schema.is_none() && in_database(name)
+                }
+                DatabaseTreeItemKind::Schema {
+                    database: d,
+                    schema: s,
+                    ..
+                } => schema == Some(s.name.as_str()) && in_database(&d.name),
+                DatabaseTreeItemKind::Table { .. } => false,
+            })
+        else {
+            return false;
+        };
+        if let Some(parent) = self.items.tree_items[..index]
+            .iter()
+            .rposition(|item| item.kind().is_database())
+        {
+            self.items.expand(parent, false);
+        }
+        self.items.expand(index, false);
+        self.selection = Some(index);
+        self.visual_selection = self.calc_visual_selection();
+        true
+    }
+
     pub fn move_selection(&mut self, dir: MoveSelection) -> bool {
         self.selection.map_or(false, |selection| {
             let new_index = match dir {
@@ -104,37 +193,13 @@ impl DatabaseTree {
         })
     }
 
-    fn visual_index_to_absolute(&self, visual_index: usize) -> Option<usize> {
-        self.items
-            .iterate(0, self.items.len())
-            .enumerate()
-            .find_map(
-                |(i, (abs, _))| {
-                    if i == visual_index {
-                        Some(abs)
-                    } else {
-                        None
-                    }
-                },
-            )
-    }
-
     fn calc_visual_selection(&self) -> Option<VisualSelection> {
-        self.selection.map(|selection_absolute| {
-            let mut count = 0;
-            let mut visual_index = 0;
-            for (index, _item) in self.items.iterate(0, self.items.len()) {
-                if selection_absolute == index {
-                    visual_index = count;
-                }
-
-                count += 1;
-            }
-
-            VisualSelection {
-                index: visual_index,
-                count,
-            }
+        self.selection.map(|selection_absolute| VisualSelection {
+            index: self
+                .items
+                .absolute_index_to_visual(selection_absolute)
+                .unwrap_or_default(),
+            count: self.items.visible_len(),
         })
     }
 
@@ -197,11 +262,7 @@ impl DatabaseTree {
 
     fn selection_down(&self, current_index: usize, lines: usize) -> Option<usize> {
         let mut index = current_index;
-        let last_visible_item_index = self
-            .items
-            .tree_items
-            .iter()
-            .rposition(|x| x.info().is_visible())?;
+        let last_visible_item_index = self.items.last_visible()?;
 
         'a: for _ in 0..lines {
             loop {
@@ -291,6 +352,11 @@ impl DatabaseTree {
             return Some(current_index);
         }
 
+        if item.kind().is_parent_table() && !item.kind().is_table_collapsed() {
+            self.items.collapse(current_index, false);
+            return Some(current_index);
+        }
+
         self.select_parent(current_index)
     }
 
@@ -329,6 +395,14 @@ impl DatabaseTree {
             return self.selection_updown(current_selection, false);
         }
 
+        if item.kind().is_parent_table() {
+            if item.kind().is_table_collapsed() {
+                self.items.expand(current_selection, false);
+                return Some(current_selection);
+            }
+            return self.selection_updown(current_selection, false);
+        }
+
         None
     }
 
@@ -353,6 +427,7 @@ mod test {
                 create_time: None,
                 update_time: None,
                 engine: None,
+                parent: None,
                 schema: None,
             }
         }
@@ -363,11 +438,76 @@ mod test {
                 create_time: None,
                 update_time: None,
                 engine: None,
+                parent: None,
                 schema: Some(schema),
             }
         }
     }
 
+    #[test]
+    fn test_selected_table_error() {
+        let items = vec![Database::new(
+            "a".to_string(),
+            vec![
+                Table::new("b".to_string()).into(),
+                Table::new("c".to_string()).into(),
+            ],
+        )];
+
+        // a
+        //   b
+        //   c
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        tree.set_table_error(
+            "a",
+            &Table::new("b".to_string()),
+            Some("SELECT command denied to user 'app'".to_string()),
+        );
+        assert_eq!(tree.selected_table_error(), None);
+        tree.move_selection(MoveSelection::Right);
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(
+            tree.selected_table_error(),
+            Some("SELECT command denied to user 'app'".to_string())
+        );
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selected_name(), Some("c".to_string()));
+        assert_eq!(tree.selected_table_error(), None);
+
+        tree.set_table_error("a", &Table::new("b".to_string()), None);
+        assert!(tree.move_selection(MoveSelection::Up));
+        assert_eq!(tree.selected_table_error(), None);
+    }
+
+    #[test]
+    fn test_selected_database_error() {
+        let items = vec![
+            Database::from_tables(
+                "secret".to_string(),
+                Err(anyhow::anyhow!("SHOW command denied to user 'app'")),
+            ),
+            Database::from_tables(
+                "a".to_string(),
+                Ok(vec![Table::new("b".to_string()).into()]),
+            ),
+        ];
+
+        // secret
+        // a
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        assert_eq!(
+            tree.selected_database_error(),
+            Some("SHOW command denied to user 'app'".to_string())
+        );
+        tree.move_selection(MoveSelection::Right);
+        assert!(tree.selected_database_error().is_some());
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selected_name(), Some("a".to_string()));
+        assert_eq!(tree.selected_database_error(), None);
+    }
+
     #[test]
     fn test_selection() {
         let items = vec![Database::new(
@@ -815,6 +955,47 @@ mod test {
         assert_eq!(s.index, 1);
     }
 
+    #[test]
+    fn test_iterate_window() {
+        let items = vec![
+            Database::new(
+                "a".to_string(),
+                vec![
+                    Table::new("b".to_string()).into(),
+                    Table::new("c".to_string()).into(),
+                ],
+            ),
+            Database::new("d".to_string(), vec![Table::new("e".to_string()).into()]),
+        ];
+
+        // a
+        //   b
+        //   c
+        // d
+        //   e
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        let names = |tree: &DatabaseTree, start: usize, max: usize| {
+            tree.iterate(start, max)
+                .map(|(item, _)| item.kind().name())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(names(&tree, 0, 10), vec!["a", "d"]);
+
+        tree.items.expand(0, false);
+        assert_eq!(names(&tree, 1, 2), vec!["b", "c"]);
+        assert_eq!(names(&tree, 3, 2), vec!["d"]);
+        assert!(names(&tree, 5, 2).is_empty());
+
+        tree.selection = Some(0);
+        assert!(tree.move_selection(MoveSelection::End));
+        assert_eq!(tree.selection, Some(3));
+        assert_eq!(tree.visual_selection().unwrap().index, 3);
+
+        tree.items.collapse(0, false);
+        assert_eq!(names(&tree, 0, 10), vec!["a", "d"]);
+    }
+
     #[test]
     fn test_selection_top() {
         let items = vec![Database::new(
@@ -912,4 +1093,106 @@ mod test {
         assert!(tree.move_selection(MoveSelection::End));
         assert_eq!(tree.selection, Some(3));
     }
+
+    #[test]
+    fn test_select() {
+        let items = vec![
+            Database::new(
+                "a".to_string(),
+                vec![Schema {
+                    name: "public".to_string(),
+                    tables: vec![Table::new_with_schema(
+                        "b".to_string(),
+                        "public".to_string(),
+                    )],
+                }
+                .into()],
+            ),
+            Database::new(
+                "c".to_string(),
+                vec![Schema {
+                    name: "app".to_string(),
+                    tables: vec![Table::new_with_schema("d".to_string(), "app".to_string())],
+                }
+                .into()],
+            ),
+        ];
+
+        // a
+        //   public
+        //     b
+        // c
+        //   app
+        //     d
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        assert!(tree.select(None, Some("app")));
+        assert_eq!(tree.selection, Some(4));
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selected_table().unwrap().1.name, "d");
+
+        assert!(!tree.select(Some("a"), Some("app")));
+        assert!(tree.select(Some("a"), None));
+        assert_eq!(tree.selection, Some(0));
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(1));
+    }
+
+    #[test]
+    fn test_nested_tables() {
+        let table = |name: &str, parent: Option<&str>| Table {
+            parent: parent.map(|parent| parent.to_string()),
+            ..Table::new_with_schema(name.to_string(), "public".to_string())
+        };
+        let items = vec![Database::new(
+            "a".to_string(),
+            vec![Schema {
+                name: "public".to_string(),
+                tables: vec![
+                    table("m_2025", Some("m")),
+                    table("m", None),
+                    table("m_2025_01", Some("m_2025")),
+                    table("x", None),
+                ],
+            }
+            .into()],
+        )];
+
+        // a
+        //   public
+        //     m
+        //       m_2025
+        //         m_2025_01
+        //     x
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        assert!(tree.select(Some("a"), Some("public")));
+        let names = tree
+            .items
+            .tree_items
+            .iter()
+            .map(|item| item.kind().name())
+            .collect::<Vec<String>>();
+        assert_eq!(names, ["a", "public", "m", "m_2025", "m_2025_01", "x"]);
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(2));
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(5));
+
+        assert!(tree.move_selection(MoveSelection::Up));
+        assert!(tree.move_selection(MoveSelection::Right));
+        assert!(!tree.items.tree_items[2].kind().is_table_collapsed());
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(3));
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(5));
+
+        assert!(tree.move_selection(MoveSelection::Up));
+        assert!(tree.move_selection(MoveSelection::Left));
+        assert_eq!(tree.selection, Some(2));
+        assert!(tree.move_selection(MoveSelection::Left));
+        assert!(tree.items.tree_items[2].kind().is_table_collapsed());
+        assert!(tree.move_selection(MoveSelection::Down));
+        assert_eq!(tree.selection, Some(5));
+    }
 }
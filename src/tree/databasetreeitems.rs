@@ -1,24 +1,43 @@
 use crate::tree::{error::Result, treeitems_iter::TreeItemsIterator};
 use crate::tree::{item::DatabaseTreeItemKind, DatabaseTreeItem};
-use crate::tree::{Child, Database};
-use std::collections::{BTreeSet, HashMap};
+use crate::tree::{Child, Database, Table};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct DatabaseTreeItems {
     pub tree_items: Vec<DatabaseTreeItem>,
+    /// Indexes of the visible items, kept up to date on every change of the visibility so that
+    /// drawing and moving the selection don't have to walk the whole tree.
+    visible: Vec<usize>,
 }
 
 impl DatabaseTreeItems {
     pub fn new(list: &[Database], collapsed: &BTreeSet<&String>) -> Result<Self> {
-        Ok(Self {
-            tree_items: Self::create_items(list, collapsed)?,
-        })
+        Ok(Self::with_items(Self::create_items(list, collapsed)?))
+    }
+
+    fn with_items(tree_items: Vec<DatabaseTreeItem>) -> Self {
+        let mut items = Self {
+            tree_items,
+            visible: vec![],
+        };
+        items.update_visible();
+        items
+    }
+
+    fn update_visible(&mut self) {
+        self.visible = self
+            .tree_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.info().is_visible())
+            .map(|(index, _)| index)
+            .collect();
     }
 
     pub fn filter(&self, filter_text: String) -> Self {
-        Self {
-            tree_items: self
-                .tree_items
+        Self::with_items(
+            self.tree_items
                 .iter()
                 .filter(|item| {
                     item.is_database() || item.kind().is_schema() || item.is_match(&filter_text)
@@ -35,7 +54,7 @@ impl DatabaseTreeItems {
                     }
                 })
                 .collect::<Vec<DatabaseTreeItem>>(),
-        }
+        )
     }
 
     fn create_items(
@@ -49,30 +68,86 @@ impl DatabaseTreeItems {
             {
                 Self::push_databases(e, &mut items, &mut items_added, collapsed)?;
             }
+            let mut tables = vec![];
             for child in &e.children {
                 match child {
-                    Child::Table(table) => items.push(DatabaseTreeItem::new_table(e, table)),
+                    Child::Table(table) => tables.push(table),
                     Child::Schema(schema) => {
+                        Self::push_tables(e, &std::mem::take(&mut tables), &mut items);
                         items.push(DatabaseTreeItem::new_schema(e, schema, true));
-                        for table in &schema.tables {
-                            items.push(DatabaseTreeItem::new_table(e, table))
-                        }
+                        Self::push_tables(
+                            e,
+                            &schema.tables.iter().collect::<Vec<&Table>>(),
+                            &mut items,
+                        );
                     }
                 }
             }
+            Self::push_tables(e, &tables, &mut items);
         }
 
         Ok(items)
     }
 
+    /// Pushes `tables` with the tables which have a parent among them nested under it.
+    fn push_tables(database: &Database, tables: &[&Table], items: &mut Vec<DatabaseTreeItem>) {
+        let names = tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect::<HashSet<&str>>();
+        let mut roots = vec![];
+        let mut children: HashMap<&str, Vec<&Table>> = HashMap::new();
+        for table in tables {
+            match table
+                .parent
+                .as_deref()
+                .filter(|parent| *parent != table.name && names.contains(parent))
+            {
+                Some(parent) => children.entry(parent).or_default().push(table),
+                None => roots.push(*table),
+            }
+        }
+        let mut stack = roots
+            .into_iter()
+            .rev()
+            .map(|table| (table, 0))
+            .collect::<Vec<(&Table, u8)>>();
+        while let Some((table, depth)) = stack.pop() {
+            let children = children.remove(table.name.as_str()).unwrap_or_default();
+            items.push(DatabaseTreeItem::new_table(
+                database,
+                table,
+                depth,
+                !children.is_empty(),
+            ));
+            stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+        }
+    }
+
     /// how many individual items are in the list
     pub fn len(&self) -> usize {
         self.tree_items.len()
     }
 
-    /// iterates visible elements
-    pub const fn iterate(&self, start: usize, max_amount: usize) -> TreeItemsIterator<'_> {
-        TreeItemsIterator::new(self, start, max_amount)
+    /// how many items are visible
+    pub fn visible_len(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// position of the visible item at `index` among the visible items
+    pub fn absolute_index_to_visual(&self, index: usize) -> Option<usize> {
+        self.visible.binary_search(&index).ok()
+    }
+
+    pub fn last_visible(&self) -> Option<usize> {
+        self.visible.last().copied()
+    }
+
+    /// iterates visible elements starting from the `start_visual`th visible one
+    pub fn iterate(&self, start_visual: usize, max_amount: usize) -> TreeItemsIterator<'_> {
+        let start = start_visual.min(self.visible.len());
+        let end = start.saturating_add(max_amount).min(self.visible.len());
+        TreeItemsIterator::new(self, &self.visible[start..end])
     }
 
     fn push_databases(
@@ -100,6 +175,23 @@ impl DatabaseTreeItems {
     }
 
     pub fn collapse(&mut self, index: usize, recursive: bool) {
+        self.collapse_items(index, recursive);
+        self.update_visible();
+    }
+
+    fn collapse_items(&mut self, index: usize, recursive: bool) {
+        if self.tree_items[index].kind().is_parent_table() {
+            self.tree_items[index].collapse_table();
+
+            let indent = self.tree_items[index].info().indent();
+            for item in &mut self.tree_items[index + 1..] {
+                if item.info().indent() <= indent {
+                    break;
+                }
+                item.hide();
+            }
+        }
+
         if self.tree_items[index].kind().is_database() {
             self.tree_items[index].collapse_database();
 
@@ -146,6 +238,29 @@ impl DatabaseTreeItems {
     }
 
     pub fn expand(&mut self, index: usize, recursive: bool) {
+        self.expand_items(index, recursive);
+        self.update_visible();
+    }
+
+    fn expand_items(&mut self, index: usize, recursive: bool) {
+        if self.tree_items[index].kind().is_parent_table() {
+            self.tree_items[index].expand_table();
+
+            let indent = self.tree_items[index].info().indent();
+            let mut hidden_below = None;
+            for item in &mut self.tree_items[index + 1..] {
+                let item_indent = item.info().indent();
+                if item_indent <= indent {
+                    break;
+                }
+                if hidden_below.is_some_and(|hidden_below| item_indent > hidden_below) {
+                    continue;
+                }
+                item.show();
+                hidden_below = item.kind().is_table_collapsed().then_some(item_indent);
+            }
+        }
+
         if self.tree_items[index].kind().is_database() {
             self.tree_items[index].expand_database();
 
@@ -201,8 +316,19 @@ impl DatabaseTreeItems {
 
     fn update_visibility(&mut self, prefix: &DatabaseTreeItemKind, start_idx: usize) {
         let mut inner_collapsed: Option<DatabaseTreeItemKind> = None;
+        // The indent of the last collapsed table, whose nested tables stay hidden.
+        let mut hidden_below: Option<u8> = None;
 
         for i in start_idx..self.tree_items.len() {
+            let indent = self.tree_items[i].info().indent();
+            if hidden_below.is_some_and(|hidden_below| indent > hidden_below) {
+                continue;
+            }
+            hidden_below = self.tree_items[i]
+                .kind()
+                .is_table_collapsed()
+                .then_some(indent);
+
             if let Some(ref collapsed_item) = inner_collapsed {
                 match collapsed_item {
                     DatabaseTreeItemKind::Database { name, .. } => {
@@ -30,10 +30,16 @@ pub enum DatabaseTreeItemKind {
     Database {
         name: String,
         collapsed: bool,
+        /// Why its tables could not be listed, such as missing privileges.
+        error: Option<String>,
     },
     Table {
         database: Database,
         table: Table,
+        /// Whether the tables nested under this one are hidden, or `None` if it has none.
+        collapsed: Option<bool>,
+        /// Why its columns could not be read, such as missing privileges.
+        error: Option<String>,
     },
     Schema {
         database: Database,
@@ -71,6 +77,27 @@ impl DatabaseTreeItemKind {
         }
     }
 
+    /// Whether this is a table with nested tables.
+    pub const fn is_parent_table(&self) -> bool {
+        matches!(
+            self,
+            Self::Table {
+                collapsed: Some(_),
+                ..
+            }
+        )
+    }
+
+    pub const fn is_table_collapsed(&self) -> bool {
+        matches!(
+            self,
+            Self::Table {
+                collapsed: Some(true),
+                ..
+            }
+        )
+    }
+
     pub fn name(&self) -> String {
         match self {
             Self::Database { name, .. } => name.to_string(),
@@ -94,6 +121,22 @@ impl DatabaseTreeItemKind {
             Self::Schema { .. } => None,
         }
     }
+
+    /// Why the tables of this database could not be listed, if it is such a database.
+    pub fn database_error(&self) -> Option<&str> {
+        match self {
+            Self::Database { error, .. } => error.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Why the columns of this table could not be read, if it is such a table.
+    pub fn table_error(&self) -> Option<&str> {
+        match self {
+            Self::Table { error, .. } => error.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 /// `DatabaseTreeItem` can be of two kinds: see `DatabaseTreeItem` but shares an info
@@ -104,12 +147,16 @@ pub struct DatabaseTreeItem {
 }
 
 impl DatabaseTreeItem {
-    pub fn new_table(database: &Database, table: &Table) -> Self {
+    /// A table nested `depth` levels under other tables, which is collapsed if it has
+    /// `children`.
+    pub fn new_table(database: &Database, table: &Table, depth: u8, children: bool) -> Self {
         Self {
-            info: TreeItemInfo::new(if table.schema.is_some() { 2 } else { 1 }, false),
+            info: TreeItemInfo::new(if table.schema.is_some() { 2 } else { 1 } + depth, false),
             kind: DatabaseTreeItemKind::Table {
                 database: database.clone(),
                 table: table.clone(),
+                collapsed: children.then_some(true),
+                error: None,
             },
         }
     }
@@ -131,16 +178,25 @@ impl DatabaseTreeItem {
             kind: DatabaseTreeItemKind::Database {
                 name: database.name.to_string(),
                 collapsed: true,
+                error: database.error.clone(),
             },
         }
     }
 
     pub fn set_collapsed(&mut self, collapsed: bool) {
-        if let DatabaseTreeItemKind::Database { name, .. } = self.kind() {
-            self.kind = DatabaseTreeItemKind::Database {
-                name: name.to_string(),
-                collapsed,
-            }
+        if let DatabaseTreeItemKind::Database {
+            collapsed: database_collapsed,
+            ..
+        } = &mut self.kind
+        {
+            *database_collapsed = collapsed;
+        }
+    }
+
+    /// Marks this table as one whose columns could not be read, or clears the mark with `None`.
+    pub fn set_table_error(&mut self, table_error: Option<String>) {
+        if let DatabaseTreeItemKind::Table { error, .. } = &mut self.kind {
+            *error = table_error;
         }
     }
 
@@ -157,21 +213,11 @@ impl DatabaseTreeItem {
     }
 
     pub fn collapse_database(&mut self) {
-        if let DatabaseTreeItemKind::Database { name, .. } = &self.kind {
-            self.kind = DatabaseTreeItemKind::Database {
-                name: name.to_string(),
-                collapsed: true,
-            }
-        }
+        self.set_collapsed(true);
     }
 
     pub fn expand_database(&mut self) {
-        if let DatabaseTreeItemKind::Database { name, .. } = &self.kind {
-            self.kind = DatabaseTreeItemKind::Database {
-                name: name.to_string(),
-                collapsed: false,
-            };
-        }
+        self.set_collapsed(false);
     }
 
     pub fn collapse_schema(&mut self) {
@@ -200,6 +246,26 @@ impl DatabaseTreeItem {
         }
     }
 
+    pub fn collapse_table(&mut self) {
+        if let DatabaseTreeItemKind::Table {
+            collapsed: collapsed @ Some(_),
+            ..
+        } = &mut self.kind
+        {
+            *collapsed = Some(true);
+        }
+    }
+
+    pub fn expand_table(&mut self) {
+        if let DatabaseTreeItemKind::Table {
+            collapsed: collapsed @ Some(_),
+            ..
+        } = &mut self.kind
+        {
+            *collapsed = Some(false);
+        }
+    }
+
     pub fn show(&mut self) {
         self.info.visible = true;
     }
@@ -13,6 +13,9 @@ pub use crate::tree::{
 pub struct Database {
     pub name: String,
     pub children: Vec<Child>,
+    /// Why the tables could not be listed, such as missing privileges, in which case it has no
+    /// children.
+    pub error: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -38,6 +41,20 @@ impl Database {
         Self {
             name: database,
             children,
+            error: None,
+        }
+    }
+
+    /// The database with `tables`, or without children but with the error when listing them
+    /// failed, so one database the user has no privileges on leaves the others browsable.
+    pub fn from_tables(database: String, tables: anyhow::Result<Vec<Child>>) -> Self {
+        match tables {
+            Ok(children) => Self::new(database, children),
+            Err(e) => Self {
+                name: database,
+                children: vec![],
+                error: Some(format!("{:#}", e)),
+            },
         }
     }
 }
@@ -54,5 +71,7 @@ pub struct Table {
     pub create_time: Option<chrono::DateTime<chrono::Utc>>,
     pub update_time: Option<chrono::DateTime<chrono::Utc>>,
     pub engine: Option<String>,
+    /// The table this one inherits from or is a partition of, which it is nested under in the tree.
+    pub parent: Option<String>,
     pub schema: Option<String>,
 }
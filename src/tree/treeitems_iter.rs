@@ -2,18 +2,15 @@ use crate::tree::{databasetreeitems::DatabaseTreeItems, item::DatabaseTreeItem};
 
 pub struct TreeItemsIterator<'a> {
     tree: &'a DatabaseTreeItems,
-    index: usize,
-    increments: Option<usize>,
-    max_amount: usize,
+    visible: std::slice::Iter<'a, usize>,
 }
 
 impl<'a> TreeItemsIterator<'a> {
-    pub const fn new(tree: &'a DatabaseTreeItems, start: usize, max_amount: usize) -> Self {
+    /// iterates the items at `visible`, the indexes of visible items
+    pub fn new(tree: &'a DatabaseTreeItems, visible: &'a [usize]) -> Self {
         TreeItemsIterator {
             tree,
-            index: start,
-            increments: None,
-            max_amount,
+            visible: visible.iter(),
         }
     }
 }
@@ -22,35 +19,8 @@ impl<'a> Iterator for TreeItemsIterator<'a> {
     type Item = (usize, &'a DatabaseTreeItem);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.increments.unwrap_or_default() < self.max_amount {
-            let items = &self.tree.tree_items;
-
-            let mut init = self.increments.is_none();
-
-            if let Some(i) = self.increments.as_mut() {
-                *i += 1;
-            } else {
-                self.increments = Some(0);
-            };
-
-            loop {
-                if !init {
-                    self.index += 1;
-                }
-                init = false;
-
-                if self.index >= self.tree.len() {
-                    break;
-                }
-
-                let elem = &items[self.index];
-
-                if elem.info().is_visible() {
-                    return Some((self.index, &items[self.index]));
-                }
-            }
-        }
-
-        None
+        self.visible
+            .next()
+            .map(|&index| (index, &self.tree.tree_items[index]))
     }
 }
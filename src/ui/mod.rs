@@ -1,6 +1,6 @@
 use crate::config::KeyConfig;
 use crate::event::Key;
-use crate::tree::MoveSelection;
+use zhobo::tree::MoveSelection;
 
 pub mod reflow;
 pub mod scrollbar;
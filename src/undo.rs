@@ -0,0 +1,802 @@
+use zhobo::database::Pool;
+
+/// Statements affecting more rows than this are not recorded, because the
+/// inverse statements would have to be kept in memory.
+const MAX_UNDO_ROWS: usize = 1000;
+/// How many of the statements reverting a change are shown before confirming it.
+const PREVIEW_STATEMENTS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoEntry {
+    pub statement: String,
+    pub inverse: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct UndoLog {
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoLog {
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoEntry> {
+        self.entries.pop()
+    }
+
+    pub fn last(&self) -> Option<&UndoEntry> {
+        self.entries.last()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Modification {
+    Insert {
+        table: String,
+        columns: Vec<String>,
+        values: Vec<Vec<String>>,
+    },
+    Update {
+        table: String,
+        columns: Vec<String>,
+        filter: Option<String>,
+    },
+    Delete {
+        table: String,
+        filter: Option<String>,
+    },
+}
+
+/// The first of `statements`, a statement per line, and how many more there are.
+pub fn preview(statements: &[String]) -> String {
+    let mut lines = statements
+        .iter()
+        .take(PREVIEW_STATEMENTS)
+        .cloned()
+        .collect::<Vec<String>>();
+    if statements.len() > PREVIEW_STATEMENTS {
+        lines.push(format!(
+            "... and {} more",
+            statements.len() - PREVIEW_STATEMENTS
+        ));
+    }
+    lines.join("\n")
+}
+
+/// How a statement changes the session it runs in, which the rows read for undo and the revert
+/// don't run in.
+#[derive(Debug, PartialEq)]
+pub enum SessionChange {
+    None,
+    /// Changes the state of the session, such as a variable, the database or temporary tables.
+    State,
+    /// Starts a transaction, which may be rolled back after a change is recorded.
+    Begin,
+    /// Ends the transaction.
+    End,
+}
+
+/// How `statement` changes the session it runs in.
+pub fn session_change(statement: &str) -> SessionChange {
+    let statement = statement.trim();
+    let starts_with = |keywords: &[&str]| {
+        keywords
+            .iter()
+            .try_fold(statement, |rest, keyword| strip_keyword(rest, keyword))
+            .is_some()
+    };
+    if starts_with(&["BEGIN"]) || starts_with(&["START", "TRANSACTION"]) {
+        SessionChange::Begin
+    } else if starts_with(&["ROLLBACK", "TO"]) {
+        SessionChange::None
+    } else if starts_with(&["COMMIT"]) || starts_with(&["ROLLBACK"]) || starts_with(&["END"]) {
+        SessionChange::End
+    } else if [
+        "SET", "USE", "ATTACH", "DETACH", "RESET", "DISCARD", "DECLARE",
+    ]
+    .iter()
+    .any(|keyword| starts_with(&[keyword]))
+        || ["TEMP", "TEMPORARY"]
+            .iter()
+            .any(|keyword| starts_with(&["CREATE", keyword]))
+    {
+        SessionChange::State
+    } else {
+        SessionChange::None
+    }
+}
+
+/// Builds the statements which revert `query`.
+/// This must be called before `query` is executed, because the current rows are needed
+/// to restore deleted or updated values. Inserted and updated rows are found again by the
+/// primary or unique key of their table, so nothing is recorded for a table without one. Values
+/// are read back as literals of what is stored, so nothing is recorded when one can't be.
+/// Returns `None` if `query` is not a modification or the inverse cannot be derived.
+pub async fn inverse_statements(
+    pool: &dyn Pool,
+    query: &str,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let modification = match parse_modification(query) {
+        Some(modification) => modification,
+        None => return Ok(None),
+    };
+    match modification {
+        Modification::Insert {
+            table,
+            columns,
+            values,
+        } => {
+            let Some(key) = pool.get_key_columns(&table).await? else {
+                return Ok(None);
+            };
+            let Some(positions) = key
+                .iter()
+                .map(|key| columns.iter().position(|column| same_column(column, key)))
+                .collect::<Option<Vec<usize>>>()
+            else {
+                return Ok(None);
+            };
+            Ok(values
+                .iter()
+                .map(|row| {
+                    let key_values = positions
+                        .iter()
+                        .map(|i| row[*i].clone())
+                        .collect::<Vec<String>>();
+                    key_values.iter().all(|value| is_literal(value)).then(|| {
+                        format!(
+                            "DELETE FROM {} WHERE {}",
+                            table,
+                            key_condition(pool, &key, &key_values)
+                        )
+                    })
+                })
+                .collect())
+        }
+        Modification::Update {
+            table,
+            columns,
+            filter,
+        } => {
+            let Some(key) = pool.get_key_columns(&table).await? else {
+                return Ok(None);
+            };
+            if columns
+                .iter()
+                .any(|column| key.iter().any(|key| same_column(column, key)))
+            {
+                return Ok(None);
+            }
+            let select = format!(
+                "SELECT {}, {} FROM {}",
+                key.iter()
+                    .map(|key| pool.quote_identifier(key))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                columns.join(", "),
+                table
+            );
+            let Some((_, rows)) = select_rows(pool, &select, filter.as_deref()).await? else {
+                return Ok(None);
+            };
+            Ok(rows
+                .iter()
+                .map(|row| {
+                    let (key_values, values) = row.split_at(key.len());
+                    // Rows of a unique key holding NULL can't be told apart.
+                    (!key_values.iter().any(|value| value == "NULL")).then(|| {
+                        format!(
+                            "UPDATE {} SET {} WHERE {}",
+                            table,
+                            columns
+                                .iter()
+                                .zip(values)
+                                .map(|(column, value)| format!("{} = {}", column, value))
+                                .collect::<Vec<String>>()
+                                .join(", "),
+                            key_condition(pool, &key, key_values)
+                        )
+                    })
+                })
+                .collect())
+        }
+        Modification::Delete { table, filter } => {
+            let select = format!("SELECT * FROM {}", table);
+            let Some((headers, rows)) = select_rows(pool, &select, filter.as_deref()).await? else {
+                return Ok(None);
+            };
+            let headers = headers
+                .iter()
+                .map(|header| pool.quote_identifier(header))
+                .collect::<Vec<String>>()
+                .join(", ");
+            Ok(Some(
+                rows.iter()
+                    .map(|row| {
+                        format!(
+                            "INSERT INTO {} ({}) VALUES ({})",
+                            table,
+                            headers,
+                            row.join(", ")
+                        )
+                    })
+                    .collect(),
+            ))
+        }
+    }
+}
+
+/// Executes `statements`, INSERTs into one table, in a transaction and returns the statements
+/// deleting the inserted rows again by the key of the table. Keys the database generates are
+/// read back where it can return them. Returns `None` when the rows can't be identified, or there
+/// are too many of them.
+pub async fn insert_with_inverse(
+    pool: &dyn Pool,
+    statements: &[String],
+) -> anyhow::Result<Option<Vec<String>>> {
+    let mut inverse = Some(vec![]);
+    for statement in statements {
+        match inverse_statements(pool, statement).await.unwrap_or(None) {
+            Some(statements) => inverse
+                .iter_mut()
+                .for_each(|i| i.extend(statements.clone())),
+            None => inverse = None,
+        }
+    }
+    if inverse.is_some() {
+        pool.execute_in_transaction(statements).await?;
+        return Ok(inverse.filter(|inverse| inverse.len() <= MAX_UNDO_ROWS));
+    }
+
+    let table = match statements.first().and_then(|s| parse_modification(s)) {
+        Some(Modification::Insert { table, .. }) => Some(table),
+        _ => None,
+    };
+    let key = match &table {
+        Some(table) => pool.get_key_columns(table).await.unwrap_or(None),
+        None => None,
+    };
+    let returning = key.as_ref().and_then(|key| {
+        pool.returning_clause(
+            &key.iter()
+                .map(|key| pool.quote_identifier(key))
+                .collect::<Vec<String>>(),
+        )
+    });
+    let (Some(table), Some(key), Some(returning)) = (table, key, returning) else {
+        pool.execute_in_transaction(statements).await?;
+        return Ok(None);
+    };
+    let results = pool
+        .execute_in_transaction(
+            &statements
+                .iter()
+                .map(|statement| format!("{} {}", statement, returning))
+                .collect::<Vec<String>>(),
+        )
+        .await?;
+    let keys = results
+        .iter()
+        .flatten()
+        .map(|row| row.iter().map(|value| pool.literal(value)).collect())
+        .collect::<Vec<Vec<String>>>();
+    if keys.len() > MAX_UNDO_ROWS {
+        return Ok(None);
+    }
+    Ok(keys
+        .iter()
+        .map(|values| {
+            (values.len() == key.len() && !values.iter().any(|value| value == "NULL")).then(|| {
+                format!(
+                    "DELETE FROM {} WHERE {}",
+                    table,
+                    key_condition(pool, &key, values)
+                )
+            })
+        })
+        .collect())
+}
+
+/// The rows `select` reads with `filter` as literals, or `None` when there are too many.
+async fn select_rows(
+    pool: &dyn Pool,
+    select: &str,
+    filter: Option<&str>,
+) -> anyhow::Result<Option<(Vec<String>, Vec<Vec<String>>)>> {
+    let query = match filter {
+        Some(filter) => format!("{} WHERE {}", select, filter),
+        None => select.to_string(),
+    };
+    let (headers, rows) = pool.fetch_values(&query).await?;
+    Ok((rows.len() <= MAX_UNDO_ROWS).then(|| {
+        let rows = rows
+            .iter()
+            .map(|row| row.iter().map(|value| pool.literal(value)).collect())
+            .collect();
+        (headers, rows)
+    }))
+}
+
+fn key_condition(pool: &dyn Pool, key: &[String], values: &[String]) -> String {
+    key.iter()
+        .zip(values)
+        .map(|(column, value)| format!("{} = {}", pool.quote_identifier(column), value))
+        .collect::<Vec<String>>()
+        .join(" AND ")
+}
+
+/// Returns true if `written`, a column as written in a statement, names the column `name`.
+/// Quoted names are compared exactly and the others ignoring case.
+fn same_column(written: &str, name: &str) -> bool {
+    let unquoted = unquote_identifier(written);
+    if unquoted.len() == written.trim().len() {
+        unquoted.eq_ignore_ascii_case(name)
+    } else {
+        unquoted == name
+    }
+}
+
+/// Returns true if `value`, a value as written in a statement, is a number or a string literal,
+/// which is the same value each time it is evaluated.
+fn is_literal(value: &str) -> bool {
+    let value = value.trim();
+    if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        return !inner.replace("''", "").contains('\'');
+    }
+    value.parse::<f64>().is_ok()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+}
+
+fn parse_modification(query: &str) -> Option<Modification> {
+    let query = query.trim().trim_end_matches(';').trim();
+    if let Some(rest) = strip_keyword(query, "DELETE") {
+        let rest = strip_keyword(rest, "FROM")?;
+        let (table, rest) = split_first_token(rest);
+        return Some(Modification::Delete {
+            table: table.to_string(),
+            filter: plain_filter(rest)?,
+        });
+    }
+    if let Some(rest) = strip_keyword(query, "UPDATE") {
+        let (table, rest) = split_first_token(rest);
+        let rest = strip_keyword(rest, "SET")?;
+        let (set, filter) = match find_keyword(rest, "WHERE") {
+            Some(index) => (&rest[..index], plain_filter(&rest[index..])?),
+            None => (rest, plain_filter("")?),
+        };
+        if ["FROM", "RETURNING", "ORDER", "LIMIT"]
+            .iter()
+            .any(|keyword| find_keyword(set, keyword).is_some())
+        {
+            return None;
+        }
+        let columns = split_top_level(set, ',')
+            .iter()
+            .map(|assignment| {
+                let column = assignment.split('=').next().unwrap_or_default().trim();
+                column
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or(column)
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        return Some(Modification::Update {
+            table: table.to_string(),
+            columns,
+            filter,
+        });
+    }
+    if let Some(rest) = strip_keyword(query, "INSERT") {
+        let rest = strip_keyword(rest, "INTO")?;
+        let (table, rest) = split_first_token(rest);
+        let rest = rest.trim_start();
+        if !rest.starts_with('(') {
+            return None;
+        }
+        let end = closing_paren(rest)?;
+        let columns = split_top_level(&rest[1..end], ',')
+            .iter()
+            .map(|c| c.trim().to_string())
+            .collect::<Vec<String>>();
+        let rest = strip_keyword(&rest[end + 1..], "VALUES")?;
+        let values = split_top_level(rest, ',')
+            .iter()
+            .map(|tuple| {
+                let tuple = tuple.trim();
+                if closing_paren(tuple)? + 1 != tuple.len() {
+                    return None;
+                }
+                let inner = tuple.strip_prefix('(')?.strip_suffix(')')?;
+                Some(
+                    split_top_level(inner, ',')
+                        .iter()
+                        .map(|v| v.trim().to_string())
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .collect::<Option<Vec<Vec<String>>>>()?;
+        if values.iter().any(|row| row.len() != columns.len()) {
+            return None;
+        }
+        return Some(Modification::Insert {
+            table: table.to_string(),
+            columns,
+            values,
+        });
+    }
+    None
+}
+
+/// The condition of `rest`, what follows the table of a `DELETE` or the assignments of an
+/// `UPDATE`, which is `Some(None)` when there is none. `None` when anything but a plain `WHERE`
+/// follows, such as an alias, `USING`, `FROM`, `RETURNING` or `LIMIT`, since the rows the
+/// condition selects on its own would not be the ones changed.
+fn plain_filter(rest: &str) -> Option<Option<String>> {
+    if rest.trim().is_empty() {
+        return Some(None);
+    }
+    let filter = strip_keyword(rest, "WHERE")?;
+    if ["RETURNING", "ORDER", "LIMIT"]
+        .iter()
+        .any(|keyword| find_keyword(filter, keyword).is_some())
+    {
+        return None;
+    }
+    Some(Some(filter.to_string()))
+}
+
+pub fn strip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    let s = s.trim_start();
+    let head = s.get(..keyword.len())?;
+    let rest = &s[keyword.len()..];
+    if head.eq_ignore_ascii_case(keyword)
+        && !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    {
+        Some(rest.trim_start())
+    } else {
+        None
+    }
+}
+
+/// Splits `s` at the first whitespace or `(` which is not inside quotes.
+fn split_first_token(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None if matches!(c, '"' | '`' | '\'') => quote = Some(c),
+            None if c.is_whitespace() || c == '(' => return (&s[..i], &s[i..]),
+            None => (),
+        }
+    }
+    (s, "")
+}
+
+/// Returns the byte index of `keyword` appearing outside quotes and parentheses.
+pub fn find_keyword(s: &str, keyword: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut depth = 0;
+    let mut previous = ' ';
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '"' | '`' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth == 0
+                    && !previous.is_alphanumeric()
+                    && previous != '_'
+                    && strip_keyword(&s[i..], keyword).is_some() =>
+                {
+                    return Some(i)
+                }
+                _ => (),
+            },
+        }
+        previous = c;
+    }
+    None
+}
+
+pub fn split_top_level(s: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut quote = None;
+    let mut depth = 0;
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '"' | '`' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if c == separator && depth == 0 => {
+                    parts.push(current.trim().to_string());
+                    current = String::new();
+                    continue;
+                }
+                _ => (),
+            },
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+fn closing_paren(s: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '"' | '`' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => (),
+            },
+        }
+    }
+    None
+}
+
+pub fn unquote_identifier(s: &str) -> String {
+    s.trim().trim_matches(|c| c == '"' || c == '`').to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        insert_with_inverse, inverse_statements, is_literal, parse_modification, preview,
+        same_column, session_change, split_top_level, Modification, SessionChange,
+    };
+    use zhobo::database::{ExecuteResult, Pool, SqlitePool};
+
+    #[test]
+    fn test_parse_delete() {
+        assert_eq!(
+            parse_modification("delete from users where id = 1;"),
+            Some(Modification::Delete {
+                table: "users".to_string(),
+                filter: Some("id = 1".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_modification("DELETE FROM `users`"),
+            Some(Modification::Delete {
+                table: "`users`".to_string(),
+                filter: None,
+            })
+        );
+        // Anything but WHERE after the table would make the condition select other rows.
+        assert_eq!(
+            parse_modification("DELETE FROM users u WHERE u.id = 1"),
+            None
+        );
+        assert_eq!(
+            parse_modification("DELETE FROM users USING banned WHERE users.id = banned.id"),
+            None
+        );
+        assert_eq!(
+            parse_modification("DELETE FROM users WHERE id = 1 RETURNING *"),
+            None
+        );
+        assert_eq!(
+            parse_modification("DELETE FROM users WHERE age > 3 ORDER BY id LIMIT 1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_update() {
+        assert_eq!(
+            parse_modification("UPDATE users SET name = 'a, b', u.age = 3 WHERE id IN (1, 2)"),
+            Some(Modification::Update {
+                table: "users".to_string(),
+                columns: vec!["name".to_string(), "age".to_string()],
+                filter: Some("id IN (1, 2)".to_string()),
+            })
+        );
+        assert_eq!(parse_modification("UPDATE users u SET age = 3"), None);
+        assert_eq!(
+            parse_modification("UPDATE users SET age = b.age FROM b WHERE users.id = b.id"),
+            None
+        );
+        assert_eq!(
+            parse_modification("UPDATE users SET age = 3 WHERE id = 1 RETURNING id"),
+            None
+        );
+        assert_eq!(parse_modification("UPDATE users SET age = 3 LIMIT 1"), None);
+    }
+
+    #[test]
+    fn test_parse_insert() {
+        assert_eq!(
+            parse_modification("INSERT INTO users (id, name) VALUES (1, 'a'), (2, NULL)"),
+            Some(Modification::Insert {
+                table: "users".to_string(),
+                columns: vec!["id".to_string(), "name".to_string()],
+                values: vec![
+                    vec!["1".to_string(), "'a'".to_string()],
+                    vec!["2".to_string(), "NULL".to_string()]
+                ],
+            })
+        );
+        assert_eq!(
+            parse_modification("INSERT INTO users SELECT * FROM others"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_session_change() {
+        assert_eq!(session_change("begin"), SessionChange::Begin);
+        assert_eq!(session_change("START TRANSACTION"), SessionChange::Begin);
+        assert_eq!(session_change("COMMIT;"), SessionChange::End);
+        assert_eq!(session_change("ROLLBACK"), SessionChange::End);
+        assert_eq!(session_change("ROLLBACK TO s1"), SessionChange::None);
+        assert_eq!(
+            session_change("SET search_path TO app"),
+            SessionChange::State
+        );
+        assert_eq!(session_change("USE app"), SessionChange::State);
+        assert_eq!(
+            session_change("CREATE TEMPORARY TABLE t (id INT)"),
+            SessionChange::State
+        );
+        assert_eq!(
+            session_change("CREATE TABLE t (id INT)"),
+            SessionChange::None
+        );
+        assert_eq!(session_change("DELETE FROM users"), SessionChange::None);
+        assert_eq!(session_change("SETTINGS"), SessionChange::None);
+    }
+
+    #[test]
+    fn test_parse_not_modification() {
+        assert_eq!(parse_modification("SELECT * FROM users"), None);
+        assert_eq!(parse_modification("UPDATED"), None);
+    }
+
+    #[test]
+    fn test_split_top_level() {
+        assert_eq!(
+            split_top_level("a, 'b,c', f(d, e)", ','),
+            vec!["a".to_string(), "'b,c'".to_string(), "f(d, e)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_literals_and_columns() {
+        assert!(is_literal("-1.5e3"));
+        assert!(is_literal("'it''s'"));
+        assert!(!is_literal("'a' || 'b'"));
+        assert!(!is_literal("NULL"));
+        assert!(!is_literal("now()"));
+        assert!(same_column("ID", "id"));
+        assert!(same_column("`id`", "id"));
+        assert!(!same_column(r#""ID""#, "id"));
+        let statements = vec!["DELETE FROM t WHERE `id` = 1".to_string(); 12];
+        assert!(preview(&statements).ends_with("`id` = 1\n... and 2 more"));
+        assert_eq!(preview(&statements[..1]), statements[0]);
+    }
+
+    #[tokio::test]
+    async fn test_inverse_statements() {
+        let pool = SqlitePool::in_memory(100).await.unwrap();
+        for statement in [
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, avatar BLOB)",
+            "CREATE TABLE logs (message TEXT)",
+            "INSERT INTO users VALUES (1, 'NULL', x'0a1b'), (2, NULL, NULL)",
+        ] {
+            pool.execute(&statement.to_string()).await.unwrap();
+        }
+        let inverse = |query: &'static str| {
+            let pool = pool.clone();
+            async move { inverse_statements(&pool, query).await.unwrap() }
+        };
+
+        assert_eq!(
+            inverse("INSERT INTO users (name, id) VALUES ('a', 3)").await,
+            Some(vec!["DELETE FROM users WHERE `id` = 3".to_string()])
+        );
+        assert_eq!(inverse("INSERT INTO users (name) VALUES ('a')").await, None);
+        assert_eq!(
+            inverse("INSERT INTO users (id) VALUES (abs(-3))").await,
+            None
+        );
+        assert_eq!(
+            inverse("INSERT INTO logs (message) VALUES ('a')").await,
+            None
+        );
+        assert_eq!(inverse("UPDATE users SET id = id + 1").await, None);
+        assert_eq!(inverse("UPDATE logs SET message = 'b'").await, None);
+        assert_eq!(
+            inverse("UPDATE users SET name = 'b' WHERE id = 1").await,
+            Some(vec![
+                "UPDATE users SET name = 'NULL' WHERE `id` = 1".to_string()
+            ])
+        );
+        assert_eq!(
+            inverse("DELETE FROM users WHERE id < 3").await,
+            Some(vec![
+                "INSERT INTO users (`id`, `name`, `avatar`) VALUES (1, 'NULL', X'0A1B')"
+                    .to_string(),
+                "INSERT INTO users (`id`, `name`, `avatar`) VALUES (2, NULL, NULL)".to_string(),
+            ])
+        );
+
+        assert_eq!(
+            insert_with_inverse(
+                &pool,
+                &["INSERT INTO users (name) VALUES ('NULL'), ('c')".to_string()]
+            )
+            .await
+            .unwrap(),
+            Some(vec![
+                "DELETE FROM users WHERE `id` = 3".to_string(),
+                "DELETE FROM users WHERE `id` = 4".to_string(),
+            ])
+        );
+        assert_eq!(
+            insert_with_inverse(
+                &pool,
+                &["INSERT INTO logs (message) VALUES ('a')".to_string()]
+            )
+            .await
+            .unwrap(),
+            None
+        );
+        // A failing statement rolls back the ones before it.
+        assert!(insert_with_inverse(
+            &pool,
+            &[
+                "INSERT INTO users (id) VALUES (5)".to_string(),
+                "INSERT INTO users (id) VALUES (1)".to_string(),
+            ]
+        )
+        .await
+        .is_err());
+        let ExecuteResult::Read { rows, .. } = pool
+            .execute(&"SELECT COUNT(*) FROM users".to_string())
+            .await
+            .unwrap()
+        else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows, [["4"]]);
+
+        // A value which can't be decoded can't be written back, so there is no inverse.
+        pool.execute(&"INSERT INTO logs VALUES (CAST(x'ff' AS TEXT))".to_string())
+            .await
+            .unwrap();
+        assert!(inverse_statements(&pool, "DELETE FROM logs").await.is_err());
+    }
+}